@@ -0,0 +1,7 @@
+-- Track attempts whose target branch is another attempt's branch (stacked branches)
+ALTER TABLE task_attempts ADD COLUMN stacked_on_attempt_id BLOB
+    REFERENCES task_attempts(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_task_attempts_stacked_on_attempt_id
+    ON task_attempts(stacked_on_attempt_id)
+    WHERE stacked_on_attempt_id IS NOT NULL;
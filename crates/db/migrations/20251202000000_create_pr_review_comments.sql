@@ -0,0 +1,23 @@
+-- Review comments pulled from open pull requests, so they can be fed back to the agent
+CREATE TABLE pr_review_comments (
+    id                   BLOB PRIMARY KEY,
+    merge_id             BLOB NOT NULL,
+    task_attempt_id      BLOB NOT NULL,
+    github_comment_id    INTEGER NOT NULL,
+    author               TEXT NOT NULL,
+    body                 TEXT NOT NULL,
+    path                 TEXT,
+    line                 INTEGER,
+    diff_hunk            TEXT,
+    url                  TEXT,
+    status               TEXT NOT NULL DEFAULT 'pending'
+                            CHECK (status IN ('pending', 'sent', 'addressed', 'dismissed')),
+    commented_at         TEXT NOT NULL,
+    created_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (merge_id) REFERENCES merges(id) ON DELETE CASCADE,
+    FOREIGN KEY (task_attempt_id) REFERENCES task_attempts(id) ON DELETE CASCADE,
+    UNIQUE (merge_id, github_comment_id)
+);
+
+CREATE INDEX idx_pr_review_comments_task_attempt_id ON pr_review_comments(task_attempt_id);
@@ -0,0 +1,4 @@
+-- CI check status for PR merges: rolled-up status plus per-check details (JSON array)
+ALTER TABLE merges ADD COLUMN pr_ci_status TEXT
+    CHECK (pr_ci_status IN ('pending', 'success', 'failure'));
+ALTER TABLE merges ADD COLUMN pr_ci_checks TEXT;
@@ -0,0 +1,13 @@
+-- Per-project identity and signing for commits made on behalf of the agent
+CREATE TABLE project_commit_settings (
+    project_id       BLOB PRIMARY KEY,
+    author_name      TEXT,
+    author_email     TEXT,
+    co_author_user   BOOLEAN NOT NULL DEFAULT 0,
+    signing          TEXT NOT NULL DEFAULT 'none'
+                        CHECK (signing IN ('none', 'gpg', 'ssh')),
+    signing_key      TEXT,
+    created_at       TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at       TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
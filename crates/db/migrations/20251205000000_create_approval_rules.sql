@@ -0,0 +1,18 @@
+-- Declarative approval policy for agent tool calls. Rules without a project apply to
+-- every project; rules without an executor apply to every executor profile.
+CREATE TABLE approval_rules (
+    id              BLOB PRIMARY KEY,
+    project_id      BLOB,
+    executor        TEXT,
+    variant         TEXT,
+    tool_pattern    TEXT NOT NULL,
+    input_pattern   TEXT,
+    action          TEXT NOT NULL CHECK (action IN ('allow', 'deny', 'ask')),
+    description     TEXT,
+    position        INTEGER NOT NULL DEFAULT 0,
+    created_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_approval_rules_project_id ON approval_rules(project_id);
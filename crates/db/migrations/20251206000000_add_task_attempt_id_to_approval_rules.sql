@@ -0,0 +1,5 @@
+-- Rules remembered from an approval can be limited to the attempt they were granted in
+ALTER TABLE approval_rules
+    ADD COLUMN task_attempt_id BLOB REFERENCES task_attempts(id) ON DELETE CASCADE;
+
+CREATE INDEX idx_approval_rules_task_attempt_id ON approval_rules(task_attempt_id);
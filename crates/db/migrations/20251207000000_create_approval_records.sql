@@ -0,0 +1,22 @@
+-- Durable record of every tool approval request and how it was decided, so pending
+-- requests survive restarts and decisions can be audited.
+CREATE TABLE approval_records (
+    id                   TEXT PRIMARY KEY,
+    execution_process_id BLOB NOT NULL,
+    tool_name            TEXT NOT NULL,
+    tool_input           TEXT NOT NULL DEFAULT 'null',
+    tool_call_id         TEXT NOT NULL,
+    status               TEXT NOT NULL DEFAULT 'pending'
+                         CHECK (status IN ('pending', 'approved', 'denied', 'timed_out')),
+    denial_reason        TEXT,
+    responded_by         TEXT,
+    approval_rule_id     BLOB,
+    created_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    timeout_at           TEXT NOT NULL,
+    responded_at         TEXT,
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE,
+    FOREIGN KEY (approval_rule_id) REFERENCES approval_rules(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_approval_records_execution_process_id ON approval_records(execution_process_id);
+CREATE INDEX idx_approval_records_status ON approval_records(status);
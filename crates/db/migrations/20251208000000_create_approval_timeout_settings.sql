@@ -0,0 +1,20 @@
+-- Approval timeout overrides for a project and/or executor profile. The most specific
+-- matching row wins for each setting; the app config supplies the defaults.
+CREATE TABLE approval_timeout_settings (
+    id              BLOB PRIMARY KEY,
+    project_id      BLOB,
+    executor        TEXT,
+    variant         TEXT,
+    timeout_seconds INTEGER CHECK (timeout_seconds IS NULL OR timeout_seconds > 0),
+    timeout_action  TEXT CHECK (timeout_action IS NULL OR timeout_action IN ('deny', 'approve', 'pause')),
+    created_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_approval_timeout_settings_project_id ON approval_timeout_settings(project_id);
+
+-- Remember the timeout outcome of each request so paused requests survive restarts
+ALTER TABLE approval_records
+    ADD COLUMN timeout_action TEXT NOT NULL DEFAULT 'deny'
+    CHECK (timeout_action IN ('deny', 'approve', 'pause'));
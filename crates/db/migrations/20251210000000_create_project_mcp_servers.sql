@@ -0,0 +1,10 @@
+-- MCP servers added to every coding agent run of a project, keyed by name in the
+-- canonical `mcpServers` shape. They are passed to the agent per execution and never
+-- written to the agent's global config file.
+CREATE TABLE project_mcp_servers (
+    project_id  BLOB PRIMARY KEY,
+    servers     TEXT NOT NULL DEFAULT '{}',
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
@@ -0,0 +1,29 @@
+-- Stable ids for the markdown documents in a project's `anyon-docs` folder, so
+-- renaming or moving a document keeps its id and history. `path` is relative to
+-- `anyon-docs`, e.g. `plan/roadmap.md`.
+CREATE TABLE documents (
+    id         BLOB PRIMARY KEY,
+    project_id BLOB NOT NULL,
+    path       TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE,
+    UNIQUE (project_id, path)
+);
+
+-- Full snapshot of a document after each save, numbered from 1 per document
+CREATE TABLE document_revisions (
+    id            BLOB PRIMARY KEY,
+    document_id   BLOB NOT NULL,
+    revision      INTEGER NOT NULL,
+    title         TEXT NOT NULL,
+    category      TEXT NOT NULL,
+    content       TEXT NOT NULL,
+    source        TEXT NOT NULL DEFAULT 'edit'
+                     CHECK (source IN ('edit', 'external', 'restore')),
+    -- Revision whose contents a `restore` revision brought back
+    restored_from INTEGER,
+    created_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (document_id) REFERENCES documents(id) ON DELETE CASCADE,
+    UNIQUE (document_id, revision)
+);
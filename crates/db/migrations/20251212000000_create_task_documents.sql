@@ -0,0 +1,12 @@
+-- Project documents (PRD, architecture, ...) a task is based on. Their contents are
+-- given to the coding agent when an attempt of the task starts.
+CREATE TABLE task_documents (
+    task_id     BLOB NOT NULL,
+    document_id BLOB NOT NULL,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    PRIMARY KEY (task_id, document_id),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (document_id) REFERENCES documents(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_task_documents_document_id ON task_documents(document_id);
@@ -0,0 +1,16 @@
+-- Runs that split a planning document into kanban tasks. The agent runs as an
+-- attempt of a task of its own; its reply is parsed into a preview, and the tasks
+-- are created once the preview is applied.
+CREATE TABLE task_breakdowns (
+    id              BLOB PRIMARY KEY,
+    project_id      BLOB NOT NULL,
+    document_id     BLOB NOT NULL,
+    task_attempt_id BLOB NOT NULL,
+    applied_at      TEXT,
+    created_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE,
+    FOREIGN KEY (document_id) REFERENCES documents(id) ON DELETE CASCADE,
+    FOREIGN KEY (task_attempt_id) REFERENCES task_attempts(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_task_breakdowns_project_id ON task_breakdowns(project_id);
@@ -0,0 +1,25 @@
+-- Every plan a plan mode run presented for a task, numbered from 1 per task, with
+-- the reviewer's decision. `tasks.plan` keeps the latest plan for prompts.
+CREATE TABLE task_plan_revisions (
+    id                   BLOB PRIMARY KEY,
+    task_id              BLOB NOT NULL,
+    revision             INTEGER NOT NULL,
+    plan                 TEXT NOT NULL,
+    -- Plan mode run that presented the plan
+    execution_process_id BLOB,
+    status               TEXT NOT NULL DEFAULT 'proposed'
+                            CHECK (status IN ('proposed', 'changes_requested', 'approved')),
+    -- Changes the reviewer asked for, when `changes_requested`
+    feedback             TEXT,
+    created_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE SET NULL,
+    UNIQUE (task_id, revision)
+);
+
+-- Plans saved before the history existed become their task's first revision
+INSERT INTO task_plan_revisions (id, task_id, revision, plan)
+SELECT randomblob(16), id, 1, plan
+FROM tasks
+WHERE plan IS NOT NULL AND trim(plan) != '';
@@ -0,0 +1,13 @@
+-- Checklist parsed from a task's approved plan, in plan order. The agent's todo
+-- updates during implementation move steps to `in_progress` and `completed`.
+CREATE TABLE task_plan_steps (
+    id         BLOB PRIMARY KEY,
+    task_id    BLOB NOT NULL,
+    position   INTEGER NOT NULL,
+    title      TEXT NOT NULL,
+    status     TEXT NOT NULL DEFAULT 'pending'
+                  CHECK (status IN ('pending', 'in_progress', 'completed')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    UNIQUE (task_id, position)
+);
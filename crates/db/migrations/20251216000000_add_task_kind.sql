@@ -0,0 +1,9 @@
+-- What a task is for. Planning conversations used to be recognised by their
+-- fixed title and description; those rows are carried over to the new kind.
+ALTER TABLE tasks ADD COLUMN kind TEXT NOT NULL DEFAULT 'implementation'
+    CHECK (kind IN ('implementation', 'planning_conversation', 'review', 'chore'));
+
+UPDATE tasks
+   SET kind = 'planning_conversation'
+ WHERE title = 'Planning Conversation'
+   AND description = 'Auto-generated planning chat session for the Conversation tab.';
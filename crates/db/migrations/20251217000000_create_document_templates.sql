@@ -0,0 +1,128 @@
+-- Markdown templates for project documents, grouped in packs. Templates may use
+-- the {{project_name}}, {{repo_name}}, {{repo_path}} and {{date}} placeholders.
+CREATE TABLE document_template_packs (
+    id          BLOB PRIMARY KEY,
+    name        TEXT NOT NULL UNIQUE,
+    description TEXT,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec'))
+);
+
+CREATE TABLE document_templates (
+    id         BLOB PRIMARY KEY,
+    pack_id    BLOB NOT NULL,
+    title      TEXT NOT NULL,
+    category   TEXT NOT NULL DEFAULT 'planning',
+    content    TEXT NOT NULL DEFAULT '',
+    position   INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (pack_id) REFERENCES document_template_packs(id) ON DELETE CASCADE,
+    UNIQUE (pack_id, category, title)
+);
+
+CREATE INDEX idx_document_templates_pack_id ON document_templates(pack_id);
+
+-- The conversation docs the planning conversation fills in; this pack has a fixed id
+-- so the app can find it
+INSERT INTO document_template_packs (id, name, description) VALUES (
+    X'6F1F3C1E5A0B4D8E9C3A2B7D4E1F0A01',
+    'Planning conversation',
+    'Documents of the Conversation tab, created when it is first opened.'
+);
+
+INSERT INTO document_templates (id, pack_id, title, category, content, position) VALUES
+(randomblob(16), X'6F1F3C1E5A0B4D8E9C3A2B7D4E1F0A01', 'prd', 'conversation',
+'# {{project_name}} PRD
+
+## Problem
+
+## Users
+
+## Goals
+
+## Features
+
+## Out of scope
+', 0),
+(randomblob(16), X'6F1F3C1E5A0B4D8E9C3A2B7D4E1F0A01', 'ux-design', 'conversation',
+'# {{project_name}} UX Design
+
+## User flows
+
+## Screens
+', 1),
+(randomblob(16), X'6F1F3C1E5A0B4D8E9C3A2B7D4E1F0A01', 'design-guide', 'conversation',
+'# {{project_name}} Design Guide
+
+## Colors
+
+## Typography
+
+## Components
+', 2),
+(randomblob(16), X'6F1F3C1E5A0B4D8E9C3A2B7D4E1F0A01', 'trd', 'conversation',
+'# {{project_name}} TRD
+
+## Requirements
+
+## Constraints
+', 3),
+(randomblob(16), X'6F1F3C1E5A0B4D8E9C3A2B7D4E1F0A01', 'architecture', 'conversation',
+'# {{project_name}} Architecture
+
+Repository: `{{repo_name}}`
+
+## Components
+
+## Data flow
+', 4),
+(randomblob(16), X'6F1F3C1E5A0B4D8E9C3A2B7D4E1F0A01', 'erd', 'conversation',
+'# {{project_name}} ERD
+
+## Entities
+
+## Relationships
+', 5);
+
+INSERT INTO document_template_packs (id, name, description) VALUES (
+    randomblob(16),
+    'Project kickoff',
+    'A product brief, a decision log and a tech stack overview for a new project.'
+);
+
+INSERT INTO document_templates (id, pack_id, title, category, content, position)
+SELECT randomblob(16), id, 'product-brief', 'planning',
+'# {{project_name}} Product Brief
+
+Created {{date}}
+
+## Summary
+
+## Target users
+
+## Success metrics
+', 0
+FROM document_template_packs WHERE name = 'Project kickoff';
+
+INSERT INTO document_templates (id, pack_id, title, category, content, position)
+SELECT randomblob(16), id, 'decision-log', 'planning',
+'# {{project_name}} Decision Log
+
+| Date | Decision | Reason |
+| ---- | -------- | ------ |
+| {{date}} | Project started | |
+', 1
+FROM document_template_packs WHERE name = 'Project kickoff';
+
+INSERT INTO document_templates (id, pack_id, title, category, content, position)
+SELECT randomblob(16), id, 'tech-stack', 'technology',
+'# {{project_name}} Tech Stack
+
+Repository: `{{repo_path}}`
+
+## Languages and frameworks
+
+## Infrastructure
+', 2
+FROM document_template_packs WHERE name = 'Project kickoff';
@@ -0,0 +1,41 @@
+-- Search index of the markdown documents in `anyon-docs`. The files stay the source
+-- of truth; the index is brought up to date from them before each search.
+CREATE VIRTUAL TABLE document_search USING fts5(
+    document_id UNINDEXED,
+    project_id UNINDEXED,
+    title,
+    headings,
+    code,
+    body,
+    tokenize = 'unicode61 remove_diacritics 2'
+);
+
+-- Hash of the contents each document was last indexed with
+CREATE TABLE document_search_state (
+    document_id  BLOB PRIMARY KEY,
+    project_id   BLOB NOT NULL,
+    content_hash TEXT NOT NULL,
+    indexed_at   TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (document_id) REFERENCES documents(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_document_search_state_project_id ON document_search_state(project_id);
+
+-- Embedded sections of documents for semantic search, per embedding model.
+-- Vectors are stored as little-endian f32 values.
+CREATE TABLE document_embeddings (
+    id           BLOB PRIMARY KEY,
+    document_id  BLOB NOT NULL,
+    project_id   BLOB NOT NULL,
+    model        TEXT NOT NULL,
+    chunk_index  INTEGER NOT NULL,
+    heading      TEXT,
+    content      TEXT NOT NULL,
+    content_hash TEXT NOT NULL,
+    vector       BLOB NOT NULL,
+    created_at   TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (document_id) REFERENCES documents(id) ON DELETE CASCADE,
+    UNIQUE (document_id, model, chunk_index)
+);
+
+CREATE INDEX idx_document_embeddings_project_model ON document_embeddings(project_id, model);
@@ -0,0 +1,3 @@
+-- Input field a rule's pattern is matched against, e.g. the command a remembered
+-- approval was granted for. Rules without one use the call's command, file path or URL.
+ALTER TABLE approval_rules ADD COLUMN input_field TEXT;
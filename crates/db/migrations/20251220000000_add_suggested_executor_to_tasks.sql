@@ -0,0 +1,3 @@
+-- Executor suggested when the task was created, e.g. by the agent splitting a task
+-- into subtasks. New attempts default to it.
+ALTER TABLE tasks ADD COLUMN suggested_executor TEXT;
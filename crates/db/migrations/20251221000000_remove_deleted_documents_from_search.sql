@@ -0,0 +1,11 @@
+-- The full-text table can't reference documents, so deleting a document cascades
+-- to its search state but left its row in document_search behind.
+DELETE FROM document_search
+WHERE document_id NOT IN (SELECT id FROM documents);
+
+CREATE TRIGGER IF NOT EXISTS trg_documents_delete_search
+AFTER DELETE ON documents
+FOR EACH ROW
+BEGIN
+    DELETE FROM document_search WHERE document_id = OLD.id;
+END;
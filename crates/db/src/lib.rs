@@ -7,6 +7,8 @@ use sqlx::{
 use utils::assets::asset_dir;
 
 pub mod models;
+#[cfg(feature = "test-utils")]
+pub mod test_utils;
 
 #[derive(Clone)]
 pub struct DBService {
@@ -25,6 +27,20 @@ impl DBService {
         Ok(DBService { pool })
     }
 
+    /// Fresh in-memory database with all migrations applied, for tests. The pool keeps
+    /// a single connection alive, since every connection would see its own database.
+    pub async fn new_in_memory() -> Result<DBService, Error> {
+        let options = SqliteConnectOptions::from_str("sqlite::memory:")?;
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+            .connect_with(options)
+            .await?;
+        sqlx::migrate!("./migrations").run(&pool).await?;
+        Ok(DBService { pool })
+    }
+
     pub async fn new_with_after_connect<F>(after_connect: F) -> Result<DBService, Error>
     where
         F: for<'a> Fn(
@@ -0,0 +1,189 @@
+use chrono::{DateTime, Utc};
+use executors::approvals::ApprovalTimeoutAction;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{FromRow, SqlitePool, Type};
+use ts_rs::TS;
+use utils::approvals::{ApprovalRequest, ApprovalStatus};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[sqlx(type_name = "approval_record_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalRecordStatus {
+    Pending,
+    Approved,
+    Denied,
+    TimedOut,
+}
+
+impl From<&ApprovalStatus> for ApprovalRecordStatus {
+    fn from(status: &ApprovalStatus) -> Self {
+        match status {
+            ApprovalStatus::Pending => Self::Pending,
+            ApprovalStatus::Approved => Self::Approved,
+            ApprovalStatus::Denied { .. } => Self::Denied,
+            ApprovalStatus::TimedOut => Self::TimedOut,
+        }
+    }
+}
+
+/// A tool approval request and its outcome, kept for restarts and auditing
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ApprovalRecord {
+    pub id: String,
+    pub execution_process_id: Uuid,
+    pub tool_name: String,
+    #[ts(type = "JsonValue")]
+    pub tool_input: sqlx::types::Json<Value>,
+    pub tool_call_id: String,
+    pub status: ApprovalRecordStatus,
+    pub denial_reason: Option<String>,
+    /// Who answered the request; `None` for rule decisions and timeouts
+    pub responded_by: Option<String>,
+    /// Approval rule that decided the request without asking
+    pub approval_rule_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub timeout_at: DateTime<Utc>,
+    pub timeout_action: ApprovalTimeoutAction,
+    pub responded_at: Option<DateTime<Utc>>,
+}
+
+impl ApprovalRecord {
+    /// Persist a request, either still pending or already decided by an approval rule.
+    pub async fn create(
+        pool: &SqlitePool,
+        request: &ApprovalRequest,
+        timeout_action: ApprovalTimeoutAction,
+        status: &ApprovalStatus,
+        approval_rule_id: Option<Uuid>,
+    ) -> Result<Self, sqlx::Error> {
+        let tool_input = sqlx::types::Json(request.tool_input.clone());
+        let record_status = ApprovalRecordStatus::from(status);
+        let denial_reason = match status {
+            ApprovalStatus::Denied { reason } => reason.clone(),
+            _ => None,
+        };
+        let responded_at = (record_status != ApprovalRecordStatus::Pending).then(Utc::now);
+
+        sqlx::query_as!(
+            ApprovalRecord,
+            r#"INSERT INTO approval_records
+                   (id, execution_process_id, tool_name, tool_input, tool_call_id, status,
+                    denial_reason, approval_rule_id, created_at, timeout_at, timeout_action,
+                    responded_at)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+               RETURNING id as "id!",
+                         execution_process_id as "execution_process_id!: Uuid",
+                         tool_name,
+                         tool_input as "tool_input!: sqlx::types::Json<Value>",
+                         tool_call_id,
+                         status as "status!: ApprovalRecordStatus",
+                         denial_reason,
+                         responded_by,
+                         approval_rule_id as "approval_rule_id: Uuid",
+                         created_at as "created_at!: DateTime<Utc>",
+                         timeout_at as "timeout_at!: DateTime<Utc>",
+                         timeout_action as "timeout_action!: ApprovalTimeoutAction",
+                         responded_at as "responded_at: DateTime<Utc>""#,
+            request.id,
+            request.execution_process_id,
+            request.tool_name,
+            tool_input,
+            request.tool_call_id,
+            record_status,
+            denial_reason,
+            approval_rule_id,
+            request.created_at,
+            request.timeout_at,
+            timeout_action,
+            responded_at
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Record the outcome of a pending request. Returns `false` when the request was
+    /// already decided.
+    pub async fn resolve(
+        pool: &SqlitePool,
+        id: &str,
+        status: &ApprovalStatus,
+        responded_by: Option<&str>,
+    ) -> Result<bool, sqlx::Error> {
+        let record_status = ApprovalRecordStatus::from(status);
+        let denial_reason = match status {
+            ApprovalStatus::Denied { reason } => reason.clone(),
+            _ => None,
+        };
+        let result = sqlx::query!(
+            r#"UPDATE approval_records
+               SET status = $2,
+                   denial_reason = $3,
+                   responded_by = $4,
+                   responded_at = datetime('now', 'subsec')
+               WHERE id = $1 AND status = 'pending'"#,
+            id,
+            record_status,
+            denial_reason,
+            responded_by
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn find_pending(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ApprovalRecord,
+            r#"SELECT id as "id!",
+                      execution_process_id as "execution_process_id!: Uuid",
+                      tool_name,
+                      tool_input as "tool_input!: sqlx::types::Json<Value>",
+                      tool_call_id,
+                      status as "status!: ApprovalRecordStatus",
+                      denial_reason,
+                      responded_by,
+                      approval_rule_id as "approval_rule_id: Uuid",
+                      created_at as "created_at!: DateTime<Utc>",
+                      timeout_at as "timeout_at!: DateTime<Utc>",
+                      timeout_action as "timeout_action!: ApprovalTimeoutAction",
+                      responded_at as "responded_at: DateTime<Utc>"
+               FROM approval_records
+               WHERE status = 'pending'
+               ORDER BY created_at ASC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Approval history of an attempt, newest first
+    pub async fn find_by_task_attempt_id(
+        pool: &SqlitePool,
+        task_attempt_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ApprovalRecord,
+            r#"SELECT ar.id as "id!",
+                      ar.execution_process_id as "execution_process_id!: Uuid",
+                      ar.tool_name,
+                      ar.tool_input as "tool_input!: sqlx::types::Json<Value>",
+                      ar.tool_call_id,
+                      ar.status as "status!: ApprovalRecordStatus",
+                      ar.denial_reason,
+                      ar.responded_by,
+                      ar.approval_rule_id as "approval_rule_id: Uuid",
+                      ar.created_at as "created_at!: DateTime<Utc>",
+                      ar.timeout_at as "timeout_at!: DateTime<Utc>",
+                      ar.timeout_action as "timeout_action!: ApprovalTimeoutAction",
+                      ar.responded_at as "responded_at: DateTime<Utc>"
+               FROM approval_records ar
+               JOIN execution_processes ep ON ep.id = ar.execution_process_id
+               WHERE ep.task_attempt_id = $1
+               ORDER BY ar.created_at DESC"#,
+            task_attempt_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
@@ -0,0 +1,276 @@
+use chrono::{DateTime, Utc};
+use executors::executors::BaseCodingAgent;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[sqlx(type_name = "approval_rule_action", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalRuleAction {
+    /// Run the tool without asking
+    Allow,
+    /// Reject the tool call
+    Deny,
+    /// Wait for a human decision, even when a broader rule would allow it
+    Ask,
+}
+
+/// A rule of the approval policy applied to agent tool calls
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ApprovalRule {
+    pub id: Uuid,
+    /// Project the rule is limited to; applies to every project when `None`
+    pub project_id: Option<Uuid>,
+    /// Attempt the rule is limited to, for approvals remembered for one attempt
+    pub task_attempt_id: Option<Uuid>,
+    /// Executor the rule is limited to; applies to every executor when `None`
+    pub executor: Option<BaseCodingAgent>,
+    /// Executor profile variant the rule is limited to
+    pub variant: Option<String>,
+    /// Regex that must match the whole tool name
+    pub tool_pattern: String,
+    /// Regex searched for in the command, file path or URL of the tool input; matches
+    /// any input when `None`
+    pub input_pattern: Option<String>,
+    /// Tool input field `input_pattern` is matched against; the command, file path
+    /// or URL of the call when `None`
+    pub input_field: Option<String>,
+    pub action: ApprovalRuleAction,
+    /// Shown in the conversation (and to the agent on deny) when the rule applies
+    pub description: Option<String>,
+    pub position: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateApprovalRule {
+    pub project_id: Option<Uuid>,
+    pub task_attempt_id: Option<Uuid>,
+    pub executor: Option<BaseCodingAgent>,
+    pub variant: Option<String>,
+    pub tool_pattern: String,
+    pub input_pattern: Option<String>,
+    pub input_field: Option<String>,
+    pub action: ApprovalRuleAction,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateApprovalRule {
+    pub executor: Option<BaseCodingAgent>,
+    pub variant: Option<String>,
+    pub tool_pattern: String,
+    pub input_pattern: Option<String>,
+    pub input_field: Option<String>,
+    pub action: ApprovalRuleAction,
+    pub description: Option<String>,
+    pub position: Option<i64>,
+}
+
+impl ApprovalRule {
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ApprovalRule,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id: Uuid",
+                      task_attempt_id as "task_attempt_id: Uuid",
+                      executor as "executor: BaseCodingAgent",
+                      variant,
+                      tool_pattern,
+                      input_pattern,
+                      input_field,
+                      action as "action!: ApprovalRuleAction",
+                      description,
+                      position as "position!: i64",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM approval_rules
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Rules scoped to exactly this project (or the global rules when `None`),
+    /// excluding rules limited to a single attempt
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Option<Uuid>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ApprovalRule,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id: Uuid",
+                      task_attempt_id as "task_attempt_id: Uuid",
+                      executor as "executor: BaseCodingAgent",
+                      variant,
+                      tool_pattern,
+                      input_pattern,
+                      input_field,
+                      action as "action!: ApprovalRuleAction",
+                      description,
+                      position as "position!: i64",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM approval_rules
+               WHERE project_id IS $1 AND task_attempt_id IS NULL
+               ORDER BY position ASC, created_at ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_task_attempt_id(
+        pool: &SqlitePool,
+        task_attempt_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ApprovalRule,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id: Uuid",
+                      task_attempt_id as "task_attempt_id: Uuid",
+                      executor as "executor: BaseCodingAgent",
+                      variant,
+                      tool_pattern,
+                      input_pattern,
+                      input_field,
+                      action as "action!: ApprovalRuleAction",
+                      description,
+                      position as "position!: i64",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM approval_rules
+               WHERE task_attempt_id = $1
+               ORDER BY position ASC, created_at ASC"#,
+            task_attempt_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Global rules plus the rules of the given project and attempt, in evaluation order
+    pub async fn find_applicable(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        task_attempt_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ApprovalRule,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id: Uuid",
+                      task_attempt_id as "task_attempt_id: Uuid",
+                      executor as "executor: BaseCodingAgent",
+                      variant,
+                      tool_pattern,
+                      input_pattern,
+                      input_field,
+                      action as "action!: ApprovalRuleAction",
+                      description,
+                      position as "position!: i64",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM approval_rules
+               WHERE (project_id IS NULL OR project_id = $1)
+                 AND (task_attempt_id IS NULL OR task_attempt_id = $2)
+               ORDER BY position ASC, created_at ASC"#,
+            project_id,
+            task_attempt_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn create(pool: &SqlitePool, data: &CreateApprovalRule) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            ApprovalRule,
+            r#"INSERT INTO approval_rules
+                   (id, project_id, task_attempt_id, executor, variant, tool_pattern, input_pattern, action, description, input_field, position)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10,
+                       (SELECT COALESCE(MAX(position), -1) + 1 FROM approval_rules
+                        WHERE project_id IS $2 AND task_attempt_id IS $3))
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id: Uuid",
+                         task_attempt_id as "task_attempt_id: Uuid",
+                         executor as "executor: BaseCodingAgent",
+                         variant,
+                         tool_pattern,
+                         input_pattern,
+                         input_field,
+                         action as "action!: ApprovalRuleAction",
+                         description,
+                         position as "position!: i64",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            data.project_id,
+            data.task_attempt_id,
+            data.executor,
+            data.variant,
+            data.tool_pattern,
+            data.input_pattern,
+            data.action,
+            data.description,
+            data.input_field
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn update(
+        pool: &SqlitePool,
+        id: Uuid,
+        data: &UpdateApprovalRule,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            ApprovalRule,
+            r#"UPDATE approval_rules
+               SET executor = $2,
+                   variant = $3,
+                   tool_pattern = $4,
+                   input_pattern = $5,
+                   action = $6,
+                   description = $7,
+                   position = COALESCE($8, position),
+                   input_field = $9,
+                   updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id: Uuid",
+                         task_attempt_id as "task_attempt_id: Uuid",
+                         executor as "executor: BaseCodingAgent",
+                         variant,
+                         tool_pattern,
+                         input_pattern,
+                         input_field,
+                         action as "action!: ApprovalRuleAction",
+                         description,
+                         position as "position!: i64",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            data.executor,
+            data.variant,
+            data.tool_pattern,
+            data.input_pattern,
+            data.action,
+            data.description,
+            data.position,
+            data.input_field
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM approval_rules WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
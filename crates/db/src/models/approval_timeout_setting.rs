@@ -0,0 +1,178 @@
+use chrono::{DateTime, Utc};
+use executors::{approvals::ApprovalTimeoutAction, executors::BaseCodingAgent};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Approval timeout override for a project and/or executor profile
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ApprovalTimeoutSetting {
+    pub id: Uuid,
+    /// Project the override applies to; every project when `None`
+    pub project_id: Option<Uuid>,
+    /// Executor the override applies to; every executor when `None`
+    pub executor: Option<BaseCodingAgent>,
+    /// Executor profile variant the override applies to
+    pub variant: Option<String>,
+    /// Keeps the broader setting when `None`
+    pub timeout_seconds: Option<i64>,
+    /// Keeps the broader setting when `None`
+    pub timeout_action: Option<ApprovalTimeoutAction>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateApprovalTimeoutSetting {
+    pub project_id: Option<Uuid>,
+    pub executor: Option<BaseCodingAgent>,
+    pub variant: Option<String>,
+    pub timeout_seconds: Option<i64>,
+    pub timeout_action: Option<ApprovalTimeoutAction>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateApprovalTimeoutSetting {
+    pub executor: Option<BaseCodingAgent>,
+    pub variant: Option<String>,
+    pub timeout_seconds: Option<i64>,
+    pub timeout_action: Option<ApprovalTimeoutAction>,
+}
+
+impl ApprovalTimeoutSetting {
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ApprovalTimeoutSetting,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id: Uuid",
+                      executor as "executor: BaseCodingAgent",
+                      variant,
+                      timeout_seconds,
+                      timeout_action as "timeout_action: ApprovalTimeoutAction",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM approval_timeout_settings
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Overrides scoped to exactly this project (or the global ones when `None`)
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Option<Uuid>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ApprovalTimeoutSetting,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id: Uuid",
+                      executor as "executor: BaseCodingAgent",
+                      variant,
+                      timeout_seconds,
+                      timeout_action as "timeout_action: ApprovalTimeoutAction",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM approval_timeout_settings
+               WHERE project_id IS $1
+               ORDER BY created_at ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Global overrides plus the overrides of the given project
+    pub async fn find_applicable(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ApprovalTimeoutSetting,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id: Uuid",
+                      executor as "executor: BaseCodingAgent",
+                      variant,
+                      timeout_seconds,
+                      timeout_action as "timeout_action: ApprovalTimeoutAction",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM approval_timeout_settings
+               WHERE project_id IS NULL OR project_id = $1
+               ORDER BY created_at ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        data: &CreateApprovalTimeoutSetting,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            ApprovalTimeoutSetting,
+            r#"INSERT INTO approval_timeout_settings
+                   (id, project_id, executor, variant, timeout_seconds, timeout_action)
+               VALUES ($1, $2, $3, $4, $5, $6)
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id: Uuid",
+                         executor as "executor: BaseCodingAgent",
+                         variant,
+                         timeout_seconds,
+                         timeout_action as "timeout_action: ApprovalTimeoutAction",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            data.project_id,
+            data.executor,
+            data.variant,
+            data.timeout_seconds,
+            data.timeout_action
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn update(
+        pool: &SqlitePool,
+        id: Uuid,
+        data: &UpdateApprovalTimeoutSetting,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            ApprovalTimeoutSetting,
+            r#"UPDATE approval_timeout_settings
+               SET executor = $2,
+                   variant = $3,
+                   timeout_seconds = $4,
+                   timeout_action = $5,
+                   updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id: Uuid",
+                         executor as "executor: BaseCodingAgent",
+                         variant,
+                         timeout_seconds,
+                         timeout_action as "timeout_action: ApprovalTimeoutAction",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            data.executor,
+            data.variant,
+            data.timeout_seconds,
+            data.timeout_action
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM approval_timeout_settings WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
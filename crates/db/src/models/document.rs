@@ -0,0 +1,146 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use uuid::Uuid;
+
+/// Stable id of a markdown document in a project's `anyon-docs` folder. The id stays
+/// the same when the document is renamed or moved to another category.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct DocumentEntry {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    /// Relative to `anyon-docs`, with `/` separators, e.g. `plan/roadmap.md`
+    pub path: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl DocumentEntry {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            DocumentEntry,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      path,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM documents
+               WHERE project_id = $1"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            DocumentEntry,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      path,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM documents
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        id: Uuid,
+        project_id: Uuid,
+        path: &str,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            DocumentEntry,
+            r#"INSERT INTO documents (id, project_id, path)
+               VALUES ($1, $2, $3)
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id!: Uuid",
+                         path,
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            project_id,
+            path
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Store `path` under `id`, unless it is stored already, e.g. by a scan running
+    /// at the same time with the same id. Returns the stored entry either way.
+    pub async fn find_or_create(
+        pool: &SqlitePool,
+        id: Uuid,
+        project_id: Uuid,
+        path: &str,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query!(
+            r#"INSERT INTO documents (id, project_id, path)
+               VALUES ($1, $2, $3)
+               ON CONFLICT DO NOTHING"#,
+            id,
+            project_id,
+            path
+        )
+        .execute(pool)
+        .await?;
+        sqlx::query_as!(
+            DocumentEntry,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      path,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM documents
+               WHERE project_id = $1 AND path = $2"#,
+            project_id,
+            path
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn update_path(pool: &SqlitePool, id: Uuid, path: &str) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE documents
+               SET path = $2, updated_at = datetime('now', 'subsec')
+               WHERE id = $1"#,
+            id,
+            path
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query!("DELETE FROM documents WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Forget the document that used to live at `path`, whose file is gone
+    pub async fn delete_by_path(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        path: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "DELETE FROM documents WHERE project_id = $1 AND path = $2",
+            project_id,
+            path
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,163 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[sqlx(type_name = "document_revision_source", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum DocumentRevisionSource {
+    /// Saved through the documents API
+    Edit,
+    /// Changed on disk outside the app, e.g. by an agent or an editor; recorded the
+    /// next time the document's history is touched
+    External,
+    Restore,
+}
+
+/// Snapshot of a document after a save
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct DocumentRevision {
+    pub id: Uuid,
+    pub document_id: Uuid,
+    /// Numbered from 1 per document
+    pub revision: i64,
+    pub title: String,
+    pub category: String,
+    pub content: String,
+    pub source: DocumentRevisionSource,
+    /// Revision whose contents a restore brought back
+    pub restored_from: Option<i64>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Revision without its content, for history listings
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct DocumentRevisionSummary {
+    pub id: Uuid,
+    pub document_id: Uuid,
+    pub revision: i64,
+    pub title: String,
+    pub category: String,
+    pub source: DocumentRevisionSource,
+    pub restored_from: Option<i64>,
+    /// Content length in bytes
+    pub size: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+impl DocumentRevision {
+    pub async fn create(
+        pool: &SqlitePool,
+        document_id: Uuid,
+        title: &str,
+        category: &str,
+        content: &str,
+        source: DocumentRevisionSource,
+        restored_from: Option<i64>,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            DocumentRevision,
+            r#"INSERT INTO document_revisions
+                   (id, document_id, revision, title, category, content, source, restored_from)
+               SELECT $1, $2, COALESCE(MAX(revision), 0) + 1, $3, $4, $5, $6, $7
+               FROM document_revisions
+               WHERE document_id = $2
+               RETURNING id as "id!: Uuid",
+                         document_id as "document_id!: Uuid",
+                         revision as "revision!: i64",
+                         title,
+                         category,
+                         content,
+                         source as "source!: DocumentRevisionSource",
+                         restored_from,
+                         created_at as "created_at!: DateTime<Utc>""#,
+            id,
+            document_id,
+            title,
+            category,
+            content,
+            source,
+            restored_from
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn find_latest(
+        pool: &SqlitePool,
+        document_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            DocumentRevision,
+            r#"SELECT id as "id!: Uuid",
+                      document_id as "document_id!: Uuid",
+                      revision as "revision!: i64",
+                      title,
+                      category,
+                      content,
+                      source as "source!: DocumentRevisionSource",
+                      restored_from,
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM document_revisions
+               WHERE document_id = $1
+               ORDER BY revision DESC
+               LIMIT 1"#,
+            document_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_by_revision(
+        pool: &SqlitePool,
+        document_id: Uuid,
+        revision: i64,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            DocumentRevision,
+            r#"SELECT id as "id!: Uuid",
+                      document_id as "document_id!: Uuid",
+                      revision as "revision!: i64",
+                      title,
+                      category,
+                      content,
+                      source as "source!: DocumentRevisionSource",
+                      restored_from,
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM document_revisions
+               WHERE document_id = $1 AND revision = $2"#,
+            document_id,
+            revision
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// History of a document, newest first
+    pub async fn find_summaries(
+        pool: &SqlitePool,
+        document_id: Uuid,
+    ) -> Result<Vec<DocumentRevisionSummary>, sqlx::Error> {
+        sqlx::query_as!(
+            DocumentRevisionSummary,
+            r#"SELECT id as "id!: Uuid",
+                      document_id as "document_id!: Uuid",
+                      revision as "revision!: i64",
+                      title,
+                      category,
+                      source as "source!: DocumentRevisionSource",
+                      restored_from,
+                      length(CAST(content AS BLOB)) as "size!: i64",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM document_revisions
+               WHERE document_id = $1
+               ORDER BY revision DESC"#,
+            document_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
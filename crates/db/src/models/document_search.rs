@@ -0,0 +1,242 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, SqlitePool};
+use uuid::Uuid;
+
+/// Text of a document split into the fields the full-text index weighs differently
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DocumentSearchFields {
+    pub title: String,
+    pub headings: String,
+    pub code: String,
+    pub body: String,
+}
+
+/// Document matching a full-text query
+#[derive(Debug, Clone, FromRow)]
+pub struct DocumentSearchHit {
+    pub document_id: Uuid,
+    /// Matching passage with the matched terms wrapped in `**`
+    pub snippet: String,
+    /// bm25 rank; lower is better
+    pub rank: f64,
+}
+
+pub struct DocumentSearchIndex;
+
+impl DocumentSearchIndex {
+    /// Hashes of the contents the project's documents were indexed with
+    pub async fn content_hashes(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<HashMap<Uuid, String>, sqlx::Error> {
+        let records = sqlx::query!(
+            r#"SELECT document_id as "document_id!: Uuid", content_hash
+               FROM document_search_state
+               WHERE project_id = $1"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await?;
+        Ok(records
+            .into_iter()
+            .map(|record| (record.document_id, record.content_hash))
+            .collect())
+    }
+
+    /// Index a document, replacing what was indexed for it before
+    pub async fn upsert(
+        pool: &SqlitePool,
+        document_id: Uuid,
+        project_id: Uuid,
+        content_hash: &str,
+        fields: &DocumentSearchFields,
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        sqlx::query!(
+            "DELETE FROM document_search WHERE document_id = $1",
+            document_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query!(
+            r#"INSERT INTO document_search (document_id, project_id, title, headings, code, body)
+               VALUES ($1, $2, $3, $4, $5, $6)"#,
+            document_id,
+            project_id,
+            fields.title,
+            fields.headings,
+            fields.code,
+            fields.body
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query!(
+            r#"INSERT INTO document_search_state (document_id, project_id, content_hash)
+               VALUES ($1, $2, $3)
+               ON CONFLICT(document_id) DO UPDATE SET
+                   content_hash = excluded.content_hash,
+                   indexed_at = datetime('now', 'subsec')"#,
+            document_id,
+            project_id,
+            content_hash
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await
+    }
+
+    /// Drop a document from the full-text index and its embeddings
+    pub async fn remove(pool: &SqlitePool, document_id: Uuid) -> Result<(), sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        sqlx::query!(
+            "DELETE FROM document_search WHERE document_id = $1",
+            document_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query!(
+            "DELETE FROM document_search_state WHERE document_id = $1",
+            document_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query!(
+            "DELETE FROM document_embeddings WHERE document_id = $1",
+            document_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await
+    }
+
+    /// Best matches of an FTS5 query among a project's documents. Title matches
+    /// weigh most, then headings, then code blocks, then the rest of the text.
+    pub async fn search(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        match_query: &str,
+        limit: i64,
+    ) -> Result<Vec<DocumentSearchHit>, sqlx::Error> {
+        sqlx::query_as!(
+            DocumentSearchHit,
+            r#"SELECT document_id as "document_id!: Uuid",
+                      snippet(document_search, -1, '**', '**', '…', 16) as "snippet!: String",
+                      bm25(document_search, 0.0, 0.0, 10.0, 4.0, 2.0, 1.0) as "rank!: f64"
+               FROM document_search
+               WHERE document_search MATCH $1 AND project_id = $2
+               ORDER BY rank ASC
+               LIMIT $3"#,
+            match_query,
+            project_id,
+            limit
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
+
+/// Embedded section of a document
+#[derive(Debug, Clone, FromRow)]
+pub struct DocumentEmbedding {
+    pub id: Uuid,
+    pub document_id: Uuid,
+    pub chunk_index: i64,
+    /// Heading the section starts with, if any
+    pub heading: Option<String>,
+    pub content: String,
+    /// Hash of the document contents the section was embedded from
+    pub content_hash: String,
+    /// Little-endian f32 values
+    pub vector: Vec<u8>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Section of a document with its embedding, to be stored
+#[derive(Debug, Clone)]
+pub struct NewDocumentEmbedding {
+    pub heading: Option<String>,
+    pub content: String,
+    pub vector: Vec<f32>,
+}
+
+impl DocumentEmbedding {
+    pub fn vector(&self) -> Vec<f32> {
+        self.vector
+            .chunks_exact(4)
+            .map(|bytes| f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+            .collect()
+    }
+
+    /// Sections of a project's documents embedded with a model
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        model: &str,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            DocumentEmbedding,
+            r#"SELECT id as "id!: Uuid",
+                      document_id as "document_id!: Uuid",
+                      chunk_index as "chunk_index!: i64",
+                      heading,
+                      content,
+                      content_hash,
+                      vector,
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM document_embeddings
+               WHERE project_id = $1 AND model = $2
+               ORDER BY document_id, chunk_index ASC"#,
+            project_id,
+            model
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Replace the sections a document has embedded with a model
+    pub async fn replace_for_document(
+        pool: &SqlitePool,
+        document_id: Uuid,
+        project_id: Uuid,
+        model: &str,
+        content_hash: &str,
+        chunks: &[NewDocumentEmbedding],
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        sqlx::query!(
+            "DELETE FROM document_embeddings WHERE document_id = $1 AND model = $2",
+            document_id,
+            model
+        )
+        .execute(&mut *tx)
+        .await?;
+        for (chunk_index, chunk) in chunks.iter().enumerate() {
+            let id = Uuid::new_v4();
+            let chunk_index = chunk_index as i64;
+            let vector: Vec<u8> = chunk
+                .vector
+                .iter()
+                .flat_map(|value| value.to_le_bytes())
+                .collect();
+            sqlx::query!(
+                r#"INSERT INTO document_embeddings
+                       (id, document_id, project_id, model, chunk_index, heading, content, content_hash, vector)
+                   VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)"#,
+                id,
+                document_id,
+                project_id,
+                model,
+                chunk_index,
+                chunk.heading,
+                chunk.content,
+                content_hash,
+                vector
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await
+    }
+}
@@ -0,0 +1,302 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Pack holding the documents of the Conversation tab, seeded by its migration
+pub const CONVERSATION_PACK_ID: Uuid = Uuid::from_u128(0x6f1f3c1e_5a0b_4d8e_9c3a_2b7d4e1f0a01);
+
+/// Named group of document templates, e.g. to scaffold the docs of a new project
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct DocumentTemplatePack {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Markdown template of a document. The content may use the `{{project_name}}`,
+/// `{{repo_name}}`, `{{repo_path}}` and `{{date}}` placeholders.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct DocumentTemplate {
+    pub id: Uuid,
+    pub pack_id: Uuid,
+    /// Title of the documents created from it, i.e. their file name without `.md`
+    pub title: String,
+    /// Category the documents are created in unless another one is asked for
+    pub category: String,
+    pub content: String,
+    /// Order in the pack, from 0
+    pub position: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct DocumentTemplatePackWithTemplates {
+    #[serde(flatten)]
+    #[ts(flatten)]
+    pub pack: DocumentTemplatePack,
+    pub templates: Vec<DocumentTemplate>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct CreateDocumentTemplatePack {
+    pub name: String,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct UpdateDocumentTemplatePack {
+    pub name: Option<String>,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct CreateDocumentTemplate {
+    pub title: String,
+    pub category: String,
+    pub content: String,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct UpdateDocumentTemplate {
+    pub title: Option<String>,
+    pub category: Option<String>,
+    pub content: Option<String>,
+    pub position: Option<i64>,
+}
+
+impl DocumentTemplatePack {
+    pub async fn find_all(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            DocumentTemplatePack,
+            r#"SELECT id as "id!: Uuid",
+                      name,
+                      description,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM document_template_packs
+               ORDER BY name ASC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            DocumentTemplatePack,
+            r#"SELECT id as "id!: Uuid",
+                      name,
+                      description,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM document_template_packs
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        data: &CreateDocumentTemplatePack,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            DocumentTemplatePack,
+            r#"INSERT INTO document_template_packs (id, name, description)
+               VALUES ($1, $2, $3)
+               RETURNING id as "id!: Uuid",
+                         name,
+                         description,
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            data.name,
+            data.description
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn update(
+        pool: &SqlitePool,
+        id: Uuid,
+        data: &UpdateDocumentTemplatePack,
+    ) -> Result<Self, sqlx::Error> {
+        let existing = Self::find_by_id(pool, id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)?;
+
+        let name = data.name.as_ref().unwrap_or(&existing.name);
+        let description = data.description.as_ref().or(existing.description.as_ref());
+
+        sqlx::query_as!(
+            DocumentTemplatePack,
+            r#"UPDATE document_template_packs
+               SET name = $2, description = $3, updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid",
+                         name,
+                         description,
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            name,
+            description
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Delete the pack together with its templates
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM document_template_packs WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+impl DocumentTemplate {
+    pub async fn find_all(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            DocumentTemplate,
+            r#"SELECT id as "id!: Uuid",
+                      pack_id as "pack_id!: Uuid",
+                      title,
+                      category,
+                      content,
+                      position as "position!: i64",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM document_templates
+               ORDER BY position ASC, title ASC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Templates of a pack in pack order
+    pub async fn find_by_pack_id(
+        pool: &SqlitePool,
+        pack_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            DocumentTemplate,
+            r#"SELECT id as "id!: Uuid",
+                      pack_id as "pack_id!: Uuid",
+                      title,
+                      category,
+                      content,
+                      position as "position!: i64",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM document_templates
+               WHERE pack_id = $1
+               ORDER BY position ASC, title ASC"#,
+            pack_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            DocumentTemplate,
+            r#"SELECT id as "id!: Uuid",
+                      pack_id as "pack_id!: Uuid",
+                      title,
+                      category,
+                      content,
+                      position as "position!: i64",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM document_templates
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Add a template at the end of a pack
+    pub async fn create(
+        pool: &SqlitePool,
+        pack_id: Uuid,
+        data: &CreateDocumentTemplate,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            DocumentTemplate,
+            r#"INSERT INTO document_templates (id, pack_id, title, category, content, position)
+               SELECT $1, $2, $3, $4, $5, COALESCE(MAX(position) + 1, 0)
+               FROM document_templates
+               WHERE pack_id = $2
+               RETURNING id as "id!: Uuid",
+                         pack_id as "pack_id!: Uuid",
+                         title,
+                         category,
+                         content,
+                         position as "position!: i64",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            pack_id,
+            data.title,
+            data.category,
+            data.content
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn update(
+        pool: &SqlitePool,
+        id: Uuid,
+        data: &UpdateDocumentTemplate,
+    ) -> Result<Self, sqlx::Error> {
+        let existing = Self::find_by_id(pool, id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)?;
+
+        let title = data.title.as_ref().unwrap_or(&existing.title);
+        let category = data.category.as_ref().unwrap_or(&existing.category);
+        let content = data.content.as_ref().unwrap_or(&existing.content);
+        let position = data.position.unwrap_or(existing.position);
+
+        sqlx::query_as!(
+            DocumentTemplate,
+            r#"UPDATE document_templates
+               SET title = $2, category = $3, content = $4, position = $5,
+                   updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid",
+                         pack_id as "pack_id!: Uuid",
+                         title,
+                         category,
+                         content,
+                         position as "position!: i64",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            title,
+            category,
+            content,
+            position
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM document_templates WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
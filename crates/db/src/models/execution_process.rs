@@ -503,6 +503,19 @@ impl ExecutionProcess {
         }
     }
 
+    /// Executor profile of a coding agent process; `None` for scripts and dev servers
+    pub fn executor_profile_id(&self) -> Option<ExecutorProfileId> {
+        match &self.executor_action().ok()?.typ {
+            ExecutorActionType::CodingAgentInitialRequest(request) => {
+                Some(request.executor_profile_id.clone())
+            }
+            ExecutorActionType::CodingAgentFollowUpRequest(request) => {
+                Some(request.executor_profile_id.clone())
+            }
+            _ => None,
+        }
+    }
+
     /// Set restore boundary: drop processes newer than the specified process, undrop older/equal
     pub async fn set_restore_boundary(
         pool: &SqlitePool,
@@ -47,6 +47,43 @@ pub struct PullRequestInfo {
     pub status: MergeStatus,
     pub merged_at: Option<chrono::DateTime<chrono::Utc>>,
     pub merge_commit_sha: Option<String>,
+    /// Rolled-up status of the PR's checks; `None` when the PR has no checks
+    pub ci_status: Option<CiStatus>,
+    #[serde(default)]
+    pub ci_checks: Vec<CiCheck>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, Type, PartialEq, Eq)]
+#[sqlx(type_name = "ci_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum CiStatus {
+    Pending,
+    Success,
+    Failure,
+}
+
+impl CiStatus {
+    /// Roll individual check results up into a single status for the PR
+    pub fn aggregate(checks: &[CiCheck]) -> Option<Self> {
+        if checks.is_empty() {
+            None
+        } else if checks.iter().any(|c| c.status == CiStatus::Failure) {
+            Some(CiStatus::Failure)
+        } else if checks.iter().any(|c| c.status == CiStatus::Pending) {
+            Some(CiStatus::Pending)
+        } else {
+            Some(CiStatus::Success)
+        }
+    }
+}
+
+/// A single check run or commit status reported on a PR
+#[derive(Debug, Clone, Serialize, Deserialize, TS, PartialEq, Eq)]
+pub struct CiCheck {
+    pub name: String,
+    pub status: CiStatus,
+    /// Link to the check's logs/details page
+    pub details_url: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -68,6 +105,8 @@ struct MergeRow {
     pr_status: Option<MergeStatus>,
     pr_merged_at: Option<DateTime<Utc>>,
     pr_merge_commit_sha: Option<String>,
+    pr_ci_status: Option<CiStatus>,
+    pr_ci_checks: Option<sqlx::types::Json<Vec<CiCheck>>>,
     created_at: DateTime<Utc>,
 }
 
@@ -104,6 +143,8 @@ impl Merge {
                 pr_status as "pr_status?: MergeStatus",
                 pr_merged_at as "pr_merged_at?: DateTime<Utc>",
                 pr_merge_commit_sha,
+                pr_ci_status as "pr_ci_status?: CiStatus",
+                pr_ci_checks as "pr_ci_checks?: sqlx::types::Json<Vec<CiCheck>>",
                 created_at as "created_at!: DateTime<Utc>",
                 target_branch_name as "target_branch_name!: String"
             "#,
@@ -143,6 +184,8 @@ impl Merge {
                 pr_status as "pr_status?: MergeStatus",
                 pr_merged_at as "pr_merged_at?: DateTime<Utc>",
                 pr_merge_commit_sha,
+                pr_ci_status as "pr_ci_status?: CiStatus",
+                pr_ci_checks as "pr_ci_checks?: sqlx::types::Json<Vec<CiCheck>>",
                 created_at as "created_at!: DateTime<Utc>",
                 target_branch_name as "target_branch_name!: String"
             "#,
@@ -172,6 +215,8 @@ impl Merge {
                 pr_status as "pr_status?: MergeStatus",
                 pr_merged_at as "pr_merged_at?: DateTime<Utc>",
                 pr_merge_commit_sha,
+                pr_ci_status as "pr_ci_status?: CiStatus",
+                pr_ci_checks as "pr_ci_checks?: sqlx::types::Json<Vec<CiCheck>>",
                 created_at as "created_at!: DateTime<Utc>",
                 target_branch_name as "target_branch_name!: String"
                FROM merges 
@@ -213,6 +258,56 @@ impl Merge {
 
         Ok(())
     }
+    /// Record the latest CI check results for an open PR
+    pub async fn update_ci_status(
+        pool: &SqlitePool,
+        merge_id: Uuid,
+        ci_status: Option<CiStatus>,
+        ci_checks: &[CiCheck],
+    ) -> Result<(), sqlx::Error> {
+        let ci_checks = sqlx::types::Json(ci_checks);
+        sqlx::query!(
+            r#"UPDATE merges
+            SET pr_ci_status = $1,
+                pr_ci_checks = $2
+            WHERE id = $3"#,
+            ci_status,
+            ci_checks,
+            merge_id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn find_by_rowid(pool: &SqlitePool, rowid: i64) -> Result<Option<Self>, sqlx::Error> {
+        let row = sqlx::query_as!(
+            MergeRow,
+            r#"SELECT
+                id as "id!: Uuid",
+                task_attempt_id as "task_attempt_id!: Uuid",
+                merge_type as "merge_type!: MergeType",
+                merge_commit,
+                pr_number,
+                pr_url,
+                pr_status as "pr_status?: MergeStatus",
+                pr_merged_at as "pr_merged_at?: DateTime<Utc>",
+                pr_merge_commit_sha,
+                pr_ci_status as "pr_ci_status?: CiStatus",
+                pr_ci_checks as "pr_ci_checks?: sqlx::types::Json<Vec<CiCheck>>",
+                target_branch_name as "target_branch_name!: String",
+                created_at as "created_at!: DateTime<Utc>"
+            FROM merges
+            WHERE rowid = $1"#,
+            rowid
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(row.map(Into::into))
+    }
+
     /// Find all merges for a task attempt (returns both direct and PR merges)
     pub async fn find_by_task_attempt_id(
         pool: &SqlitePool,
@@ -231,6 +326,8 @@ impl Merge {
                 pr_status as "pr_status?: MergeStatus",
                 pr_merged_at as "pr_merged_at?: DateTime<Utc>",
                 pr_merge_commit_sha,
+                pr_ci_status as "pr_ci_status?: CiStatus",
+                pr_ci_checks as "pr_ci_checks?: sqlx::types::Json<Vec<CiCheck>>",
                 target_branch_name as "target_branch_name!: String",
                 created_at as "created_at!: DateTime<Utc>"
             FROM merges 
@@ -283,6 +380,8 @@ impl From<MergeRow> for PrMerge {
                 status: row.pr_status.expect("pr merge must have status"),
                 merged_at: row.pr_merged_at,
                 merge_commit_sha: row.pr_merge_commit_sha,
+                ci_status: row.pr_ci_status,
+                ci_checks: row.pr_ci_checks.map(|checks| checks.0).unwrap_or_default(),
             },
             created_at: row.created_at,
         }
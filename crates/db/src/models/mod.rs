@@ -1,11 +1,26 @@
+pub mod approval_record;
+pub mod approval_rule;
+pub mod approval_timeout_setting;
+pub mod document;
+pub mod document_revision;
+pub mod document_search;
+pub mod document_template;
 pub mod draft;
 pub mod execution_process;
 pub mod execution_process_logs;
 pub mod executor_session;
 pub mod image;
 pub mod merge;
+pub mod pr_review_comment;
 pub mod project;
+pub mod project_commit_settings;
+pub mod project_mcp_servers;
 pub mod shared_task;
 pub mod tag;
 pub mod task;
 pub mod task_attempt;
+pub mod task_breakdown;
+pub mod task_dependency;
+pub mod task_document;
+pub mod task_plan_revision;
+pub mod task_plan_step;
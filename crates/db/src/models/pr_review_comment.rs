@@ -0,0 +1,164 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[sqlx(type_name = "review_comment_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewCommentStatus {
+    /// Fetched from the PR, not yet acted on
+    Pending,
+    /// Sent to the agent as a follow-up
+    Sent,
+    /// The agent's fix was pushed to the PR branch
+    Addressed,
+    /// Ignored by the user
+    Dismissed,
+}
+
+/// A review comment left on a pull request opened from a task attempt
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct PrReviewComment {
+    pub id: Uuid,
+    pub merge_id: Uuid,
+    pub task_attempt_id: Uuid,
+    pub github_comment_id: i64,
+    pub author: String,
+    pub body: String,
+    /// File the comment is anchored to; `None` for comments on the whole PR
+    pub path: Option<String>,
+    pub line: Option<i64>,
+    pub diff_hunk: Option<String>,
+    pub url: Option<String>,
+    pub status: ReviewCommentStatus,
+    pub commented_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreatePrReviewComment {
+    pub merge_id: Uuid,
+    pub task_attempt_id: Uuid,
+    pub github_comment_id: i64,
+    pub author: String,
+    pub body: String,
+    pub path: Option<String>,
+    pub line: Option<i64>,
+    pub diff_hunk: Option<String>,
+    pub url: Option<String>,
+    pub commented_at: DateTime<Utc>,
+}
+
+impl PrReviewComment {
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            PrReviewComment,
+            r#"SELECT id as "id!: Uuid", merge_id as "merge_id!: Uuid", task_attempt_id as "task_attempt_id!: Uuid",
+                      github_comment_id, author, body, path, line, diff_hunk, url,
+                      status as "status!: ReviewCommentStatus",
+                      commented_at as "commented_at!: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM pr_review_comments
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_by_task_attempt_id(
+        pool: &SqlitePool,
+        task_attempt_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            PrReviewComment,
+            r#"SELECT id as "id!: Uuid", merge_id as "merge_id!: Uuid", task_attempt_id as "task_attempt_id!: Uuid",
+                      github_comment_id, author, body, path, line, diff_hunk, url,
+                      status as "status!: ReviewCommentStatus",
+                      commented_at as "commented_at!: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM pr_review_comments
+               WHERE task_attempt_id = $1
+               ORDER BY commented_at ASC"#,
+            task_attempt_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_task_attempt_and_status(
+        pool: &SqlitePool,
+        task_attempt_id: Uuid,
+        status: ReviewCommentStatus,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            PrReviewComment,
+            r#"SELECT id as "id!: Uuid", merge_id as "merge_id!: Uuid", task_attempt_id as "task_attempt_id!: Uuid",
+                      github_comment_id, author, body, path, line, diff_hunk, url,
+                      status as "status!: ReviewCommentStatus",
+                      commented_at as "commented_at!: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM pr_review_comments
+               WHERE task_attempt_id = $1 AND status = $2
+               ORDER BY commented_at ASC"#,
+            task_attempt_id,
+            status
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Insert a comment unless it was already ingested. Returns `None` for duplicates.
+    pub async fn create_if_new(
+        pool: &SqlitePool,
+        data: &CreatePrReviewComment,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            PrReviewComment,
+            r#"INSERT INTO pr_review_comments (
+                   id, merge_id, task_attempt_id, github_comment_id, author, body,
+                   path, line, diff_hunk, url, commented_at
+               ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+               ON CONFLICT(merge_id, github_comment_id) DO NOTHING
+               RETURNING id as "id!: Uuid", merge_id as "merge_id!: Uuid", task_attempt_id as "task_attempt_id!: Uuid",
+                         github_comment_id, author, body, path, line, diff_hunk, url,
+                         status as "status!: ReviewCommentStatus",
+                         commented_at as "commented_at!: DateTime<Utc>",
+                         created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            data.merge_id,
+            data.task_attempt_id,
+            data.github_comment_id,
+            data.author,
+            data.body,
+            data.path,
+            data.line,
+            data.diff_hunk,
+            data.url,
+            data.commented_at
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn update_status(
+        pool: &SqlitePool,
+        id: Uuid,
+        status: ReviewCommentStatus,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE pr_review_comments
+               SET status = $2, updated_at = datetime('now', 'subsec')
+               WHERE id = $1"#,
+            id,
+            status
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
@@ -46,6 +46,8 @@ pub struct CreateProject {
     pub dev_script: Option<String>,
     pub cleanup_script: Option<String>,
     pub copy_files: Option<String>,
+    /// Template pack whose documents are created in the new project
+    pub document_template_pack_id: Option<Uuid>,
 }
 
 #[derive(Debug, Deserialize, TS)]
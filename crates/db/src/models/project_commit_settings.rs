@@ -0,0 +1,113 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Default, Type, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[sqlx(type_name = "commit_signing", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum CommitSigning {
+    /// Follow the repository's own `commit.gpgsign` setting
+    #[default]
+    None,
+    Gpg,
+    Ssh,
+}
+
+/// Identity and signing used for commits the agent makes in a project
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ProjectCommitSettings {
+    pub project_id: Uuid,
+    /// Author and committer for agent commits; the ambient git identity when unset
+    pub author_name: Option<String>,
+    pub author_email: Option<String>,
+    /// Credit the user's own git identity with a `Co-authored-by` trailer
+    pub co_author_user: bool,
+    pub signing: CommitSigning,
+    /// Signing key passed to git; the user's configured `user.signingkey` when unset
+    pub signing_key: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateProjectCommitSettings {
+    pub author_name: Option<String>,
+    pub author_email: Option<String>,
+    pub co_author_user: bool,
+    pub signing: CommitSigning,
+    pub signing_key: Option<String>,
+}
+
+impl ProjectCommitSettings {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectCommitSettings,
+            r#"SELECT project_id as "project_id!: Uuid",
+                      author_name,
+                      author_email,
+                      co_author_user as "co_author_user!: bool",
+                      signing as "signing!: CommitSigning",
+                      signing_key,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_commit_settings
+               WHERE project_id = $1"#,
+            project_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn upsert(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        data: &UpdateProjectCommitSettings,
+    ) -> Result<Self, sqlx::Error> {
+        // Blank inputs from the settings form mean "not set"
+        let non_empty = |value: &Option<String>| {
+            value
+                .as_deref()
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .map(str::to_string)
+        };
+        let author_name = non_empty(&data.author_name);
+        let author_email = non_empty(&data.author_email);
+        let signing_key = non_empty(&data.signing_key);
+
+        sqlx::query_as!(
+            ProjectCommitSettings,
+            r#"INSERT INTO project_commit_settings
+                   (project_id, author_name, author_email, co_author_user, signing, signing_key)
+               VALUES ($1, $2, $3, $4, $5, $6)
+               ON CONFLICT(project_id) DO UPDATE SET
+                   author_name = excluded.author_name,
+                   author_email = excluded.author_email,
+                   co_author_user = excluded.co_author_user,
+                   signing = excluded.signing,
+                   signing_key = excluded.signing_key,
+                   updated_at = datetime('now', 'subsec')
+               RETURNING project_id as "project_id!: Uuid",
+                         author_name,
+                         author_email,
+                         co_author_user as "co_author_user!: bool",
+                         signing as "signing!: CommitSigning",
+                         signing_key,
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            project_id,
+            author_name,
+            author_email,
+            data.co_author_user,
+            data.signing,
+            signing_key
+        )
+        .fetch_one(pool)
+        .await
+    }
+}
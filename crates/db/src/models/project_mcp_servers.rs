@@ -0,0 +1,68 @@
+use chrono::{DateTime, Utc};
+use executors::mcp_config::ExecutionMcpServers;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// MCP servers added to the coding agent runs of one project only
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ProjectMcpServers {
+    pub project_id: Uuid,
+    /// Servers by name, e.g. `{"db": {"command": "npx", "args": [...]}}` or
+    /// `{"docs": {"type": "http", "url": "..."}}`
+    #[ts(type = "{ [key in string]?: JsonValue }")]
+    pub servers: sqlx::types::Json<ExecutionMcpServers>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateProjectMcpServers {
+    #[ts(type = "{ [key in string]?: JsonValue }")]
+    pub servers: ExecutionMcpServers,
+}
+
+impl ProjectMcpServers {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectMcpServers,
+            r#"SELECT project_id as "project_id!: Uuid",
+                      servers as "servers!: sqlx::types::Json<ExecutionMcpServers>",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_mcp_servers
+               WHERE project_id = $1"#,
+            project_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn upsert(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        data: &UpdateProjectMcpServers,
+    ) -> Result<Self, sqlx::Error> {
+        let servers = sqlx::types::Json(data.servers.clone());
+        sqlx::query_as!(
+            ProjectMcpServers,
+            r#"INSERT INTO project_mcp_servers (project_id, servers)
+               VALUES ($1, $2)
+               ON CONFLICT(project_id) DO UPDATE SET
+                   servers = excluded.servers,
+                   updated_at = datetime('now', 'subsec')
+               RETURNING project_id as "project_id!: Uuid",
+                         servers as "servers!: sqlx::types::Json<ExecutionMcpServers>",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            project_id,
+            servers
+        )
+        .fetch_one(pool)
+        .await
+    }
+}
@@ -1,4 +1,5 @@
 use chrono::{DateTime, Utc};
+use executors::executors::BaseCodingAgent;
 use serde::{Deserialize, Serialize};
 use sqlx::{Executor, FromRow, Sqlite, SqlitePool, Type};
 use strum_macros::{Display, EnumString};
@@ -23,6 +24,59 @@ pub enum TaskStatus {
     Cancelled,
 }
 
+/// What a task is for. The kind decides how its attempts are set up.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Type,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    TS,
+    EnumString,
+    Display,
+    Default,
+)]
+#[sqlx(type_name = "task_kind", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum TaskKind {
+    #[default]
+    Implementation,
+    /// Chat session of the Conversation tab, working on the project's base branch
+    PlanningConversation,
+    Review,
+    Chore,
+}
+
+impl TaskKind {
+    /// Whether attempts get a branch of their own instead of working on a detached
+    /// checkout of the base branch
+    pub fn creates_branch(&self) -> bool {
+        !matches!(self, TaskKind::PlanningConversation)
+    }
+
+    /// Whether the first agent run is started with the task's title and description
+    pub fn sends_initial_prompt(&self) -> bool {
+        !matches!(self, TaskKind::PlanningConversation)
+    }
+
+    /// How the agent should approach the task, put before it in the first prompt
+    pub fn prompt_instructions(&self) -> Option<&'static str> {
+        match self {
+            TaskKind::Review => Some(
+                "Review the code this task points at and report what should change, most important first. Do not modify any files.",
+            ),
+            TaskKind::Chore => Some(
+                "This is a maintenance chore. Make the smallest change that gets it done and leave unrelated code alone.",
+            ),
+            TaskKind::Implementation | TaskKind::PlanningConversation => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
 pub struct Task {
     pub id: Uuid,
@@ -31,6 +85,9 @@ pub struct Task {
     pub description: Option<String>,
     pub plan: Option<String>, // Plan generated from plan mode execution
     pub status: TaskStatus,
+    pub kind: TaskKind,
+    /// Executor new attempts default to when the task has none yet
+    pub suggested_executor: Option<BaseCodingAgent>,
     pub parent_task_attempt: Option<Uuid>, // Foreign key to parent TaskAttempt
     pub shared_task_id: Option<Uuid>,
     pub created_at: DateTime<Utc>,
@@ -46,6 +103,30 @@ pub struct TaskWithAttemptStatus {
     pub has_merged_attempt: bool,
     pub last_attempt_failed: bool,
     pub executor: String,
+    /// Progress through the approved plan's checklist, if the task has one
+    pub plan_progress: Option<PlanProgress>,
+}
+
+/// How far the implementation of a task's approved plan has come
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, PartialEq, Eq)]
+pub struct PlanProgress {
+    pub completed_steps: i64,
+    pub total_steps: i64,
+    /// Step being worked on, from 1; the last step once all are completed
+    pub current_step: i64,
+    /// Completed share of the steps, 0 to 100
+    pub percent: i64,
+}
+
+impl PlanProgress {
+    pub fn new(completed_steps: i64, total_steps: i64) -> Option<Self> {
+        (total_steps > 0).then(|| Self {
+            completed_steps,
+            total_steps,
+            current_step: (completed_steps + 1).min(total_steps),
+            percent: completed_steps * 100 / total_steps,
+        })
+    }
 }
 
 impl std::ops::Deref for TaskWithAttemptStatus {
@@ -74,6 +155,9 @@ pub struct CreateTask {
     pub title: String,
     pub description: Option<String>,
     pub status: Option<TaskStatus>,
+    /// Defaults to an implementation task
+    pub kind: Option<TaskKind>,
+    pub suggested_executor: Option<BaseCodingAgent>,
     pub parent_task_attempt: Option<Uuid>,
     pub image_ids: Option<Vec<Uuid>>,
     pub shared_task_id: Option<Uuid>,
@@ -90,6 +174,8 @@ impl CreateTask {
             title,
             description,
             status: Some(TaskStatus::Todo),
+            kind: None,
+            suggested_executor: None,
             parent_task_attempt: None,
             image_ids: None,
             shared_task_id: None,
@@ -108,6 +194,8 @@ impl CreateTask {
             title,
             description,
             status: Some(status),
+            kind: None,
+            suggested_executor: None,
             parent_task_attempt: None,
             image_ids: None,
             shared_task_id: Some(shared_task_id),
@@ -141,6 +229,10 @@ impl Task {
             } else {
                 self.title.clone()
             };
+        let base_prompt = match self.kind.prompt_instructions() {
+            Some(instructions) => format!("{instructions}\n\n{base_prompt}"),
+            None => base_prompt,
+        };
 
         // If there's a plan from plan mode, prepend it to the prompt
         if let Some(plan) = self.plan.as_ref().filter(|p| !p.trim().is_empty()) {
@@ -166,6 +258,8 @@ impl Task {
   t.description,
   t.plan,
   t.status                        AS "status!: TaskStatus",
+  t.kind                          AS "kind!: TaskKind",
+  t.suggested_executor            AS "suggested_executor: BaseCodingAgent",
   t.parent_task_attempt           AS "parent_task_attempt: Uuid",
   t.shared_task_id                AS "shared_task_id: Uuid",
   t.created_at                    AS "created_at!: DateTime<Utc>",
@@ -199,7 +293,18 @@ impl Task {
       WHERE ta.task_id = t.id
      ORDER BY ta.created_at DESC
       LIMIT 1
-    )                               AS "executor!: String"
+    )                               AS "executor!: String",
+
+  ( SELECT COUNT(*)
+      FROM task_plan_steps s
+     WHERE s.task_id = t.id
+  )                                 AS "plan_steps_total!: i64",
+
+  ( SELECT COUNT(*)
+      FROM task_plan_steps s
+     WHERE s.task_id = t.id
+       AND s.status = 'completed'
+  )                                 AS "plan_steps_completed!: i64"
 
 FROM tasks t
 WHERE t.project_id = $1
@@ -219,6 +324,8 @@ ORDER BY t.created_at DESC"#,
                     description: rec.description,
                     plan: rec.plan,
                     status: rec.status,
+                    kind: rec.kind,
+                    suggested_executor: rec.suggested_executor,
                     parent_task_attempt: rec.parent_task_attempt,
                     shared_task_id: rec.shared_task_id,
                     created_at: rec.created_at,
@@ -228,6 +335,7 @@ ORDER BY t.created_at DESC"#,
                 has_merged_attempt: false, // TODO use merges table
                 last_attempt_failed: rec.last_attempt_failed != 0,
                 executor: rec.executor,
+                plan_progress: PlanProgress::new(rec.plan_steps_completed, rec.plan_steps_total),
             })
             .collect();
 
@@ -237,7 +345,7 @@ ORDER BY t.created_at DESC"#,
     pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             Task,
-            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, plan, status as "status!: TaskStatus", parent_task_attempt as "parent_task_attempt: Uuid", shared_task_id as "shared_task_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, plan, status as "status!: TaskStatus", kind as "kind!: TaskKind", suggested_executor as "suggested_executor: BaseCodingAgent", parent_task_attempt as "parent_task_attempt: Uuid", shared_task_id as "shared_task_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
                FROM tasks
                WHERE id = $1"#,
             id
@@ -249,7 +357,7 @@ ORDER BY t.created_at DESC"#,
     pub async fn find_by_rowid(pool: &SqlitePool, rowid: i64) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             Task,
-            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, plan, status as "status!: TaskStatus", parent_task_attempt as "parent_task_attempt: Uuid", shared_task_id as "shared_task_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, plan, status as "status!: TaskStatus", kind as "kind!: TaskKind", suggested_executor as "suggested_executor: BaseCodingAgent", parent_task_attempt as "parent_task_attempt: Uuid", shared_task_id as "shared_task_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
                FROM tasks
                WHERE rowid = $1"#,
             rowid
@@ -265,7 +373,7 @@ ORDER BY t.created_at DESC"#,
     ) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             Task,
-            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, plan, status as "status!: TaskStatus", parent_task_attempt as "parent_task_attempt: Uuid", shared_task_id as "shared_task_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, plan, status as "status!: TaskStatus", kind as "kind!: TaskKind", suggested_executor as "suggested_executor: BaseCodingAgent", parent_task_attempt as "parent_task_attempt: Uuid", shared_task_id as "shared_task_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
                FROM tasks
                WHERE id = $1 AND project_id = $2"#,
             id,
@@ -284,7 +392,7 @@ ORDER BY t.created_at DESC"#,
     {
         sqlx::query_as!(
             Task,
-            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, plan, status as "status!: TaskStatus", parent_task_attempt as "parent_task_attempt: Uuid", shared_task_id as "shared_task_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, plan, status as "status!: TaskStatus", kind as "kind!: TaskKind", suggested_executor as "suggested_executor: BaseCodingAgent", parent_task_attempt as "parent_task_attempt: Uuid", shared_task_id as "shared_task_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
                FROM tasks
                WHERE shared_task_id = $1
                LIMIT 1"#,
@@ -294,26 +402,32 @@ ORDER BY t.created_at DESC"#,
         .await
     }
 
-    pub async fn create(
-        pool: &SqlitePool,
+    pub async fn create<'e, E>(
+        executor: E,
         data: &CreateTask,
         task_id: Uuid,
-    ) -> Result<Self, sqlx::Error> {
+    ) -> Result<Self, sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
         let status = data.status.clone().unwrap_or_default();
+        let kind = data.kind.unwrap_or_default();
         sqlx::query_as!(
             Task,
-            r#"INSERT INTO tasks (id, project_id, title, description, status, parent_task_attempt, shared_task_id)
-               VALUES ($1, $2, $3, $4, $5, $6, $7)
-               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, plan, status as "status!: TaskStatus", parent_task_attempt as "parent_task_attempt: Uuid", shared_task_id as "shared_task_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            r#"INSERT INTO tasks (id, project_id, title, description, status, kind, suggested_executor, parent_task_attempt, shared_task_id)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, plan, status as "status!: TaskStatus", kind as "kind!: TaskKind", suggested_executor as "suggested_executor: BaseCodingAgent", parent_task_attempt as "parent_task_attempt: Uuid", shared_task_id as "shared_task_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
             task_id,
             data.project_id,
             data.title,
             data.description,
             status,
+            kind,
+            data.suggested_executor,
             data.parent_task_attempt,
             data.shared_task_id
         )
-        .fetch_one(pool)
+        .fetch_one(executor)
         .await
     }
 
@@ -331,7 +445,7 @@ ORDER BY t.created_at DESC"#,
             r#"UPDATE tasks
                SET title = $3, description = $4, status = $5, parent_task_attempt = $6
                WHERE id = $1 AND project_id = $2
-               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, plan, status as "status!: TaskStatus", parent_task_attempt as "parent_task_attempt: Uuid", shared_task_id as "shared_task_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, plan, status as "status!: TaskStatus", kind as "kind!: TaskKind", suggested_executor as "suggested_executor: BaseCodingAgent", parent_task_attempt as "parent_task_attempt: Uuid", shared_task_id as "shared_task_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
             id,
             project_id,
             title,
@@ -500,7 +614,7 @@ ORDER BY t.created_at DESC"#,
         // Find only child tasks that have this attempt as their parent
         sqlx::query_as!(
             Task,
-            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, plan, status as "status!: TaskStatus", parent_task_attempt as "parent_task_attempt: Uuid", shared_task_id as "shared_task_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, plan, status as "status!: TaskStatus", kind as "kind!: TaskKind", suggested_executor as "suggested_executor: BaseCodingAgent", parent_task_attempt as "parent_task_attempt: Uuid", shared_task_id as "shared_task_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
                FROM tasks
                WHERE parent_task_attempt = $1
                ORDER BY created_at DESC"#,
@@ -559,3 +673,63 @@ ORDER BY t.created_at DESC"#,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(kind: TaskKind, plan: Option<&str>) -> Task {
+        Task {
+            id: Uuid::new_v4(),
+            project_id: Uuid::new_v4(),
+            title: "Check the login flow".to_string(),
+            description: Some("Session handling in auth.rs".to_string()),
+            plan: plan.map(str::to_string),
+            status: TaskStatus::Todo,
+            kind,
+            suggested_executor: None,
+            parent_task_attempt: None,
+            shared_task_id: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn only_planning_conversations_work_without_a_branch_or_first_prompt() {
+        for kind in [TaskKind::Implementation, TaskKind::Review, TaskKind::Chore] {
+            assert!(kind.creates_branch());
+            assert!(kind.sends_initial_prompt());
+        }
+        assert!(!TaskKind::PlanningConversation.creates_branch());
+        assert!(!TaskKind::PlanningConversation.sends_initial_prompt());
+    }
+
+    #[test]
+    fn implementation_prompts_are_the_task_itself() {
+        assert_eq!(
+            task(TaskKind::Implementation, None).to_prompt(),
+            "Check the login flow\n\nSession handling in auth.rs"
+        );
+    }
+
+    #[test]
+    fn review_prompts_ask_for_findings_without_changes() {
+        let prompt = task(TaskKind::Review, None).to_prompt();
+        assert!(prompt.starts_with(TaskKind::Review.prompt_instructions().unwrap()));
+        assert!(prompt.contains("Do not modify any files"));
+        assert!(prompt.ends_with("Check the login flow\n\nSession handling in auth.rs"));
+    }
+
+    #[test]
+    fn chore_prompts_keep_the_instructions_under_the_plan() {
+        let prompt = task(TaskKind::Chore, Some("1. Bump the crate")).to_prompt();
+        assert_eq!(
+            prompt,
+            format!(
+                "## Plan\n1. Bump the crate\n\n## Task\n{}\n\nCheck the login flow\n\nSession handling in auth.rs",
+                TaskKind::Chore.prompt_instructions().unwrap()
+            )
+        );
+    }
+}
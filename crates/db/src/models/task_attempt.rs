@@ -1,7 +1,7 @@
 use chrono::{DateTime, Utc};
 use executors::executors::BaseCodingAgent;
 use serde::{Deserialize, Serialize};
-use sqlx::{FromRow, SqlitePool, Type};
+use sqlx::{Executor, FromRow, Sqlite, SqlitePool, Type};
 use thiserror::Error;
 use ts_rs::TS;
 use uuid::Uuid;
@@ -45,6 +45,7 @@ pub struct TaskAttempt {
     // "GEMINI", etc.)
     pub worktree_deleted: bool, // Flag indicating if worktree has been cleaned up
     pub setup_completed_at: Option<DateTime<Utc>>, // When setup script was last completed
+    pub stacked_on_attempt_id: Option<Uuid>, // Attempt whose branch this attempt is stacked on
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -84,6 +85,7 @@ pub struct CreateTaskAttempt {
     pub executor: BaseCodingAgent,
     pub base_branch: String,
     pub branch: String,
+    pub stacked_on_attempt_id: Option<Uuid>,
 }
 
 impl TaskAttempt {
@@ -107,6 +109,7 @@ impl TaskAttempt {
                               executor AS "executor!",
                               worktree_deleted AS "worktree_deleted!: bool",
                               setup_completed_at AS "setup_completed_at: DateTime<Utc>",
+                              stacked_on_attempt_id AS "stacked_on_attempt_id: Uuid",
                               created_at AS "created_at!: DateTime<Utc>",
                               updated_at AS "updated_at!: DateTime<Utc>"
                        FROM task_attempts
@@ -127,6 +130,7 @@ impl TaskAttempt {
                               executor AS "executor!",
                               worktree_deleted AS "worktree_deleted!: bool",
                               setup_completed_at AS "setup_completed_at: DateTime<Utc>",
+                              stacked_on_attempt_id AS "stacked_on_attempt_id: Uuid",
                               created_at AS "created_at!: DateTime<Utc>",
                               updated_at AS "updated_at!: DateTime<Utc>"
                        FROM task_attempts
@@ -158,6 +162,7 @@ impl TaskAttempt {
                        ta.executor AS "executor!",
                        ta.worktree_deleted  AS "worktree_deleted!: bool",
                        ta.setup_completed_at AS "setup_completed_at: DateTime<Utc>",
+                       ta.stacked_on_attempt_id AS "stacked_on_attempt_id: Uuid",
                        ta.created_at        AS "created_at!: DateTime<Utc>",
                        ta.updated_at        AS "updated_at!: DateTime<Utc>"
                FROM    task_attempts ta
@@ -231,6 +236,7 @@ impl TaskAttempt {
                        executor AS "executor!",
                        worktree_deleted  AS "worktree_deleted!: bool",
                        setup_completed_at AS "setup_completed_at: DateTime<Utc>",
+                       stacked_on_attempt_id AS "stacked_on_attempt_id: Uuid",
                        created_at        AS "created_at!: DateTime<Utc>",
                        updated_at        AS "updated_at!: DateTime<Utc>"
                FROM    task_attempts
@@ -252,6 +258,7 @@ impl TaskAttempt {
                        executor AS "executor!",
                        worktree_deleted  AS "worktree_deleted!: bool",
                        setup_completed_at AS "setup_completed_at: DateTime<Utc>",
+                       stacked_on_attempt_id AS "stacked_on_attempt_id: Uuid",
                        created_at        AS "created_at!: DateTime<Utc>",
                        updated_at        AS "updated_at!: DateTime<Utc>"
                FROM    task_attempts
@@ -362,19 +369,22 @@ impl TaskAttempt {
             .collect())
     }
 
-    pub async fn create(
-        pool: &SqlitePool,
+    pub async fn create<'e, E>(
+        executor: E,
         data: &CreateTaskAttempt,
         id: Uuid,
         task_id: Uuid,
-    ) -> Result<Self, TaskAttemptError> {
+    ) -> Result<Self, TaskAttemptError>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
         // let prefixed_id = format!("anyon-{}", attempt_id);
         // Insert the record into the database
         Ok(sqlx::query_as!(
             TaskAttempt,
-            r#"INSERT INTO task_attempts (id, task_id, container_ref, branch, target_branch, executor, worktree_deleted, setup_completed_at)
-               VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
-               RETURNING id as "id!: Uuid", task_id as "task_id!: Uuid", container_ref, branch, target_branch, executor as "executor!",  worktree_deleted as "worktree_deleted!: bool", setup_completed_at as "setup_completed_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            r#"INSERT INTO task_attempts (id, task_id, container_ref, branch, target_branch, executor, worktree_deleted, setup_completed_at, stacked_on_attempt_id)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+               RETURNING id as "id!: Uuid", task_id as "task_id!: Uuid", container_ref, branch, target_branch, executor as "executor!",  worktree_deleted as "worktree_deleted!: bool", setup_completed_at as "setup_completed_at: DateTime<Utc>", stacked_on_attempt_id as "stacked_on_attempt_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
             id,
             task_id,
             Option::<String>::None, // Container isn't known yet
@@ -382,9 +392,10 @@ impl TaskAttempt {
             data.base_branch, // Target branch is same as base branch during creation
             data.executor,
             false, // worktree_deleted is false during creation
-            Option::<DateTime<Utc>>::None // setup_completed_at is None during creation
+            Option::<DateTime<Utc>>::None, // setup_completed_at is None during creation
+            data.stacked_on_attempt_id
         )
-        .fetch_one(pool)
+        .fetch_one(executor)
         .await?)
     }
 
@@ -430,9 +441,12 @@ impl TaskAttempt {
             r#"UPDATE task_attempts
                SET target_branch = $3, updated_at = datetime('now')
                WHERE target_branch = $2
-                 AND task_id IN (
-                   SELECT id FROM tasks 
-                   WHERE parent_task_attempt = $1
+                 AND (
+                   stacked_on_attempt_id = $1
+                   OR task_id IN (
+                     SELECT id FROM tasks 
+                     WHERE parent_task_attempt = $1
+                   )
                  )"#,
             parent_attempt_id,
             old_branch,
@@ -444,6 +458,58 @@ impl TaskAttempt {
         Ok(result.rows_affected())
     }
 
+    /// Find attempts stacked directly on `attempt_id` that still target `branch`.
+    /// Attempts whose target was changed away from the parent branch are no longer
+    /// considered part of the stack.
+    pub async fn find_stacked_on(
+        pool: &SqlitePool,
+        attempt_id: Uuid,
+        branch: &str,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskAttempt,
+            r#"SELECT  id                AS "id!: Uuid",
+                       task_id           AS "task_id!: Uuid",
+                       container_ref,
+                       branch,
+                       target_branch,
+                       executor AS "executor!",
+                       worktree_deleted  AS "worktree_deleted!: bool",
+                       setup_completed_at AS "setup_completed_at: DateTime<Utc>",
+                       stacked_on_attempt_id AS "stacked_on_attempt_id: Uuid",
+                       created_at        AS "created_at!: DateTime<Utc>",
+                       updated_at        AS "updated_at!: DateTime<Utc>"
+               FROM    task_attempts
+               WHERE   stacked_on_attempt_id = $1 AND target_branch = $2
+               ORDER BY created_at ASC"#,
+            attempt_id,
+            branch
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Move an attempt to a new position in a stack (or out of it when `None`).
+    pub async fn update_stacked_on(
+        pool: &SqlitePool,
+        attempt_id: Uuid,
+        stacked_on_attempt_id: Option<Uuid>,
+        new_target_branch: &str,
+    ) -> Result<(), TaskAttemptError> {
+        sqlx::query!(
+            r#"UPDATE task_attempts
+               SET stacked_on_attempt_id = $1, target_branch = $2, updated_at = datetime('now')
+               WHERE id = $3"#,
+            stacked_on_attempt_id,
+            new_target_branch,
+            attempt_id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
     pub async fn resolve_container_ref(
         pool: &SqlitePool,
         container_ref: &str,
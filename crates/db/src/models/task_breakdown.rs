@@ -0,0 +1,83 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Executor, FromRow, Sqlite, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Run of a coding agent splitting a project document into kanban tasks
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct TaskBreakdown {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    /// Document the tasks are derived from
+    pub document_id: Uuid,
+    /// Attempt the agent runs in
+    pub task_attempt_id: Uuid,
+    /// When the previewed tasks were created; a breakdown is applied once
+    pub applied_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TaskBreakdown {
+    pub async fn create<'e, E>(
+        executor: E,
+        id: Uuid,
+        project_id: Uuid,
+        document_id: Uuid,
+        task_attempt_id: Uuid,
+    ) -> Result<Self, sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        sqlx::query_as!(
+            TaskBreakdown,
+            r#"INSERT INTO task_breakdowns (id, project_id, document_id, task_attempt_id)
+               VALUES ($1, $2, $3, $4)
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id!: Uuid",
+                         document_id as "document_id!: Uuid",
+                         task_attempt_id as "task_attempt_id!: Uuid",
+                         applied_at as "applied_at: DateTime<Utc>",
+                         created_at as "created_at!: DateTime<Utc>""#,
+            id,
+            project_id,
+            document_id,
+            task_attempt_id
+        )
+        .fetch_one(executor)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskBreakdown,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      document_id as "document_id!: Uuid",
+                      task_attempt_id as "task_attempt_id!: Uuid",
+                      applied_at as "applied_at: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM task_breakdowns
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Mark the breakdown applied; `false` when it already was
+    pub async fn mark_applied<'e, E>(executor: E, id: Uuid) -> Result<bool, sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        let result = sqlx::query!(
+            r#"UPDATE task_breakdowns
+               SET applied_at = datetime('now', 'subsec')
+               WHERE id = $1 AND applied_at IS NULL"#,
+            id
+        )
+        .execute(executor)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,52 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Executor, FromRow, Sqlite, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// `task_id` should only start once `depends_on_task_id` is done
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct TaskDependency {
+    pub task_id: Uuid,
+    pub depends_on_task_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TaskDependency {
+    pub async fn create<'e, E>(
+        executor: E,
+        task_id: Uuid,
+        depends_on_task_id: Uuid,
+    ) -> Result<Self, sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        sqlx::query_as!(
+            TaskDependency,
+            r#"INSERT INTO task_dependencies (task_id, depends_on_task_id)
+               VALUES ($1, $2)
+               RETURNING task_id as "task_id!: Uuid", depends_on_task_id as "depends_on_task_id!: Uuid", created_at as "created_at!: DateTime<Utc>""#,
+            task_id,
+            depends_on_task_id
+        )
+        .fetch_one(executor)
+        .await
+    }
+
+    /// Tasks that must be done before `task_id`
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskDependency,
+            r#"SELECT task_id as "task_id!: Uuid", depends_on_task_id as "depends_on_task_id!: Uuid", created_at as "created_at!: DateTime<Utc>"
+               FROM task_dependencies
+               WHERE task_id = $1
+               ORDER BY created_at ASC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
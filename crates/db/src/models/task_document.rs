@@ -0,0 +1,74 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Executor, FromRow, Sqlite, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Project document a task is based on
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct TaskDocument {
+    pub task_id: Uuid,
+    pub document_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateTaskDocuments {
+    /// Replaces the task's linked documents, in the order given to the agent
+    pub document_ids: Vec<Uuid>,
+}
+
+impl TaskDocument {
+    pub async fn create<'e, E>(
+        executor: E,
+        task_id: Uuid,
+        document_id: Uuid,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        sqlx::query!(
+            r#"INSERT INTO task_documents (task_id, document_id)
+               VALUES ($1, $2)
+               ON CONFLICT DO NOTHING"#,
+            task_id,
+            document_id
+        )
+        .execute(executor)
+        .await?;
+        Ok(())
+    }
+
+    /// Linked documents in the order they were linked
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskDocument,
+            r#"SELECT task_id as "task_id!: Uuid", document_id as "document_id!: Uuid", created_at as "created_at!: DateTime<Utc>"
+               FROM task_documents
+               WHERE task_id = $1
+               ORDER BY rowid ASC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn replace_for_task(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        document_ids: &[Uuid],
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        sqlx::query!("DELETE FROM task_documents WHERE task_id = $1", task_id)
+            .execute(&mut *tx)
+            .await?;
+        for document_id in document_ids {
+            Self::create(&mut *tx, task_id, *document_id).await?;
+        }
+        tx.commit().await?;
+        Self::find_by_task_id(pool, task_id).await
+    }
+}
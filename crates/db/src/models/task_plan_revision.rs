@@ -0,0 +1,178 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Executor, FromRow, Sqlite, SqlitePool, Type};
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[sqlx(type_name = "task_plan_revision_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum TaskPlanRevisionStatus {
+    /// Waiting for review
+    Proposed,
+    /// The reviewer asked for a revised plan
+    ChangesRequested,
+    Approved,
+}
+
+/// A plan presented by a plan mode run of a task
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct TaskPlanRevision {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    /// Numbered from 1 per task
+    pub revision: i64,
+    pub plan: String,
+    pub execution_process_id: Option<Uuid>,
+    pub status: TaskPlanRevisionStatus,
+    /// Changes the reviewer asked for
+    pub feedback: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl TaskPlanRevision {
+    /// Save a presented plan as the task's latest revision and its current plan.
+    /// A plan identical to the latest revision is not recorded again.
+    pub async fn record(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        execution_process_id: Option<Uuid>,
+        plan: &str,
+    ) -> Result<Self, sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        if let Some(latest) = Self::find_latest(&mut *tx, task_id).await?
+            && latest.plan == plan
+        {
+            return Ok(latest);
+        }
+
+        let id = Uuid::new_v4();
+        let revision = sqlx::query_as!(
+            TaskPlanRevision,
+            r#"INSERT INTO task_plan_revisions (id, task_id, revision, plan, execution_process_id)
+               SELECT $1, $2, COALESCE(MAX(revision), 0) + 1, $3, $4
+               FROM task_plan_revisions
+               WHERE task_id = $2
+               RETURNING id as "id!: Uuid",
+                         task_id as "task_id!: Uuid",
+                         revision as "revision!: i64",
+                         plan,
+                         execution_process_id as "execution_process_id: Uuid",
+                         status as "status!: TaskPlanRevisionStatus",
+                         feedback,
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            task_id,
+            plan,
+            execution_process_id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+        sqlx::query!(
+            "UPDATE tasks SET plan = $2, updated_at = CURRENT_TIMESTAMP WHERE id = $1",
+            task_id,
+            plan
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(revision)
+    }
+
+    pub async fn find_latest<'e, E>(executor: E, task_id: Uuid) -> Result<Option<Self>, sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        sqlx::query_as!(
+            TaskPlanRevision,
+            r#"SELECT id as "id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      revision as "revision!: i64",
+                      plan,
+                      execution_process_id as "execution_process_id: Uuid",
+                      status as "status!: TaskPlanRevisionStatus",
+                      feedback,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_plan_revisions
+               WHERE task_id = $1
+               ORDER BY revision DESC
+               LIMIT 1"#,
+            task_id
+        )
+        .fetch_optional(executor)
+        .await
+    }
+
+    pub async fn find_by_revision(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        revision: i64,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskPlanRevision,
+            r#"SELECT id as "id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      revision as "revision!: i64",
+                      plan,
+                      execution_process_id as "execution_process_id: Uuid",
+                      status as "status!: TaskPlanRevisionStatus",
+                      feedback,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_plan_revisions
+               WHERE task_id = $1 AND revision = $2"#,
+            task_id,
+            revision
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Plan history of a task, newest first
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskPlanRevision,
+            r#"SELECT id as "id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      revision as "revision!: i64",
+                      plan,
+                      execution_process_id as "execution_process_id: Uuid",
+                      status as "status!: TaskPlanRevisionStatus",
+                      feedback,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_plan_revisions
+               WHERE task_id = $1
+               ORDER BY revision DESC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Record the reviewer's decision on a revision
+    pub async fn update_status(
+        pool: &SqlitePool,
+        id: Uuid,
+        status: TaskPlanRevisionStatus,
+        feedback: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE task_plan_revisions
+               SET status = $2, feedback = $3, updated_at = datetime('now', 'subsec')
+               WHERE id = $1"#,
+            id,
+            status,
+            feedback
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
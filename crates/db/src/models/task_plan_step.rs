@@ -0,0 +1,110 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, TS)]
+#[sqlx(type_name = "task_plan_step_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum TaskPlanStepStatus {
+    Pending,
+    InProgress,
+    Completed,
+}
+
+/// Checklist item of a task's approved plan
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct TaskPlanStep {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    /// Order in the plan, from 0
+    pub position: i64,
+    pub title: String,
+    pub status: TaskPlanStepStatus,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl TaskPlanStep {
+    /// Checklist of a task in plan order
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskPlanStep,
+            r#"SELECT id as "id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      position as "position!: i64",
+                      title,
+                      status as "status!: TaskPlanStepStatus",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_plan_steps
+               WHERE task_id = $1
+               ORDER BY position ASC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Replace the checklist of a task, e.g. when a new plan is approved
+    pub async fn replace_for_task(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        titles: &[String],
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        sqlx::query!("DELETE FROM task_plan_steps WHERE task_id = $1", task_id)
+            .execute(&mut *tx)
+            .await?;
+        for (position, title) in titles.iter().enumerate() {
+            let id = Uuid::new_v4();
+            let position = position as i64;
+            sqlx::query!(
+                r#"INSERT INTO task_plan_steps (id, task_id, position, title)
+                   VALUES ($1, $2, $3, $4)"#,
+                id,
+                task_id,
+                position,
+                title
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Self::find_by_task_id(pool, task_id).await
+    }
+
+    /// Set the status of several steps of a task at once. The task row is touched
+    /// too, so task streams pick up the new progress.
+    pub async fn update_statuses(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        updates: &[(Uuid, TaskPlanStepStatus)],
+    ) -> Result<(), sqlx::Error> {
+        if updates.is_empty() {
+            return Ok(());
+        }
+        let mut tx = pool.begin().await?;
+        for (id, status) in updates {
+            sqlx::query!(
+                r#"UPDATE task_plan_steps
+                   SET status = $3, updated_at = datetime('now', 'subsec')
+                   WHERE id = $1 AND task_id = $2"#,
+                id,
+                task_id,
+                status
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        sqlx::query!(
+            "UPDATE tasks SET updated_at = CURRENT_TIMESTAMP WHERE id = $1",
+            task_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await
+    }
+}
@@ -0,0 +1,34 @@
+//! Fixtures for tests of crates using the database, enabled with the `test-utils`
+//! feature.
+
+use std::path::Path;
+
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+use crate::models::project::{CreateProject, Project};
+
+/// A project for an existing repository at `git_repo_path`, named after its directory
+pub async fn create_project(pool: &SqlitePool, git_repo_path: impl AsRef<Path>) -> Project {
+    let git_repo_path = git_repo_path.as_ref();
+    let name = git_repo_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "project".to_string());
+    Project::create(
+        pool,
+        &CreateProject {
+            name,
+            git_repo_path: git_repo_path.to_string_lossy().to_string(),
+            use_existing_repo: true,
+            setup_script: None,
+            dev_script: None,
+            cleanup_script: None,
+            copy_files: None,
+            document_template_pack_id: None,
+        },
+        Uuid::new_v4(),
+    )
+    .await
+    .expect("failed to create test project")
+}
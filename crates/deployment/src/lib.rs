@@ -29,13 +29,15 @@ use services::services::{
     filesystem_watcher::FilesystemWatcherError,
     git::{GitService, GitServiceError},
     image::{ImageError, ImageService},
-    pr_monitor::PrMonitorService,
+    pr_checks::PrChecksService,
+    pr_monitor::{PrMonitorEvent, PrMonitorService},
+    pr_review::PrReviewService,
     share::{RemoteSync, RemoteSyncHandle, ShareConfig, SharePublisher},
     worktree_manager::WorktreeError,
 };
 use sqlx::{Error as SqlxError, types::Uuid};
 use thiserror::Error;
-use tokio::sync::{Mutex, RwLock};
+use tokio::sync::{Mutex, RwLock, mpsc};
 use utils::{msg_store::MsgStore, sentry as sentry_utils};
 
 #[derive(Debug, Clone, Copy, Error)]
@@ -90,7 +92,7 @@ pub trait Deployment: Clone + Send + Sync + 'static {
 
     fn analytics(&self) -> &Option<AnalyticsService>;
 
-    fn container(&self) -> &impl ContainerService;
+    fn container(&self) -> &(impl ContainerService + Send + Sync);
 
     fn git(&self) -> &GitService;
 
@@ -152,7 +154,73 @@ pub trait Deployment: Clone + Send + Sync + 'static {
                 analytics_service: analytics_service.clone(),
             });
         let publisher = self.share_publisher().ok();
-        PrMonitorService::spawn(db, analytics, publisher).await
+        let (events_tx, mut events_rx) = mpsc::unbounded_channel::<PrMonitorEvent>();
+
+        // Auto-mode: hand new review comments and failing checks straight to the agent
+        let deployment = self.clone();
+        tokio::spawn(async move {
+            while let Some(event) = events_rx.recv().await {
+                let (attempt_id, enabled) = {
+                    let config = deployment.config().read().await;
+                    match event {
+                        PrMonitorEvent::ReviewComments(id) => {
+                            (id, config.auto_address_review_comments)
+                        }
+                        PrMonitorEvent::ChecksFailed(id) => (id, config.auto_fix_failed_checks),
+                    }
+                };
+                if !enabled {
+                    continue;
+                }
+                let Ok(Some(task_attempt)) =
+                    TaskAttempt::find_by_id(&deployment.db().pool, attempt_id).await
+                else {
+                    continue;
+                };
+                let result = match event {
+                    PrMonitorEvent::ReviewComments(_) => {
+                        PrReviewService::new(deployment.db().clone())
+                            .send_to_agent(
+                                deployment.drafts(),
+                                deployment.container(),
+                                &task_attempt,
+                                None,
+                            )
+                            .await
+                            .map(|_| ())
+                            .map_err(|e| e.to_string())
+                    }
+                    PrMonitorEvent::ChecksFailed(_) => {
+                        PrChecksService::new(deployment.db().clone())
+                            .send_failures_to_agent(
+                                deployment.drafts(),
+                                deployment.container(),
+                                &task_attempt,
+                            )
+                            .await
+                            .map(|_| ())
+                            .map_err(|e| e.to_string())
+                    }
+                };
+                if let Err(e) = result {
+                    tracing::warn!(
+                        "Failed to send PR feedback ({:?}) to agent for attempt {}: {}",
+                        event,
+                        attempt_id,
+                        e
+                    );
+                }
+            }
+        });
+
+        PrMonitorService::spawn(
+            db,
+            self.git().clone(),
+            analytics,
+            publisher,
+            Some(events_tx),
+        )
+        .await
     }
 
     async fn track_if_analytics_allowed(&self, event_name: &str, properties: Value) {
@@ -236,6 +304,14 @@ pub trait Deployment: Clone + Send + Sync + 'static {
                 }
             }
         }
+        // Config files are only read when an agent starts, so earlier runs are done with them
+        executors::mcp_config::remove_execution_mcp_configs().await;
+        // Approvals the orphaned agents were waiting on stay answerable until they time out
+        match self.approvals().restore_pending().await {
+            Ok(0) => {}
+            Ok(count) => tracing::info!("Restored {} pending approval requests", count),
+            Err(e) => tracing::error!("Failed to restore pending approval requests: {}", e),
+        }
         Ok(())
     }
 
@@ -316,6 +392,7 @@ pub trait Deployment: Clone + Send + Sync + 'static {
                         dev_script: None,
                         cleanup_script: None,
                         copy_files: None,
+                        document_template_pack_id: None,
                     };
                     // Ensure existing repo has a main branch if it's empty
                     if let Err(e) = self.git().ensure_main_branch_exists(&repo.path) {
@@ -8,6 +8,7 @@ use crate::{
     actions::Executable,
     approvals::ExecutorApprovalService,
     executors::{BaseCodingAgent, ExecutorError, SpawnedChild, StandardCodingAgentExecutor},
+    mcp_config::ExecutionMcpServers,
     profile::{ExecutorConfigs, ExecutorProfileId},
 };
 
@@ -38,6 +39,7 @@ impl Executable for CodingAgentFollowUpRequest {
         &self,
         current_dir: &Path,
         approvals: Arc<dyn ExecutorApprovalService>,
+        mcp_servers: &ExecutionMcpServers,
     ) -> Result<SpawnedChild, ExecutorError> {
         let executor_profile_id = self.get_executor_profile_id();
         let mut agent = ExecutorConfigs::get_cached()
@@ -47,6 +49,7 @@ impl Executable for CodingAgentFollowUpRequest {
             ))?;
 
         agent.use_approvals(approvals.clone());
+        agent.use_execution_mcp_servers(mcp_servers);
 
         agent
             .spawn_follow_up(current_dir, &self.prompt, &self.session_id)
@@ -8,6 +8,7 @@ use crate::{
     actions::Executable,
     approvals::ExecutorApprovalService,
     executors::{BaseCodingAgent, ExecutorError, SpawnedChild, StandardCodingAgentExecutor},
+    mcp_config::ExecutionMcpServers,
     profile::{ExecutorConfigs, ExecutorProfileId},
 };
 
@@ -32,6 +33,7 @@ impl Executable for CodingAgentInitialRequest {
         &self,
         current_dir: &Path,
         approvals: Arc<dyn ExecutorApprovalService>,
+        mcp_servers: &ExecutionMcpServers,
     ) -> Result<SpawnedChild, ExecutorError> {
         let executor_profile_id = self.executor_profile_id.clone();
         let mut agent = ExecutorConfigs::get_cached()
@@ -41,6 +43,7 @@ impl Executable for CodingAgentInitialRequest {
             ))?;
 
         agent.use_approvals(approvals.clone());
+        agent.use_execution_mcp_servers(mcp_servers);
 
         agent.spawn(current_dir, &self.prompt).await
     }
@@ -12,6 +12,7 @@ use crate::{
     },
     approvals::ExecutorApprovalService,
     executors::{BaseCodingAgent, ExecutorError, SpawnedChild},
+    mcp_config::ExecutionMcpServers,
 };
 pub mod coding_agent_follow_up;
 pub mod coding_agent_initial;
@@ -67,10 +68,12 @@ impl ExecutorAction {
 #[async_trait]
 #[enum_dispatch(ExecutorActionType)]
 pub trait Executable {
+    /// `mcp_servers` are added to coding agents for this execution only
     async fn spawn(
         &self,
         current_dir: &Path,
         approvals: Arc<dyn ExecutorApprovalService>,
+        mcp_servers: &ExecutionMcpServers,
     ) -> Result<SpawnedChild, ExecutorError>;
 }
 
@@ -80,7 +83,8 @@ impl Executable for ExecutorAction {
         &self,
         current_dir: &Path,
         approvals: Arc<dyn ExecutorApprovalService>,
+        mcp_servers: &ExecutionMcpServers,
     ) -> Result<SpawnedChild, ExecutorError> {
-        self.typ.spawn(current_dir, approvals).await
+        self.typ.spawn(current_dir, approvals, mcp_servers).await
     }
 }
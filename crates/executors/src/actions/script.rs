@@ -11,6 +11,7 @@ use crate::{
     actions::Executable,
     approvals::ExecutorApprovalService,
     executors::{ExecutorError, SpawnedChild},
+    mcp_config::ExecutionMcpServers,
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
@@ -39,6 +40,7 @@ impl Executable for ScriptRequest {
         &self,
         current_dir: &Path,
         _approvals: Arc<dyn ExecutorApprovalService>,
+        _mcp_servers: &ExecutionMcpServers,
     ) -> Result<SpawnedChild, ExecutorError> {
         let (shell_cmd, shell_arg) = get_shell_command();
         let mut command = Command::new(shell_cmd);
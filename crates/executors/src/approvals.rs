@@ -3,7 +3,9 @@ use std::fmt;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use sqlx::Type;
 use thiserror::Error;
+use ts_rs::TS;
 use workspace_utils::approvals::ApprovalStatus;
 
 /// Errors emitted by executor approval services.
@@ -50,6 +52,21 @@ impl ExecutorApprovalService for NoopExecutorApprovalService {
     }
 }
 
+/// What happens to a tool approval request nobody answers before its deadline
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS, Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "approval_timeout_action", rename_all = "snake_case")]
+#[ts(export)]
+pub enum ApprovalTimeoutAction {
+    /// Deny the tool call, telling the agent the approval timed out
+    #[default]
+    Deny,
+    /// Let the tool call run
+    Approve,
+    /// Keep the agent waiting until someone answers
+    Pause,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ToolCallMetadata {
     pub tool_call_id: String,
@@ -8,12 +8,17 @@ use crate::executors::acp::AcpEvent;
 /// ACP client that handles agent-client protocol communication
 pub struct AcpClient {
     event_tx: mpsc::UnboundedSender<AcpEvent>,
+    /// Reject permission requests instead of approving them (plan mode)
+    read_only: bool,
 }
 
 impl AcpClient {
     /// Create a new ACP client
-    pub fn new(event_tx: mpsc::UnboundedSender<AcpEvent>) -> Self {
-        Self { event_tx }
+    pub fn new(event_tx: mpsc::UnboundedSender<AcpEvent>, read_only: bool) -> Self {
+        Self {
+            event_tx,
+            read_only,
+        }
     }
 
     pub fn record_user_prompt_event(&self, prompt: &str) {
@@ -37,6 +42,27 @@ impl acp::Client for AcpClient {
         // Forward the request as an event
         self.send_event(AcpEvent::RequestPermission(args.clone()));
 
+        if self.read_only {
+            // Edits and commands need permission, reads do not
+            let outcome = match args
+                .options
+                .iter()
+                .find(|o| matches!(o.kind, acp::PermissionOptionKind::RejectOnce))
+            {
+                Some(opt) => {
+                    debug!("Rejecting permission in read-only mode: {}", opt.id);
+                    acp::RequestPermissionOutcome::Selected {
+                        option_id: opt.id.clone(),
+                    }
+                }
+                None => acp::RequestPermissionOutcome::Cancelled,
+            };
+            return Ok(acp::RequestPermissionResponse {
+                outcome,
+                meta: None,
+            });
+        }
+
         // Auto-approve with best available option
         let chosen_option = args
             .options
@@ -25,6 +25,7 @@ use crate::{
 /// Reusable harness for ACP-based conns (Gemini, Qwen, etc.)
 pub struct AcpAgentHarness {
     session_namespace: String,
+    read_only: bool,
 }
 
 impl Default for AcpAgentHarness {
@@ -39,6 +40,7 @@ impl AcpAgentHarness {
     pub fn new() -> Self {
         Self {
             session_namespace: "gemini_sessions".to_string(),
+            read_only: false,
         }
     }
 
@@ -46,9 +48,16 @@ impl AcpAgentHarness {
     pub fn with_session_namespace(namespace: impl Into<String>) -> Self {
         Self {
             session_namespace: namespace.into(),
+            read_only: false,
         }
     }
 
+    /// Decline every permission request, so the agent can only read the workspace
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
     pub async fn spawn_with_command(
         &self,
         current_dir: &Path,
@@ -76,12 +85,14 @@ impl AcpAgentHarness {
             prompt,
             Some(exit_tx),
             self.session_namespace.clone(),
+            self.read_only,
         )
         .await?;
 
         Ok(SpawnedChild {
             child,
             exit_signal: Some(exit_rx),
+            config_files: Vec::new(),
         })
     }
 
@@ -113,12 +124,14 @@ impl AcpAgentHarness {
             prompt,
             Some(exit_tx),
             self.session_namespace.clone(),
+            self.read_only,
         )
         .await?;
 
         Ok(SpawnedChild {
             child,
             exit_signal: Some(exit_rx),
+            config_files: Vec::new(),
         })
     }
 
@@ -129,6 +142,7 @@ impl AcpAgentHarness {
         prompt: String,
         exit_signal: Option<tokio::sync::oneshot::Sender<()>>,
         session_namespace: String,
+        read_only: bool,
     ) -> Result<(), ExecutorError> {
         // Take child's stdio for ACP wiring
         let orig_stdout = child.inner().stdout.take().ok_or_else(|| {
@@ -244,7 +258,7 @@ impl AcpAgentHarness {
                         let session_manager = std::sync::Arc::new(session_manager);
 
                         // Create ACP client
-                        let client = AcpClient::new(event_tx.clone());
+                        let client = AcpClient::new(event_tx.clone(), read_only);
 
                         client.record_user_prompt_event(&prompt);
 
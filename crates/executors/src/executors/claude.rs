@@ -3,7 +3,12 @@ pub mod client;
 pub mod protocol;
 pub mod types;
 
-use std::{collections::HashMap, path::Path, process::Stdio, sync::Arc};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    process::Stdio,
+    sync::Arc,
+};
 
 use async_trait::async_trait;
 use command_group::AsyncCommandGroup;
@@ -31,6 +36,7 @@ use crate::{
         stderr_processor::normalize_stderr_logs,
         utils::{EntryIndexProvider, patch::ConversationPatch},
     },
+    mcp_config::{attach_execution_mcp_config, write_execution_mcp_config},
     stdout_dup::create_stdout_pipe_writer,
 };
 
@@ -66,6 +72,11 @@ pub struct ClaudeCode {
     #[ts(skip)]
     #[derivative(Debug = "ignore", PartialEq = "ignore")]
     approvals_service: Option<Arc<dyn ExecutorApprovalService>>,
+
+    #[serde(skip)]
+    #[ts(skip)]
+    #[derivative(PartialEq = "ignore")]
+    mcp_servers: Option<serde_json::Map<String, serde_json::Value>>,
 }
 
 impl ClaudeCode {
@@ -110,6 +121,24 @@ impl ClaudeCode {
         apply_overrides(builder, &self.cmd)
     }
 
+    /// Execution MCP servers are added with `--mcp-config`, next to the user's own.
+    /// They go through a private file, as their env vars often hold credentials; its
+    /// path is returned for removal once the run exits.
+    async fn with_mcp_config(
+        &self,
+        command_parts: CommandParts,
+    ) -> Result<(CommandParts, Option<PathBuf>), ExecutorError> {
+        let Some(servers) = &self.mcp_servers else {
+            return Ok((command_parts, None));
+        };
+        let config_path = write_execution_mcp_config(servers).await?;
+        let command_parts = command_parts.with_args([
+            "--mcp-config".to_string(),
+            config_path.to_string_lossy().to_string(),
+        ]);
+        Ok((command_parts, Some(config_path)))
+    }
+
     pub fn permission_mode(&self) -> PermissionMode {
         if self.plan.unwrap_or(false) {
             PermissionMode::Plan
@@ -154,11 +183,19 @@ impl StandardCodingAgentExecutor for ClaudeCode {
         self.approvals_service = Some(approvals);
     }
 
+    fn use_mcp_servers(&mut self, servers: serde_json::Map<String, serde_json::Value>) {
+        self.mcp_servers = Some(servers).filter(|servers| !servers.is_empty());
+    }
+
     async fn spawn(&self, current_dir: &Path, prompt: &str) -> Result<SpawnedChild, ExecutorError> {
         let command_builder = self.build_command_builder().await;
-        let command_parts = command_builder.build_initial()?;
-        self.spawn_internal(current_dir, prompt, command_parts)
-            .await
+        let (command_parts, mcp_config) = self
+            .with_mcp_config(command_builder.build_initial()?)
+            .await?;
+        let spawned = self
+            .spawn_internal(current_dir, prompt, command_parts)
+            .await;
+        attach_execution_mcp_config(spawned, mcp_config).await
     }
 
     async fn spawn_follow_up(
@@ -168,13 +205,17 @@ impl StandardCodingAgentExecutor for ClaudeCode {
         session_id: &str,
     ) -> Result<SpawnedChild, ExecutorError> {
         let command_builder = self.build_command_builder().await;
-        let command_parts = command_builder.build_follow_up(&[
-            "--fork-session".to_string(),
-            "--resume".to_string(),
-            session_id.to_string(),
-        ])?;
-        self.spawn_internal(current_dir, prompt, command_parts)
-            .await
+        let (command_parts, mcp_config) = self
+            .with_mcp_config(command_builder.build_follow_up(&[
+                "--fork-session".to_string(),
+                "--resume".to_string(),
+                session_id.to_string(),
+            ])?)
+            .await?;
+        let spawned = self
+            .spawn_internal(current_dir, prompt, command_parts)
+            .await;
+        attach_execution_mcp_config(spawned, mcp_config).await
     }
 
     fn normalize_logs(&self, msg_store: Arc<MsgStore>, current_dir: &Path) {
@@ -260,10 +301,7 @@ impl ClaudeCode {
             }
         });
 
-        Ok(SpawnedChild {
-            child,
-            exit_signal: None,
-        })
+        Ok(child.into())
     }
 }
 
@@ -1973,6 +2011,7 @@ mod tests {
                 additional_params: None,
             },
             approvals_service: None,
+            mcp_servers: None,
         };
         let msg_store = Arc::new(MsgStore::new());
         let current_dir = std::path::PathBuf::from("/tmp/test-worktree");
@@ -2296,4 +2335,44 @@ mod tests {
 
         // ToolResult entry is ignored - no third entry
     }
+
+    #[tokio::test]
+    async fn test_execution_mcp_servers_are_passed_in_a_config_file() {
+        let mut executor: ClaudeCode = serde_json::from_value(serde_json::json!({})).unwrap();
+        let servers = serde_json::Map::from_iter([(
+            "db".to_string(),
+            serde_json::json!({"command": "npx", "env": {"PGPASSWORD": "secret"}}),
+        )]);
+        executor.use_mcp_servers(servers.clone());
+
+        let (command_parts, config_path) = executor
+            .with_mcp_config(CommandParts::new("sh".to_string(), vec![]))
+            .await
+            .unwrap();
+        let (_, args) = command_parts.into_resolved().await.unwrap();
+
+        assert_eq!(args.len(), 2);
+        assert_eq!(config_path.unwrap().to_string_lossy(), args[1]);
+        assert_eq!(args[0], "--mcp-config");
+        assert!(!args[1].contains("secret"));
+        let config: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&args[1]).unwrap()).unwrap();
+        assert_eq!(config["mcpServers"], serde_json::Value::Object(servers));
+        std::fs::remove_file(&args[1]).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_no_mcp_config_without_execution_mcp_servers() {
+        let mut executor: ClaudeCode = serde_json::from_value(serde_json::json!({})).unwrap();
+        executor.use_mcp_servers(serde_json::Map::new());
+
+        let (command_parts, config_path) = executor
+            .with_mcp_config(CommandParts::new("sh".to_string(), vec![]))
+            .await
+            .unwrap();
+        let (_, args) = command_parts.into_resolved().await.unwrap();
+
+        assert!(args.is_empty());
+        assert!(config_path.is_none());
+    }
 }
@@ -105,6 +105,8 @@ pub struct Codex {
     #[serde(default)]
     pub append_prompt: AppendPrompt,
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub plan: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub sandbox: Option<SandboxMode>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub ask_for_approval: Option<AskForApproval>,
@@ -133,6 +135,11 @@ pub struct Codex {
     #[ts(skip)]
     #[derivative(Debug = "ignore", PartialEq = "ignore")]
     approvals: Option<Arc<dyn ExecutorApprovalService>>,
+
+    #[serde(skip)]
+    #[ts(skip)]
+    #[derivative(PartialEq = "ignore")]
+    mcp_servers: Option<serde_json::Map<String, Value>>,
 }
 
 #[async_trait]
@@ -141,6 +148,10 @@ impl StandardCodingAgentExecutor for Codex {
         self.approvals = Some(approvals);
     }
 
+    fn use_mcp_servers(&mut self, servers: serde_json::Map<String, Value>) {
+        self.mcp_servers = Some(servers).filter(|servers| !servers.is_empty());
+    }
+
     async fn spawn(&self, current_dir: &Path, prompt: &str) -> Result<SpawnedChild, ExecutorError> {
         let command_parts = self.build_command_builder().build_initial()?;
         self.spawn(current_dir, prompt, command_parts, None).await
@@ -178,14 +189,40 @@ impl Codex {
     }
 
     fn build_new_conversation_params(&self, cwd: &Path) -> NewConversationParams {
-        let sandbox = match self.sandbox.as_ref() {
+        let (sandbox, approval_policy) = if self.plan.unwrap_or(false) {
+            // Planning runs read-only, without asking to leave the sandbox
+            (
+                Some(CodexSandboxMode::ReadOnly),
+                Some(CodexAskForApproval::Never),
+            )
+        } else {
+            (self.sandbox_mode(), self.approval_policy())
+        };
+
+        NewConversationParams {
+            model: self.model.clone(),
+            profile: self.profile.clone(),
+            cwd: Some(cwd.to_string_lossy().to_string()),
+            approval_policy,
+            sandbox,
+            config: self.build_config_overrides(),
+            base_instructions: self.base_instructions.clone(),
+            include_plan_tool: self.include_plan_tool,
+            include_apply_patch_tool: self.include_apply_patch_tool,
+        }
+    }
+
+    fn sandbox_mode(&self) -> Option<CodexSandboxMode> {
+        match self.sandbox.as_ref() {
             None | Some(SandboxMode::Auto) => Some(CodexSandboxMode::WorkspaceWrite), // match the Auto preset in codex
             Some(SandboxMode::ReadOnly) => Some(CodexSandboxMode::ReadOnly),
             Some(SandboxMode::WorkspaceWrite) => Some(CodexSandboxMode::WorkspaceWrite),
             Some(SandboxMode::DangerFullAccess) => Some(CodexSandboxMode::DangerFullAccess),
-        };
+        }
+    }
 
-        let approval_policy = match self.ask_for_approval.as_ref() {
+    fn approval_policy(&self) -> Option<CodexAskForApproval> {
+        match self.ask_for_approval.as_ref() {
             None if matches!(self.sandbox.as_ref(), None | Some(SandboxMode::Auto)) => {
                 // match the Auto preset in codex
                 Some(CodexAskForApproval::OnRequest)
@@ -195,18 +232,6 @@ impl Codex {
             Some(AskForApproval::OnFailure) => Some(CodexAskForApproval::OnFailure),
             Some(AskForApproval::OnRequest) => Some(CodexAskForApproval::OnRequest),
             Some(AskForApproval::Never) => Some(CodexAskForApproval::Never),
-        };
-
-        NewConversationParams {
-            model: self.model.clone(),
-            profile: self.profile.clone(),
-            cwd: Some(cwd.to_string_lossy().to_string()),
-            approval_policy,
-            sandbox,
-            config: self.build_config_overrides(),
-            base_instructions: self.base_instructions.clone(),
-            include_plan_tool: self.include_plan_tool,
-            include_apply_patch_tool: self.include_apply_patch_tool,
         }
     }
 
@@ -236,6 +261,13 @@ impl Codex {
             );
         }
 
+        // Execution MCP servers override `mcp_servers.<name>` for this conversation only
+        if let Some(servers) = &self.mcp_servers {
+            for (name, server) in servers {
+                overrides.insert(format!("mcp_servers.{name}"), server.clone());
+            }
+        }
+
         if overrides.is_empty() {
             None
         } else {
@@ -250,7 +282,7 @@ impl Codex {
         command_parts: CommandParts,
         resume_session: Option<&str>,
     ) -> Result<SpawnedChild, ExecutorError> {
-        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        let combined_prompt = self.append_prompt.combine_plan_prompt(prompt, self.plan);
         let (program_path, args) = command_parts.into_resolved().await?;
 
         let mut process = Command::new(program_path);
@@ -279,10 +311,11 @@ impl Codex {
 
         let params = self.build_new_conversation_params(current_dir);
         let resume_session = resume_session.map(|s| s.to_string());
-        let auto_approve = matches!(
-            (&self.sandbox, &self.ask_for_approval),
-            (Some(SandboxMode::DangerFullAccess), None)
-        );
+        let auto_approve = !self.plan.unwrap_or(false)
+            && matches!(
+                (&self.sandbox, &self.ask_for_approval),
+                (Some(SandboxMode::DangerFullAccess), None)
+            );
         let approvals = self.approvals.clone();
         tokio::spawn(async move {
             let exit_signal_tx = ExitSignalSender::new(exit_signal_tx);
@@ -317,6 +350,7 @@ impl Codex {
         Ok(SpawnedChild {
             child,
             exit_signal: Some(exit_signal_rx),
+            config_files: Vec::new(),
         })
     }
 
@@ -21,12 +21,13 @@ use uuid::Uuid;
 use workspace_utils::{msg_store::MsgStore, path::get_anyon_temp_dir};
 
 use crate::{
-    command::{CmdOverrides, CommandBuilder, apply_overrides},
+    command::{CmdOverrides, CommandBuilder, CommandParts, apply_overrides},
     executors::{AppendPrompt, ExecutorError, SpawnedChild, StandardCodingAgentExecutor},
     logs::{
         NormalizedEntry, NormalizedEntryType, plain_text_processor::PlainTextLogProcessor,
         stderr_processor::normalize_stderr_logs, utils::EntryIndexProvider,
     },
+    mcp_config::{attach_execution_mcp_config, write_execution_mcp_config},
     stdout_dup::{self, StdoutAppender},
 };
 
@@ -48,6 +49,10 @@ pub struct Copilot {
     pub disable_mcp_server: Option<Vec<String>>,
     #[serde(flatten)]
     pub cmd: CmdOverrides,
+
+    #[serde(skip)]
+    #[ts(skip)]
+    mcp_servers: Option<serde_json::Map<String, serde_json::Value>>,
 }
 
 impl Copilot {
@@ -90,15 +95,32 @@ impl Copilot {
 
         apply_overrides(builder, &self.cmd)
     }
-}
 
-#[async_trait]
-impl StandardCodingAgentExecutor for Copilot {
-    async fn spawn(&self, current_dir: &Path, prompt: &str) -> Result<SpawnedChild, ExecutorError> {
-        let log_dir = Self::create_temp_log_dir(current_dir).await?;
-        let command_parts = self
-            .build_command_builder(&log_dir.to_string_lossy())
-            .build_initial()?;
+    /// Execution MCP servers are added with `--additional-mcp-config`, read from a
+    /// private file as their env vars often hold credentials. The file's path is
+    /// returned for removal once the run exits.
+    async fn with_mcp_config(
+        &self,
+        command_parts: CommandParts,
+    ) -> Result<(CommandParts, Option<PathBuf>), ExecutorError> {
+        let Some(servers) = &self.mcp_servers else {
+            return Ok((command_parts, None));
+        };
+        let config_path = write_execution_mcp_config(servers).await?;
+        let command_parts = command_parts.with_args([
+            "--additional-mcp-config".to_string(),
+            format!("@{}", config_path.display()),
+        ]);
+        Ok((command_parts, Some(config_path)))
+    }
+
+    async fn spawn_command(
+        &self,
+        current_dir: &Path,
+        log_dir: PathBuf,
+        prompt: &str,
+        command_parts: CommandParts,
+    ) -> Result<SpawnedChild, ExecutorError> {
         let (program_path, args) = command_parts.into_resolved().await?;
 
         let combined_prompt = self.append_prompt.combine_prompt(prompt);
@@ -126,6 +148,27 @@ impl StandardCodingAgentExecutor for Copilot {
 
         Ok(child.into())
     }
+}
+
+#[async_trait]
+impl StandardCodingAgentExecutor for Copilot {
+    fn use_mcp_servers(&mut self, servers: serde_json::Map<String, serde_json::Value>) {
+        self.mcp_servers = Some(servers).filter(|servers| !servers.is_empty());
+    }
+
+    async fn spawn(&self, current_dir: &Path, prompt: &str) -> Result<SpawnedChild, ExecutorError> {
+        let log_dir = Self::create_temp_log_dir(current_dir).await?;
+        let (command_parts, mcp_config) = self
+            .with_mcp_config(
+                self.build_command_builder(&log_dir.to_string_lossy())
+                    .build_initial()?,
+            )
+            .await?;
+        let spawned = self
+            .spawn_command(current_dir, log_dir, prompt, command_parts)
+            .await;
+        attach_execution_mcp_config(spawned, mcp_config).await
+    }
 
     async fn spawn_follow_up(
         &self,
@@ -134,36 +177,16 @@ impl StandardCodingAgentExecutor for Copilot {
         session_id: &str,
     ) -> Result<SpawnedChild, ExecutorError> {
         let log_dir = Self::create_temp_log_dir(current_dir).await?;
-        let command_parts = self
-            .build_command_builder(&log_dir.to_string_lossy())
-            .build_follow_up(&["--resume".to_string(), session_id.to_string()])?;
-        let (program_path, args) = command_parts.into_resolved().await?;
-
-        let combined_prompt = self.append_prompt.combine_prompt(prompt);
-
-        let mut command = Command::new(program_path);
-
-        command
-            .kill_on_drop(true)
-            .stdin(Stdio::piped())
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .current_dir(current_dir)
-            .args(&args)
-            .env("NODE_NO_WARNINGS", "1");
-
-        let mut child = command.group_spawn()?;
-
-        // Write comprehensive prompt to stdin
-        if let Some(mut stdin) = child.inner().stdin.take() {
-            stdin.write_all(combined_prompt.as_bytes()).await?;
-            stdin.shutdown().await?;
-        }
-
-        let (_, appender) = stdout_dup::tee_stdout_with_appender(&mut child)?;
-        Self::send_session_id(log_dir, appender);
-
-        Ok(child.into())
+        let (command_parts, mcp_config) = self
+            .with_mcp_config(
+                self.build_command_builder(&log_dir.to_string_lossy())
+                    .build_follow_up(&["--resume".to_string(), session_id.to_string()])?,
+            )
+            .await?;
+        let spawned = self
+            .spawn_command(current_dir, log_dir, prompt, command_parts)
+            .await;
+        attach_execution_mcp_config(spawned, mcp_config).await
     }
 
     /// Parses both stderr and stdout logs for Copilot executor using PlainTextLogProcessor.
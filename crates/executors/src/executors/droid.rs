@@ -63,6 +63,13 @@ pub struct Droid {
     )]
     pub autonomy: Autonomy,
 
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        title = "Plan",
+        description = "Only plan: run read-only and present the plan for approval"
+    )]
+    pub plan: Option<bool>,
+
     #[serde(default, skip_serializing_if = "Option::is_none")]
     #[schemars(
         title = "Model",
@@ -86,7 +93,13 @@ impl Droid {
         use crate::command::{CommandBuilder, apply_overrides};
         let mut builder =
             CommandBuilder::new("droid exec").params(["--output-format", "stream-json"]);
-        builder = match &self.autonomy {
+        // Without an autonomy flag droid exec is read-only, which plan mode relies on
+        let autonomy = if self.plan.unwrap_or(false) {
+            &Autonomy::Normal
+        } else {
+            &self.autonomy
+        };
+        builder = match autonomy {
             Autonomy::Normal => builder,
             Autonomy::Low => builder.extend_params(["--auto", "low"]),
             Autonomy::Medium => builder.extend_params(["--auto", "medium"]),
@@ -134,7 +147,7 @@ async fn spawn(
 impl StandardCodingAgentExecutor for Droid {
     async fn spawn(&self, current_dir: &Path, prompt: &str) -> Result<SpawnedChild, ExecutorError> {
         let droid_command = self.build_command_builder().build_initial()?;
-        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        let combined_prompt = self.append_prompt.combine_plan_prompt(prompt, self.plan);
 
         spawn(droid_command, &combined_prompt, current_dir).await
     }
@@ -153,7 +166,7 @@ impl StandardCodingAgentExecutor for Droid {
         let continue_cmd = self
             .build_command_builder()
             .build_follow_up(&["--session-id".to_string(), forked_session_id.clone()])?;
-        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        let combined_prompt = self.append_prompt.combine_plan_prompt(prompt, self.plan);
 
         spawn(continue_cmd, &combined_prompt, current_dir).await
     }
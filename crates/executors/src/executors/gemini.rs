@@ -20,6 +20,8 @@ pub struct Gemini {
     pub model: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub yolo: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub plan: Option<bool>,
     #[serde(flatten)]
     pub cmd: CmdOverrides,
 }
@@ -32,7 +34,8 @@ impl Gemini {
             builder = builder.extend_params(["--model", model.as_str()]);
         }
 
-        if self.yolo.unwrap_or(false) {
+        // Plan mode keeps the default approval mode, whose requests the harness declines
+        if self.yolo.unwrap_or(false) && !self.plan.unwrap_or(false) {
             builder = builder.extend_params(["--yolo"]);
             builder = builder.extend_params(["--allowed-tools", "run_shell_command"]);
         }
@@ -46,8 +49,8 @@ impl Gemini {
 #[async_trait]
 impl StandardCodingAgentExecutor for Gemini {
     async fn spawn(&self, current_dir: &Path, prompt: &str) -> Result<SpawnedChild, ExecutorError> {
-        let harness = AcpAgentHarness::new();
-        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        let harness = AcpAgentHarness::new().read_only(self.plan.unwrap_or(false));
+        let combined_prompt = self.append_prompt.combine_plan_prompt(prompt, self.plan);
         let gemini_command = self.build_command_builder().build_initial()?;
         harness
             .spawn_with_command(current_dir, combined_prompt, gemini_command)
@@ -60,8 +63,8 @@ impl StandardCodingAgentExecutor for Gemini {
         prompt: &str,
         session_id: &str,
     ) -> Result<SpawnedChild, ExecutorError> {
-        let harness = AcpAgentHarness::new();
-        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        let harness = AcpAgentHarness::new().read_only(self.plan.unwrap_or(false));
+        let combined_prompt = self.append_prompt.combine_plan_prompt(prompt, self.plan);
         let gemini_command = self.build_command_builder().build_follow_up(&[])?;
         harness
             .spawn_follow_up_with_command(current_dir, combined_prompt, session_id, gemini_command)
@@ -1,4 +1,7 @@
-use std::{path::Path, sync::Arc};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 use async_trait::async_trait;
 use command_group::AsyncGroupChild;
@@ -162,6 +165,21 @@ impl CodingAgent {
             Self::Opencode(_) | Self::Copilot(_) => vec![],
         }
     }
+
+    /// Whether this profile only plans. Claude Code presents the plan through its
+    /// ExitPlanMode hook, the others in `<plan>` tags of their final message.
+    pub fn is_plan_mode(&self) -> bool {
+        match self {
+            Self::ClaudeCode(agent) => agent.plan,
+            Self::Codex(agent) => agent.plan,
+            Self::Gemini(agent) => agent.plan,
+            Self::QwenCode(agent) => agent.plan,
+            Self::Opencode(agent) => agent.plan,
+            Self::Droid(agent) => agent.plan,
+            Self::Amp(_) | Self::CursorAgent(_) | Self::Copilot(_) => None,
+        }
+        .unwrap_or(false)
+    }
 }
 
 #[async_trait]
@@ -169,6 +187,17 @@ impl CodingAgent {
 pub trait StandardCodingAgentExecutor {
     fn use_approvals(&mut self, _approvals: Arc<dyn ExecutorApprovalService>) {}
 
+    /// Add MCP servers for this execution only, without touching the agent's config
+    /// file. Agents that cannot take servers per execution skip them.
+    fn use_mcp_servers(&mut self, servers: serde_json::Map<String, serde_json::Value>) {
+        if !servers.is_empty() {
+            tracing::warn!(
+                "This coding agent cannot take MCP servers per execution, skipping {} project MCP server(s)",
+                servers.len()
+            );
+        }
+    }
+
     async fn spawn(&self, current_dir: &Path, prompt: &str) -> Result<SpawnedChild, ExecutorError>;
     async fn spawn_follow_up(
         &self,
@@ -201,6 +230,9 @@ pub type ExecutorExitSignal = tokio::sync::oneshot::Receiver<()>;
 pub struct SpawnedChild {
     pub child: AsyncGroupChild,
     pub exit_signal: Option<ExecutorExitSignal>,
+    /// Files written for this run only, such as MCP configs holding credentials, to
+    /// remove once it exits
+    pub config_files: Vec<PathBuf>,
 }
 
 impl From<AsyncGroupChild> for SpawnedChild {
@@ -208,6 +240,7 @@ impl From<AsyncGroupChild> for SpawnedChild {
         Self {
             child,
             exit_signal: None,
+            config_files: Vec::new(),
         }
     }
 }
@@ -233,6 +266,27 @@ impl AppendPrompt {
             AppendPrompt(None) => prompt.to_string(),
         }
     }
+
+    /// Like [`Self::combine_prompt`], asking for a plan instead of changes when `plan` is set
+    pub fn combine_plan_prompt(&self, prompt: &str, plan: Option<bool>) -> String {
+        if plan.unwrap_or(false) {
+            self.combine_prompt(&format!("{prompt}{PLAN_MODE_INSTRUCTIONS}"))
+        } else {
+            self.combine_prompt(prompt)
+        }
+    }
+}
+
+/// Plan mode for executors without a plan approval hook: they run read-only and
+/// write the plan into their final message, where [`extract_plan`] finds it
+const PLAN_MODE_INSTRUCTIONS: &str = "\n\nYou are in plan mode. Do not modify any files or run commands that change the workspace. Investigate the codebase and work out how to implement the request, then put the complete implementation plan, in Markdown, between <plan> and </plan> tags in your final message. The plan is reviewed before anything is implemented.";
+
+/// The plan an executor in plan mode wrote between `<plan>` tags; the last one wins
+pub fn extract_plan(message: &str) -> Option<String> {
+    let start = message.rfind("<plan>")? + "<plan>".len();
+    let body = &message[start..];
+    let plan = body.find("</plan>").map_or(body, |end| &body[..end]).trim();
+    (!plan.is_empty()).then(|| plan.to_string())
 }
 
 #[cfg(test)]
@@ -266,4 +320,14 @@ mod tests {
         assert!(result.is_ok(), "CURSOR should deserialize via serde");
         assert_eq!(result.unwrap(), BaseCodingAgent::CursorAgent);
     }
+
+    #[test]
+    fn test_extract_plan() {
+        let message = "Draft: <plan>old</plan>\n\nFinal:\n<plan>\n1. Add the route\n</plan>\nDone.";
+        assert_eq!(extract_plan(message).as_deref(), Some("1. Add the route"));
+        // An unterminated block still counts
+        assert_eq!(extract_plan("<plan>1. Step").as_deref(), Some("1. Step"));
+        assert_eq!(extract_plan("No plan here"), None);
+        assert_eq!(extract_plan("<plan> </plan>"), None);
+    }
 }
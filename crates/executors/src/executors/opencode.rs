@@ -102,6 +102,8 @@ pub struct Opencode {
     pub model: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub agent: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub plan: Option<bool>,
     #[serde(flatten)]
     pub cmd: CmdOverrides,
 }
@@ -118,7 +120,10 @@ impl Opencode {
             builder = builder.extend_params(["--model", model]);
         }
 
-        if let Some(agent) = &self.agent {
+        if self.plan.unwrap_or(false) {
+            // opencode's built-in plan agent cannot edit files
+            builder = builder.extend_params(["--agent", "plan"]);
+        } else if let Some(agent) = &self.agent {
             builder = builder.extend_params(["--agent", agent]);
         }
 
@@ -134,7 +139,7 @@ impl StandardCodingAgentExecutor for Opencode {
         let command_parts = self.build_command_builder().build_initial()?;
         let (program_path, args) = command_parts.into_resolved().await?;
 
-        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        let combined_prompt = self.append_prompt.combine_plan_prompt(prompt, self.plan);
 
         let mut command = Command::new(program_path);
         command
@@ -200,7 +205,7 @@ impl StandardCodingAgentExecutor for Opencode {
             .build_follow_up(&["--session".to_string(), session_id.to_string()])?;
         let (program_path, args) = command_parts.into_resolved().await?;
 
-        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        let combined_prompt = self.append_prompt.combine_plan_prompt(prompt, self.plan);
 
         let mut command = Command::new(program_path);
         command
@@ -20,6 +20,8 @@ pub struct QwenCode {
     pub append_prompt: AppendPrompt,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub yolo: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub plan: Option<bool>,
     #[serde(flatten)]
     pub cmd: CmdOverrides,
 }
@@ -28,7 +30,7 @@ impl QwenCode {
     fn build_command_builder(&self) -> CommandBuilder {
         let mut builder = CommandBuilder::new("npx -y @qwen-code/qwen-code@0.2.1");
 
-        if self.yolo.unwrap_or(false) {
+        if self.yolo.unwrap_or(false) && !self.plan.unwrap_or(false) {
             builder = builder.extend_params(["--yolo"]);
         }
         builder = builder.extend_params(["--experimental-acp"]);
@@ -40,8 +42,9 @@ impl QwenCode {
 impl StandardCodingAgentExecutor for QwenCode {
     async fn spawn(&self, current_dir: &Path, prompt: &str) -> Result<SpawnedChild, ExecutorError> {
         let qwen_command = self.build_command_builder().build_initial()?;
-        let combined_prompt = self.append_prompt.combine_prompt(prompt);
-        let harness = AcpAgentHarness::with_session_namespace("qwen_sessions");
+        let combined_prompt = self.append_prompt.combine_plan_prompt(prompt, self.plan);
+        let harness = AcpAgentHarness::with_session_namespace("qwen_sessions")
+            .read_only(self.plan.unwrap_or(false));
         harness
             .spawn_with_command(current_dir, combined_prompt, qwen_command)
             .await
@@ -54,8 +57,9 @@ impl StandardCodingAgentExecutor for QwenCode {
         session_id: &str,
     ) -> Result<SpawnedChild, ExecutorError> {
         let qwen_command = self.build_command_builder().build_follow_up(&[])?;
-        let combined_prompt = self.append_prompt.combine_prompt(prompt);
-        let harness = AcpAgentHarness::with_session_namespace("qwen_sessions");
+        let combined_prompt = self.append_prompt.combine_plan_prompt(prompt, self.plan);
+        let harness = AcpAgentHarness::with_session_namespace("qwen_sessions")
+            .read_only(self.plan.unwrap_or(false));
         harness
             .spawn_follow_up_with_command(current_dir, combined_prompt, session_id, qwen_command)
             .await
@@ -3,6 +3,8 @@ use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 use workspace_utils::approvals::ApprovalStatus;
 
+use crate::approvals::ApprovalTimeoutAction;
+
 pub mod plain_text_processor;
 pub mod stderr_processor;
 pub mod utils;
@@ -143,6 +145,9 @@ pub enum ToolStatus {
         approval_id: String,
         requested_at: DateTime<Utc>,
         timeout_at: DateTime<Utc>,
+        /// Outcome applied when `timeout_at` passes without an answer
+        #[serde(default)]
+        on_timeout: ApprovalTimeoutAction,
     },
     TimedOut,
 }
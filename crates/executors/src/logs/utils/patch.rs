@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{collections::BTreeMap, sync::Arc};
 
 use json_patch::Patch;
 use serde::{Deserialize, Serialize};
@@ -142,6 +142,45 @@ pub fn extract_normalized_entry_from_patch(patch: &Patch) -> Option<(usize, Norm
     })
 }
 
+/// Fold a conversation's patches into its normalized entries, in entry order.
+/// Later patches replace earlier ones at the same index; non-entry values such as
+/// stdout chunks are skipped.
+pub fn normalized_entries_from_patches<'a>(
+    patches: impl IntoIterator<Item = &'a Patch>,
+) -> Vec<NormalizedEntry> {
+    let mut entries = BTreeMap::new();
+    for patch in patches {
+        let Ok(value) = to_value(patch) else {
+            continue;
+        };
+        for op in value.as_array().into_iter().flatten() {
+            let Some(index) = op
+                .get("path")
+                .and_then(|path| path.as_str())
+                .and_then(|path| path.strip_prefix("/entries/"))
+                .and_then(|index| index.parse::<usize>().ok())
+            else {
+                continue;
+            };
+            if op.get("op").and_then(|op| op.as_str()) == Some("remove") {
+                entries.remove(&index);
+                continue;
+            }
+            let entry = op
+                .get("value")
+                .filter(|value| {
+                    value.get("type").and_then(|t| t.as_str()) == Some("NORMALIZED_ENTRY")
+                })
+                .and_then(|value| value.get("content"))
+                .and_then(|content| from_value::<NormalizedEntry>(content.clone()).ok());
+            if let Some(entry) = entry {
+                entries.insert(index, entry);
+            }
+        }
+    }
+    entries.into_values().collect()
+}
+
 pub fn upsert_normalized_entry(
     msg_store: &Arc<MsgStore>,
     index: usize,
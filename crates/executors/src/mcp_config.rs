@@ -2,20 +2,38 @@
 //!
 //! These helpers abstract over JSON vs TOML formats used by different agents.
 
-use std::{collections::HashMap, sync::LazyLock};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::LazyLock,
+    time::Duration,
+};
 
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
-use tokio::fs;
+use tokio::{fs, io::AsyncWriteExt};
 use ts_rs::TS;
+use uuid::Uuid;
+use workspace_utils::path::get_anyon_temp_dir;
 
-use crate::executors::{CodingAgent, ExecutorError};
+use crate::executors::{
+    BaseCodingAgent, CodingAgent, ExecutorError, SpawnedChild, StandardCodingAgentExecutor,
+};
 
 static DEFAULT_MCP_JSON: &str = include_str!("../default_mcp.json");
 pub static PRECONFIGURED_MCP_SERVERS: LazyLock<Value> = LazyLock::new(|| {
     serde_json::from_str::<Value>(DEFAULT_MCP_JSON).expect("Failed to parse default MCP JSON")
 });
 
+/// MCP servers added to a single execution on top of the agent's own configuration,
+/// keyed by name in the canonical `mcpServers` shape
+pub type ExecutionMcpServers = HashMap<String, Value>;
+
+/// Execution MCP config files are removed when their run exits and when the server
+/// starts. Files older than this are also removed when a new one is written, in case
+/// a run's exit was missed; agents read the file when they start.
+const EXECUTION_MCP_CONFIG_MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 pub struct McpConfig {
     servers: HashMap<String, serde_json::Value>,
@@ -85,6 +103,98 @@ pub async fn write_agent_config(
     Ok(())
 }
 
+/// Write execution MCP servers to a new `{"mcpServers": ...}` file only the current
+/// user can read, for agents that take a config file path. Server env vars and
+/// headers often hold credentials, which must not show up in the process list.
+pub async fn write_execution_mcp_config(
+    servers: &Map<String, Value>,
+) -> Result<PathBuf, ExecutorError> {
+    write_mcp_config_file(&execution_mcp_config_dir(), servers).await
+}
+
+/// Hand the run's MCP config file to the spawned child, so it is removed when the run
+/// exits. The file is removed right away when the run failed to start.
+pub async fn attach_execution_mcp_config(
+    spawned: Result<SpawnedChild, ExecutorError>,
+    config_path: Option<PathBuf>,
+) -> Result<SpawnedChild, ExecutorError> {
+    let Some(config_path) = config_path else {
+        return spawned;
+    };
+    match spawned {
+        Ok(mut spawned) => {
+            spawned.config_files.push(config_path);
+            Ok(spawned)
+        }
+        Err(e) => {
+            remove_execution_mcp_config(&config_path).await;
+            Err(e)
+        }
+    }
+}
+
+/// Remove a run's MCP config file once the run has exited
+pub async fn remove_execution_mcp_config(path: &Path) {
+    if let Err(e) = fs::remove_file(path).await
+        && e.kind() != std::io::ErrorKind::NotFound
+    {
+        tracing::warn!("Failed to remove MCP config {}: {e}", path.display());
+    }
+}
+
+/// Remove the MCP config files of all earlier runs, for use at startup when none of
+/// them is running anymore
+pub async fn remove_execution_mcp_configs() {
+    remove_mcp_config_files(&execution_mcp_config_dir(), Duration::ZERO).await;
+}
+
+fn execution_mcp_config_dir() -> PathBuf {
+    get_anyon_temp_dir().join("mcp_configs")
+}
+
+async fn write_mcp_config_file(dir: &Path, servers: &ServerMap) -> Result<PathBuf, ExecutorError> {
+    let mut dir_builder = fs::DirBuilder::new();
+    dir_builder.recursive(true);
+    #[cfg(unix)]
+    dir_builder.mode(0o700);
+    dir_builder.create(dir).await.map_err(ExecutorError::Io)?;
+    remove_mcp_config_files(dir, EXECUTION_MCP_CONFIG_MAX_AGE).await;
+
+    let path = dir.join(format!("{}.json", Uuid::new_v4()));
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    options.mode(0o600);
+    let mut file = options.open(&path).await.map_err(ExecutorError::Io)?;
+    let content = serde_json::to_vec(&serde_json::json!({ "mcpServers": servers }))?;
+    file.write_all(&content).await.map_err(ExecutorError::Io)?;
+    file.flush().await.map_err(ExecutorError::Io)?;
+    Ok(path)
+}
+
+/// Remove the config files in `dir` at least `min_age` old
+async fn remove_mcp_config_files(dir: &Path, min_age: Duration) {
+    let Ok(mut entries) = fs::read_dir(dir).await else {
+        return;
+    };
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let stale = min_age.is_zero()
+            || entry
+                .metadata()
+                .await
+                .ok()
+                .and_then(|metadata| metadata.modified().ok())
+                .and_then(|modified| modified.elapsed().ok())
+                .is_some_and(|age| age >= min_age);
+        if stale && let Err(e) = fs::remove_file(entry.path()).await {
+            tracing::warn!(
+                "Failed to remove stale MCP config {}: {e}",
+                entry.path().display()
+            );
+        }
+    }
+}
+
 type ServerMap = Map<String, Value>;
 
 fn is_http_server(s: &Map<String, Value>) -> bool {
@@ -289,19 +399,155 @@ fn apply_adapter(adapter: Adapter, canonical: Value) -> Value {
 }
 
 impl CodingAgent {
-    pub fn preconfigured_mcp(&self) -> Value {
+    fn mcp_adapter(&self) -> Adapter {
         use Adapter::*;
 
-        let adapter = match self {
+        match self {
             CodingAgent::ClaudeCode(_) | CodingAgent::Amp(_) | CodingAgent::Droid(_) => Passthrough,
             CodingAgent::QwenCode(_) | CodingAgent::Gemini(_) => Gemini,
             CodingAgent::CursorAgent(_) => Cursor,
             CodingAgent::Codex(_) => Codex,
             CodingAgent::Opencode(_) => Opencode,
             CodingAgent::Copilot(..) => Copilot,
-        };
+        }
+    }
 
+    pub fn preconfigured_mcp(&self) -> Value {
         let canonical = PRECONFIGURED_MCP_SERVERS.clone();
-        apply_adapter(adapter, canonical)
+        apply_adapter(self.mcp_adapter(), canonical)
+    }
+
+    /// Hand execution-scoped MCP servers to the agent, in the agent's own format
+    pub fn use_execution_mcp_servers(&mut self, servers: &ExecutionMcpServers) {
+        if servers.is_empty() {
+            return;
+        }
+        let adapted = self.adapt_execution_mcp_servers(servers);
+        self.use_mcp_servers(adapted);
+    }
+
+    fn adapt_execution_mcp_servers(&self, servers: &ExecutionMcpServers) -> ServerMap {
+        let canonical = Value::Object(servers.clone().into_iter().collect());
+        match apply_adapter(self.mcp_adapter(), canonical) {
+            Value::Object(mut adapted) => {
+                adapted.remove("meta");
+                adapted
+            }
+            _ => ServerMap::new(),
+        }
+    }
+}
+
+impl BaseCodingAgent {
+    /// Whether the agent takes MCP servers per execution. The others run without a
+    /// project's MCP servers.
+    pub fn supports_execution_mcp_servers(&self) -> bool {
+        matches!(
+            self,
+            BaseCodingAgent::ClaudeCode | BaseCodingAgent::Codex | BaseCodingAgent::Copilot
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn servers() -> ExecutionMcpServers {
+        HashMap::from([
+            (
+                "db".to_string(),
+                json!({"command": "npx", "args": ["server-postgres"], "env": {"PGPASSWORD": "secret"}}),
+            ),
+            (
+                "docs".to_string(),
+                json!({"type": "http", "url": "https://docs.example.com/mcp"}),
+            ),
+        ])
+    }
+
+    fn agent(json: Value) -> CodingAgent {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn claude_gets_the_servers_unchanged() {
+        let adapted = agent(json!({"CLAUDE_CODE": {}})).adapt_execution_mcp_servers(&servers());
+        assert_eq!(Value::Object(adapted), json!(servers()));
+    }
+
+    #[test]
+    fn codex_only_gets_stdio_servers() {
+        let adapted = agent(json!({"CODEX": {}})).adapt_execution_mcp_servers(&servers());
+        assert_eq!(adapted.keys().collect::<Vec<_>>(), vec!["db"]);
+    }
+
+    #[test]
+    fn copilot_servers_expose_all_their_tools() {
+        let adapted = agent(json!({"COPILOT": {}})).adapt_execution_mcp_servers(&servers());
+        assert_eq!(adapted["db"]["tools"], json!(["*"]));
+        assert_eq!(adapted["docs"]["tools"], json!(["*"]));
+    }
+
+    #[test]
+    fn only_agents_that_take_servers_per_execution_support_them() {
+        assert!(BaseCodingAgent::ClaudeCode.supports_execution_mcp_servers());
+        assert!(BaseCodingAgent::Codex.supports_execution_mcp_servers());
+        assert!(BaseCodingAgent::Copilot.supports_execution_mcp_servers());
+        assert!(!BaseCodingAgent::Gemini.supports_execution_mcp_servers());
+        assert!(!BaseCodingAgent::Amp.supports_execution_mcp_servers());
+    }
+
+    #[tokio::test]
+    async fn config_file_is_private_to_the_user() {
+        let dir = std::env::temp_dir().join(format!("mcp-config-test-{}", Uuid::new_v4()));
+        let servers = agent(json!({"CLAUDE_CODE": {}})).adapt_execution_mcp_servers(&servers());
+
+        let path = write_mcp_config_file(&dir, &servers).await.unwrap();
+
+        let content: Value = serde_json::from_slice(&fs::read(&path).await.unwrap()).unwrap();
+        assert_eq!(content["mcpServers"]["db"]["env"]["PGPASSWORD"], "secret");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).await.unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        fs::remove_dir_all(&dir).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn config_files_are_removed_by_age_or_all_at_once() {
+        let dir = std::env::temp_dir().join(format!("mcp-config-test-{}", Uuid::new_v4()));
+        let path = write_mcp_config_file(&dir, &ServerMap::new())
+            .await
+            .unwrap();
+
+        remove_mcp_config_files(&dir, EXECUTION_MCP_CONFIG_MAX_AGE).await;
+        assert!(path.exists());
+
+        remove_mcp_config_files(&dir, Duration::ZERO).await;
+        assert!(!path.exists());
+        fs::remove_dir_all(&dir).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn config_file_of_a_run_that_failed_to_start_is_removed() {
+        let dir = std::env::temp_dir().join(format!("mcp-config-test-{}", Uuid::new_v4()));
+        let path = write_mcp_config_file(&dir, &ServerMap::new())
+            .await
+            .unwrap();
+
+        let result = attach_execution_mcp_config(
+            Err(ExecutorError::Io(std::io::Error::other("spawn failed"))),
+            Some(path.clone()),
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert!(!path.exists());
+        fs::remove_dir_all(&dir).await.unwrap();
     }
 }
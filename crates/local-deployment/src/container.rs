@@ -20,22 +20,26 @@ use db::{
         image::TaskImage,
         merge::Merge,
         project::Project,
+        project_mcp_servers::ProjectMcpServers,
         task::{Task, TaskStatus},
         task_attempt::TaskAttempt,
+        task_plan_revision::TaskPlanRevision,
     },
 };
 use deployment::{DeploymentError, RemoteClientNotConfigured};
 use executors::{
     actions::{Executable, ExecutorAction},
     approvals::{ExecutorApprovalService, NoopExecutorApprovalService},
-    executors::BaseCodingAgent,
+    executors::{BaseCodingAgent, extract_plan},
     logs::{
-        ActionType, NormalizedEntryType,
+        ActionType, NormalizedEntry, NormalizedEntryType, ToolStatus,
         utils::{
-            ConversationPatch,
+            ConversationPatch, EntryIndexProvider,
             patch::{escape_json_pointer_segment, extract_normalized_entry_from_patch},
         },
     },
+    mcp_config,
+    profile::ExecutorConfigs,
 };
 use futures::{FutureExt, StreamExt, TryStreamExt, stream::select};
 use serde_json::json;
@@ -45,10 +49,10 @@ use services::services::{
     config::Config,
     container::{ContainerError, ContainerRef, ContainerService},
     diff_stream::{self, DiffStreamHandle},
-    git::{Commit, DiffTarget, GitService},
+    git::{Commit, CommitOptions, DiffTarget, GitService},
     image::ImageService,
     notification::NotificationService,
-    planning,
+    pr_review::PrReviewService,
     share::SharePublisher,
     worktree_manager::WorktreeManager,
 };
@@ -324,11 +328,12 @@ impl LocalContainerService {
     }
 
     /// Spawn a background task that polls the child process for completion and
-    /// cleans up the execution entry when it exits.
+    /// cleans up the execution entry and the run's config files when it exits.
     pub fn spawn_exit_monitor(
         &self,
         exec_id: &Uuid,
         exit_signal: Option<tokio::sync::oneshot::Receiver<()>>,
+        config_files: Vec<PathBuf>,
     ) -> JoinHandle<()> {
         let exec_id = *exec_id;
         let child_store = self.child_store.clone();
@@ -369,6 +374,10 @@ impl LocalContainerService {
                 }
             }
 
+            for path in &config_files {
+                mcp_config::remove_execution_mcp_config(path).await;
+            }
+
             let (exit_code, status) = match status_result {
                 Ok(exit_status) => {
                     let code = exit_status.code().unwrap_or(-1) as i64;
@@ -396,8 +405,10 @@ impl LocalContainerService {
                 }
 
                 // Extract and save plan from PlanPresentation if present
-                if let Some(plan) = container.extract_plan_from_history(&exec_id) {
-                    if let Err(e) = Task::update_plan(&db.pool, ctx.task.id, Some(plan)).await {
+                if let Some(plan) = container.extract_plan_from_history(&ctx.execution_process) {
+                    if let Err(e) =
+                        TaskPlanRevision::record(&db.pool, ctx.task.id, Some(exec_id), &plan).await
+                    {
                         tracing::warn!("Failed to update task plan: {}", e);
                     } else {
                         tracing::info!("Saved plan for task {}", ctx.task.id);
@@ -455,6 +466,9 @@ impl LocalContainerService {
 
                 if Self::should_finalize(&ctx) {
                     Self::finalize_task(&db, &config, &publisher, &ctx).await;
+                    if success {
+                        container.try_push_review_fixes(&ctx).await;
+                    }
                     // After finalization, check if a queued follow-up exists and start it
                     if let Err(e) = container.try_consume_queued_followup(&ctx).await {
                         tracing::error!(
@@ -557,6 +571,15 @@ impl LocalContainerService {
         format!("{}-{}", short_uuid(attempt_id), task_title_id)
     }
 
+    /// Whether the attempt works directly in the project's repository, as planning
+    /// conversations did before they got a worktree of their own
+    fn works_in_project_repo(task_attempt: &TaskAttempt, git_repo_path: Option<&Path>) -> bool {
+        match (&task_attempt.container_ref, git_repo_path) {
+            (Some(container_ref), Some(git_repo_path)) => Path::new(container_ref) == git_repo_path,
+            _ => false,
+        }
+    }
+
     async fn track_child_msgs_in_store(&self, id: Uuid, child: &mut AsyncGroupChild) {
         let store = Arc::new(MsgStore::new());
 
@@ -703,31 +726,65 @@ impl LocalContainerService {
         None
     }
 
-    /// Extract the plan from PlanPresentation tool use in the MsgStore history
-    fn extract_plan_from_history(&self, exec_id: &Uuid) -> Option<String> {
+    /// Extract the plan from PlanPresentation tool use in the MsgStore history.
+    /// Executors in plan mode without an ExitPlanMode hook write the plan in `<plan>`
+    /// tags of their final message instead; it is added to the conversation as a
+    /// PlanPresentation so it shows up like a native plan.
+    fn extract_plan_from_history(&self, execution_process: &ExecutionProcess) -> Option<String> {
         // Get the MsgStore for this execution
-        let msg_stores = self.msg_stores.try_read().ok()?;
-        let msg_store = msg_stores.get(exec_id)?;
+        let msg_store = self
+            .msg_stores
+            .try_read()
+            .ok()?
+            .get(&execution_process.id)?
+            .clone();
 
         // Get the history and scan for PlanPresentation
         let history = msg_store.get_history();
+        let mut last_assistant_message = None;
 
         for msg in history.iter().rev() {
             if let LogMsg::JsonPatch(patch) = msg {
                 // Try to extract a NormalizedEntry from the patch
                 if let Some((_, entry)) = extract_normalized_entry_from_patch(patch) {
-                    if let NormalizedEntryType::ToolUse { action_type, .. } = &entry.entry_type {
-                        if let ActionType::PlanPresentation { plan } = action_type {
-                            if !plan.trim().is_empty() {
-                                return Some(plan.clone());
-                            }
+                    match &entry.entry_type {
+                        NormalizedEntryType::ToolUse {
+                            action_type: ActionType::PlanPresentation { plan },
+                            ..
+                        } if !plan.trim().is_empty() => return Some(plan.clone()),
+                        NormalizedEntryType::AssistantMessage
+                            if last_assistant_message.is_none() =>
+                        {
+                            last_assistant_message = Some(entry.content);
                         }
+                        _ => {}
                     }
                 }
             }
         }
 
-        None
+        let plan_mode = execution_process
+            .executor_profile_id()
+            .and_then(|profile_id| ExecutorConfigs::get_cached().get_coding_agent(&profile_id))
+            .is_some_and(|agent| agent.is_plan_mode());
+        if !plan_mode {
+            return None;
+        }
+        let plan = extract_plan(&last_assistant_message?)?;
+
+        let entry = NormalizedEntry {
+            timestamp: None,
+            entry_type: NormalizedEntryType::ToolUse {
+                tool_name: "plan".to_string(),
+                action_type: ActionType::PlanPresentation { plan: plan.clone() },
+                status: ToolStatus::Success,
+            },
+            content: plan.clone(),
+            metadata: None,
+        };
+        let index = EntryIndexProvider::start_from(&msg_store).next();
+        msg_store.push_patch(ConversationPatch::add_normalized_entry(index, entry));
+        Some(plan)
     }
 
     /// Update the executor session summary with the final assistant message
@@ -750,6 +807,40 @@ impl LocalContainerService {
         Ok(())
     }
 
+    /// Push the attempt branch to its PR when this run was addressing review comments.
+    /// Skipped while another follow-up is still queued behind it.
+    async fn try_push_review_fixes(&self, ctx: &ExecutionContext) {
+        let queued = Draft::find_by_task_attempt_and_type(
+            &self.db.pool,
+            ctx.task_attempt.id,
+            DraftType::FollowUp,
+        )
+        .await
+        .ok()
+        .flatten()
+        .is_some_and(|d| d.queued);
+        if queued {
+            return;
+        }
+
+        match PrReviewService::new(self.db.clone())
+            .push_addressed(&self.git, &ctx.task_attempt)
+            .await
+        {
+            Ok(0) => {}
+            Ok(count) => tracing::info!(
+                "Pushed fixes for {} review comments on attempt {}",
+                count,
+                ctx.task_attempt.id
+            ),
+            Err(e) => tracing::error!(
+                "Failed to push review fixes for attempt {}: {}",
+                ctx.task_attempt.id,
+                e
+            ),
+        }
+    }
+
     /// If a queued follow-up draft exists for this attempt and nothing is running,
     /// start it immediately and clear the draft.
     async fn try_consume_queued_followup(
@@ -944,35 +1035,26 @@ impl ContainerService for LocalContainerService {
             .await?
             .ok_or(sqlx::Error::RowNotFound)?;
 
-        // Planning conversations should reuse the project's main worktree/branch
-        if planning::is_planning_task(&task) {
-            let repo_path = PathBuf::from(&project.git_repo_path);
-            if !repo_path.exists() {
-                return Err(ContainerError::Other(anyhow!(
-                    "Project repository path does not exist: {}",
-                    repo_path.display()
-                )));
-            }
-
-            TaskAttempt::update_container_ref(
-                &self.db.pool,
-                task_attempt.id,
-                &repo_path.to_string_lossy(),
+        if task.kind.creates_branch() {
+            WorktreeManager::create_worktree(
+                &project.git_repo_path,
+                &task_attempt.branch,
+                &worktree_path,
+                &task_attempt.target_branch,
+                true, // create new branch
+            )
+            .await?;
+        } else {
+            // Planning conversations stay on the base branch, detached in a worktree of
+            // their own so that several of them can run side by side
+            WorktreeManager::ensure_detached_worktree_exists(
+                &project.git_repo_path,
+                &task_attempt.branch,
+                &worktree_path,
             )
             .await?;
-
-            return Ok(repo_path.to_string_lossy().to_string());
         }
 
-        WorktreeManager::create_worktree(
-            &project.git_repo_path,
-            &task_attempt.branch,
-            &worktree_path,
-            &task_attempt.target_branch,
-            true, // create new branch
-        )
-        .await?;
-
         // Copy files specified in the project's copy_files field
         if let Some(copy_files) = &project.copy_files
             && !copy_files.trim().is_empty()
@@ -1010,10 +1092,6 @@ impl ContainerService for LocalContainerService {
             .parent_task(&self.db.pool)
             .await?
             .ok_or(sqlx::Error::RowNotFound)?;
-        if planning::is_planning_task(&task) {
-            // Planning conversations reuse the main repo; never delete it.
-            return Ok(());
-        }
         let git_repo_path = match Project::find_by_id(&self.db.pool, task.project_id).await {
             Ok(Some(project)) => Some(project.git_repo_path.clone()),
             Ok(None) => None,
@@ -1022,6 +1100,10 @@ impl ContainerService for LocalContainerService {
                 None
             }
         };
+        if Self::works_in_project_repo(task_attempt, git_repo_path.as_deref()) {
+            // Older planning conversations ran in the project's repository; never delete it.
+            return Ok(());
+        }
         WorktreeManager::cleanup_worktree(
             &PathBuf::from(task_attempt.container_ref.clone().unwrap_or_default()),
             git_repo_path.as_deref(),
@@ -1052,38 +1134,33 @@ impl ContainerService for LocalContainerService {
             .await?
             .ok_or(sqlx::Error::RowNotFound)?;
 
-        if planning::is_planning_task(&task) {
-            let repo_path = PathBuf::from(&project.git_repo_path);
-            if !repo_path.exists() {
-                return Err(ContainerError::Other(anyhow!(
-                    "Project repository path does not exist: {}",
-                    repo_path.display()
-                )));
-            }
-
-            if task_attempt.container_ref.is_none() {
-                TaskAttempt::update_container_ref(
-                    &self.db.pool,
-                    task_attempt.id,
-                    &repo_path.to_string_lossy(),
-                )
-                .await?;
-            }
-
-            return Ok(repo_path.to_string_lossy().to_string());
-        }
-
         let container_ref = task_attempt.container_ref.as_ref().ok_or_else(|| {
             ContainerError::Other(anyhow!("Container ref not found for task attempt"))
         })?;
         let worktree_path = PathBuf::from(container_ref);
 
-        WorktreeManager::ensure_worktree_exists(
-            &project.git_repo_path,
-            &task_attempt.branch,
-            &worktree_path,
-        )
-        .await?;
+        if Self::works_in_project_repo(task_attempt, Some(project.git_repo_path.as_path())) {
+            if !worktree_path.exists() {
+                return Err(ContainerError::Other(anyhow!(
+                    "Project repository path does not exist: {}",
+                    worktree_path.display()
+                )));
+            }
+        } else if task.kind.creates_branch() {
+            WorktreeManager::ensure_worktree_exists(
+                &project.git_repo_path,
+                &task_attempt.branch,
+                &worktree_path,
+            )
+            .await?;
+        } else {
+            WorktreeManager::ensure_detached_worktree_exists(
+                &project.git_repo_path,
+                &task_attempt.branch,
+                &worktree_path,
+            )
+            .await?;
+        }
 
         Ok(container_ref.to_string())
     }
@@ -1123,16 +1200,29 @@ impl ContainerService for LocalContainerService {
                     ExecutorApprovalBridge::new(
                         self.approvals.clone(),
                         self.db.clone(),
+                        self.config.clone(),
                         execution_process.id,
                     )
                 }
                 _ => Arc::new(NoopExecutorApprovalService {}),
             };
 
+        // Project MCP servers are handed to the agent for this execution only
+        let mcp_servers = match executor_action.base_executor() {
+            Some(_) => match task_attempt.parent_task(&self.db.pool).await? {
+                Some(task) => ProjectMcpServers::find_by_project_id(&self.db.pool, task.project_id)
+                    .await?
+                    .map(|settings| settings.servers.0)
+                    .unwrap_or_default(),
+                None => Default::default(),
+            },
+            None => Default::default(),
+        };
+
         // Create the child and stream, add to execution tracker with timeout
         let mut spawned = tokio::time::timeout(
             Duration::from_secs(30),
-            executor_action.spawn(&current_dir, approvals_service),
+            executor_action.spawn(&current_dir, approvals_service, &mcp_servers),
         )
         .await
         .map_err(|_| {
@@ -1148,7 +1238,11 @@ impl ContainerService for LocalContainerService {
             .await;
 
         // Spawn unified exit monitor: watches OS exit and optional executor signal
-        let _hn = self.spawn_exit_monitor(&execution_process.id, spawned.exit_signal);
+        let _hn = self.spawn_exit_monitor(
+            &execution_process.id,
+            spawned.exit_signal,
+            spawned.config_files,
+        );
 
         Ok(())
     }
@@ -1349,7 +1443,11 @@ impl ContainerService for LocalContainerService {
             message
         );
 
-        let changes_committed = self.git().commit(Path::new(container_ref), &message)?;
+        let commit_options =
+            CommitOptions::for_project(&self.db().pool, ctx.task.project_id).await?;
+        let changes_committed =
+            self.git()
+                .commit_with_options(Path::new(container_ref), &message, &commit_options)?;
         Ok(changes_committed)
     }
 
@@ -120,7 +120,7 @@ impl Deployment for LocalDeployment {
             });
         }
 
-        let approvals = Approvals::new(msg_stores.clone());
+        let approvals = Approvals::new(db.clone(), msg_stores.clone());
 
         let share_config = ShareConfig::from_env();
 
@@ -240,7 +240,7 @@ impl Deployment for LocalDeployment {
         &self.analytics
     }
 
-    fn container(&self) -> &impl ContainerService {
+    fn container(&self) -> &(impl ContainerService + Send + Sync) {
         &self.container
     }
 
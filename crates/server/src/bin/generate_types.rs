@@ -15,10 +15,39 @@ fn generate_types_content() -> String {
         db::models::project::Project::decl(),
         db::models::project::CreateProject::decl(),
         db::models::project::UpdateProject::decl(),
+        db::models::project_commit_settings::CommitSigning::decl(),
+        db::models::project_commit_settings::ProjectCommitSettings::decl(),
+        db::models::project_commit_settings::UpdateProjectCommitSettings::decl(),
+        db::models::project_mcp_servers::ProjectMcpServers::decl(),
+        db::models::project_mcp_servers::UpdateProjectMcpServers::decl(),
+        db::models::document_revision::DocumentRevisionSource::decl(),
+        db::models::document_revision::DocumentRevision::decl(),
+        db::models::document_revision::DocumentRevisionSummary::decl(),
+        services::services::documents::DocumentRevisionDiff::decl(),
+        db::models::document_template::DocumentTemplatePack::decl(),
+        db::models::document_template::DocumentTemplate::decl(),
+        db::models::document_template::DocumentTemplatePackWithTemplates::decl(),
+        db::models::document_template::CreateDocumentTemplatePack::decl(),
+        db::models::document_template::UpdateDocumentTemplatePack::decl(),
+        db::models::document_template::CreateDocumentTemplate::decl(),
+        db::models::document_template::UpdateDocumentTemplate::decl(),
+        services::services::documents::InstantiateDocumentTemplate::decl(),
+        services::services::document_search::DocumentSearchMode::decl(),
+        services::services::document_search::DocumentSearchResult::decl(),
+        db::models::approval_record::ApprovalRecordStatus::decl(),
+        db::models::approval_record::ApprovalRecord::decl(),
+        db::models::approval_rule::ApprovalRuleAction::decl(),
+        db::models::approval_rule::ApprovalRule::decl(),
+        db::models::approval_rule::CreateApprovalRule::decl(),
+        db::models::approval_rule::UpdateApprovalRule::decl(),
+        db::models::approval_timeout_setting::ApprovalTimeoutSetting::decl(),
+        db::models::approval_timeout_setting::CreateApprovalTimeoutSetting::decl(),
+        db::models::approval_timeout_setting::UpdateApprovalTimeoutSetting::decl(),
         db::models::project::SearchResult::decl(),
         db::models::project::SearchMatchType::decl(),
         server::routes::projects::CreateRemoteProjectRequest::decl(),
         server::routes::projects::LinkToExistingRequest::decl(),
+        server::routes::projects::ProjectMcpServersResponse::decl(),
         executors::actions::ExecutorAction::decl(),
         executors::mcp_config::McpConfig::decl(),
         executors::actions::ExecutorActionType::decl(),
@@ -32,11 +61,21 @@ fn generate_types_content() -> String {
         db::models::tag::UpdateTag::decl(),
         server::routes::tags::TagSearchParams::decl(),
         db::models::task::TaskStatus::decl(),
+        db::models::task::TaskKind::decl(),
         db::models::task::Task::decl(),
         db::models::task::TaskWithAttemptStatus::decl(),
+        db::models::task::PlanProgress::decl(),
         db::models::task::TaskRelationships::decl(),
         db::models::task::CreateTask::decl(),
         db::models::task::UpdateTask::decl(),
+        db::models::task_dependency::TaskDependency::decl(),
+        db::models::task_document::TaskDocument::decl(),
+        db::models::task_document::UpdateTaskDocuments::decl(),
+        db::models::task_breakdown::TaskBreakdown::decl(),
+        db::models::task_plan_revision::TaskPlanRevisionStatus::decl(),
+        db::models::task_plan_revision::TaskPlanRevision::decl(),
+        db::models::task_plan_step::TaskPlanStepStatus::decl(),
+        db::models::task_plan_step::TaskPlanStep::decl(),
         db::models::shared_task::SharedTask::decl(),
         db::models::image::Image::decl(),
         db::models::image::CreateImage::decl(),
@@ -85,12 +124,23 @@ fn generate_types_content() -> String {
         server::routes::task_attempts::RenameBranchRequest::decl(),
         server::routes::task_attempts::RenameBranchResponse::decl(),
         server::routes::task_attempts::CommitCompareResult::decl(),
+        server::routes::task_attempts::AttemptStack::decl(),
         server::routes::task_attempts::OpenEditorRequest::decl(),
         server::routes::task_attempts::OpenEditorResponse::decl(),
         server::routes::shared_tasks::AssignSharedTaskRequest::decl(),
         server::routes::shared_tasks::AssignSharedTaskResponse::decl(),
         server::routes::tasks::ShareTaskResponse::decl(),
         server::routes::tasks::CreateAndStartTaskRequest::decl(),
+        server::routes::tasks::CreateSubtask::decl(),
+        server::routes::tasks::CreateSubtasks::decl(),
+        services::services::subtasks::Subtask::decl(),
+        server::routes::tasks::RequestPlanChanges::decl(),
+        server::routes::tasks::TaskPlanRevisionDiff::decl(),
+        services::services::task_breakdown::ProposedTask::decl(),
+        server::routes::task_breakdowns::StartTaskBreakdown::decl(),
+        server::routes::task_breakdowns::TaskBreakdownStatus::decl(),
+        server::routes::task_breakdowns::TaskBreakdownPreview::decl(),
+        server::routes::task_breakdowns::ApplyTaskBreakdown::decl(),
         server::routes::task_attempts::CreateGitHubPrRequest::decl(),
         server::routes::images::ImageResponse::decl(),
         services::services::config::Config::decl(),
@@ -103,6 +153,8 @@ fn generate_types_content() -> String {
         services::services::config::SoundFile::decl(),
         services::services::config::UiLanguage::decl(),
         services::services::config::ShowcaseState::decl(),
+        services::services::config::ApprovalWebhookConfig::decl(),
+        services::services::config::DocumentSearchConfig::decl(),
         services::services::git::GitBranch::decl(),
         utils::diff::Diff::decl(),
         utils::diff::DiffChangeKind::decl(),
@@ -136,10 +188,20 @@ fn generate_types_content() -> String {
         server::routes::task_attempts::gh_cli_setup::GhCliSetupError::decl(),
         server::routes::task_attempts::RebaseTaskAttemptRequest::decl(),
         server::routes::task_attempts::GitOperationError::decl(),
+        server::routes::task_attempts::ApplyChangesTarget::decl(),
+        server::routes::task_attempts::ApplyChangesRequest::decl(),
+        server::routes::task_attempts::ApplyChangesResponse::decl(),
+        server::routes::task_attempts::review_comments::SendReviewCommentsRequest::decl(),
+        server::routes::task_attempts::review_comments::DismissReviewCommentsRequest::decl(),
+        server::routes::task_attempts::inspect::AttemptConversationEntry::decl(),
+        server::routes::task_attempts::inspect::AttemptConversation::decl(),
+        server::routes::task_attempts::inspect::DiffFileSummary::decl(),
+        server::routes::task_attempts::inspect::FileDiff::decl(),
         server::routes::task_attempts::CreatePrError::decl(),
         server::routes::task_attempts::CommitInfo::decl(),
         server::routes::task_attempts::BranchStatus::decl(),
         services::services::git::ConflictOp::decl(),
+        services::services::git::DiffSelection::decl(),
         db::models::task_attempt::TaskAttempt::decl(),
         db::models::execution_process::ExecutionProcess::decl(),
         db::models::execution_process::ExecutionProcessStatus::decl(),
@@ -149,6 +211,10 @@ fn generate_types_content() -> String {
         db::models::merge::PrMerge::decl(),
         db::models::merge::MergeStatus::decl(),
         db::models::merge::PullRequestInfo::decl(),
+        db::models::merge::CiStatus::decl(),
+        db::models::merge::CiCheck::decl(),
+        db::models::pr_review_comment::ReviewCommentStatus::decl(),
+        db::models::pr_review_comment::PrReviewComment::decl(),
         db::models::draft::Draft::decl(),
         db::models::draft::DraftType::decl(),
         executors::logs::CommandExitStatus::decl(),
@@ -161,10 +227,12 @@ fn generate_types_content() -> String {
         executors::logs::NormalizedEntryError::decl(),
         executors::logs::ToolResult::decl(),
         executors::logs::ToolResultValueType::decl(),
+        executors::approvals::ApprovalTimeoutAction::decl(),
         executors::logs::ToolStatus::decl(),
         executors::logs::utils::patch::PatchType::decl(),
         utils::approvals::ApprovalStatus::decl(),
         utils::approvals::CreateApprovalRequest::decl(),
+        utils::approvals::ApprovalRememberScope::decl(),
         utils::approvals::ApprovalResponse::decl(),
         serde_json::Value::decl(),
     ];
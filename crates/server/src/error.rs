@@ -15,12 +15,17 @@ use git2::Error as Git2Error;
 use services::services::{
     config::{ConfigError, EditorOpenError},
     container::ContainerError,
+    documents::DocumentError,
     drafts::DraftsServiceError,
     git::GitServiceError,
     github::GitHubServiceError,
     image::ImageError,
+    pr_checks::PrChecksError,
+    pr_review::PrReviewError,
     remote_client::RemoteClientError,
     share::ShareError,
+    stacked_branches::StackedBranchError,
+    subtasks::SubtaskError,
     worktree_manager::WorktreeError,
 };
 use thiserror::Error;
@@ -55,6 +60,16 @@ pub enum ApiError {
     Image(#[from] ImageError),
     #[error(transparent)]
     Drafts(#[from] DraftsServiceError),
+    #[error(transparent)]
+    Document(#[from] DocumentError),
+    #[error(transparent)]
+    StackedBranch(#[from] StackedBranchError),
+    #[error(transparent)]
+    Subtask(#[from] SubtaskError),
+    #[error(transparent)]
+    PrReview(#[from] PrReviewError),
+    #[error(transparent)]
+    PrChecks(#[from] PrChecksError),
     #[error("Multipart error: {0}")]
     Multipart(#[from] MultipartError),
     #[error("IO error: {0}")]
@@ -67,6 +82,8 @@ pub enum ApiError {
     Unauthorized,
     #[error("Bad request: {0}")]
     BadRequest(String),
+    #[error("Not found: {0}")]
+    NotFound(String),
     #[error("Conflict: {0}")]
     Conflict(String),
     #[error("Forbidden: {0}")]
@@ -110,6 +127,9 @@ impl IntoResponse for ApiError {
                 services::services::git::GitServiceError::RebaseInProgress => {
                     (StatusCode::CONFLICT, "GitServiceError")
                 }
+                services::services::git::GitServiceError::InvalidSelection(_) => {
+                    (StatusCode::BAD_REQUEST, "GitServiceError")
+                }
                 _ => (StatusCode::INTERNAL_SERVER_ERROR, "GitServiceError"),
             },
             ApiError::GitHubService(_) => (StatusCode::INTERNAL_SERVER_ERROR, "GitHubServiceError"),
@@ -138,6 +158,45 @@ impl IntoResponse for ApiError {
                     (StatusCode::INTERNAL_SERVER_ERROR, "ExecutionProcessError")
                 }
             },
+            ApiError::Document(doc_err) => match doc_err {
+                DocumentError::NotFound
+                | DocumentError::RevisionNotFound
+                | DocumentError::TemplateNotFound => (StatusCode::NOT_FOUND, "DocumentError"),
+                DocumentError::AlreadyExists => (StatusCode::BAD_REQUEST, "DocumentError"),
+                DocumentError::NotWritable(_) => (StatusCode::FORBIDDEN, "DocumentError"),
+                DocumentError::Io(io_err) if io_err.kind() == ErrorKind::PermissionDenied => {
+                    (StatusCode::FORBIDDEN, "DocumentError")
+                }
+                _ => (StatusCode::INTERNAL_SERVER_ERROR, "DocumentError"),
+            },
+            ApiError::StackedBranch(stack_err) => match stack_err {
+                StackedBranchError::ParentNotFound => (StatusCode::NOT_FOUND, "StackedBranchError"),
+                StackedBranchError::ParentInOtherProject => {
+                    (StatusCode::BAD_REQUEST, "StackedBranchError")
+                }
+                StackedBranchError::ParentWorktreeDeleted
+                | StackedBranchError::ParentMerged
+                | StackedBranchError::Cycle(_) => (StatusCode::CONFLICT, "StackedBranchError"),
+                _ => (StatusCode::INTERNAL_SERVER_ERROR, "StackedBranchError"),
+            },
+            ApiError::Subtask(subtask_err) => match subtask_err {
+                SubtaskError::Invalid(_) => (StatusCode::BAD_REQUEST, "SubtaskError"),
+                SubtaskError::Database(_) => (StatusCode::INTERNAL_SERVER_ERROR, "SubtaskError"),
+            },
+            ApiError::PrReview(review_err) => match review_err {
+                PrReviewError::NothingToSend => (StatusCode::BAD_REQUEST, "PrReviewError"),
+                PrReviewError::Drafts(DraftsServiceError::Conflict(_)) => {
+                    (StatusCode::CONFLICT, "ConflictError")
+                }
+                _ => (StatusCode::INTERNAL_SERVER_ERROR, "PrReviewError"),
+            },
+            ApiError::PrChecks(checks_err) => match checks_err {
+                PrChecksError::NoFailedChecks => (StatusCode::BAD_REQUEST, "PrChecksError"),
+                PrChecksError::Drafts(DraftsServiceError::Conflict(_)) => {
+                    (StatusCode::CONFLICT, "ConflictError")
+                }
+                _ => (StatusCode::INTERNAL_SERVER_ERROR, "PrChecksError"),
+            },
             ApiError::Io(io_err) => match io_err.kind() {
                 ErrorKind::PermissionDenied => (StatusCode::FORBIDDEN, "IoError"),
                 _ => (StatusCode::INTERNAL_SERVER_ERROR, "IoError"),
@@ -183,6 +242,7 @@ impl IntoResponse for ApiError {
             },
             ApiError::Unauthorized => (StatusCode::UNAUTHORIZED, "Unauthorized"),
             ApiError::BadRequest(_) => (StatusCode::BAD_REQUEST, "BadRequest"),
+            ApiError::NotFound(_) => (StatusCode::NOT_FOUND, "NotFound"),
             ApiError::Conflict(_) => (StatusCode::CONFLICT, "ConflictError"),
             ApiError::Forbidden(_) => (StatusCode::FORBIDDEN, "ForbiddenError"),
         };
@@ -259,6 +319,7 @@ impl IntoResponse for ApiError {
             },
             ApiError::Unauthorized => "Unauthorized. Please sign in again.".to_string(),
             ApiError::BadRequest(msg) => msg.clone(),
+            ApiError::NotFound(msg) => msg.clone(),
             ApiError::Conflict(msg) => msg.clone(),
             ApiError::Forbidden(msg) => msg.clone(),
             ApiError::Drafts(drafts_err) => match drafts_err {
@@ -270,12 +331,29 @@ impl IntoResponse for ApiError {
                     format!("{}: {}", error_type, drafts_err)
                 }
             },
+            ApiError::PrReview(PrReviewError::NothingToSend) => self.to_string(),
+            ApiError::PrReview(PrReviewError::Drafts(DraftsServiceError::Conflict(msg))) => {
+                msg.clone()
+            }
+            ApiError::PrChecks(PrChecksError::NoFailedChecks) => self.to_string(),
+            ApiError::PrChecks(PrChecksError::Drafts(DraftsServiceError::Conflict(msg))) => {
+                msg.clone()
+            }
             ApiError::Io(io_err) => match io_err.kind() {
                 ErrorKind::PermissionDenied => {
                     "문서 경로에 접근할 권한이 없습니다. macOS 전체 디스크 접근 또는 폴더 권한을 확인하세요.".to_string()
                 }
                 _ => format!("{}: {}", error_type, self),
             },
+            ApiError::Document(DocumentError::Io(io_err))
+                if io_err.kind() == ErrorKind::PermissionDenied =>
+            {
+                "문서 경로에 접근할 권한이 없습니다. macOS 전체 디스크 접근 또는 폴더 권한을 확인하세요.".to_string()
+            }
+            ApiError::Document(DocumentError::Database(_) | DocumentError::Io(_)) => {
+                format!("{}: {}", error_type, self)
+            }
+            ApiError::Document(doc_err) => doc_err.to_string(),
             _ => format!("{}: {}", error_type, self),
         };
         let response = ApiResponse::<()>::error(&error_message);
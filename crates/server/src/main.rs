@@ -7,7 +7,7 @@ use strip_ansi_escapes::strip;
 use thiserror::Error;
 use tracing_subscriber::{EnvFilter, prelude::*};
 use utils::{
-    assets::asset_dir,
+    assets::{asset_dir, mcp_token_path},
     browser::open_browser,
     port_file::write_port_file,
     sentry::{self as sentry_utils, SentrySource, sentry_layer},
@@ -65,8 +65,6 @@ async fn main() -> Result<(), AnyonError> {
         }
     });
 
-    let app_router = routes::router(deployment.clone());
-
     let port = std::env::var("BACKEND_PORT")
         .or_else(|_| std::env::var("PORT"))
         .ok()
@@ -92,6 +90,14 @@ async fn main() -> Result<(), AnyonError> {
 
     tracing::info!("Server running on http://{host}:{actual_port}");
 
+    // The MCP endpoint calls back into the REST API
+    let backend_url = routes::mcp::backend_url(listener.local_addr()?);
+    let app_router = routes::router(deployment.clone(), &backend_url);
+    tracing::info!(
+        "MCP endpoint at {backend_url}/api/mcp, token in {:?} unless ANYON_MCP_TOKEN is set",
+        mcp_token_path()
+    );
+
     if !cfg!(debug_assertions) {
         tracing::info!("Opening browser...");
         tokio::spawn(async move {
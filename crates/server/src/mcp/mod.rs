@@ -1 +1,2 @@
+pub mod resources;
 pub mod task_server;
@@ -0,0 +1,180 @@
+//! URIs and rendering of the task server's MCP resources and prompts: project
+//! documents from `anyon-docs`, tasks with their plan, and the current attempt's
+//! context.
+
+use db::models::task::Task;
+use uuid::Uuid;
+
+const SCHEME: &str = "anyon://";
+
+pub const IMPLEMENT_TASK_PROMPT: &str = "implement_task";
+pub const PLAN_TASK_PROMPT: &str = "plan_task";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResourceUri {
+    /// Project, task and attempt of the attempt the server runs in
+    Context,
+    Task(Uuid),
+    Document {
+        project_id: Uuid,
+        document_id: String,
+    },
+}
+
+impl ResourceUri {
+    pub const CONTEXT: &str = "anyon://context";
+    pub const TASK_TEMPLATE: &str = "anyon://tasks/{task_id}";
+    pub const DOCUMENT_TEMPLATE: &str = "anyon://projects/{project_id}/documents/{document_id}";
+
+    pub fn parse(uri: &str) -> Option<Self> {
+        let path = uri.strip_prefix(SCHEME)?;
+        let segments: Vec<&str> = path.split('/').collect();
+        match segments.as_slice() {
+            ["context"] => Some(Self::Context),
+            ["tasks", task_id] => task_id.parse().ok().map(Self::Task),
+            ["projects", project_id, "documents", document_id] if !document_id.is_empty() => {
+                Some(Self::Document {
+                    project_id: project_id.parse().ok()?,
+                    document_id: document_id.to_string(),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    pub fn to_uri(&self) -> String {
+        match self {
+            Self::Context => Self::CONTEXT.to_string(),
+            Self::Task(task_id) => format!("{SCHEME}tasks/{task_id}"),
+            Self::Document {
+                project_id,
+                document_id,
+            } => format!("{SCHEME}projects/{project_id}/documents/{document_id}"),
+        }
+    }
+}
+
+pub fn render_task(task: &Task) -> String {
+    let mut text = format!("# {}\n\nStatus: {}\n", task.title, task.status);
+    if let Some(description) = task.description.as_deref().filter(|d| !d.trim().is_empty()) {
+        text.push_str(&format!("\n## Description\n\n{}\n", description.trim()));
+    }
+    if let Some(plan) = task.plan.as_deref().filter(|p| !p.trim().is_empty()) {
+        text.push_str(&format!("\n## Plan\n\n{}\n", plan.trim()));
+    }
+    text
+}
+
+/// Ask the agent to implement a task, following its approved plan when there is one
+pub fn implement_task_prompt(task: &Task) -> String {
+    let guidance = if task.plan.as_deref().is_some_and(|p| !p.trim().is_empty()) {
+        "Implement the task below by following its plan step by step. If the plan turns out to be wrong, explain why before deviating from it."
+    } else {
+        "Implement the task below. It has no approved plan yet, so briefly outline your approach before making changes."
+    };
+    format!(
+        "{guidance}\nProject documents (PRD, design and technical docs) are available as `anyon://projects/{}/documents/...` resources; read the relevant ones first.\n\n{}",
+        task.project_id,
+        render_task(task)
+    )
+}
+
+/// Ask the agent to write an implementation plan for a task without changing code
+pub fn plan_task_prompt(task: &Task) -> String {
+    format!(
+        "Write an implementation plan for the task below. Do not change any code yet. Read the relevant project documents (`anyon://projects/{}/documents/...` resources) and the code first, then list the steps, the files they touch and how to verify the result.\n\n{}",
+        task.project_id,
+        render_task(task)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use db::models::task::{TaskKind, TaskStatus};
+
+    use super::*;
+
+    fn task(description: Option<&str>, plan: Option<&str>) -> Task {
+        Task {
+            id: Uuid::new_v4(),
+            project_id: Uuid::new_v4(),
+            title: "Add login".to_string(),
+            description: description.map(str::to_string),
+            plan: plan.map(str::to_string),
+            status: TaskStatus::InProgress,
+            kind: TaskKind::Implementation,
+            suggested_executor: None,
+            parent_task_attempt: None,
+            shared_task_id: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn uris_round_trip() {
+        let uris = [
+            ResourceUri::Context,
+            ResourceUri::Task(Uuid::new_v4()),
+            ResourceUri::Document {
+                project_id: Uuid::new_v4(),
+                document_id: "prd".to_string(),
+            },
+        ];
+        for uri in uris {
+            assert_eq!(ResourceUri::parse(&uri.to_uri()), Some(uri));
+        }
+    }
+
+    #[test]
+    fn malformed_uris_are_rejected() {
+        let project_id = Uuid::new_v4();
+        for uri in [
+            "https://context".to_string(),
+            "anyon://tasks/not-a-uuid".to_string(),
+            "anyon://tasks".to_string(),
+            format!("anyon://projects/{project_id}/documents/"),
+            format!("anyon://projects/{project_id}/documents/prd/extra"),
+            "anyon://projects/not-a-uuid/documents/prd".to_string(),
+        ] {
+            assert_eq!(ResourceUri::parse(&uri), None, "{uri}");
+        }
+    }
+
+    #[test]
+    fn rendered_task_skips_blank_sections() {
+        assert_eq!(
+            render_task(&task(Some("  "), None)),
+            "# Add login\n\nStatus: in-progress\n"
+        );
+        assert_eq!(
+            render_task(&task(Some("Use OAuth.\n"), Some("1. Add route\n"))),
+            "# Add login\n\nStatus: in-progress\n\n## Description\n\nUse OAuth.\n\n## Plan\n\n1. Add route\n"
+        );
+    }
+
+    #[test]
+    fn implement_prompt_follows_the_plan_when_there_is_one() {
+        let planned = task(None, Some("1. Add route"));
+        let prompt = implement_task_prompt(&planned);
+        assert!(prompt.starts_with("Implement the task below by following its plan"));
+        assert!(prompt.contains(&format!(
+            "anyon://projects/{}/documents/",
+            planned.project_id
+        )));
+        assert!(prompt.ends_with(&render_task(&planned)));
+
+        assert!(
+            implement_task_prompt(&task(None, Some(" "))).contains("It has no approved plan yet")
+        );
+    }
+
+    #[test]
+    fn plan_prompt_forbids_code_changes() {
+        let unplanned = task(Some("Use OAuth."), None);
+        let prompt = plan_task_prompt(&unplanned);
+        assert!(prompt.contains("Do not change any code yet."));
+        assert!(prompt.ends_with(&render_task(&unplanned)));
+    }
+}
@@ -1,24 +1,52 @@
 use std::{future::Future, path::PathBuf, str::FromStr};
 
 use db::models::{
+    approval_record::{ApprovalRecord, ApprovalRecordStatus},
+    execution_process::ExecutionProcess,
     project::Project,
     task::{CreateTask, Task, TaskStatus, TaskWithAttemptStatus, UpdateTask},
     task_attempt::{TaskAttempt, TaskAttemptContext},
+    task_dependency::TaskDependency,
 };
 use executors::{executors::BaseCodingAgent, profile::ExecutorProfileId};
 use rmcp::{
-    ErrorData, ServerHandler,
+    ErrorData, RoleServer, ServerHandler,
     handler::server::tool::{Parameters, ToolRouter},
     model::{
-        CallToolResult, Content, Implementation, ProtocolVersion, ServerCapabilities, ServerInfo,
+        AnnotateAble, CallToolResult, Content, GetPromptRequestParam, GetPromptResult,
+        Implementation, JsonObject, ListPromptsResult, ListResourceTemplatesResult,
+        ListResourcesResult, PaginatedRequestParam, Prompt, PromptArgument, PromptMessage,
+        PromptMessageRole, ProtocolVersion, RawResource, RawResourceTemplate,
+        ReadResourceRequestParam, ReadResourceResult, Resource, ResourceContents, ResourceTemplate,
+        ServerCapabilities, ServerInfo,
     },
-    schemars, tool, tool_handler, tool_router,
+    schemars,
+    service::RequestContext,
+    tool, tool_handler, tool_router,
 };
 use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use serde_json;
+use services::services::{
+    documents::{Document, DocumentListResponse},
+    subtasks::Subtask,
+};
+use utils::approvals::{ApprovalResponse, ApprovalStatus};
 use uuid::Uuid;
 
-use crate::routes::{containers::ContainerQuery, task_attempts::CreateTaskAttemptBody};
+use crate::{
+    mcp::resources::{
+        IMPLEMENT_TASK_PROMPT, PLAN_TASK_PROMPT, ResourceUri, implement_task_prompt,
+        plan_task_prompt, render_task,
+    },
+    routes::{
+        containers::ContainerQuery,
+        task_attempts::{
+            CreateFollowUpAttempt, CreateTaskAttemptBody,
+            inspect::{AttemptConversation, DiffFileSummary, FileDiff},
+        },
+        tasks::{CreateSubtask, CreateSubtasks},
+    },
+};
 
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
 pub struct CreateTaskRequest {
@@ -35,6 +63,49 @@ pub struct CreateTaskResponse {
     pub task_id: String,
 }
 
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct SubtaskSpec {
+    #[schemars(description = "The title of the subtask")]
+    pub title: String,
+    #[schemars(description = "Optional description of the subtask")]
+    pub description: Option<String>,
+    #[schemars(
+        description = "Optional executor suggested for the subtask, e.g. 'claude-code' or 'codex'"
+    )]
+    pub executor: Option<String>,
+    #[schemars(
+        description = "Zero-based indices of earlier subtasks in this list that must be done before this one"
+    )]
+    #[serde(default)]
+    pub depends_on: Vec<usize>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct CreateSubtasksRequest {
+    #[schemars(description = "The ID of the task to split into subtasks")]
+    pub parent_task_id: Uuid,
+    #[schemars(
+        description = "Optional attempt of the parent task the subtasks come from. Defaults to the current attempt when working on the parent task, otherwise its latest attempt"
+    )]
+    pub parent_attempt_id: Option<Uuid>,
+    #[schemars(description = "The subtasks, in the order they should be worked on")]
+    pub subtasks: Vec<SubtaskSpec>,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct CreatedSubtask {
+    pub task_id: String,
+    pub title: String,
+    #[schemars(description = "IDs of the subtasks that must be done first")]
+    pub depends_on: Vec<String>,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct CreateSubtasksResponse {
+    pub parent_task_id: String,
+    pub subtasks: Vec<CreatedSubtask>,
+}
+
 #[derive(Debug, Serialize, schemars::JsonSchema)]
 pub struct ProjectSummary {
     #[schemars(description = "The unique identifier of the project")]
@@ -143,6 +214,8 @@ pub struct TaskDetails {
     pub has_merged_attempt: Option<bool>,
     #[schemars(description = "Whether the last execution attempt failed")]
     pub last_attempt_failed: Option<bool>,
+    #[schemars(description = "IDs of the tasks that must be done before this one")]
+    pub depends_on: Option<Vec<String>>,
 }
 
 impl TaskDetails {
@@ -157,6 +230,7 @@ impl TaskDetails {
             has_in_progress_attempt: None,
             has_merged_attempt: None,
             last_attempt_failed: None,
+            depends_on: None,
         }
     }
 }
@@ -234,6 +308,184 @@ pub struct GetTaskResponse {
     pub task: TaskDetails,
 }
 
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct ListTaskAttemptsRequest {
+    #[schemars(description = "The ID of the task whose attempts to list")]
+    pub task_id: Uuid,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct TaskAttemptSummary {
+    #[schemars(description = "The unique identifier of the attempt")]
+    pub id: String,
+    #[schemars(description = "The git branch the attempt works on")]
+    pub branch: String,
+    #[schemars(description = "The branch the attempt will be merged into")]
+    pub target_branch: String,
+    #[schemars(description = "The coding agent executor running the attempt")]
+    pub executor: String,
+    #[schemars(description = "When the attempt was created")]
+    pub created_at: String,
+    #[schemars(description = "When the attempt was last updated")]
+    pub updated_at: String,
+}
+
+impl TaskAttemptSummary {
+    fn from_attempt(attempt: TaskAttempt) -> Self {
+        Self {
+            id: attempt.id.to_string(),
+            branch: attempt.branch,
+            target_branch: attempt.target_branch,
+            executor: attempt.executor,
+            created_at: attempt.created_at.to_rfc3339(),
+            updated_at: attempt.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct ListTaskAttemptsResponse {
+    pub attempts: Vec<TaskAttemptSummary>,
+    pub count: usize,
+    pub task_id: String,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct GetAttemptConversationRequest {
+    #[schemars(description = "The ID of the task attempt")]
+    pub attempt_id: Uuid,
+    #[schemars(description = "Index of the first entry to return (default: 0)")]
+    pub offset: Option<usize>,
+    #[schemars(description = "Maximum number of entries to return (default: 50)")]
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct GetAttemptDiffRequest {
+    #[schemars(description = "The ID of the task attempt")]
+    pub attempt_id: Uuid,
+    #[schemars(
+        description = "Optional file path; returns that file's unified diff instead of the list of changed files"
+    )]
+    pub path: Option<String>,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct DiffSummaryResponse {
+    #[schemars(description = "Changed files with their line counts")]
+    pub files: Vec<DiffFileEntry>,
+    pub count: usize,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct DiffFileEntry {
+    pub path: String,
+    #[schemars(
+        description = "'added', 'deleted', 'modified', 'renamed', 'copied' or 'permissionChange'"
+    )]
+    pub change: String,
+    pub additions: usize,
+    pub deletions: usize,
+}
+
+impl DiffFileEntry {
+    fn from_summary(summary: DiffFileSummary) -> Self {
+        Self {
+            path: summary.path,
+            change: serde_json::to_value(&summary.change)
+                .ok()
+                .and_then(|v| v.as_str().map(str::to_string))
+                .unwrap_or_default(),
+            additions: summary.additions,
+            deletions: summary.deletions,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct SendFollowUpRequest {
+    #[schemars(description = "The ID of the task attempt to continue")]
+    pub attempt_id: Uuid,
+    #[schemars(description = "The follow-up prompt for the coding agent")]
+    pub prompt: String,
+    #[schemars(description = "Optional executor variant to use for the follow-up")]
+    pub variant: Option<String>,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct SendFollowUpResponse {
+    pub attempt_id: String,
+    pub execution_process_id: String,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct StopAttemptRequest {
+    #[schemars(description = "The ID of the task attempt whose running executions to stop")]
+    pub attempt_id: Uuid,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct StopAttemptResponse {
+    pub stopped_attempt_id: String,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct ListPendingApprovalsRequest {
+    #[schemars(description = "The ID of the task attempt")]
+    pub attempt_id: Uuid,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct PendingApprovalSummary {
+    pub approval_id: String,
+    pub execution_process_id: String,
+    #[schemars(description = "The tool the agent wants to use")]
+    pub tool_name: String,
+    #[schemars(description = "The input the agent wants to call the tool with")]
+    pub tool_input: serde_json::Value,
+    pub created_at: String,
+    #[schemars(description = "When the request times out")]
+    pub timeout_at: String,
+}
+
+impl PendingApprovalSummary {
+    fn from_record(record: ApprovalRecord) -> Self {
+        Self {
+            approval_id: record.id,
+            execution_process_id: record.execution_process_id.to_string(),
+            tool_name: record.tool_name,
+            tool_input: record.tool_input.0,
+            created_at: record.created_at.to_rfc3339(),
+            timeout_at: record.timeout_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct ListPendingApprovalsResponse {
+    pub approvals: Vec<PendingApprovalSummary>,
+    pub count: usize,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct RespondToApprovalRequest {
+    #[schemars(description = "The ID of the approval request, from `list_pending_approvals`")]
+    pub approval_id: String,
+    #[schemars(description = "The execution process that asked for approval")]
+    pub execution_process_id: Uuid,
+    #[schemars(description = "true to let the agent run the tool, false to deny it")]
+    pub approve: bool,
+    #[schemars(description = "Optional reason passed to the agent when denying")]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct RespondToApprovalResponse {
+    pub approval_id: String,
+    #[schemars(description = "The recorded decision")]
+    pub status: String,
+}
+
 #[derive(Debug, Clone)]
 pub struct TaskServer {
     client: reqwest::Client,
@@ -264,16 +516,21 @@ impl TaskServer {
     }
 
     pub async fn init(mut self) -> Self {
-        let context = self.fetch_context_at_startup().await;
-
-        if context.is_none() {
-            self.tool_router.map.remove("get_context");
+        let Some(context) = self.fetch_context_at_startup().await else {
             tracing::debug!("VK context not available, get_context tool will not be registered");
-        } else {
-            tracing::info!("VK context loaded, get_context tool available");
-        }
+            return self.without_context();
+        };
+
+        tracing::info!("VK context loaded, get_context tool available");
+        self.context = Some(context);
+        self
+    }
 
-        self.context = context;
+    /// Server for clients that do not run inside an attempt's worktree, such as
+    /// clients of the HTTP endpoint
+    pub fn without_context(mut self) -> Self {
+        self.tool_router.map.remove("get_context");
+        self.context = None;
         self
     }
 
@@ -318,6 +575,33 @@ impl TaskServer {
     }
 }
 
+/// Failed VK API call, reported as a tool error or as an MCP error
+struct ApiCallError {
+    error: String,
+    details: Option<String>,
+}
+
+impl ApiCallError {
+    fn new(error: impl Into<String>, details: Option<String>) -> Self {
+        Self {
+            error: error.into(),
+            details,
+        }
+    }
+
+    fn into_tool_result(self) -> CallToolResult {
+        TaskServer::err(self.error, self.details).unwrap()
+    }
+
+    fn into_error_data(self) -> ErrorData {
+        let message = match self.details {
+            Some(details) => format!("{}: {}", self.error, details),
+            None => self.error,
+        };
+        ErrorData::internal_error(message, None)
+    }
+}
+
 #[derive(Debug, Deserialize)]
 struct ApiResponseEnvelope<T> {
     success: bool,
@@ -352,6 +636,46 @@ impl TaskServer {
         &self,
         rb: reqwest::RequestBuilder,
     ) -> Result<T, CallToolResult> {
+        self.request_json(rb)
+            .await
+            .map_err(ApiCallError::into_tool_result)
+    }
+
+    async fn request_json<T: DeserializeOwned>(
+        &self,
+        rb: reqwest::RequestBuilder,
+    ) -> Result<T, ApiCallError> {
+        let resp = rb
+            .send()
+            .await
+            .map_err(|e| ApiCallError::new("Failed to connect to VK API", Some(e.to_string())))?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            return Err(ApiCallError::new(
+                format!("VK API returned error status: {}", status),
+                None,
+            ));
+        }
+
+        let api_response = resp.json::<ApiResponseEnvelope<T>>().await.map_err(|e| {
+            ApiCallError::new("Failed to parse VK API response", Some(e.to_string()))
+        })?;
+
+        if !api_response.success {
+            let msg = api_response
+                .message
+                .unwrap_or_else(|| "Unknown error".to_string());
+            return Err(ApiCallError::new("VK API returned error", Some(msg)));
+        }
+
+        api_response
+            .data
+            .ok_or_else(|| ApiCallError::new("VK API response missing data field", None))
+    }
+
+    /// Like [`Self::send_json`] for endpoints that return no data
+    async fn send_ok(&self, rb: reqwest::RequestBuilder) -> Result<(), CallToolResult> {
         let resp = rb
             .send()
             .await
@@ -364,18 +688,23 @@ impl TaskServer {
             );
         }
 
-        let api_response = resp.json::<ApiResponseEnvelope<T>>().await.map_err(|e| {
-            Self::err("Failed to parse VK API response", Some(&e.to_string())).unwrap()
-        })?;
+        let api_response = resp
+            .json::<ApiResponseEnvelope<serde_json::Value>>()
+            .await
+            .map_err(|e| {
+                Self::err("Failed to parse VK API response", Some(&e.to_string())).unwrap()
+            })?;
 
         if !api_response.success {
             let msg = api_response.message.as_deref().unwrap_or("Unknown error");
             return Err(Self::err("VK API returned error", Some(msg)).unwrap());
         }
+        Ok(())
+    }
 
-        api_response
-            .data
-            .ok_or_else(|| Self::err("VK API response missing data field", None).unwrap())
+    /// Accepts executor names case-insensitively, with hyphens or underscores
+    fn parse_executor(executor: &str) -> Option<BaseCodingAgent> {
+        BaseCodingAgent::from_str(&executor.trim().replace('-', "_").to_ascii_uppercase()).ok()
     }
 
     fn url(&self, path: &str) -> String {
@@ -387,6 +716,78 @@ impl TaskServer {
     }
 }
 
+impl TaskServer {
+    /// Projects whose documents and tasks are published as resources: the current
+    /// attempt's project, or every project outside an attempt
+    async fn resource_project_ids(&self) -> Result<Vec<Uuid>, ApiCallError> {
+        if let Some(context) = &self.context {
+            return Ok(vec![context.project_id]);
+        }
+        let projects: Vec<Project> = self
+            .request_json(self.client.get(self.url("/api/projects")))
+            .await?;
+        Ok(projects.into_iter().map(|p| p.id).collect())
+    }
+
+    /// Non-empty documents of a project, including the conversation documents (PRD,
+    /// UX design, TRD, ...)
+    async fn project_documents(&self, project_id: Uuid) -> Result<Vec<Document>, ApiCallError> {
+        let url = self.url(&format!("/api/projects/{}/documents", project_id));
+        let mut documents = Vec::new();
+        for category in [None, Some("conversation")] {
+            let mut rb = self.client.get(&url);
+            if let Some(category) = category {
+                rb = rb.query(&[("category", category)]);
+            }
+            let list: DocumentListResponse = self.request_json(rb).await?;
+            documents.extend(
+                list.items
+                    .into_iter()
+                    .filter(|doc| !doc.content.trim().is_empty()),
+            );
+        }
+        Ok(documents)
+    }
+
+    async fn fetch_task(&self, task_id: Uuid) -> Result<Task, ApiCallError> {
+        let url = self.url(&format!("/api/tasks/{}", task_id));
+        self.request_json(self.client.get(&url)).await
+    }
+
+    /// Task a prompt is about: its `task_id` argument, or the current attempt's task
+    fn prompt_task_id(&self, arguments: Option<&JsonObject>) -> Result<Uuid, ErrorData> {
+        match arguments
+            .and_then(|args| args.get("task_id"))
+            .and_then(|v| v.as_str())
+        {
+            Some(task_id) => Uuid::parse_str(task_id.trim())
+                .map_err(|_| ErrorData::invalid_params("`task_id` must be a UUID", None)),
+            None => self
+                .context
+                .as_ref()
+                .map(|context| context.task_id)
+                .ok_or_else(|| ErrorData::invalid_params("`task_id` is required", None)),
+        }
+    }
+}
+
+fn resource(uri: String, name: String, description: Option<String>, mime_type: &str) -> Resource {
+    let mut raw = RawResource::new(uri, name);
+    raw.description = description;
+    raw.mime_type = Some(mime_type.to_string());
+    raw.no_annotation()
+}
+
+fn resource_template(uri_template: &str, name: &str, description: &str) -> ResourceTemplate {
+    RawResourceTemplate {
+        uri_template: uri_template.to_string(),
+        name: name.to_string(),
+        description: Some(description.to_string()),
+        mime_type: Some("text/markdown".to_string()),
+    }
+    .no_annotation()
+}
+
 #[tool_router]
 impl TaskServer {
     #[tool(
@@ -431,6 +832,73 @@ impl TaskServer {
         })
     }
 
+    #[tool(
+        description = "Split a task into subtasks in one step. The subtasks are linked to an attempt of the parent task and can depend on earlier subtasks in the list; either all of them are created or none. `parent_task_id` is required!"
+    )]
+    async fn create_subtasks(
+        &self,
+        Parameters(CreateSubtasksRequest {
+            parent_task_id,
+            parent_attempt_id,
+            subtasks,
+        }): Parameters<CreateSubtasksRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let mut specs = Vec::with_capacity(subtasks.len());
+        for spec in subtasks {
+            let executor = match spec.executor.as_deref().map(str::trim) {
+                Some(executor) if !executor.is_empty() => match Self::parse_executor(executor) {
+                    Some(executor) => Some(executor),
+                    None => {
+                        return Self::err(
+                            format!(
+                                "Unknown executor '{executor}' for subtask '{}'.",
+                                spec.title
+                            ),
+                            None::<String>,
+                        );
+                    }
+                },
+                _ => None,
+            };
+            specs.push(CreateSubtask {
+                title: spec.title,
+                description: spec.description,
+                executor,
+                depends_on: spec.depends_on,
+            });
+        }
+
+        let parent_task_attempt = parent_attempt_id.or_else(|| {
+            self.context
+                .as_ref()
+                .filter(|context| context.task_id == parent_task_id)
+                .map(|context| context.attempt_id)
+        });
+        let url = self.url(&format!("/api/tasks/{}/subtasks", parent_task_id));
+        let created: Vec<Subtask> = match self
+            .send_json(self.client.post(&url).json(&CreateSubtasks {
+                parent_task_attempt,
+                subtasks: specs,
+            }))
+            .await
+        {
+            Ok(created) => created,
+            Err(e) => return Ok(e),
+        };
+
+        TaskServer::success(&CreateSubtasksResponse {
+            parent_task_id: parent_task_id.to_string(),
+            subtasks: created
+                .into_iter()
+                .map(|subtask| CreatedSubtask {
+                    task_id: subtask.task.id.to_string(),
+                    title: subtask.task.title,
+                    depends_on: subtask.depends_on.iter().map(Uuid::to_string).collect(),
+                })
+                .collect(),
+        })
+    }
+
     #[tool(description = "List all the available projects")]
     async fn list_projects(&self) -> Result<CallToolResult, ErrorData> {
         let url = self.url("/api/projects");
@@ -532,15 +1000,11 @@ impl TaskServer {
             return Self::err("Executor must not be empty.".to_string(), None::<String>);
         }
 
-        let normalized_executor = executor_trimmed.replace('-', "_").to_ascii_uppercase();
-        let base_executor = match BaseCodingAgent::from_str(&normalized_executor) {
-            Ok(exec) => exec,
-            Err(_) => {
-                return Self::err(
-                    format!("Unknown executor '{executor_trimmed}'."),
-                    None::<String>,
-                );
-            }
+        let Some(base_executor) = Self::parse_executor(executor_trimmed) else {
+            return Self::err(
+                format!("Unknown executor '{executor_trimmed}'."),
+                None::<String>,
+            );
         };
 
         let variant = variant.and_then(|v| {
@@ -645,7 +1109,7 @@ impl TaskServer {
     }
 
     #[tool(
-        description = "Get detailed information (like task description) about a specific task/ticket. You can use `list_tasks` to find the `task_ids` of all tasks in a project. `project_id` and `task_id` are required!"
+        description = "Get detailed information (like task description and the tasks it depends on) about a specific task/ticket. You can use `list_tasks` to find the `task_ids` of all tasks in a project. `project_id` and `task_id` are required!"
     )]
     async fn get_task(
         &self,
@@ -656,18 +1120,255 @@ impl TaskServer {
             Ok(t) => t,
             Err(e) => return Ok(e),
         };
+        let url = self.url(&format!("/api/tasks/{}/dependencies", task_id));
+        let dependencies: Vec<TaskDependency> = match self.send_json(self.client.get(&url)).await {
+            Ok(d) => d,
+            Err(e) => return Ok(e),
+        };
 
-        let details = TaskDetails::from_task(task);
+        let mut details = TaskDetails::from_task(task);
+        details.depends_on = Some(
+            dependencies
+                .iter()
+                .map(|dependency| dependency.depends_on_task_id.to_string())
+                .collect(),
+        );
         let response = GetTaskResponse { task: details };
 
         TaskServer::success(&response)
     }
+
+    #[tool(
+        description = "List the attempts of a task, newest first. Use the attempt ids to read conversations, diffs and approvals. `task_id` is required!"
+    )]
+    async fn list_task_attempts(
+        &self,
+        Parameters(ListTaskAttemptsRequest { task_id }): Parameters<ListTaskAttemptsRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let url = self.url(&format!("/api/task-attempts?task_id={}", task_id));
+        let attempts: Vec<TaskAttempt> = match self.send_json(self.client.get(&url)).await {
+            Ok(a) => a,
+            Err(e) => return Ok(e),
+        };
+
+        let attempts: Vec<TaskAttemptSummary> = attempts
+            .into_iter()
+            .map(TaskAttemptSummary::from_attempt)
+            .collect();
+        let response = ListTaskAttemptsResponse {
+            count: attempts.len(),
+            attempts,
+            task_id: task_id.to_string(),
+        };
+
+        TaskServer::success(&response)
+    }
+
+    #[tool(
+        description = "Read the normalized conversation (messages, tool calls and their results) of a task attempt's coding agent, paginated with `offset` and `limit`. `attempt_id` is required!"
+    )]
+    async fn get_attempt_conversation(
+        &self,
+        Parameters(GetAttemptConversationRequest {
+            attempt_id,
+            offset,
+            limit,
+        }): Parameters<GetAttemptConversationRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let url = self.url(&format!("/api/task-attempts/{}/conversation", attempt_id));
+        let query = [
+            ("offset", offset.unwrap_or(0)),
+            ("limit", limit.unwrap_or(50)),
+        ];
+        let conversation: AttemptConversation =
+            match self.send_json(self.client.get(&url).query(&query)).await {
+                Ok(c) => c,
+                Err(e) => return Ok(e),
+            };
+
+        TaskServer::success(&conversation)
+    }
+
+    #[tool(
+        description = "Get the files a task attempt changed compared to its target branch, or the unified diff of one file when `path` is given. `attempt_id` is required!"
+    )]
+    async fn get_attempt_diff(
+        &self,
+        Parameters(GetAttemptDiffRequest { attempt_id, path }): Parameters<GetAttemptDiffRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if let Some(path) = path {
+            let url = self.url(&format!("/api/task-attempts/{}/diff/file", attempt_id));
+            let diff: FileDiff = match self
+                .send_json(self.client.get(&url).query(&[("path", path)]))
+                .await
+            {
+                Ok(d) => d,
+                Err(e) => return Ok(e),
+            };
+            return TaskServer::success(&diff);
+        }
+
+        let url = self.url(&format!("/api/task-attempts/{}/diff", attempt_id));
+        let summaries: Vec<DiffFileSummary> = match self.send_json(self.client.get(&url)).await {
+            Ok(s) => s,
+            Err(e) => return Ok(e),
+        };
+        let files: Vec<DiffFileEntry> = summaries
+            .into_iter()
+            .map(DiffFileEntry::from_summary)
+            .collect();
+        let response = DiffSummaryResponse {
+            count: files.len(),
+            files,
+        };
+
+        TaskServer::success(&response)
+    }
+
+    #[tool(
+        description = "Send a follow-up prompt to the coding agent of a task attempt, continuing its session. `attempt_id` and `prompt` are required!"
+    )]
+    async fn send_follow_up(
+        &self,
+        Parameters(SendFollowUpRequest {
+            attempt_id,
+            prompt,
+            variant,
+        }): Parameters<SendFollowUpRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if prompt.trim().is_empty() {
+            return Self::err("Prompt must not be empty.".to_string(), None::<String>);
+        }
+
+        let payload = CreateFollowUpAttempt {
+            prompt,
+            variant,
+            image_ids: None,
+            retry_process_id: None,
+            force_when_dirty: None,
+            perform_git_reset: None,
+        };
+        let url = self.url(&format!("/api/task-attempts/{}/follow-up", attempt_id));
+        let process: ExecutionProcess =
+            match self.send_json(self.client.post(&url).json(&payload)).await {
+                Ok(p) => p,
+                Err(e) => return Ok(e),
+            };
+
+        let response = SendFollowUpResponse {
+            attempt_id: attempt_id.to_string(),
+            execution_process_id: process.id.to_string(),
+        };
+
+        TaskServer::success(&response)
+    }
+
+    #[tool(
+        description = "Stop the running executions of a task attempt. `attempt_id` is required!"
+    )]
+    async fn stop_attempt(
+        &self,
+        Parameters(StopAttemptRequest { attempt_id }): Parameters<StopAttemptRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let url = self.url(&format!("/api/task-attempts/{}/stop", attempt_id));
+        if let Err(e) = self.send_ok(self.client.post(&url)).await {
+            return Ok(e);
+        }
+
+        let response = StopAttemptResponse {
+            stopped_attempt_id: attempt_id.to_string(),
+        };
+
+        TaskServer::success(&response)
+    }
+
+    #[tool(
+        description = "List the tool calls of a task attempt's coding agent that are waiting for approval. `attempt_id` is required!"
+    )]
+    async fn list_pending_approvals(
+        &self,
+        Parameters(ListPendingApprovalsRequest { attempt_id }): Parameters<
+            ListPendingApprovalsRequest,
+        >,
+    ) -> Result<CallToolResult, ErrorData> {
+        let url = self.url(&format!("/api/task-attempts/{}/approvals", attempt_id));
+        let records: Vec<ApprovalRecord> = match self.send_json(self.client.get(&url)).await {
+            Ok(r) => r,
+            Err(e) => return Ok(e),
+        };
+
+        let approvals: Vec<PendingApprovalSummary> = records
+            .into_iter()
+            .filter(|record| record.status == ApprovalRecordStatus::Pending)
+            .map(PendingApprovalSummary::from_record)
+            .collect();
+        let response = ListPendingApprovalsResponse {
+            count: approvals.len(),
+            approvals,
+        };
+
+        TaskServer::success(&response)
+    }
+
+    #[tool(
+        description = "Approve or deny a tool call that a coding agent is waiting on. Use `list_pending_approvals` to find `approval_id` and `execution_process_id`, which are required!"
+    )]
+    async fn respond_to_approval(
+        &self,
+        Parameters(RespondToApprovalRequest {
+            approval_id,
+            execution_process_id,
+            approve,
+            reason,
+        }): Parameters<RespondToApprovalRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let status = if approve {
+            ApprovalStatus::Approved
+        } else {
+            ApprovalStatus::Denied { reason }
+        };
+        let payload = ApprovalResponse {
+            execution_process_id,
+            status,
+            remember: None,
+        };
+
+        // This endpoint answers with the bare status rather than the API envelope
+        let url = self.url(&format!("/api/approvals/{}/respond", approval_id));
+        let resp = match self.client.post(&url).json(&payload).send().await {
+            Ok(resp) => resp,
+            Err(e) => return Self::err("Failed to connect to VK API", Some(&e.to_string())),
+        };
+        if !resp.status().is_success() {
+            return Self::err(
+                format!("VK API returned error status: {}", resp.status()),
+                Some("The approval may already be answered or timed out".to_string()),
+            );
+        }
+        let status: ApprovalStatus = match resp.json().await {
+            Ok(status) => status,
+            Err(e) => return Self::err("Failed to parse VK API response", Some(&e.to_string())),
+        };
+
+        let response = RespondToApprovalResponse {
+            approval_id,
+            status: match status {
+                ApprovalStatus::Approved => "approved",
+                ApprovalStatus::Denied { .. } => "denied",
+                ApprovalStatus::TimedOut => "timed_out",
+                ApprovalStatus::Pending => "pending",
+            }
+            .to_string(),
+        };
+
+        TaskServer::success(&response)
+    }
 }
 
 #[tool_handler]
 impl ServerHandler for TaskServer {
     fn get_info(&self) -> ServerInfo {
-        let mut instruction = "A task and project management server. If you need to create or update tickets or tasks then use these tools. Most of them absolutely require that you pass the `project_id` of the project that you are currently working on. You can get project ids by using `list projects`. Call `list_tasks` to fetch the `task_ids` of all the tasks in a project`.. TOOLS: 'list_projects', 'list_tasks', 'create_task', 'start_task_attempt', 'get_task', 'update_task', 'delete_task'. Make sure to pass `project_id` or `task_id` where required. You can use list tools to get the available ids.".to_string();
+        let mut instruction = "A task and project management server. If you need to create or update tickets or tasks then use these tools. Most of them absolutely require that you pass the `project_id` of the project that you are currently working on. You can get project ids by using `list projects`. Call `list_tasks` to fetch the `task_ids` of all the tasks in a project`.. TOOLS: 'list_projects', 'list_tasks', 'create_task', 'create_subtasks', 'start_task_attempt', 'get_task', 'update_task', 'delete_task', 'list_task_attempts', 'get_attempt_conversation', 'get_attempt_diff', 'send_follow_up', 'stop_attempt', 'list_pending_approvals', 'respond_to_approval'. To break a task down, use 'create_subtasks' rather than repeated 'create_task' calls so the order between subtasks is kept. To supervise an attempt, read its conversation and diff, answer its pending approvals and send follow-ups. Make sure to pass `project_id` or `task_id` where required. You can use list tools to get the available ids. Project documents (PRD, design, technical docs) and tasks with their plans are also available as resources, and the 'implement_task' and 'plan_task' prompts start work on a task.".to_string();
         if self.context.is_some() {
             let context_instruction = "Use 'get_context' to fetch project/task/attempt metadata for the active Anyon attempt when available.";
             instruction = format!("{} {}", context_instruction, instruction);
@@ -675,7 +1376,11 @@ impl ServerHandler for TaskServer {
 
         ServerInfo {
             protocol_version: ProtocolVersion::V_2025_03_26,
-            capabilities: ServerCapabilities::builder().enable_tools().build(),
+            capabilities: ServerCapabilities::builder()
+                .enable_tools()
+                .enable_resources()
+                .enable_prompts()
+                .build(),
             server_info: Implementation {
                 name: "anyon".to_string(),
                 version: "1.0.0".to_string(),
@@ -683,4 +1388,442 @@ impl ServerHandler for TaskServer {
             instructions: Some(instruction),
         }
     }
+
+    async fn list_resources(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListResourcesResult, ErrorData> {
+        let mut resources = Vec::new();
+        if self.context.is_some() {
+            resources.push(resource(
+                ResourceUri::Context.to_uri(),
+                "Current attempt context".to_string(),
+                Some("Project, task and attempt metadata of the current Anyon attempt".to_string()),
+                "application/json",
+            ));
+        }
+
+        let project_ids = self
+            .resource_project_ids()
+            .await
+            .map_err(ApiCallError::into_error_data)?;
+        for project_id in project_ids {
+            let documents = self
+                .project_documents(project_id)
+                .await
+                .map_err(ApiCallError::into_error_data)?;
+            resources.extend(documents.into_iter().map(|doc| {
+                let uri = ResourceUri::Document {
+                    project_id,
+                    document_id: doc.id.to_string(),
+                };
+                resource(
+                    uri.to_uri(),
+                    doc.title,
+                    Some(format!("Project document ({})", doc.category)),
+                    "text/markdown",
+                )
+            }));
+
+            let url = self.url(&format!("/api/tasks?project_id={}", project_id));
+            let tasks: Vec<TaskWithAttemptStatus> = self
+                .request_json(self.client.get(&url))
+                .await
+                .map_err(ApiCallError::into_error_data)?;
+            resources.extend(tasks.into_iter().map(|task| {
+                resource(
+                    ResourceUri::Task(task.id).to_uri(),
+                    task.title.to_string(),
+                    Some(format!(
+                        "Task ({}) with its description and plan",
+                        task.status
+                    )),
+                    "text/markdown",
+                )
+            }));
+        }
+
+        Ok(ListResourcesResult {
+            resources,
+            next_cursor: None,
+        })
+    }
+
+    async fn list_resource_templates(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListResourceTemplatesResult, ErrorData> {
+        Ok(ListResourceTemplatesResult {
+            resource_templates: vec![
+                resource_template(
+                    ResourceUri::TASK_TEMPLATE,
+                    "Task",
+                    "A task with its description and plan",
+                ),
+                resource_template(
+                    ResourceUri::DOCUMENT_TEMPLATE,
+                    "Project document",
+                    "A document from the project's anyon-docs folder",
+                ),
+            ],
+            next_cursor: None,
+        })
+    }
+
+    async fn read_resource(
+        &self,
+        ReadResourceRequestParam { uri }: ReadResourceRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ReadResourceResult, ErrorData> {
+        let text = match ResourceUri::parse(&uri) {
+            Some(ResourceUri::Context) => {
+                let context = self.context.as_ref().ok_or_else(|| {
+                    ErrorData::resource_not_found("Not running inside an Anyon attempt", None)
+                })?;
+                serde_json::to_string_pretty(context)
+                    .map_err(|e| ErrorData::internal_error(e.to_string(), None))?
+            }
+            Some(ResourceUri::Task(task_id)) => render_task(
+                &self
+                    .fetch_task(task_id)
+                    .await
+                    .map_err(ApiCallError::into_error_data)?,
+            ),
+            Some(ResourceUri::Document {
+                project_id,
+                document_id,
+            }) => {
+                let url = self.url(&format!(
+                    "/api/projects/{}/documents/{}",
+                    project_id, document_id
+                ));
+                let document: Document = self
+                    .request_json(self.client.get(&url))
+                    .await
+                    .map_err(ApiCallError::into_error_data)?;
+                document.content
+            }
+            None => {
+                return Err(ErrorData::resource_not_found(
+                    format!("Unknown resource: {uri}"),
+                    None,
+                ));
+            }
+        };
+
+        Ok(ReadResourceResult {
+            contents: vec![ResourceContents::text(text, uri)],
+        })
+    }
+
+    async fn list_prompts(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListPromptsResult, ErrorData> {
+        let task_id = PromptArgument {
+            name: "task_id".to_string(),
+            description: Some(
+                "The task to work on; defaults to the current attempt's task".to_string(),
+            ),
+            required: Some(self.context.is_none()),
+        };
+        Ok(ListPromptsResult {
+            prompts: vec![
+                Prompt::new(
+                    IMPLEMENT_TASK_PROMPT,
+                    Some("Implement a task, following its plan when it has one"),
+                    Some(vec![task_id.clone()]),
+                ),
+                Prompt::new(
+                    PLAN_TASK_PROMPT,
+                    Some("Write an implementation plan for a task without changing code"),
+                    Some(vec![task_id]),
+                ),
+            ],
+            next_cursor: None,
+        })
+    }
+
+    async fn get_prompt(
+        &self,
+        GetPromptRequestParam { name, arguments }: GetPromptRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<GetPromptResult, ErrorData> {
+        let render: fn(&Task) -> String = match name.as_str() {
+            IMPLEMENT_TASK_PROMPT => implement_task_prompt,
+            PLAN_TASK_PROMPT => plan_task_prompt,
+            _ => {
+                return Err(ErrorData::invalid_params(
+                    format!("Unknown prompt: {name}"),
+                    None,
+                ));
+            }
+        };
+        let task_id = self.prompt_task_id(arguments.as_ref())?;
+        let task = self
+            .fetch_task(task_id)
+            .await
+            .map_err(ApiCallError::into_error_data)?;
+
+        Ok(GetPromptResult {
+            description: Some(task.title.clone()),
+            messages: vec![PromptMessage::new_text(
+                PromptMessageRole::User,
+                render(&task),
+            )],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        Json, Router,
+        extract::Query,
+        routing::{get, post},
+    };
+    use chrono::Utc;
+    use db::models::task::TaskKind;
+    use executors::approvals::ApprovalTimeoutAction;
+    use serde_json::{Value, json};
+    use utils::diff::DiffChangeKind;
+
+    use super::*;
+
+    /// Task server talking to a stand-in for the VK API serving `router`
+    async fn server_for(router: Router) -> TaskServer {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+        TaskServer::new(&format!("http://{addr}")).without_context()
+    }
+
+    fn envelope(data: impl Serialize) -> Json<Value> {
+        Json(json!({"success": true, "data": data}))
+    }
+
+    fn result_json(result: &CallToolResult) -> Value {
+        let value = serde_json::to_value(result).unwrap();
+        serde_json::from_str(value["content"][0]["text"].as_str().unwrap()).unwrap()
+    }
+
+    fn is_error(result: &CallToolResult) -> bool {
+        serde_json::to_value(result).unwrap()["isError"]
+            .as_bool()
+            .unwrap_or(false)
+    }
+
+    fn record(id: &str, status: ApprovalRecordStatus) -> ApprovalRecord {
+        ApprovalRecord {
+            id: id.to_string(),
+            execution_process_id: Uuid::new_v4(),
+            tool_name: "Bash".to_string(),
+            tool_input: sqlx::types::Json(json!({"command": "cargo test"})),
+            tool_call_id: format!("call-{id}"),
+            status,
+            denial_reason: None,
+            responded_by: None,
+            approval_rule_id: None,
+            created_at: Utc::now(),
+            timeout_at: Utc::now(),
+            timeout_action: ApprovalTimeoutAction::Deny,
+            responded_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn list_pending_approvals_leaves_out_answered_requests() {
+        let attempt_id = Uuid::new_v4();
+        let server = server_for(Router::new().route(
+            &format!("/api/task-attempts/{attempt_id}/approvals"),
+            get(|| async {
+                envelope(vec![
+                    record("waiting", ApprovalRecordStatus::Pending),
+                    record("done", ApprovalRecordStatus::Approved),
+                ])
+            }),
+        ))
+        .await;
+
+        let result = server
+            .list_pending_approvals(Parameters(ListPendingApprovalsRequest { attempt_id }))
+            .await
+            .unwrap();
+
+        let body = result_json(&result);
+        assert_eq!(body["count"], 1);
+        assert_eq!(body["approvals"][0]["approval_id"], "waiting");
+        assert_eq!(body["approvals"][0]["tool_input"]["command"], "cargo test");
+    }
+
+    #[tokio::test]
+    async fn get_attempt_diff_lists_changed_files() {
+        let attempt_id = Uuid::new_v4();
+        let server = server_for(Router::new().route(
+            &format!("/api/task-attempts/{attempt_id}/diff"),
+            get(|| async {
+                envelope(vec![DiffFileSummary {
+                    path: "src/lib.rs".to_string(),
+                    change: DiffChangeKind::PermissionChange,
+                    additions: 0,
+                    deletions: 0,
+                }])
+            }),
+        ))
+        .await;
+
+        let result = server
+            .get_attempt_diff(Parameters(GetAttemptDiffRequest {
+                attempt_id,
+                path: None,
+            }))
+            .await
+            .unwrap();
+
+        let body = result_json(&result);
+        assert_eq!(body["count"], 1);
+        assert_eq!(body["files"][0]["path"], "src/lib.rs");
+        assert_eq!(body["files"][0]["change"], "permissionChange");
+    }
+
+    #[tokio::test]
+    async fn get_task_lists_the_tasks_it_depends_on() {
+        let task_id = Uuid::new_v4();
+        let dependency_id = Uuid::new_v4();
+        let task = Task {
+            id: task_id,
+            project_id: Uuid::new_v4(),
+            title: "Add endpoint".to_string(),
+            description: None,
+            plan: None,
+            status: TaskStatus::Todo,
+            kind: TaskKind::Implementation,
+            suggested_executor: None,
+            parent_task_attempt: None,
+            shared_task_id: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        let server = server_for(
+            Router::new()
+                .route(
+                    &format!("/api/tasks/{task_id}"),
+                    get(move || async move { envelope(task) }),
+                )
+                .route(
+                    &format!("/api/tasks/{task_id}/dependencies"),
+                    get(move || async move {
+                        envelope(vec![TaskDependency {
+                            task_id,
+                            depends_on_task_id: dependency_id,
+                            created_at: Utc::now(),
+                        }])
+                    }),
+                ),
+        )
+        .await;
+
+        let result = server
+            .get_task(Parameters(GetTaskRequest { task_id }))
+            .await
+            .unwrap();
+
+        let body = result_json(&result);
+        assert_eq!(body["task"]["title"], "Add endpoint");
+        assert_eq!(
+            body["task"]["depends_on"],
+            json!([dependency_id.to_string()])
+        );
+    }
+
+    #[tokio::test]
+    async fn get_attempt_conversation_forwards_pagination() {
+        let attempt_id = Uuid::new_v4();
+        let server = server_for(Router::new().route(
+            &format!("/api/task-attempts/{attempt_id}/conversation"),
+            get(
+                |Query(query): Query<std::collections::HashMap<String, usize>>| async move {
+                    envelope(AttemptConversation {
+                        entries: vec![],
+                        total: query["limit"],
+                        offset: query["offset"],
+                        running: false,
+                    })
+                },
+            ),
+        ))
+        .await;
+
+        let result = server
+            .get_attempt_conversation(Parameters(GetAttemptConversationRequest {
+                attempt_id,
+                offset: Some(20),
+                limit: None,
+            }))
+            .await
+            .unwrap();
+
+        let body = result_json(&result);
+        assert_eq!(body["offset"], 20);
+        assert_eq!(body["total"], 50);
+    }
+
+    #[tokio::test]
+    async fn respond_to_approval_sends_the_denial_reason() {
+        let server = server_for(Router::new().route(
+            "/api/approvals/approval-1/respond",
+            post(|Json(response): Json<ApprovalResponse>| async move { Json(response.status) }),
+        ))
+        .await;
+
+        let result = server
+            .respond_to_approval(Parameters(RespondToApprovalRequest {
+                approval_id: "approval-1".to_string(),
+                execution_process_id: Uuid::new_v4(),
+                approve: false,
+                reason: Some("use the staging database".to_string()),
+            }))
+            .await
+            .unwrap();
+
+        assert!(!is_error(&result));
+        assert_eq!(result_json(&result)["status"], "denied");
+    }
+
+    #[tokio::test]
+    async fn api_errors_are_reported_as_tool_errors() {
+        let attempt_id = Uuid::new_v4();
+        let server = server_for(Router::new().route(
+            &format!("/api/task-attempts/{attempt_id}/stop"),
+            post(|| async { Json(json!({"success": false, "message": "Task attempt not found"})) }),
+        ))
+        .await;
+
+        let result = server
+            .stop_attempt(Parameters(StopAttemptRequest { attempt_id }))
+            .await
+            .unwrap();
+
+        assert!(is_error(&result));
+        assert_eq!(result_json(&result)["details"], "Task attempt not found");
+    }
+
+    #[tokio::test]
+    async fn send_follow_up_rejects_an_empty_prompt() {
+        let server = server_for(Router::new()).await;
+
+        let result = server
+            .send_follow_up(Parameters(SendFollowUpRequest {
+                attempt_id: Uuid::new_v4(),
+                prompt: "  ".to_string(),
+                variant: None,
+            }))
+            .await
+            .unwrap();
+
+        assert!(is_error(&result));
+    }
 }
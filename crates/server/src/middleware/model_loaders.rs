@@ -5,6 +5,7 @@ use axum::{
     response::Response,
 };
 use db::models::{
+    approval_rule::ApprovalRule, approval_timeout_setting::ApprovalTimeoutSetting,
     execution_process::ExecutionProcess, project::Project, tag::Tag, task::Task,
     task_attempt::TaskAttempt,
 };
@@ -15,10 +16,16 @@ use crate::DeploymentImpl;
 
 pub async fn load_project_middleware(
     State(deployment): State<DeploymentImpl>,
-    Path(project_id): Path<Uuid>,
+    Path(params): Path<Vec<String>>,
     request: Request,
     next: Next,
 ) -> Result<Response, StatusCode> {
+    // The project id comes first; nested routes such as documents add more params
+    let project_id: Uuid = params
+        .first()
+        .and_then(|id| id.parse().ok())
+        .ok_or(StatusCode::BAD_REQUEST)?;
+
     // Load the project from the database
     let project = match Project::find_by_id(&deployment.db().pool, project_id).await {
         Ok(Some(project)) => project,
@@ -147,3 +154,52 @@ pub async fn load_tag_middleware(
     // Continue with the next middleware/handler
     Ok(next.run(request).await)
 }
+
+pub async fn load_approval_rule_middleware(
+    State(deployment): State<DeploymentImpl>,
+    Path(rule_id): Path<Uuid>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let rule = match ApprovalRule::find_by_id(&deployment.db().pool, rule_id).await {
+        Ok(Some(rule)) => rule,
+        Ok(None) => {
+            tracing::warn!("Approval rule {} not found", rule_id);
+            return Err(StatusCode::NOT_FOUND);
+        }
+        Err(e) => {
+            tracing::error!("Failed to fetch approval rule {}: {}", rule_id, e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+
+    request.extensions_mut().insert(rule);
+    Ok(next.run(request).await)
+}
+
+pub async fn load_approval_timeout_setting_middleware(
+    State(deployment): State<DeploymentImpl>,
+    Path(setting_id): Path<Uuid>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let setting = match ApprovalTimeoutSetting::find_by_id(&deployment.db().pool, setting_id).await
+    {
+        Ok(Some(setting)) => setting,
+        Ok(None) => {
+            tracing::warn!("Approval timeout setting {} not found", setting_id);
+            return Err(StatusCode::NOT_FOUND);
+        }
+        Err(e) => {
+            tracing::error!(
+                "Failed to fetch approval timeout setting {}: {}",
+                setting_id,
+                e
+            );
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+
+    request.extensions_mut().insert(setting);
+    Ok(next.run(request).await)
+}
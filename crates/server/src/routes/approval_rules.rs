@@ -0,0 +1,123 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{Query, State},
+    middleware::from_fn_with_state,
+    response::Json as ResponseJson,
+    routing::{get, put},
+};
+use db::models::approval_rule::{ApprovalRule, CreateApprovalRule, UpdateApprovalRule};
+use deployment::Deployment;
+use serde::Deserialize;
+use services::services::approvals::policy;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::load_approval_rule_middleware};
+
+#[derive(Debug, Deserialize)]
+pub struct ApprovalRuleQuery {
+    /// Rules of this project; the global rules when omitted
+    pub project_id: Option<Uuid>,
+    /// Rules remembered for this attempt; takes precedence over `project_id`
+    pub task_attempt_id: Option<Uuid>,
+}
+
+pub async fn get_approval_rules(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<ApprovalRuleQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<ApprovalRule>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let rules = match query.task_attempt_id {
+        Some(task_attempt_id) => {
+            ApprovalRule::find_by_task_attempt_id(pool, task_attempt_id).await?
+        }
+        None => ApprovalRule::find_by_project_id(pool, query.project_id).await?,
+    };
+    Ok(ResponseJson(ApiResponse::success(rules)))
+}
+
+pub async fn create_approval_rule(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateApprovalRule>,
+) -> Result<ResponseJson<ApiResponse<ApprovalRule>>, ApiError> {
+    if let Err(e) =
+        policy::validate_patterns(&payload.tool_pattern, payload.input_pattern.as_deref())
+    {
+        return Ok(ResponseJson(ApiResponse::error(&format!(
+            "Invalid rule pattern: {e}"
+        ))));
+    }
+    if let Some(field) = payload.input_field.as_deref()
+        && !policy::is_subject_field(field)
+    {
+        return Ok(ResponseJson(ApiResponse::error(&format!(
+            "Invalid input field: {field}"
+        ))));
+    }
+
+    let rule = ApprovalRule::create(&deployment.db().pool, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "approval_rule_created",
+            serde_json::json!({
+                "rule_id": rule.id.to_string(),
+                "action": rule.action,
+                "project_scoped": rule.project_id.is_some(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(rule)))
+}
+
+pub async fn update_approval_rule(
+    Extension(rule): Extension<ApprovalRule>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpdateApprovalRule>,
+) -> Result<ResponseJson<ApiResponse<ApprovalRule>>, ApiError> {
+    if let Err(e) =
+        policy::validate_patterns(&payload.tool_pattern, payload.input_pattern.as_deref())
+    {
+        return Ok(ResponseJson(ApiResponse::error(&format!(
+            "Invalid rule pattern: {e}"
+        ))));
+    }
+    if let Some(field) = payload.input_field.as_deref()
+        && !policy::is_subject_field(field)
+    {
+        return Ok(ResponseJson(ApiResponse::error(&format!(
+            "Invalid input field: {field}"
+        ))));
+    }
+
+    let updated = ApprovalRule::update(&deployment.db().pool, rule.id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(updated)))
+}
+
+pub async fn delete_approval_rule(
+    Extension(rule): Extension<ApprovalRule>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let rows_affected = ApprovalRule::delete(&deployment.db().pool, rule.id).await?;
+    if rows_affected == 0 {
+        Err(ApiError::Database(sqlx::Error::RowNotFound))
+    } else {
+        Ok(ResponseJson(ApiResponse::success(())))
+    }
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let rule_router = Router::new()
+        .route("/", put(update_approval_rule).delete(delete_approval_rule))
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_approval_rule_middleware,
+        ));
+
+    let inner = Router::new()
+        .route("/", get(get_approval_rules).post(create_approval_rule))
+        .nest("/{rule_id}", rule_router);
+
+    Router::new().nest("/approval-rules", inner)
+}
@@ -0,0 +1,94 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{Query, State},
+    middleware::from_fn_with_state,
+    response::Json as ResponseJson,
+    routing::{get, put},
+};
+use db::models::approval_timeout_setting::{
+    ApprovalTimeoutSetting, CreateApprovalTimeoutSetting, UpdateApprovalTimeoutSetting,
+};
+use deployment::Deployment;
+use serde::Deserialize;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl, error::ApiError, middleware::load_approval_timeout_setting_middleware,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct ApprovalTimeoutQuery {
+    /// Overrides of this project; the global overrides when omitted
+    pub project_id: Option<Uuid>,
+}
+
+pub async fn get_approval_timeouts(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<ApprovalTimeoutQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<ApprovalTimeoutSetting>>>, ApiError> {
+    let settings =
+        ApprovalTimeoutSetting::find_by_project_id(&deployment.db().pool, query.project_id).await?;
+    Ok(ResponseJson(ApiResponse::success(settings)))
+}
+
+pub async fn create_approval_timeout(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateApprovalTimeoutSetting>,
+) -> Result<ResponseJson<ApiResponse<ApprovalTimeoutSetting>>, ApiError> {
+    if payload.timeout_seconds.is_some_and(|s| s <= 0) {
+        return Ok(ResponseJson(ApiResponse::error(
+            "Timeout must be a positive number of seconds",
+        )));
+    }
+    let setting = ApprovalTimeoutSetting::create(&deployment.db().pool, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(setting)))
+}
+
+pub async fn update_approval_timeout(
+    Extension(setting): Extension<ApprovalTimeoutSetting>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpdateApprovalTimeoutSetting>,
+) -> Result<ResponseJson<ApiResponse<ApprovalTimeoutSetting>>, ApiError> {
+    if payload.timeout_seconds.is_some_and(|s| s <= 0) {
+        return Ok(ResponseJson(ApiResponse::error(
+            "Timeout must be a positive number of seconds",
+        )));
+    }
+    let updated =
+        ApprovalTimeoutSetting::update(&deployment.db().pool, setting.id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(updated)))
+}
+
+pub async fn delete_approval_timeout(
+    Extension(setting): Extension<ApprovalTimeoutSetting>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let rows_affected = ApprovalTimeoutSetting::delete(&deployment.db().pool, setting.id).await?;
+    if rows_affected == 0 {
+        Err(ApiError::Database(sqlx::Error::RowNotFound))
+    } else {
+        Ok(ResponseJson(ApiResponse::success(())))
+    }
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let setting_router = Router::new()
+        .route(
+            "/",
+            put(update_approval_timeout).delete(delete_approval_timeout),
+        )
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_approval_timeout_setting_middleware,
+        ));
+
+    let inner = Router::new()
+        .route(
+            "/",
+            get(get_approval_timeouts).post(create_approval_timeout),
+        )
+        .nest("/{setting_id}", setting_router);
+
+    Router::new().nest("/approval-timeouts", inner)
+}
@@ -1,13 +1,17 @@
 use axum::{
-    Json, Router,
-    extract::{Path, State},
-    http::StatusCode,
-    routing::post,
+    Extension, Json, Router,
+    extract::{Path, Query, State},
+    http::{Response, StatusCode},
+    response::Json as ResponseJson,
+    routing::{get, post},
 };
 use db::models::{
+    approval_record::ApprovalRecord,
     execution_process::{ExecutionProcess, ExecutionProcessRunReason},
     executor_session::ExecutorSession,
     task::{Task, TaskStatus},
+    task_attempt::TaskAttempt,
+    task_plan_revision::{TaskPlanRevision, TaskPlanRevisionStatus},
 };
 use deployment::Deployment;
 use executors::{
@@ -16,51 +20,35 @@ use executors::{
     },
     profile::to_default_variant,
 };
-use services::services::container::ContainerService;
-use utils::approvals::{ApprovalResponse, ApprovalStatus};
+use serde::Deserialize;
+use services::services::{
+    approvals::{
+        ApprovalError, ToolContext,
+        webhook::{ApprovalCallback, ApprovalLinkAction},
+    },
+    container::ContainerService,
+    plan_progress,
+};
+use utils::{
+    approvals::{ApprovalRememberScope, ApprovalResponse, ApprovalStatus},
+    response::ApiResponse,
+};
 
-use crate::DeploymentImpl;
+use crate::{DeploymentImpl, error::ApiError};
+
+/// Name recorded as the responder in the approval history
+pub(crate) async fn responder_name(deployment: &DeploymentImpl) -> Option<String> {
+    deployment.config().read().await.github.username.clone()
+}
 
 pub async fn respond_to_approval(
     State(deployment): State<DeploymentImpl>,
     Path(id): Path<String>,
     Json(request): Json<ApprovalResponse>,
 ) -> Result<Json<ApprovalStatus>, StatusCode> {
-    let service = deployment.approvals();
-
-    match service.respond(&deployment.db().pool, &id, request).await {
-        Ok((status, context)) => {
-            deployment
-                .track_if_analytics_allowed(
-                    "approval_responded",
-                    serde_json::json!({
-                        "approval_id": &id,
-                        "status": format!("{:?}", status),
-                        "tool_name": context.tool_name,
-                        "execution_process_id": context.execution_process_id.to_string(),
-                    }),
-                )
-                .await;
-
-            // Handle ExitPlanMode approval: move to dev column and start implementation
-            if context.tool_name == "ExitPlanMode" && matches!(status, ApprovalStatus::Approved) {
-                if let Some(plan) = context.plan {
-                    if let Err(e) = handle_exit_plan_mode_approval(
-                        &deployment,
-                        context.execution_process_id,
-                        plan,
-                    )
-                    .await
-                    {
-                        tracing::error!("Failed to handle ExitPlanMode approval: {:?}", e);
-                    }
-                } else {
-                    tracing::error!("ExitPlanMode approved but no plan found in context");
-                }
-            }
-
-            Ok(Json(status))
-        }
+    let responded_by = responder_name(&deployment).await;
+    match apply_response(&deployment, &id, request, responded_by.as_deref()).await {
+        Ok(status) => Ok(Json(status)),
         Err(e) => {
             tracing::error!("Failed to respond to approval: {:?}", e);
             Err(StatusCode::INTERNAL_SERVER_ERROR)
@@ -68,6 +56,294 @@ pub async fn respond_to_approval(
     }
 }
 
+#[derive(Debug, Deserialize)]
+pub struct ApprovalLinkQuery {
+    action: ApprovalLinkAction,
+    token: String,
+}
+
+/// Landing page of a signed approval link. Opening the link only asks for
+/// confirmation, so link previews in chat tools cannot answer the request.
+pub async fn get_approval_link(
+    State(deployment): State<DeploymentImpl>,
+    Path(id): Path<String>,
+    Query(query): Query<ApprovalLinkQuery>,
+) -> Response<String> {
+    let approvals = deployment.approvals();
+    if let Err(e) = approvals.signer().verify(&id, query.action, &query.token) {
+        return html_response(
+            StatusCode::FORBIDDEN,
+            "Invalid approval link",
+            &e.to_string(),
+        );
+    }
+    let verb = match query.action {
+        ApprovalLinkAction::Approve => "Approve",
+        ApprovalLinkAction::Deny => "Deny",
+        ApprovalLinkAction::Respond => {
+            return html_response(
+                StatusCode::BAD_REQUEST,
+                "Invalid approval link",
+                "This token is for the callback endpoint.",
+            );
+        }
+    };
+    let Some((_, tool_name)) = approvals.unanswered_request(&id) else {
+        return html_response(
+            StatusCode::GONE,
+            "Approval already answered",
+            "This request was answered or has expired.",
+        );
+    };
+    let form = format!(
+        "<form method=\"post\"><button type=\"submit\" style=\"font-size: 1.1rem; padding: 0.5rem 1.5rem;\">{verb}</button></form>"
+    );
+    html_response(
+        StatusCode::OK,
+        &format!("{verb} {}?", escape_html(&tool_name)),
+        &form,
+    )
+}
+
+/// Answer the request of a signed approval link
+pub async fn post_approval_link(
+    State(deployment): State<DeploymentImpl>,
+    Path(id): Path<String>,
+    Query(query): Query<ApprovalLinkQuery>,
+) -> Response<String> {
+    let status = match query.action {
+        ApprovalLinkAction::Approve => ApprovalStatus::Approved,
+        ApprovalLinkAction::Deny => ApprovalStatus::Denied {
+            reason: Some("Denied through an approval link".to_string()),
+        },
+        ApprovalLinkAction::Respond => {
+            return html_response(
+                StatusCode::BAD_REQUEST,
+                "Invalid approval link",
+                "This token is for the callback endpoint.",
+            );
+        }
+    };
+    if let Err(e) = deployment
+        .approvals()
+        .signer()
+        .verify(&id, query.action, &query.token)
+    {
+        return html_response(
+            StatusCode::FORBIDDEN,
+            "Invalid approval link",
+            &e.to_string(),
+        );
+    }
+
+    match respond_from_outside(&deployment, &id, status, None, Some("approval link")).await {
+        Ok(ApprovalStatus::Approved) => {
+            html_response(StatusCode::OK, "Approved", "You can close this page.")
+        }
+        Ok(_) => html_response(StatusCode::OK, "Denied", "You can close this page."),
+        Err(ApprovalError::NotFound | ApprovalError::AlreadyCompleted) => html_response(
+            StatusCode::GONE,
+            "Approval already answered",
+            "This request was answered or has expired.",
+        ),
+        Err(e) => {
+            tracing::error!("Failed to respond to approval through a link: {:?}", e);
+            html_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Something went wrong",
+                "The approval could not be recorded.",
+            )
+        }
+    }
+}
+
+/// Answer an approval from chat tooling, with the token from the webhook payload
+pub async fn approval_callback(
+    State(deployment): State<DeploymentImpl>,
+    Path(id): Path<String>,
+    Json(callback): Json<ApprovalCallback>,
+) -> Result<ResponseJson<ApiResponse<ApprovalStatus>>, ApiError> {
+    if let Err(e) =
+        deployment
+            .approvals()
+            .signer()
+            .verify(&id, ApprovalLinkAction::Respond, &callback.token)
+    {
+        return Err(ApiError::Forbidden(e.to_string()));
+    }
+    if !ApprovalLinkAction::Respond.permits(&callback.status) {
+        return Ok(ResponseJson(ApiResponse::error(
+            "Callbacks can only approve or deny a request",
+        )));
+    }
+
+    let responded_by = callback
+        .responded_by
+        .as_deref()
+        .or(Some("approval callback"));
+    match respond_from_outside(
+        &deployment,
+        &id,
+        callback.status,
+        callback.remember,
+        responded_by,
+    )
+    .await
+    {
+        Ok(status) => Ok(ResponseJson(ApiResponse::success(status))),
+        Err(ApprovalError::NotFound | ApprovalError::AlreadyCompleted) => Ok(ResponseJson(
+            ApiResponse::error("Approval request was already answered or has expired"),
+        )),
+        Err(e) => {
+            tracing::error!("Failed to respond to approval through a callback: {:?}", e);
+            Ok(ResponseJson(ApiResponse::error(
+                "Failed to record the approval decision",
+            )))
+        }
+    }
+}
+
+async fn respond_from_outside(
+    deployment: &DeploymentImpl,
+    id: &str,
+    status: ApprovalStatus,
+    remember: Option<ApprovalRememberScope>,
+    responded_by: Option<&str>,
+) -> Result<ApprovalStatus, ApprovalError> {
+    let (execution_process_id, _) = deployment
+        .approvals()
+        .unanswered_request(id)
+        .ok_or(ApprovalError::NotFound)?;
+    let request = ApprovalResponse {
+        execution_process_id,
+        status,
+        remember,
+    };
+    apply_response(deployment, id, request, responded_by).await
+}
+
+/// Answer an approval and carry out what follows from the decision
+async fn apply_response(
+    deployment: &DeploymentImpl,
+    id: &str,
+    request: ApprovalResponse,
+    responded_by: Option<&str>,
+) -> Result<ApprovalStatus, ApprovalError> {
+    let (status, context) = deployment
+        .approvals()
+        .respond(id, request, responded_by)
+        .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "approval_responded",
+            serde_json::json!({
+                "approval_id": id,
+                "status": format!("{:?}", status),
+                "tool_name": context.tool_name,
+                "execution_process_id": context.execution_process_id.to_string(),
+            }),
+        )
+        .await;
+
+    if context.interrupted {
+        if let Err(e) = send_interrupted_decision(deployment, &context, &status).await {
+            tracing::error!("Failed to send approval decision to the agent: {:?}", e);
+        }
+        return Ok(status);
+    }
+
+    // A denied plan stays in the plan history with the reason as requested changes
+    if context.tool_name == "ExitPlanMode"
+        && let ApprovalStatus::Denied { reason } = &status
+        && let Some(plan) = &context.plan
+        && let Err(e) = record_plan_changes_requested(
+            deployment,
+            context.execution_process_id,
+            plan,
+            reason.as_deref(),
+        )
+        .await
+    {
+        tracing::error!("Failed to record the denied plan: {:?}", e);
+    }
+
+    // Handle ExitPlanMode approval: move to dev column and start implementation
+    if context.tool_name == "ExitPlanMode" && matches!(status, ApprovalStatus::Approved) {
+        if let Some(plan) = context.plan {
+            if let Err(e) =
+                handle_exit_plan_mode_approval(deployment, context.execution_process_id, plan).await
+            {
+                tracing::error!("Failed to handle ExitPlanMode approval: {:?}", e);
+            }
+        } else {
+            tracing::error!("ExitPlanMode approved but no plan found in context");
+        }
+    }
+
+    Ok(status)
+}
+
+fn html_response(status: StatusCode, title: &str, body: &str) -> Response<String> {
+    let body = format!(
+        "<!doctype html><html><head><meta charset=\"utf-8\"><title>{title}</title></head>\
+         <body style=\"font-family: sans-serif; margin: 3rem;\"><h1>{title}</h1>{body}</body></html>"
+    );
+    Response::builder()
+        .status(status)
+        .header("content-type", "text/html; charset=utf-8")
+        .body(body)
+        .unwrap()
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// The agent that asked stopped waiting when the server restarted, so pass the
+/// decision on as a queued follow-up in its session.
+async fn send_interrupted_decision(
+    deployment: &DeploymentImpl,
+    context: &ToolContext,
+    status: &ApprovalStatus,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let ctx =
+        ExecutionProcess::load_context(&deployment.db().pool, context.execution_process_id).await?;
+    let tool_call = format!(
+        "`{}` with input:\n```json\n{}\n```",
+        context.tool_name,
+        serde_json::to_string_pretty(&context.tool_input)?
+    );
+    let prompt = match status {
+        ApprovalStatus::Approved => format!(
+            "You were interrupted while waiting for approval of {tool_call}\nIt has now been approved. Run it again and continue."
+        ),
+        ApprovalStatus::Denied { reason } => format!(
+            "You were interrupted while waiting for approval of {tool_call}\nIt was denied: {}\nContinue without it.",
+            reason.as_deref().unwrap_or("no reason given")
+        ),
+        _ => return Ok(()),
+    };
+    deployment
+        .drafts()
+        .queue_follow_up_prompt(deployment.container(), &ctx.task_attempt, &prompt)
+        .await?;
+    Ok(())
+}
+
+/// Approval history of an attempt, including decisions made by approval rules
+pub async fn get_attempt_approvals(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ApprovalRecord>>>, ApiError> {
+    let records =
+        ApprovalRecord::find_by_task_attempt_id(&deployment.db().pool, task_attempt.id).await?;
+    Ok(ResponseJson(ApiResponse::success(records)))
+}
+
 async fn handle_exit_plan_mode_approval(
     deployment: &DeploymentImpl,
     execution_process_id: uuid::Uuid,
@@ -79,7 +355,11 @@ async fn handle_exit_plan_mode_approval(
     let ctx = ExecutionProcess::load_context(pool, execution_process_id).await?;
 
     // Save the plan to the task
-    Task::update_plan(pool, ctx.task.id, Some(plan.clone())).await?;
+    let revision =
+        TaskPlanRevision::record(pool, ctx.task.id, Some(execution_process_id), &plan).await?;
+    TaskPlanRevision::update_status(pool, revision.id, TaskPlanRevisionStatus::Approved, None)
+        .await?;
+    plan_progress::save_plan_steps(pool, ctx.task.id, &plan).await?;
     tracing::info!("Saved plan for task {}", ctx.task.id);
 
     // Move task from Plan to InProgress (dev column)
@@ -130,6 +410,33 @@ async fn handle_exit_plan_mode_approval(
     Ok(())
 }
 
+/// Save a plan the reviewer sent back, with the changes they asked for
+pub(crate) async fn record_plan_changes_requested(
+    deployment: &DeploymentImpl,
+    execution_process_id: uuid::Uuid,
+    plan: &str,
+    feedback: Option<&str>,
+) -> Result<TaskPlanRevision, sqlx::Error> {
+    let pool = &deployment.db().pool;
+    let ctx = ExecutionProcess::load_context(pool, execution_process_id).await?;
+    let revision =
+        TaskPlanRevision::record(pool, ctx.task.id, Some(execution_process_id), plan).await?;
+    TaskPlanRevision::update_status(
+        pool,
+        revision.id,
+        TaskPlanRevisionStatus::ChangesRequested,
+        feedback,
+    )
+    .await?;
+    Ok(revision)
+}
+
 pub fn router() -> Router<DeploymentImpl> {
-    Router::new().route("/approvals/{id}/respond", post(respond_to_approval))
+    Router::new()
+        .route("/approvals/{id}/respond", post(respond_to_approval))
+        .route(
+            "/approvals/{id}/link",
+            get(get_approval_link).post(post_approval_link),
+        )
+        .route("/approvals/{id}/callback", post(approval_callback))
 }
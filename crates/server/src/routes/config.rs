@@ -120,6 +120,12 @@ async fn update_config(
         ));
     }
 
+    if new_config.approval_timeout_seconds == 0 {
+        return ResponseJson(ApiResponse::error(
+            "Approval timeout must be a positive number of seconds",
+        ));
+    }
+
     // Get old config state before updating
     let old_config = deployment.config().read().await.clone();
 
@@ -0,0 +1,164 @@
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::{get, post, put},
+};
+use db::models::document_template::{
+    CONVERSATION_PACK_ID, CreateDocumentTemplate, CreateDocumentTemplatePack, DocumentTemplate,
+    DocumentTemplatePack, DocumentTemplatePackWithTemplates, UpdateDocumentTemplate,
+    UpdateDocumentTemplatePack,
+};
+use deployment::Deployment;
+use services::services::documents::DocumentError;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// All packs with their templates in pack order
+pub async fn list_packs(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<DocumentTemplatePackWithTemplates>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let templates = DocumentTemplate::find_all(pool).await?;
+    let packs = DocumentTemplatePack::find_all(pool)
+        .await?
+        .into_iter()
+        .map(|pack| {
+            let templates = templates
+                .iter()
+                .filter(|template| template.pack_id == pack.id)
+                .cloned()
+                .collect();
+            DocumentTemplatePackWithTemplates { pack, templates }
+        })
+        .collect();
+    Ok(ResponseJson(ApiResponse::success(packs)))
+}
+
+pub async fn create_pack(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateDocumentTemplatePack>,
+) -> Result<ResponseJson<ApiResponse<DocumentTemplatePack>>, ApiError> {
+    if payload.name.trim().is_empty() {
+        return Ok(ResponseJson(ApiResponse::error("Pack name is required")));
+    }
+    let pack = DocumentTemplatePack::create(&deployment.db().pool, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "document_template_pack_created",
+            serde_json::json!({
+                "pack_id": pack.id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(pack)))
+}
+
+pub async fn update_pack(
+    State(deployment): State<DeploymentImpl>,
+    Path(pack_id): Path<Uuid>,
+    Json(payload): Json<UpdateDocumentTemplatePack>,
+) -> Result<ResponseJson<ApiResponse<DocumentTemplatePack>>, ApiError> {
+    let pool = &deployment.db().pool;
+    if DocumentTemplatePack::find_by_id(pool, pack_id)
+        .await?
+        .is_none()
+    {
+        return Err(DocumentError::TemplateNotFound.into());
+    }
+    let pack = DocumentTemplatePack::update(pool, pack_id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(pack)))
+}
+
+pub async fn delete_pack(
+    State(deployment): State<DeploymentImpl>,
+    Path(pack_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    if pack_id == CONVERSATION_PACK_ID {
+        return Ok(ResponseJson(ApiResponse::error(
+            "The planning conversation pack can't be deleted",
+        )));
+    }
+    let rows_affected = DocumentTemplatePack::delete(&deployment.db().pool, pack_id).await?;
+    if rows_affected == 0 {
+        return Err(DocumentError::TemplateNotFound.into());
+    }
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub async fn create_template(
+    State(deployment): State<DeploymentImpl>,
+    Path(pack_id): Path<Uuid>,
+    Json(payload): Json<CreateDocumentTemplate>,
+) -> Result<ResponseJson<ApiResponse<DocumentTemplate>>, ApiError> {
+    let pool = &deployment.db().pool;
+    if payload.title.trim().is_empty() {
+        return Ok(ResponseJson(ApiResponse::error(
+            "Template title is required",
+        )));
+    }
+    if DocumentTemplatePack::find_by_id(pool, pack_id)
+        .await?
+        .is_none()
+    {
+        return Err(DocumentError::TemplateNotFound.into());
+    }
+    let template = DocumentTemplate::create(pool, pack_id, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "document_template_created",
+            serde_json::json!({
+                "template_id": template.id.to_string(),
+                "pack_id": pack_id.to_string(),
+                "category": template.category,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(template)))
+}
+
+pub async fn update_template(
+    State(deployment): State<DeploymentImpl>,
+    Path(template_id): Path<Uuid>,
+    Json(payload): Json<UpdateDocumentTemplate>,
+) -> Result<ResponseJson<ApiResponse<DocumentTemplate>>, ApiError> {
+    let pool = &deployment.db().pool;
+    if DocumentTemplate::find_by_id(pool, template_id)
+        .await?
+        .is_none()
+    {
+        return Err(DocumentError::TemplateNotFound.into());
+    }
+    let template = DocumentTemplate::update(pool, template_id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(template)))
+}
+
+pub async fn delete_template(
+    State(deployment): State<DeploymentImpl>,
+    Path(template_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let rows_affected = DocumentTemplate::delete(&deployment.db().pool, template_id).await?;
+    if rows_affected == 0 {
+        return Err(DocumentError::TemplateNotFound.into());
+    }
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    let inner = Router::new()
+        .route("/packs", get(list_packs).post(create_pack))
+        .route("/packs/{pack_id}", put(update_pack).delete(delete_pack))
+        .route("/packs/{pack_id}/templates", post(create_template))
+        .route(
+            "/{template_id}",
+            put(update_template).delete(delete_template),
+        );
+
+    Router::new().nest("/document-templates", inner)
+}
@@ -0,0 +1,202 @@
+//! Streamable HTTP endpoint of the MCP task server, for agents and IDEs that cannot
+//! spawn the stdio binary, such as remote or containerized ones.
+//!
+//! Clients authenticate with `Authorization: Bearer <token>`. The token comes from
+//! `ANYON_MCP_TOKEN`, or is generated on first start and kept in the asset directory.
+
+use std::{
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    sync::Arc,
+};
+
+use axum::{
+    Router,
+    extract::{Request, State},
+    http::{StatusCode, header},
+    middleware::{Next, from_fn_with_state},
+    response::{IntoResponse, Response},
+};
+use rmcp::transport::streamable_http_server::{
+    StreamableHttpServerConfig, StreamableHttpService, session::local::LocalSessionManager,
+};
+use utils::assets::mcp_token_path;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, mcp::task_server::TaskServer};
+
+const TOKEN_ENV: &str = "ANYON_MCP_TOKEN";
+
+/// Token MCP clients must present, created on first use
+fn load_or_create_token() -> String {
+    if let Ok(token) = std::env::var(TOKEN_ENV)
+        && !token.trim().is_empty()
+    {
+        return token.trim().to_string();
+    }
+
+    let path = mcp_token_path();
+    if let Ok(token) = std::fs::read_to_string(&path)
+        && !token.trim().is_empty()
+    {
+        return token.trim().to_string();
+    }
+
+    let token = format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple());
+    if let Err(e) = write_token(&path, &token) {
+        tracing::warn!(
+            "Failed to store MCP token at {:?}, it will change after a restart: {}",
+            path,
+            e
+        );
+    }
+    token
+}
+
+fn write_token(path: &std::path::Path, token: &str) -> std::io::Result<()> {
+    #[cfg(unix)]
+    {
+        use std::{io::Write, os::unix::fs::OpenOptionsExt};
+        let mut file = std::fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .mode(0o600)
+            .open(path)?;
+        file.write_all(token.as_bytes())
+    }
+    #[cfg(not(unix))]
+    {
+        std::fs::write(path, token)
+    }
+}
+
+async fn require_token(State(token): State<Arc<str>>, request: Request, next: Next) -> Response {
+    let authorized = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|provided| tokens_match(provided.trim(), &token));
+    if !authorized {
+        return (
+            StatusCode::UNAUTHORIZED,
+            [(header::WWW_AUTHENTICATE, "Bearer")],
+            "Missing or invalid MCP token",
+        )
+            .into_response();
+    }
+    next.run(request).await
+}
+
+/// Compare without returning early, so response times do not leak the token
+fn tokens_match(provided: &str, expected: &str) -> bool {
+    provided.len() == expected.len()
+        && provided
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
+
+/// Where the task server reaches this server's REST API. A server bound to all
+/// interfaces is reached over the loopback address of the same IP version.
+pub fn backend_url(local_addr: SocketAddr) -> String {
+    let ip = match local_addr.ip() {
+        IpAddr::V4(ip) if ip.is_unspecified() => IpAddr::V4(Ipv4Addr::LOCALHOST),
+        IpAddr::V6(ip) if ip.is_unspecified() => IpAddr::V6(Ipv6Addr::LOCALHOST),
+        ip => ip,
+    };
+    format!("http://{}", SocketAddr::new(ip, local_addr.port()))
+}
+
+/// `backend_url` is where the task server reaches this server's REST API
+pub fn router(backend_url: &str) -> Router<DeploymentImpl> {
+    let token: Arc<str> = load_or_create_token().into();
+    let backend_url = backend_url.to_string();
+    let service = StreamableHttpService::new(
+        move || Ok(TaskServer::new(&backend_url).without_context()),
+        Arc::new(LocalSessionManager::default()),
+        StreamableHttpServerConfig::default(),
+    );
+
+    Router::new()
+        .nest_service("/mcp", service)
+        .layer(from_fn_with_state(token, require_token))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::routing::get;
+
+    use super::*;
+
+    /// Status of a request to a route behind the token check, expecting `secret`
+    async fn status_with(authorization: Option<&str>) -> StatusCode {
+        let router = Router::new()
+            .route("/mcp", get(|| async { "ok" }))
+            .layer(from_fn_with_state(
+                Arc::<str>::from("secret"),
+                require_token,
+            ));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+        let mut request = reqwest::Client::new().get(format!("http://{addr}/mcp"));
+        if let Some(value) = authorization {
+            request = request.header(header::AUTHORIZATION, value);
+        }
+        request.send().await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn requests_with_the_token_pass() {
+        assert_eq!(status_with(Some("Bearer secret")).await, StatusCode::OK);
+        assert_eq!(status_with(Some("Bearer  secret ")).await, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn requests_without_a_valid_token_are_rejected() {
+        for authorization in [
+            None,
+            Some("secret"),
+            Some("Basic secret"),
+            Some("Bearer wrong!"),
+            Some("Bearer secret-and-more"),
+        ] {
+            assert_eq!(
+                status_with(authorization).await,
+                StatusCode::UNAUTHORIZED,
+                "{authorization:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn tokens_match_only_identical_tokens() {
+        assert!(tokens_match("abc123", "abc123"));
+        assert!(!tokens_match("abc124", "abc123"));
+        assert!(!tokens_match("abc12", "abc123"));
+        assert!(!tokens_match("", "abc123"));
+    }
+
+    #[test]
+    fn backend_url_reaches_wildcard_binds_over_loopback() {
+        assert_eq!(
+            backend_url("0.0.0.0:8080".parse().unwrap()),
+            "http://127.0.0.1:8080"
+        );
+        assert_eq!(
+            backend_url("[::]:8080".parse().unwrap()),
+            "http://[::1]:8080"
+        );
+        assert_eq!(
+            backend_url("[fe80::1]:8080".parse().unwrap()),
+            "http://[fe80::1]:8080"
+        );
+        assert_eq!(
+            backend_url("192.168.1.5:3000".parse().unwrap()),
+            "http://192.168.1.5:3000"
+        );
+    }
+}
@@ -5,9 +5,12 @@ use axum::{
 
 use crate::DeploymentImpl;
 
+pub mod approval_rules;
+pub mod approval_timeouts;
 pub mod approvals;
 pub mod config;
 pub mod containers;
+pub mod document_templates;
 pub mod documents;
 pub mod filesystem;
 // pub mod github;
@@ -17,21 +20,27 @@ pub mod execution_processes;
 pub mod frontend;
 pub mod health;
 pub mod images;
+pub mod mcp;
 pub mod oauth;
 pub mod organizations;
 pub mod projects;
 pub mod shared_tasks;
 pub mod tags;
 pub mod task_attempts;
+pub mod task_breakdowns;
 pub mod tasks;
 
-pub fn router(deployment: DeploymentImpl) -> IntoMakeService<Router> {
+/// `backend_url` is the address this server listens on, used by the MCP endpoint to
+/// call back into the REST API
+pub fn router(deployment: DeploymentImpl, backend_url: &str) -> IntoMakeService<Router> {
     // Create routers with different middleware layers
     let base_routes = Router::new()
         .route("/health", get(health::health_check))
         .merge(config::router())
         .merge(containers::router(&deployment))
         .merge(documents::router(&deployment))
+        .merge(document_templates::router())
+        .merge(task_breakdowns::router(&deployment))
         .merge(projects::router(&deployment))
         .merge(drafts::router(&deployment))
         .merge(tasks::router(&deployment))
@@ -44,6 +53,9 @@ pub fn router(deployment: DeploymentImpl) -> IntoMakeService<Router> {
         .merge(filesystem::router())
         .merge(events::router(&deployment))
         .merge(approvals::router())
+        .merge(approval_rules::router(&deployment))
+        .merge(approval_timeouts::router(&deployment))
+        .merge(mcp::router(backend_url))
         .nest("/images", images::routes())
         .with_state(deployment);
 
@@ -10,13 +10,17 @@ use axum::{
 };
 use db::models::{
     project::{CreateProject, Project, ProjectError, SearchMatchType, SearchResult, UpdateProject},
+    project_commit_settings::{ProjectCommitSettings, UpdateProjectCommitSettings},
+    project_mcp_servers::{ProjectMcpServers, UpdateProjectMcpServers},
     task::Task,
 };
 use deployment::Deployment;
+use executors::{executors::BaseCodingAgent, profile::ExecutorConfigs};
 use ignore::WalkBuilder;
 use serde::{Deserialize, Serialize};
 use services::services::{
     agent_bundle::{AgentBundleError, AgentBundleService, AgentBundleSettings},
+    documents::DocumentService,
     file_ranker::FileRanker,
     file_search_cache::{CacheError, SearchMode, SearchQuery},
     git::GitBranch,
@@ -76,6 +80,120 @@ pub async fn get_project_branches(
     Ok(ResponseJson(ApiResponse::success(branches)))
 }
 
+pub async fn get_project_commit_settings(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Option<ProjectCommitSettings>>>, ApiError> {
+    let settings =
+        ProjectCommitSettings::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(settings)))
+}
+
+pub async fn update_project_commit_settings(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpdateProjectCommitSettings>,
+) -> Result<ResponseJson<ApiResponse<ProjectCommitSettings>>, ApiError> {
+    let is_set = |value: &Option<String>| value.as_deref().is_some_and(|v| !v.trim().is_empty());
+    if is_set(&payload.author_name) != is_set(&payload.author_email) {
+        return Ok(ResponseJson(ApiResponse::error(
+            "Commit author name and email must be set together",
+        )));
+    }
+
+    let settings =
+        ProjectCommitSettings::upsert(&deployment.db().pool, project.id, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_commit_settings_updated",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "signing": payload.signing,
+                "custom_author": is_set(&payload.author_name),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(settings)))
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct ProjectMcpServersResponse {
+    pub settings: Option<ProjectMcpServers>,
+    /// Configured coding agents that cannot take MCP servers per execution, and so
+    /// run without the project's servers
+    pub unsupported_executors: Vec<BaseCodingAgent>,
+}
+
+impl ProjectMcpServersResponse {
+    fn new(settings: Option<ProjectMcpServers>) -> Self {
+        let mut unsupported_executors: Vec<BaseCodingAgent> = ExecutorConfigs::get_cached()
+            .executors
+            .into_keys()
+            .filter(|executor| !executor.supports_execution_mcp_servers())
+            .collect();
+        unsupported_executors.sort_by_key(|executor| executor.to_string());
+        Self {
+            settings,
+            unsupported_executors,
+        }
+    }
+}
+
+pub async fn get_project_mcp_servers(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<ProjectMcpServersResponse>>, ApiError> {
+    let settings = ProjectMcpServers::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(
+        ProjectMcpServersResponse::new(settings),
+    )))
+}
+
+/// Replace the project's MCP servers. Each is either a stdio server with a `command`
+/// or an HTTP server with a `url`.
+pub async fn update_project_mcp_servers(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpdateProjectMcpServers>,
+) -> Result<ResponseJson<ApiResponse<ProjectMcpServersResponse>>, ApiError> {
+    for (name, server) in &payload.servers {
+        let has_string = |key: &str| {
+            server
+                .get(key)
+                .and_then(|v| v.as_str())
+                .is_some_and(|v| !v.trim().is_empty())
+        };
+        if name.trim().is_empty() || name == "meta" {
+            return Ok(ResponseJson(ApiResponse::error(&format!(
+                "Invalid MCP server name '{name}'"
+            ))));
+        }
+        if !server.is_object() || !(has_string("command") || has_string("url")) {
+            return Ok(ResponseJson(ApiResponse::error(&format!(
+                "MCP server '{name}' needs a `command` or a `url`"
+            ))));
+        }
+    }
+
+    let settings = ProjectMcpServers::upsert(&deployment.db().pool, project.id, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_mcp_servers_updated",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "server_count": payload.servers.len(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(
+        ProjectMcpServersResponse::new(Some(settings)),
+    )))
+}
+
 pub async fn import_agent_bundle(
     Extension(project): Extension<Project>,
     Json(payload): Json<ImportAgentBundleRequest>,
@@ -254,6 +372,7 @@ pub async fn create_project(
         cleanup_script,
         copy_files,
         use_existing_repo,
+        document_template_pack_id,
     } = payload;
     tracing::debug!("Creating project '{}'", name);
 
@@ -340,12 +459,26 @@ pub async fn create_project(
             dev_script,
             cleanup_script,
             copy_files,
+            document_template_pack_id,
         },
         id,
     )
     .await
     {
         Ok(project) => {
+            if let Some(pack_id) = document_template_pack_id
+                && let Err(e) =
+                    DocumentService::apply_template_pack(&deployment.db().pool, &project, pack_id)
+                        .await
+            {
+                tracing::error!(
+                    "Failed to create documents of template pack {} for project {}: {}",
+                    pack_id,
+                    project.id,
+                    e
+                );
+            }
+
             // Track project creation event
             deployment
                 .track_if_analytics_allowed(
@@ -355,6 +488,7 @@ pub async fn create_project(
                         "use_existing_repo": use_existing_repo,
                         "has_setup_script": project.setup_script.is_some(),
                         "has_dev_script": project.dev_script.is_some(),
+                        "has_document_template_pack": document_template_pack_id.is_some(),
                         "trigger": "manual",
                     }),
                 )
@@ -709,6 +843,14 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         )
         .route("/remote/members", get(get_project_remote_members))
         .route("/branches", get(get_project_branches))
+        .route(
+            "/commit-settings",
+            get(get_project_commit_settings).put(update_project_commit_settings),
+        )
+        .route(
+            "/mcp-servers",
+            get(get_project_mcp_servers).put(update_project_mcp_servers),
+        )
         .route("/search", get(search_project_files))
         .route("/open-editor", post(open_project_in_editor))
         .route("/agent-bundle/import", post(import_agent_bundle))
@@ -1,6 +1,9 @@
 pub mod cursor_setup;
 pub mod drafts;
 pub mod gh_cli_setup;
+pub mod inspect;
+pub mod pr_checks;
+pub mod review_comments;
 pub mod util;
 
 use axum::{
@@ -36,8 +39,12 @@ use git2::BranchType;
 use serde::{Deserialize, Serialize};
 use services::services::{
     container::ContainerService,
-    git::{ConflictOp, GitCliError, GitServiceError, WorktreeResetOptions},
+    git::{
+        CommitOptions, ConflictOp, DiffSelection, DiffTarget, GitCliError, GitServiceError,
+        WorktreeResetOptions,
+    },
     github::{CreatePrRequest, GitHubService, GitHubServiceError},
+    stacked_branches::StackedBranchService,
 };
 use sqlx::Error as SqlxError;
 use ts_rs::TS;
@@ -48,9 +55,12 @@ use crate::{
     DeploymentImpl,
     error::ApiError,
     middleware::load_task_attempt_middleware,
-    routes::task_attempts::{
-        gh_cli_setup::GhCliSetupError,
-        util::{ensure_worktree_path, handle_images_for_prompt},
+    routes::{
+        approvals::get_attempt_approvals,
+        task_attempts::{
+            gh_cli_setup::GhCliSetupError,
+            util::{ensure_worktree_path, handle_images_for_prompt},
+        },
     },
 };
 
@@ -68,6 +78,30 @@ pub enum GitOperationError {
     RebaseInProgress,
 }
 
+#[derive(Debug, Deserialize, Serialize, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[ts(tag = "type", rename_all = "snake_case")]
+pub enum ApplyChangesTarget {
+    /// The attempt's own target branch
+    BaseBranch,
+    /// Another attempt's worktree in the same project
+    Attempt { attempt_id: Uuid },
+}
+
+#[derive(Debug, Deserialize, Serialize, TS)]
+pub struct ApplyChangesRequest {
+    /// Files (and optionally hunks) from the attempt's diff to apply
+    pub files: Vec<DiffSelection>,
+    pub target: ApplyChangesTarget,
+    pub commit_message: Option<String>,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct ApplyChangesResponse {
+    /// Commit created on the target branch
+    pub commit_sha: String,
+}
+
 #[derive(Debug, Deserialize, Serialize, TS)]
 pub struct CreateGitHubPrRequest {
     pub title: String,
@@ -108,6 +142,8 @@ pub struct CreateTaskAttemptBody {
     /// Executor profile specification
     pub executor_profile_id: ExecutorProfileId,
     pub base_branch: String,
+    /// Stack the new attempt on another attempt's branch instead of `base_branch`
+    pub stacked_on_attempt_id: Option<Uuid>,
 }
 
 impl CreateTaskAttemptBody {
@@ -135,18 +171,33 @@ pub async fn create_task_attempt(
         .await?
         .ok_or(SqlxError::RowNotFound)?;
 
+    let base_branch = match payload.stacked_on_attempt_id {
+        Some(parent_id) => {
+            StackedBranchService::new(deployment.db().clone(), deployment.git().clone())
+                .stack_parent(task.project_id, parent_id)
+                .await?
+                .branch
+        }
+        None => payload.base_branch.clone(),
+    };
+
     let attempt_id = Uuid::new_v4();
-    let git_branch_name = deployment
-        .container()
-        .git_branch_from_task_attempt(&attempt_id, &task.title)
-        .await;
+    let git_branch_name = if task.kind.creates_branch() {
+        deployment
+            .container()
+            .git_branch_from_task_attempt(&attempt_id, &task.title)
+            .await
+    } else {
+        base_branch.clone()
+    };
 
     let task_attempt = TaskAttempt::create(
         &deployment.db().pool,
         &CreateTaskAttempt {
             executor: executor_profile_id.executor,
-            base_branch: payload.base_branch.clone(),
+            base_branch,
             branch: git_branch_name.clone(),
+            stacked_on_attempt_id: payload.stacked_on_attempt_id,
         },
         attempt_id,
         payload.task_id,
@@ -169,6 +220,7 @@ pub async fn create_task_attempt(
                 "variant": &executor_profile_id.variant,
                 "executor": &executor_profile_id.executor,
                 "attempt_id": task_attempt.id.to_string(),
+                "stacked": task_attempt.stacked_on_attempt_id.is_some(),
             }),
         )
         .await;
@@ -207,7 +259,7 @@ pub async fn run_agent_setup(
     Ok(ResponseJson(ApiResponse::success(RunAgentSetupResponse {})))
 }
 
-#[derive(Debug, Deserialize, TS)]
+#[derive(Debug, Serialize, Deserialize, TS)]
 pub struct CreateFollowUpAttempt {
     pub prompt: String,
     pub variant: Option<String>,
@@ -518,12 +570,18 @@ pub async fn merge_task_attempt(
         commit_message.push_str(description);
     }
 
+    let old_branch_head = deployment
+        .git()
+        .get_branch_oid(&ctx.project.git_repo_path, &ctx.task_attempt.branch)?;
+
+    let commit_options = CommitOptions::for_project(pool, ctx.project.id).await?;
     let merge_commit_id = deployment.git().merge_changes(
         &ctx.project.git_repo_path,
         worktree_path,
         &ctx.task_attempt.branch,
         &ctx.task_attempt.target_branch,
         &commit_message,
+        &commit_options,
     )?;
 
     Merge::create_direct(
@@ -535,6 +593,28 @@ pub async fn merge_task_attempt(
     .await?;
     Task::update_status(pool, ctx.task.id, TaskStatus::Done).await?;
 
+    // Attempts stacked on this one now build directly on the merged target
+    match StackedBranchService::new(deployment.db().clone(), deployment.git().clone())
+        .promote_dependents_after_merge(&ctx.task_attempt, &old_branch_head)
+        .await
+    {
+        Ok(outcomes) if !outcomes.is_empty() => {
+            tracing::info!(
+                "Restacked {} attempts after merging attempt {}",
+                outcomes.len(),
+                task_attempt.id
+            );
+        }
+        Ok(_) => {}
+        Err(e) => {
+            tracing::error!(
+                "Failed to restack attempts stacked on {}: {}",
+                task_attempt.id,
+                e
+            );
+        }
+    }
+
     // Stop any running dev servers for this task attempt
     let dev_servers =
         ExecutionProcess::find_running_dev_servers_by_task_attempt(pool, task_attempt.id).await?;
@@ -993,6 +1073,9 @@ pub async fn change_target_branch(
                 &new_target_branch,
             )
             .await?;
+            StackedBranchService::new(deployment.db().clone(), deployment.git().clone())
+                .detach_if_retargeted(&task_attempt, &new_target_branch)
+                .await?;
         }
         false => {
             return Ok(ResponseJson(ApiResponse::error(
@@ -1159,6 +1242,9 @@ pub async fn rebase_task_attempt(
                 &new_base_branch,
             )
             .await?;
+            StackedBranchService::new(deployment.db().clone(), deployment.git().clone())
+                .detach_if_retargeted(&task_attempt, &new_base_branch)
+                .await?;
         }
         false => {
             return Ok(ResponseJson(ApiResponse::error(
@@ -1174,12 +1260,18 @@ pub async fn rebase_task_attempt(
     let worktree_path_buf = ensure_worktree_path(&deployment, &task_attempt).await?;
     let worktree_path = worktree_path_buf.as_path();
 
+    let old_branch_head = deployment
+        .git()
+        .get_branch_oid(&ctx.project.git_repo_path, &task_attempt.branch)?;
+
+    let commit_options = CommitOptions::for_project(pool, ctx.project.id).await?;
     let result = deployment.git().rebase_branch(
         &ctx.project.git_repo_path,
         worktree_path,
         &new_base_branch,
         &old_base_branch,
         &task_attempt.branch.clone(),
+        &commit_options,
     );
     if let Err(e) = result {
         use services::services::git::GitServiceError;
@@ -1203,6 +1295,18 @@ pub async fn rebase_task_attempt(
         };
     }
 
+    // Carry attempts stacked on this branch along with the rebase
+    if let Err(e) = StackedBranchService::new(deployment.db().clone(), deployment.git().clone())
+        .restack_dependents(&task_attempt, &old_branch_head)
+        .await
+    {
+        tracing::error!(
+            "Failed to restack attempts stacked on {}: {}",
+            task_attempt.id,
+            e
+        );
+    }
+
     deployment
         .track_if_analytics_allowed(
             "task_attempt_rebased",
@@ -1231,6 +1335,129 @@ pub async fn abort_conflicts_task_attempt(
     Ok(ResponseJson(ApiResponse::success(())))
 }
 
+#[axum::debug_handler]
+pub async fn apply_task_attempt_changes(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<ApplyChangesRequest>,
+) -> Result<ResponseJson<ApiResponse<ApplyChangesResponse, GitOperationError>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let task = task_attempt
+        .parent_task(pool)
+        .await?
+        .ok_or(ApiError::TaskAttempt(TaskAttemptError::TaskNotFound))?;
+    let ctx = TaskAttempt::load_context(pool, task_attempt.id, task.id, task.project_id).await?;
+
+    let worktree_path_buf = ensure_worktree_path(&deployment, &task_attempt).await?;
+    let worktree_path = worktree_path_buf.as_path();
+
+    // Same diff the attempt's Diffs tab shows: worktree vs merge-base with the target
+    let base_commit = deployment.git().get_base_commit(
+        &ctx.project.git_repo_path,
+        &task_attempt.branch,
+        &task_attempt.target_branch,
+    )?;
+    let paths: Vec<&str> = payload.files.iter().map(|f| f.path.as_str()).collect();
+    let diffs = deployment.git().get_diffs(
+        DiffTarget::Worktree {
+            worktree_path,
+            base_commit: &base_commit,
+        },
+        Some(&paths),
+    )?;
+
+    let commit_message = payload
+        .commit_message
+        .filter(|m| !m.trim().is_empty())
+        .unwrap_or_else(|| format!("{} (partial)", ctx.task.title));
+    let commit_options = CommitOptions::for_project(pool, ctx.project.id).await?;
+    let commit = match deployment.git().create_partial_commit(
+        worktree_path,
+        &base_commit,
+        &diffs,
+        &payload.files,
+        &commit_message,
+        &commit_options,
+    ) {
+        Ok(commit) => commit,
+        Err(GitServiceError::InvalidSelection(msg)) => {
+            return Ok(ResponseJson(ApiResponse::error(&msg)));
+        }
+        Err(e) => return Err(ApiError::GitService(e)),
+    };
+
+    let result = match &payload.target {
+        ApplyChangesTarget::BaseBranch => deployment.git().cherry_pick_onto_branch(
+            &ctx.project.git_repo_path,
+            &task_attempt.target_branch,
+            &commit,
+            &commit_options,
+        ),
+        ApplyChangesTarget::Attempt { attempt_id } => {
+            if *attempt_id == task_attempt.id {
+                return Ok(ResponseJson(ApiResponse::error(
+                    "Cannot apply an attempt's changes onto itself",
+                )));
+            }
+            let Some(target_attempt) = TaskAttempt::find_by_id(pool, *attempt_id).await? else {
+                return Ok(ResponseJson(ApiResponse::error("Target attempt not found")));
+            };
+            let target_task = target_attempt
+                .parent_task(pool)
+                .await?
+                .ok_or(ApiError::TaskAttempt(TaskAttemptError::TaskNotFound))?;
+            if target_task.project_id != task.project_id {
+                return Ok(ResponseJson(ApiResponse::error(
+                    "Target attempt belongs to a different project",
+                )));
+            }
+            let target_worktree = ensure_worktree_path(&deployment, &target_attempt).await?;
+            deployment
+                .git()
+                .cherry_pick_into_worktree(&target_worktree, &commit, &commit_options)
+        }
+    };
+
+    let commit_sha = match result {
+        Ok(sha) => sha,
+        Err(GitServiceError::MergeConflicts(message)) => {
+            return Ok(ResponseJson(ApiResponse::error_with_data(
+                GitOperationError::MergeConflicts {
+                    message,
+                    op: ConflictOp::CherryPick,
+                },
+            )));
+        }
+        Err(GitServiceError::RebaseInProgress) => {
+            return Ok(ResponseJson(ApiResponse::error_with_data(
+                GitOperationError::RebaseInProgress,
+            )));
+        }
+        Err(e) => return Err(ApiError::GitService(e)),
+    };
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_attempt_changes_applied",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "project_id": ctx.project.id.to_string(),
+                "attempt_id": task_attempt.id.to_string(),
+                "files": payload.files.len(),
+                "partial_files": payload.files.iter().filter(|f| f.hunks.is_some()).count(),
+                "target": match payload.target {
+                    ApplyChangesTarget::BaseBranch => "base_branch",
+                    ApplyChangesTarget::Attempt { .. } => "attempt",
+                },
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(ApplyChangesResponse {
+        commit_sha,
+    })))
+}
+
 #[axum::debug_handler]
 pub async fn start_dev_server(
     Extension(task_attempt): Extension<TaskAttempt>,
@@ -1351,6 +1578,28 @@ pub async fn get_task_attempt_children(
     }
 }
 
+#[derive(Debug, Serialize, TS)]
+pub struct AttemptStack {
+    /// Attempts this attempt is stacked on, closest first
+    pub ancestors: Vec<TaskAttempt>,
+    /// Attempts stacked directly on this attempt's branch
+    pub dependents: Vec<TaskAttempt>,
+}
+
+pub async fn get_task_attempt_stack(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<AttemptStack>>, ApiError> {
+    let stacks = StackedBranchService::new(deployment.db().clone(), deployment.git().clone());
+    let ancestors = stacks.ancestors(&task_attempt).await?;
+    let dependents = stacks.dependents(&task_attempt).await?;
+
+    Ok(ResponseJson(ApiResponse::success(AttemptStack {
+        ancestors,
+        dependents,
+    })))
+}
+
 pub async fn stop_task_attempt_execution(
     Extension(task_attempt): Extension<TaskAttempt>,
     State(deployment): State<DeploymentImpl>,
@@ -1528,14 +1777,37 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         .route("/start-dev-server", post(start_dev_server))
         .route("/branch-status", get(get_task_attempt_branch_status))
         .route("/diff/ws", get(stream_task_attempt_diff_ws))
+        .route("/diff", get(inspect::get_diff_summary))
+        .route("/diff/file", get(inspect::get_file_diff))
+        .route("/conversation", get(inspect::get_conversation))
         .route("/merge", post(merge_task_attempt))
         .route("/push", post(push_task_attempt_branch))
         .route("/rebase", post(rebase_task_attempt))
         .route("/conflicts/abort", post(abort_conflicts_task_attempt))
+        .route("/apply-changes", post(apply_task_attempt_changes))
         .route("/pr", post(create_github_pr))
         .route("/pr/attach", post(attach_existing_pr))
+        .route("/pr/ws", get(pr_checks::stream_pr_ws))
+        .route(
+            "/pr/checks/send-failures",
+            post(pr_checks::send_failed_checks),
+        )
+        .route(
+            "/review-comments",
+            get(review_comments::get_review_comments),
+        )
+        .route(
+            "/review-comments/send",
+            post(review_comments::send_review_comments),
+        )
+        .route(
+            "/review-comments/dismiss",
+            post(review_comments::dismiss_review_comments),
+        )
+        .route("/approvals", get(get_attempt_approvals))
         .route("/open-editor", post(open_task_attempt_in_editor))
         .route("/children", get(get_task_attempt_children))
+        .route("/stack", get(get_task_attempt_stack))
         .route("/stop", post(stop_task_attempt_execution))
         .route("/change-target-branch", post(change_target_branch))
         .route("/rename-branch", post(rename_branch))
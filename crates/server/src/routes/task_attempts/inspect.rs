@@ -0,0 +1,336 @@
+//! Point-in-time snapshots of an attempt's conversation and diff, for clients that
+//! cannot follow the WebSocket streams, such as the MCP task server.
+
+use axum::{
+    Extension,
+    extract::{Query, State},
+    response::Json as ResponseJson,
+};
+use db::models::{
+    execution_process::{ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus},
+    task_attempt::{TaskAttempt, TaskAttemptError},
+};
+use deployment::Deployment;
+use executors::logs::NormalizedEntry;
+use serde::{Deserialize, Serialize};
+use services::services::{
+    container::ContainerService,
+    git::{DiffTarget, GitService},
+};
+use ts_rs::TS;
+use utils::{
+    diff::{Diff, DiffChangeKind, compute_line_change_counts, create_unified_diff},
+    response::ApiResponse,
+};
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, routes::task_attempts::util::ensure_worktree_path};
+
+const DEFAULT_CONVERSATION_PAGE: usize = 50;
+
+#[derive(Debug, Deserialize)]
+pub struct ConversationQuery {
+    /// Index of the first entry to return
+    pub offset: Option<usize>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, TS)]
+pub struct AttemptConversationEntry {
+    pub execution_process_id: Uuid,
+    pub entry: NormalizedEntry,
+}
+
+#[derive(Debug, Serialize, Deserialize, TS)]
+pub struct AttemptConversation {
+    pub entries: Vec<AttemptConversationEntry>,
+    /// Number of entries across all coding agent runs of the attempt
+    pub total: usize,
+    pub offset: usize,
+    /// Whether a coding agent run of the attempt is still in progress
+    pub running: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FileDiffQuery {
+    pub path: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, TS)]
+pub struct DiffFileSummary {
+    pub path: String,
+    pub change: DiffChangeKind,
+    pub additions: usize,
+    pub deletions: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, TS)]
+pub struct FileDiff {
+    pub path: String,
+    pub change: DiffChangeKind,
+    /// Unified diff of the file; `None` for binary files
+    pub unified_diff: Option<String>,
+}
+
+/// Normalized conversation of the attempt's coding agent runs, oldest first
+pub async fn get_conversation(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<ConversationQuery>,
+) -> Result<ResponseJson<ApiResponse<AttemptConversation>>, ApiError> {
+    let processes =
+        ExecutionProcess::find_by_task_attempt_id(&deployment.db().pool, task_attempt.id, false)
+            .await?;
+
+    let mut entries = Vec::new();
+    let mut running = false;
+    for process in processes
+        .iter()
+        .filter(|p| p.run_reason == ExecutionProcessRunReason::CodingAgent)
+    {
+        running |= process.status == ExecutionProcessStatus::Running;
+        let Some(process_entries) = deployment.container().normalized_entries(&process.id).await
+        else {
+            continue;
+        };
+        entries.extend(
+            process_entries
+                .into_iter()
+                .map(|entry| AttemptConversationEntry {
+                    execution_process_id: process.id,
+                    entry,
+                }),
+        );
+    }
+
+    Ok(ResponseJson(ApiResponse::success(
+        AttemptConversation::page(entries, &query, running),
+    )))
+}
+
+impl AttemptConversation {
+    fn page(
+        entries: Vec<AttemptConversationEntry>,
+        query: &ConversationQuery,
+        running: bool,
+    ) -> Self {
+        let total = entries.len();
+        let offset = query.offset.unwrap_or(0).min(total);
+        let limit = query.limit.unwrap_or(DEFAULT_CONVERSATION_PAGE);
+        Self {
+            entries: entries.into_iter().skip(offset).take(limit).collect(),
+            total,
+            offset,
+            running,
+        }
+    }
+}
+
+/// Changed files of the attempt's worktree against the merge-base with its target branch
+pub async fn get_diff_summary(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<DiffFileSummary>>>, ApiError> {
+    let diffs = attempt_diffs(&deployment, &task_attempt, None).await?;
+    let summaries = diffs.into_iter().map(DiffFileSummary::from_diff).collect();
+    Ok(ResponseJson(ApiResponse::success(summaries)))
+}
+
+impl DiffFileSummary {
+    fn from_diff(diff: Diff) -> Self {
+        // Omitted contents come with precomputed counts
+        let (additions, deletions) = match (diff.additions, diff.deletions) {
+            (Some(additions), Some(deletions)) => (additions, deletions),
+            _ => compute_line_change_counts(
+                diff.old_content.as_deref().unwrap_or(""),
+                diff.new_content.as_deref().unwrap_or(""),
+            ),
+        };
+        Self {
+            path: GitService::diff_path(&diff),
+            change: diff.change,
+            additions,
+            deletions,
+        }
+    }
+}
+
+pub async fn get_file_diff(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<FileDiffQuery>,
+) -> Result<ResponseJson<ApiResponse<FileDiff>>, ApiError> {
+    let diffs = attempt_diffs(&deployment, &task_attempt, Some(&query.path)).await?;
+    let Some(diff) = diffs
+        .into_iter()
+        .find(|diff| GitService::diff_path(diff) == query.path)
+    else {
+        return Ok(ResponseJson(ApiResponse::error(
+            "File has no changes in this attempt",
+        )));
+    };
+
+    Ok(ResponseJson(ApiResponse::success(FileDiff::from_diff(
+        query.path, diff,
+    ))))
+}
+
+impl FileDiff {
+    fn from_diff(path: String, diff: Diff) -> Self {
+        let unified_diff = match (&diff.old_content, &diff.new_content) {
+            (None, None) => None,
+            (old, new) => Some(create_unified_diff(
+                &path,
+                old.as_deref().unwrap_or(""),
+                new.as_deref().unwrap_or(""),
+            )),
+        };
+        Self {
+            path,
+            change: diff.change,
+            unified_diff,
+        }
+    }
+}
+
+async fn attempt_diffs(
+    deployment: &DeploymentImpl,
+    task_attempt: &TaskAttempt,
+    path: Option<&str>,
+) -> Result<Vec<Diff>, ApiError> {
+    let pool = &deployment.db().pool;
+    let task = task_attempt
+        .parent_task(pool)
+        .await?
+        .ok_or(ApiError::TaskAttempt(TaskAttemptError::TaskNotFound))?;
+    let ctx = TaskAttempt::load_context(pool, task_attempt.id, task.id, task.project_id).await?;
+    let worktree_path = ensure_worktree_path(deployment, task_attempt).await?;
+
+    // Same diff the attempt's Diffs tab shows: worktree vs merge-base with the target
+    let base_commit = deployment.git().get_base_commit(
+        &ctx.project.git_repo_path,
+        &task_attempt.branch,
+        &task_attempt.target_branch,
+    )?;
+    let path_filter = path.map(|path| [path]);
+    Ok(deployment.git().get_diffs(
+        DiffTarget::Worktree {
+            worktree_path: &worktree_path,
+            base_commit: &base_commit,
+        },
+        path_filter.as_ref().map(|paths| paths.as_slice()),
+    )?)
+}
+
+#[cfg(test)]
+mod tests {
+    use executors::logs::NormalizedEntryType;
+
+    use super::*;
+
+    fn entries(count: usize) -> Vec<AttemptConversationEntry> {
+        (0..count)
+            .map(|i| AttemptConversationEntry {
+                execution_process_id: Uuid::new_v4(),
+                entry: NormalizedEntry {
+                    timestamp: None,
+                    entry_type: NormalizedEntryType::AssistantMessage,
+                    content: format!("message {i}"),
+                    metadata: None,
+                },
+            })
+            .collect()
+    }
+
+    fn diff(old: Option<&str>, new: Option<&str>) -> Diff {
+        Diff {
+            change: DiffChangeKind::Modified,
+            old_path: Some("src/lib.rs".to_string()),
+            new_path: Some("src/lib.rs".to_string()),
+            old_content: old.map(str::to_string),
+            new_content: new.map(str::to_string),
+            content_omitted: false,
+            additions: None,
+            deletions: None,
+        }
+    }
+
+    #[test]
+    fn conversation_page_defaults_to_the_first_entries() {
+        let query = ConversationQuery {
+            offset: None,
+            limit: None,
+        };
+
+        let page = AttemptConversation::page(entries(60), &query, true);
+
+        assert_eq!(page.total, 60);
+        assert_eq!(page.offset, 0);
+        assert_eq!(page.entries.len(), DEFAULT_CONVERSATION_PAGE);
+        assert_eq!(page.entries[0].entry.content, "message 0");
+        assert!(page.running);
+    }
+
+    #[test]
+    fn conversation_page_clamps_an_offset_past_the_end() {
+        let query = ConversationQuery {
+            offset: Some(8),
+            limit: Some(5),
+        };
+        assert_eq!(
+            AttemptConversation::page(entries(10), &query, false)
+                .entries
+                .iter()
+                .map(|e| e.entry.content.as_str())
+                .collect::<Vec<_>>(),
+            vec!["message 8", "message 9"]
+        );
+
+        let query = ConversationQuery {
+            offset: Some(50),
+            limit: None,
+        };
+        let page = AttemptConversation::page(entries(10), &query, false);
+        assert_eq!(page.offset, 10);
+        assert!(page.entries.is_empty());
+    }
+
+    #[test]
+    fn diff_summary_counts_changed_lines() {
+        let summary = DiffFileSummary::from_diff(diff(Some("a\nb\n"), Some("a\nc\nd\n")));
+
+        assert_eq!(summary.path, "src/lib.rs");
+        assert_eq!((summary.additions, summary.deletions), (2, 1));
+    }
+
+    #[test]
+    fn diff_summary_uses_precomputed_counts_of_omitted_contents() {
+        let summary = DiffFileSummary::from_diff(Diff {
+            content_omitted: true,
+            additions: Some(400),
+            deletions: Some(12),
+            ..diff(None, None)
+        });
+
+        assert_eq!((summary.additions, summary.deletions), (400, 12));
+    }
+
+    #[test]
+    fn file_diff_renders_a_unified_diff() {
+        let file = FileDiff::from_diff(
+            "src/lib.rs".to_string(),
+            diff(Some("a\nb\n"), Some("a\nc\n")),
+        );
+
+        let unified = file.unified_diff.unwrap();
+        assert!(unified.contains("-b"));
+        assert!(unified.contains("+c"));
+    }
+
+    #[test]
+    fn file_diff_without_contents_has_no_unified_diff() {
+        let file = FileDiff::from_diff("logo.png".to_string(), diff(None, None));
+
+        assert!(file.unified_diff.is_none());
+    }
+}
@@ -0,0 +1,80 @@
+use axum::{
+    Extension,
+    extract::{
+        State,
+        ws::{WebSocket, WebSocketUpgrade},
+    },
+    response::{IntoResponse, Json as ResponseJson},
+};
+use db::models::{merge::CiCheck, task_attempt::TaskAttempt};
+use deployment::Deployment;
+use futures_util::{SinkExt, StreamExt, TryStreamExt};
+use services::services::pr_checks::PrChecksService;
+use utils::response::ApiResponse;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// Stream the attempt's PR status, including CI checks, as JSON patches
+pub async fn stream_pr_ws(
+    ws: WebSocketUpgrade,
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| async move {
+        if let Err(e) = handle_pr_ws(socket, deployment, task_attempt).await {
+            tracing::warn!("PR status WS closed: {}", e);
+        }
+    })
+}
+
+async fn handle_pr_ws(
+    socket: WebSocket,
+    deployment: DeploymentImpl,
+    task_attempt: TaskAttempt,
+) -> anyhow::Result<()> {
+    let mut stream = deployment
+        .events()
+        .stream_pr_merge_for_attempt_raw(task_attempt.id)
+        .await?
+        .map_ok(|msg| msg.to_ws_message_unchecked());
+
+    let (mut sender, mut receiver) = socket.split();
+    tokio::spawn(async move { while let Some(Ok(_)) = receiver.next().await {} });
+
+    while let Some(item) = stream.next().await {
+        match item {
+            Ok(msg) => {
+                if sender.send(msg).await.is_err() {
+                    break;
+                }
+            }
+            Err(e) => {
+                tracing::error!("stream error: {}", e);
+                break;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Send the output of the PR's failing checks to the agent as a queued follow-up
+pub async fn send_failed_checks(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<CiCheck>>>, ApiError> {
+    let failed = PrChecksService::new(deployment.db().clone())
+        .send_failures_to_agent(deployment.drafts(), deployment.container(), &task_attempt)
+        .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "pr_failed_checks_sent",
+            serde_json::json!({
+                "attempt_id": task_attempt.id.to_string(),
+                "check_count": failed.len(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(failed)))
+}
@@ -0,0 +1,100 @@
+use axum::{
+    Extension, Json,
+    extract::{Query, State},
+    response::Json as ResponseJson,
+};
+use db::models::{
+    pr_review_comment::{PrReviewComment, ReviewCommentStatus},
+    task_attempt::TaskAttempt,
+};
+use deployment::Deployment;
+use serde::Deserialize;
+use services::services::pr_review::PrReviewService;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+#[derive(Debug, Deserialize)]
+pub struct ReviewCommentsQuery {
+    /// Fetch new comments from GitHub before returning
+    #[serde(default)]
+    pub refresh: bool,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct SendReviewCommentsRequest {
+    /// Send only these comments; all pending comments when omitted
+    pub comment_ids: Option<Vec<Uuid>>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct DismissReviewCommentsRequest {
+    pub comment_ids: Vec<Uuid>,
+}
+
+pub async fn get_review_comments(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<ReviewCommentsQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<PrReviewComment>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    if query.refresh {
+        PrReviewService::new(deployment.db().clone())
+            .sync_for_attempt(task_attempt.id)
+            .await?;
+    }
+    let comments = PrReviewComment::find_by_task_attempt_id(pool, task_attempt.id).await?;
+    Ok(ResponseJson(ApiResponse::success(comments)))
+}
+
+pub async fn send_review_comments(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<SendReviewCommentsRequest>,
+) -> Result<ResponseJson<ApiResponse<Vec<PrReviewComment>>>, ApiError> {
+    let sent = PrReviewService::new(deployment.db().clone())
+        .send_to_agent(
+            deployment.drafts(),
+            deployment.container(),
+            &task_attempt,
+            payload.comment_ids.as_deref(),
+        )
+        .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "pr_review_comments_sent",
+            serde_json::json!({
+                "attempt_id": task_attempt.id.to_string(),
+                "comment_count": sent.len(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(sent)))
+}
+
+pub async fn dismiss_review_comments(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<DismissReviewCommentsRequest>,
+) -> Result<ResponseJson<ApiResponse<Vec<PrReviewComment>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    for comment_id in &payload.comment_ids {
+        let belongs_to_attempt = PrReviewComment::find_by_id(pool, *comment_id)
+            .await?
+            .is_some_and(|c| c.task_attempt_id == task_attempt.id);
+        if !belongs_to_attempt {
+            return Ok(ResponseJson(ApiResponse::error(
+                "Review comment does not belong to this attempt",
+            )));
+        }
+    }
+    for comment_id in &payload.comment_ids {
+        PrReviewComment::update_status(pool, *comment_id, ReviewCommentStatus::Dismissed).await?;
+    }
+    let comments = PrReviewComment::find_by_task_attempt_id(pool, task_attempt.id).await?;
+    Ok(ResponseJson(ApiResponse::success(comments)))
+}
@@ -0,0 +1,339 @@
+//! Splitting a planning document into kanban tasks: start an agent run on the
+//! document, preview the tasks it proposes, then create the (edited) tasks at once.
+
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    middleware::from_fn_with_state,
+    response::Json as ResponseJson,
+    routing::{get, post},
+};
+use db::models::{
+    execution_process::{ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus},
+    project::Project,
+    task::{CreateTask, Task, TaskStatus},
+    task_attempt::{CreateTaskAttempt, TaskAttempt},
+    task_breakdown::TaskBreakdown,
+    task_document::TaskDocument,
+};
+use deployment::Deployment;
+use executors::{logs::NormalizedEntryType, profile::ExecutorProfileId};
+use serde::{Deserialize, Serialize};
+use services::services::{
+    container::ContainerService,
+    documents::DocumentService,
+    subtasks::{self, Subtask},
+    task_breakdown::{self, ProposedTask},
+};
+use sqlx::Error as SqlxError;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::load_project_middleware};
+
+#[derive(Debug, Deserialize, TS)]
+pub struct StartTaskBreakdown {
+    /// Defaults to the configured executor profile
+    pub executor_profile_id: Option<ExecutorProfileId>,
+    /// Defaults to the branch checked out in the project repository
+    pub base_branch: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskBreakdownStatus {
+    Running,
+    /// The agent finished and its tasks can be previewed
+    Ready,
+    Failed,
+    Applied,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct TaskBreakdownPreview {
+    pub breakdown: TaskBreakdown,
+    /// Task the agent runs under, where follow-ups can refine the breakdown
+    pub task_id: Uuid,
+    pub status: TaskBreakdownStatus,
+    pub tasks: Vec<ProposedTask>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct ApplyTaskBreakdown {
+    /// The previewed tasks, possibly edited
+    pub tasks: Vec<ProposedTask>,
+}
+
+/// Start an agent run that splits the document into tasks
+pub async fn start_breakdown(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Path((_project_id, document_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<StartTaskBreakdown>,
+) -> Result<ResponseJson<ApiResponse<TaskBreakdownPreview>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let document = DocumentService::get(pool, &project, document_id).await?;
+    if document.content.trim().is_empty() {
+        return Ok(ResponseJson(ApiResponse::error(
+            "The document is empty, there is nothing to break down",
+        )));
+    }
+
+    let executor_profile_id = match payload.executor_profile_id {
+        Some(profile) => profile,
+        None => deployment.config().read().await.executor_profile.clone(),
+    };
+    let base_branch = match payload.base_branch {
+        Some(branch) => branch,
+        None => deployment
+            .git()
+            .get_current_branch(&project.git_repo_path)?,
+    };
+
+    // The document reaches the agent as a linked document of the breakdown task
+    let mut tx = pool.begin().await?;
+    let task = Task::create(
+        &mut *tx,
+        &CreateTask {
+            project_id: project.id,
+            title: task_breakdown::breakdown_task_title(&document),
+            description: Some(task_breakdown::breakdown_task_description(&document)),
+            status: Some(TaskStatus::InProgress),
+            kind: None,
+            suggested_executor: None,
+            parent_task_attempt: None,
+            image_ids: None,
+            shared_task_id: None,
+        },
+        Uuid::new_v4(),
+    )
+    .await?;
+    TaskDocument::create(&mut *tx, task.id, document.id).await?;
+
+    let attempt_id = Uuid::new_v4();
+    let branch = deployment
+        .container()
+        .git_branch_from_task_attempt(&attempt_id, &task.title)
+        .await;
+    let task_attempt = TaskAttempt::create(
+        &mut *tx,
+        &CreateTaskAttempt {
+            executor: executor_profile_id.executor,
+            base_branch,
+            branch,
+            stacked_on_attempt_id: None,
+        },
+        attempt_id,
+        task.id,
+    )
+    .await?;
+    let breakdown = TaskBreakdown::create(
+        &mut *tx,
+        Uuid::new_v4(),
+        project.id,
+        document.id,
+        task_attempt.id,
+    )
+    .await?;
+    tx.commit().await?;
+
+    // Without a running agent the breakdown could never finish, so drop it
+    if let Err(e) = deployment
+        .container()
+        .start_attempt(&task_attempt, executor_profile_id.clone())
+        .await
+    {
+        if let Err(delete_error) = Task::delete(pool, task.id).await {
+            tracing::warn!(
+                "Failed to remove breakdown task {} after its agent failed to start: {}",
+                task.id,
+                delete_error
+            );
+        }
+        return Err(e.into());
+    }
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_breakdown_started",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "document_id": document.id.to_string(),
+                "executor": executor_profile_id.executor,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(TaskBreakdownPreview {
+        breakdown,
+        task_id: task.id,
+        status: TaskBreakdownStatus::Running,
+        tasks: Vec::new(),
+        error: None,
+    })))
+}
+
+/// Status of a breakdown and, once the agent is done, the tasks it proposes
+pub async fn get_breakdown(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Path((_project_id, breakdown_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<TaskBreakdownPreview>>, ApiError> {
+    let breakdown = find_breakdown(&deployment, &project, breakdown_id).await?;
+    Ok(ResponseJson(ApiResponse::success(
+        preview(&deployment, breakdown).await?,
+    )))
+}
+
+/// Create the tasks of a breakdown, with their dependencies, in one transaction
+pub async fn apply_breakdown(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Path((_project_id, breakdown_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<ApplyTaskBreakdown>,
+) -> Result<ResponseJson<ApiResponse<Vec<Subtask>>>, ApiError> {
+    if let Err(e) = task_breakdown::validate_breakdown(&payload.tasks) {
+        return Ok(ResponseJson(ApiResponse::error(&e.to_string())));
+    }
+    let breakdown = find_breakdown(&deployment, &project, breakdown_id).await?;
+    if breakdown.applied_at.is_some() {
+        return Err(ApiError::Conflict(
+            "The tasks of this breakdown were already created".to_string(),
+        ));
+    }
+
+    let pool = &deployment.db().pool;
+    let mut tx = pool.begin().await?;
+    if !TaskBreakdown::mark_applied(&mut *tx, breakdown.id).await? {
+        return Err(ApiError::Conflict(
+            "The tasks of this breakdown were already created".to_string(),
+        ));
+    }
+    let new_tasks: Vec<_> = payload.tasks.iter().map(ProposedTask::to_subtask).collect();
+    let created = subtasks::create_subtasks(
+        &mut tx,
+        project.id,
+        breakdown.task_attempt_id,
+        Some(breakdown.document_id),
+        &new_tasks,
+    )
+    .await?;
+    tx.commit().await?;
+
+    // The breakdown task itself is done once its tasks exist
+    if let Some(task_attempt) = TaskAttempt::find_by_id(pool, breakdown.task_attempt_id).await? {
+        Task::update_status(pool, task_attempt.task_id, TaskStatus::Done).await?;
+    }
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_breakdown_applied",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "document_id": breakdown.document_id.to_string(),
+                "task_count": created.len(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(created)))
+}
+
+async fn find_breakdown(
+    deployment: &DeploymentImpl,
+    project: &Project,
+    breakdown_id: Uuid,
+) -> Result<TaskBreakdown, ApiError> {
+    TaskBreakdown::find_by_id(&deployment.db().pool, breakdown_id)
+        .await?
+        .filter(|breakdown| breakdown.project_id == project.id)
+        .ok_or_else(|| ApiError::NotFound("Task breakdown not found".to_string()))
+}
+
+async fn preview(
+    deployment: &DeploymentImpl,
+    breakdown: TaskBreakdown,
+) -> Result<TaskBreakdownPreview, ApiError> {
+    let pool = &deployment.db().pool;
+    let task_attempt = TaskAttempt::find_by_id(pool, breakdown.task_attempt_id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    let mut preview = TaskBreakdownPreview {
+        breakdown,
+        task_id: task_attempt.task_id,
+        status: TaskBreakdownStatus::Running,
+        tasks: Vec::new(),
+        error: None,
+    };
+    if preview.breakdown.applied_at.is_some() {
+        preview.status = TaskBreakdownStatus::Applied;
+        return Ok(preview);
+    }
+
+    let processes = ExecutionProcess::find_by_task_attempt_id(pool, task_attempt.id, false).await?;
+    // Follow-ups may refine the breakdown, so the latest agent run counts
+    let Some(process) = processes
+        .iter()
+        .rev()
+        .find(|p| p.run_reason == ExecutionProcessRunReason::CodingAgent)
+    else {
+        if processes.iter().any(|p| {
+            matches!(
+                p.status,
+                ExecutionProcessStatus::Failed | ExecutionProcessStatus::Killed
+            )
+        }) {
+            preview.status = TaskBreakdownStatus::Failed;
+            preview.error = Some("The setup script failed before the agent started".to_string());
+        }
+        return Ok(preview);
+    };
+
+    match process.status {
+        ExecutionProcessStatus::Running => {}
+        ExecutionProcessStatus::Failed | ExecutionProcessStatus::Killed => {
+            preview.status = TaskBreakdownStatus::Failed;
+            preview.error = Some("The agent stopped before finishing the breakdown".to_string());
+        }
+        ExecutionProcessStatus::Completed => {
+            let Some(entries) = deployment.container().normalized_entries(&process.id).await else {
+                preview.status = TaskBreakdownStatus::Failed;
+                preview.error = Some("The agent's reply could not be read".to_string());
+                return Ok(preview);
+            };
+            let replies = entries
+                .iter()
+                .filter(|entry| matches!(entry.entry_type, NormalizedEntryType::AssistantMessage))
+                .map(|entry| entry.content.as_str());
+            match task_breakdown::parse_latest_breakdown(replies) {
+                Ok(tasks) => {
+                    preview.status = TaskBreakdownStatus::Ready;
+                    preview.tasks = tasks;
+                }
+                Err(e) => {
+                    preview.status = TaskBreakdownStatus::Failed;
+                    preview.error = Some(e.to_string());
+                }
+            }
+        }
+    }
+    Ok(preview)
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let project_router = Router::new()
+        .route("/documents/{document_id}/breakdown", post(start_breakdown))
+        .route("/task-breakdowns/{breakdown_id}", get(get_breakdown))
+        .route(
+            "/task-breakdowns/{breakdown_id}/apply",
+            post(apply_breakdown),
+        )
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_project_middleware,
+        ));
+
+    Router::new().nest("/projects/{project_id}", project_router)
+}
@@ -18,24 +18,34 @@ use db::models::{
     image::TaskImage,
     task::{CreateTask, Task, TaskStatus, TaskWithAttemptStatus, UpdateTask},
     task_attempt::{CreateTaskAttempt, TaskAttempt},
+    task_dependency::TaskDependency,
+    task_document::{TaskDocument, UpdateTaskDocuments},
+    task_plan_revision::{TaskPlanRevision, TaskPlanRevisionStatus},
+    task_plan_step::TaskPlanStep,
 };
 use deployment::Deployment;
 use executors::{
     actions::{
         ExecutorAction, ExecutorActionType, coding_agent_follow_up::CodingAgentFollowUpRequest,
     },
-    profile::{ExecutorProfileId, to_default_variant},
+    executors::BaseCodingAgent,
+    profile::{ExecutorConfigs, ExecutorProfileId, to_default_variant},
 };
 use futures_util::{SinkExt, StreamExt, TryStreamExt};
 use serde::{Deserialize, Serialize};
 use services::services::{
     container::{ContainerService, WorktreeCleanupData, cleanup_worktrees_direct},
-    planning,
+    documents::{Document, DocumentError, DocumentService},
+    plan_progress,
     share::ShareError,
+    subtasks::{self, NewSubtask, Subtask},
 };
-use sqlx::Error as SqlxError;
+use sqlx::{Error as SqlxError, SqlitePool};
 use ts_rs::TS;
-use utils::{approvals::ApprovalStatus as ApprovalStatusUtil, response::ApiResponse};
+use utils::{
+    approvals::ApprovalStatus as ApprovalStatusUtil, diff::create_unified_diff,
+    response::ApiResponse,
+};
 use uuid::Uuid;
 
 use crate::{DeploymentImpl, error::ApiError, middleware::load_task_middleware};
@@ -143,6 +153,89 @@ pub async fn create_task(
     Ok(ResponseJson(ApiResponse::success(task)))
 }
 
+#[derive(Debug, Serialize, Deserialize, TS)]
+pub struct CreateSubtask {
+    pub title: String,
+    pub description: Option<String>,
+    /// Executor suggested for the subtask, used by default for its attempts
+    pub executor: Option<BaseCodingAgent>,
+    /// Indices of earlier subtasks in the same request that must be done first
+    #[serde(default)]
+    pub depends_on: Vec<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, TS)]
+pub struct CreateSubtasks {
+    /// Attempt the subtasks are created from; defaults to the parent task's latest
+    /// attempt
+    pub parent_task_attempt: Option<Uuid>,
+    pub subtasks: Vec<CreateSubtask>,
+}
+
+/// Create subtasks of a task, linked to one of its attempts, with the order between
+/// them. All subtasks are created or none.
+pub async fn create_subtasks(
+    Extension(parent): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateSubtasks>,
+) -> Result<ResponseJson<ApiResponse<Vec<Subtask>>>, ApiError> {
+    if let Err(e) = subtasks::validate_subtasks(
+        payload
+            .subtasks
+            .iter()
+            .map(|subtask| (subtask.title.as_str(), subtask.depends_on.as_slice())),
+    ) {
+        return Ok(ResponseJson(ApiResponse::error(&e.to_string())));
+    }
+
+    let pool = &deployment.db().pool;
+    let attempts = TaskAttempt::fetch_all(pool, Some(parent.id)).await?;
+    let parent_attempt = match payload.parent_task_attempt {
+        Some(attempt_id) => attempts.iter().find(|attempt| attempt.id == attempt_id),
+        None => attempts.first(),
+    };
+    let Some(parent_attempt) = parent_attempt else {
+        return Ok(ResponseJson(ApiResponse::error(
+            "The parent task has no such attempt to create subtasks from",
+        )));
+    };
+
+    let new_subtasks: Vec<NewSubtask> = payload
+        .subtasks
+        .iter()
+        .map(|subtask| NewSubtask {
+            title: subtask.title.clone(),
+            description: subtask.description.clone(),
+            suggested_executor: subtask.executor,
+            depends_on: subtask.depends_on.clone(),
+        })
+        .collect();
+
+    let mut tx = pool.begin().await?;
+    let created = subtasks::create_subtasks(
+        &mut tx,
+        parent.project_id,
+        parent_attempt.id,
+        None,
+        &new_subtasks,
+    )
+    .await?;
+    tx.commit().await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "subtasks_created",
+            serde_json::json!({
+                "task_id": parent.id.to_string(),
+                "project_id": parent.project_id,
+                "subtask_count": created.len(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(created)))
+}
+
 #[derive(Debug, Deserialize, TS)]
 pub struct CreateAndStartTaskRequest {
     pub task: CreateTask,
@@ -173,11 +266,10 @@ pub async fn create_task_and_start(
         )
         .await;
     let attempt_id = Uuid::new_v4();
-    let git_branch_name = if planning::is_planning_conversation_task(&task) {
-        // Only Planning Conversation tasks use base branch directly (no new branch)
+    let git_branch_name = if !task.kind.creates_branch() {
+        // Planning conversations use the base branch directly (no new branch)
         payload.base_branch.clone()
     } else {
-        // All other tasks (including Plan status) create their own git branch
         deployment
             .container()
             .git_branch_from_task_attempt(&attempt_id, &task.title)
@@ -190,6 +282,7 @@ pub async fn create_task_and_start(
             executor: payload.executor_profile_id.executor,
             base_branch: payload.base_branch,
             branch: git_branch_name,
+            stacked_on_attempt_id: None,
         },
         attempt_id,
         task.id,
@@ -224,6 +317,7 @@ pub async fn create_task_and_start(
         has_merged_attempt: false,
         last_attempt_failed: false,
         executor: task_attempt.executor,
+        plan_progress: None,
     })))
 }
 
@@ -299,23 +393,22 @@ pub async fn delete_task(
         .await?
         .ok_or_else(|| ApiError::Database(SqlxError::RowNotFound))?;
 
-    let cleanup_data: Vec<WorktreeCleanupData> = if planning::is_planning_task(&task) {
-        vec![]
-    } else {
-        attempts
-            .iter()
-            .filter_map(|attempt| {
-                attempt
-                    .container_ref
-                    .as_ref()
-                    .map(|worktree_path| WorktreeCleanupData {
-                        attempt_id: attempt.id,
-                        worktree_path: PathBuf::from(worktree_path),
-                        git_repo_path: Some(project.git_repo_path.clone()),
-                    })
-            })
-            .collect()
-    };
+    let cleanup_data: Vec<WorktreeCleanupData> = attempts
+        .iter()
+        .filter_map(|attempt| {
+            attempt
+                .container_ref
+                .as_ref()
+                .map(PathBuf::from)
+                // Older planning conversations ran in the project's repository itself
+                .filter(|worktree_path| *worktree_path != project.git_repo_path)
+                .map(|worktree_path| WorktreeCleanupData {
+                    attempt_id: attempt.id,
+                    worktree_path,
+                    git_repo_path: Some(project.git_repo_path.clone()),
+                })
+        })
+        .collect();
 
     if let Some(shared_task_id) = task.shared_task_id {
         let Ok(publisher) = deployment.share_publisher() else {
@@ -401,49 +494,318 @@ pub async fn approve_plan(
     State(deployment): State<DeploymentImpl>,
 ) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
     let pool = &deployment.db().pool;
+    let latest_process = latest_plan_process(pool, task.id).await?;
+
+    let approvals = deployment.approvals();
+    let revision = match approvals.find_pending_exit_plan_mode(latest_process.id) {
+        // Claude Code waits in its ExitPlanMode hook until the plan is approved
+        Some((approval_id, plan)) if latest_process.status == ExecutionProcessStatus::Running => {
+            let req = utils::approvals::ApprovalResponse {
+                execution_process_id: latest_process.id,
+                status: ApprovalStatusUtil::Approved,
+                remember: None,
+            };
+            let responded_by = super::approvals::responder_name(&deployment).await;
+            let _ = approvals
+                .respond(&approval_id, req, responded_by.as_deref())
+                .await
+                .map_err(|e| ApiError::BadRequest(format!("Failed to approve: {:?}", e)))?;
+
+            // Save the plan to the task
+            TaskPlanRevision::record(pool, task.id, Some(latest_process.id), &plan).await?
+        }
+        // Other executors finish their plan mode run with the plan in the final message,
+        // which was saved to the task on exit
+        _ => finished_plan_revision(pool, &task, &latest_process).await?,
+    };
+    approve_plan_revision(pool, &task, &revision).await?;
+
+    // Start the implementation in the planning session
+    continue_plan_session(
+        &deployment,
+        &latest_process,
+        format!("Execute the following plan:\n\n{}", revision.plan),
+        true,
+    )
+    .await?;
+
+    tracing::info!(
+        "Auto-approved plan and started implementation for task {}",
+        task.id
+    );
+    Ok(ResponseJson(ApiResponse::success(())))
+}
 
-    // Find the latest execution process for this task
-    let latest_process = ExecutionProcess::find_latest_for_task(pool, task.id)
+#[derive(Debug, Deserialize, TS)]
+pub struct RequestPlanChanges {
+    /// What the revised plan should change
+    pub feedback: String,
+}
+
+/// Send the pending plan back to the agent with the reviewer's feedback. The task
+/// stays in Plan and the revised plan becomes the next revision.
+pub async fn request_plan_changes(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<RequestPlanChanges>,
+) -> Result<ResponseJson<ApiResponse<TaskPlanRevision>>, ApiError> {
+    let feedback = payload.feedback.trim();
+    if feedback.is_empty() {
+        return Ok(ResponseJson(ApiResponse::error(
+            "Describe the changes the plan needs",
+        )));
+    }
+    let pool = &deployment.db().pool;
+    let latest_process = latest_plan_process(pool, task.id).await?;
+
+    let approvals = deployment.approvals();
+    let revision = match approvals.find_pending_exit_plan_mode(latest_process.id) {
+        // Denying ExitPlanMode hands the feedback to Claude Code, which keeps planning
+        Some((approval_id, plan)) if latest_process.status == ExecutionProcessStatus::Running => {
+            let req = utils::approvals::ApprovalResponse {
+                execution_process_id: latest_process.id,
+                status: ApprovalStatusUtil::Denied {
+                    reason: Some(feedback.to_string()),
+                },
+                remember: None,
+            };
+            let responded_by = super::approvals::responder_name(&deployment).await;
+            approvals
+                .respond(&approval_id, req, responded_by.as_deref())
+                .await
+                .map_err(|e| ApiError::BadRequest(format!("Failed to request changes: {:?}", e)))?;
+            super::approvals::record_plan_changes_requested(
+                &deployment,
+                latest_process.id,
+                &plan,
+                Some(feedback),
+            )
+            .await?
+        }
+        // Other executors revise the plan in a follow-up on the same plan mode profile
+        _ => {
+            let revision = finished_plan_revision(pool, &task, &latest_process).await?;
+            send_plan_back_for_changes(pool, &task, &revision, feedback).await?;
+            continue_plan_session(
+                &deployment,
+                &latest_process,
+                format!("Revise the plan based on this feedback:\n\n{feedback}"),
+                false,
+            )
+            .await?;
+            revision
+        }
+    };
+
+    deployment
+        .track_if_analytics_allowed(
+            "plan_changes_requested",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "revision": revision.revision,
+            }),
+        )
+        .await;
+
+    let revision = TaskPlanRevision::find_by_revision(pool, task.id, revision.revision)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    Ok(ResponseJson(ApiResponse::success(revision)))
+}
+
+/// Every plan presented for the task, newest first
+pub async fn get_plan_revisions(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskPlanRevision>>>, ApiError> {
+    let revisions = TaskPlanRevision::find_by_task_id(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(revisions)))
+}
+
+pub async fn get_plan_steps(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskPlanStep>>>, ApiError> {
+    let steps = TaskPlanStep::find_by_task_id(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(steps)))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PlanRevisionDiffQuery {
+    pub from: i64,
+    /// Defaults to the latest revision
+    pub to: Option<i64>,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct TaskPlanRevisionDiff {
+    pub from: i64,
+    pub to: i64,
+    /// Empty when both revisions have the same plan
+    pub unified_diff: String,
+}
+
+/// Unified diff between two plan revisions
+pub async fn diff_plan_revisions(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<PlanRevisionDiffQuery>,
+) -> Result<ResponseJson<ApiResponse<TaskPlanRevisionDiff>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let from = TaskPlanRevision::find_by_revision(pool, task.id, query.from).await?;
+    let to = match query.to {
+        Some(to) => TaskPlanRevision::find_by_revision(pool, task.id, to).await?,
+        None => TaskPlanRevision::find_latest(pool, task.id).await?,
+    };
+    let (Some(from), Some(to)) = (from, to) else {
+        return Ok(ResponseJson(ApiResponse::error("Plan revision not found")));
+    };
+
+    let unified_diff = if from.plan == to.plan {
+        String::new()
+    } else {
+        create_unified_diff("plan.md", &from.plan, &to.plan)
+    };
+    Ok(ResponseJson(ApiResponse::success(TaskPlanRevisionDiff {
+        from: from.revision,
+        to: to.revision,
+        unified_diff,
+    })))
+}
+
+/// Latest coding agent run of the task; a cleanup script may have run after it
+async fn latest_plan_process(
+    pool: &SqlitePool,
+    task_id: Uuid,
+) -> Result<ExecutionProcess, ApiError> {
+    let latest_process = ExecutionProcess::find_latest_for_task(pool, task_id)
         .await?
         .ok_or_else(|| ApiError::BadRequest("No execution process found for task".to_string()))?;
+    if latest_process.run_reason == ExecutionProcessRunReason::CodingAgent {
+        return Ok(latest_process);
+    }
+    ExecutionProcess::find_latest_by_task_attempt_and_run_reason(
+        pool,
+        latest_process.task_attempt_id,
+        &ExecutionProcessRunReason::CodingAgent,
+    )
+    .await?
+    .ok_or_else(|| ApiError::BadRequest("No coding agent run found for task".to_string()))
+}
+
+/// Plan revision a finished plan mode run left for review
+async fn finished_plan_revision(
+    pool: &SqlitePool,
+    task: &Task,
+    process: &ExecutionProcess,
+) -> Result<TaskPlanRevision, ApiError> {
+    let plan_mode = process
+        .executor_profile_id()
+        .and_then(|profile_id| ExecutorConfigs::get_cached().get_coding_agent(&profile_id))
+        .is_some_and(|agent| agent.is_plan_mode());
+    ensure_plan_awaiting_review(&process.status, plan_mode)?;
+    pending_plan_revision(pool, task, process.id).await
+}
 
-    // Check if process is still running (plan mode should be active)
-    if latest_process.status != ExecutionProcessStatus::Running {
+/// Only a plan mode run that completed has a plan waiting for review
+fn ensure_plan_awaiting_review(
+    status: &ExecutionProcessStatus,
+    plan_mode: bool,
+) -> Result<(), ApiError> {
+    if *status == ExecutionProcessStatus::Running {
         return Err(ApiError::BadRequest(
-            "No running execution process for plan approval".to_string(),
+            "The plan is still being written".to_string(),
         ));
     }
+    if *status != ExecutionProcessStatus::Completed || !plan_mode {
+        return Err(ApiError::BadRequest(
+            "No plan awaiting approval".to_string(),
+        ));
+    }
+    Ok(())
+}
 
-    // Find pending ExitPlanMode approval
-    let approvals = deployment.approvals();
-    let (approval_id, plan) = approvals
-        .find_pending_exit_plan_mode(latest_process.id)
-        .ok_or_else(|| {
-            ApiError::BadRequest("No pending ExitPlanMode approval found".to_string())
-        })?;
-
-    // Respond to the approval (approve it)
-    let req = utils::approvals::ApprovalResponse {
-        execution_process_id: latest_process.id,
-        status: ApprovalStatusUtil::Approved,
-    };
-    let _ = approvals
-        .respond(pool, &approval_id, req)
-        .await
-        .map_err(|e| ApiError::BadRequest(format!("Failed to approve: {:?}", e)))?;
+/// The task's latest plan revision, if it was proposed by the given run and is still
+/// waiting for review. A plan saved to the task without any revision becomes the
+/// first one.
+async fn pending_plan_revision(
+    pool: &SqlitePool,
+    task: &Task,
+    execution_process_id: Uuid,
+) -> Result<TaskPlanRevision, ApiError> {
+    match TaskPlanRevision::find_latest(pool, task.id).await? {
+        Some(revision)
+            if revision.status == TaskPlanRevisionStatus::Proposed
+                && revision.execution_process_id == Some(execution_process_id) =>
+        {
+            Ok(revision)
+        }
+        Some(_) => Err(ApiError::BadRequest(
+            "No plan awaiting approval".to_string(),
+        )),
+        None => {
+            let plan = task
+                .plan
+                .as_deref()
+                .filter(|plan| !plan.trim().is_empty())
+                .ok_or_else(|| {
+                    ApiError::BadRequest("The plan mode run did not produce a plan".to_string())
+                })?;
+            Ok(TaskPlanRevision::record(pool, task.id, Some(execution_process_id), plan).await?)
+        }
+    }
+}
 
-    // Save the plan to the task
-    Task::update_plan(pool, task.id, Some(plan.clone())).await?;
+/// Approve the revision, save its steps for progress tracking and move the task from
+/// Plan to InProgress
+async fn approve_plan_revision(
+    pool: &SqlitePool,
+    task: &Task,
+    revision: &TaskPlanRevision,
+) -> Result<(), ApiError> {
+    TaskPlanRevision::update_status(pool, revision.id, TaskPlanRevisionStatus::Approved, None)
+        .await?;
+    plan_progress::save_plan_steps(pool, task.id, &revision.plan).await?;
     tracing::info!("Saved plan for task {}", task.id);
 
-    // Move task to InProgress if it's in Plan status
     if task.status == TaskStatus::Plan {
         Task::update_status(pool, task.id, TaskStatus::InProgress).await?;
         tracing::info!("Task {} moved from Plan to InProgress", task.id);
     }
+    Ok(())
+}
+
+/// Mark the revision as needing changes and keep the task in Plan until the revised
+/// plan is approved
+async fn send_plan_back_for_changes(
+    pool: &SqlitePool,
+    task: &Task,
+    revision: &TaskPlanRevision,
+    feedback: &str,
+) -> Result<(), ApiError> {
+    TaskPlanRevision::update_status(
+        pool,
+        revision.id,
+        TaskPlanRevisionStatus::ChangesRequested,
+        Some(feedback),
+    )
+    .await?;
+    if task.status != TaskStatus::Plan {
+        Task::update_status(pool, task.id, TaskStatus::Plan).await?;
+    }
+    Ok(())
+}
 
-    // Load context for starting implementation
-    let ctx = ExecutionProcess::load_context(pool, latest_process.id).await?;
+/// Follow up in the session of a plan mode run, on the same profile to keep planning
+/// or on its default variant to implement the plan
+async fn continue_plan_session(
+    deployment: &DeploymentImpl,
+    process: &ExecutionProcess,
+    prompt: String,
+    implement: bool,
+) -> Result<(), ApiError> {
+    let pool = &deployment.db().pool;
+    let ctx = ExecutionProcess::load_context(pool, process.id).await?;
 
     // Get executor profile from the current action
     let action = ctx
@@ -461,26 +823,27 @@ pub async fn approve_plan(
     };
 
     // Get session ID for follow-up
-    let session = ExecutorSession::find_by_execution_process_id(pool, latest_process.id)
+    let session = ExecutorSession::find_by_execution_process_id(pool, process.id)
         .await?
         .ok_or_else(|| ApiError::BadRequest("No executor session found".to_string()))?;
     let session_id = session
         .session_id
         .ok_or_else(|| ApiError::BadRequest("No session ID in executor session".to_string()))?;
 
-    // Create follow-up request with the plan as prompt
-    let default_profile = to_default_variant(&executor_profile_id);
     let follow_up = CodingAgentFollowUpRequest {
-        prompt: format!("Execute the following plan:\n\n{}", plan),
+        prompt,
         session_id,
-        executor_profile_id: default_profile,
+        executor_profile_id: if implement {
+            to_default_variant(&executor_profile_id)
+        } else {
+            executor_profile_id
+        },
     };
     let new_action = ExecutorAction::new(
         ExecutorActionType::CodingAgentFollowUpRequest(follow_up),
         action.next_action().cloned().map(Box::new),
     );
 
-    // Start the implementation execution
     deployment
         .container()
         .start_execution(
@@ -490,12 +853,7 @@ pub async fn approve_plan(
         )
         .await
         .map_err(|e| ApiError::BadRequest(format!("Failed to start execution: {:?}", e)))?;
-
-    tracing::info!(
-        "Auto-approved plan and started implementation for task {}",
-        task.id
-    );
-    Ok(ResponseJson(ApiResponse::success(())))
+    Ok(())
 }
 
 pub async fn share_task(
@@ -525,15 +883,84 @@ pub async fn share_task(
     })))
 }
 
+/// Tasks that must be done before this one
+pub async fn get_task_dependencies(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskDependency>>>, ApiError> {
+    let dependencies = TaskDependency::find_by_task_id(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(dependencies)))
+}
+
+/// Project documents the task is based on, in the order they are given to the agent
+pub async fn get_task_documents(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<Document>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let project = task
+        .parent_project(pool)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    let documents = DocumentService::linked_to_task(pool, &project, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(documents)))
+}
+
+pub async fn update_task_documents(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpdateTaskDocuments>,
+) -> Result<ResponseJson<ApiResponse<Vec<Document>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let project = task
+        .parent_project(pool)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    for document_id in &payload.document_ids {
+        match DocumentService::get(pool, &project, *document_id).await {
+            Ok(_) => {}
+            Err(DocumentError::NotFound) => {
+                return Ok(ResponseJson(ApiResponse::error(&format!(
+                    "Document {document_id} is not a document of this project"
+                ))));
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    TaskDocument::replace_for_task(pool, task.id, &payload.document_ids).await?;
+    let documents = DocumentService::linked_to_task(pool, &project, task.id).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_documents_updated",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "document_count": documents.len(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(documents)))
+}
+
 pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let task_actions_router = Router::new()
         .route("/", put(update_task))
         .route("/", delete(delete_task))
         .route("/share", post(share_task))
-        .route("/approve-plan", post(approve_plan));
+        .route("/approve-plan", post(approve_plan))
+        .route("/plan/request-changes", post(request_plan_changes))
+        .route("/subtasks", post(create_subtasks))
+        .route("/documents", put(update_task_documents));
 
     let task_id_router = Router::new()
         .route("/", get(get_task))
+        .route("/dependencies", get(get_task_dependencies))
+        .route("/documents", get(get_task_documents))
+        .route("/plan/revisions", get(get_plan_revisions))
+        .route("/plan/revisions/diff", get(diff_plan_revisions))
+        .route("/plan/steps", get(get_plan_steps))
         .merge(task_actions_router)
         .layer(from_fn_with_state(deployment.clone(), load_task_middleware));
 
@@ -546,3 +973,270 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     // mount under /projects/:project_id/tasks
     Router::new().nest("/tasks", inner)
 }
+
+#[cfg(test)]
+mod tests {
+    use db::{
+        DBService, models::execution_process::CreateExecutionProcess, test_utils::create_project,
+    };
+    use executors::actions::script::{ScriptContext, ScriptRequest, ScriptRequestLanguage};
+
+    use super::*;
+
+    async fn task_in(db: &DBService, status: TaskStatus, plan: Option<&str>) -> Task {
+        let project = create_project(&db.pool, "/tmp/plans").await;
+        let task = Task::create(
+            &db.pool,
+            &CreateTask::from_title_description(project.id, "Plan me".to_string(), None),
+            Uuid::new_v4(),
+        )
+        .await
+        .unwrap();
+        Task::update_status(&db.pool, task.id, status)
+            .await
+            .unwrap();
+        Task::update_plan(&db.pool, task.id, plan.map(str::to_string))
+            .await
+            .unwrap();
+        Task::find_by_id(&db.pool, task.id).await.unwrap().unwrap()
+    }
+
+    /// A finished coding agent run on a new attempt of the task
+    async fn plan_run(db: &DBService, task: &Task) -> Uuid {
+        let attempt = TaskAttempt::create(
+            &db.pool,
+            &CreateTaskAttempt {
+                executor: BaseCodingAgent::ClaudeCode,
+                base_branch: "main".to_string(),
+                branch: format!("plan-{}", Uuid::new_v4()),
+                stacked_on_attempt_id: None,
+            },
+            Uuid::new_v4(),
+            task.id,
+        )
+        .await
+        .unwrap();
+        ExecutionProcess::create(
+            &db.pool,
+            &CreateExecutionProcess {
+                task_attempt_id: attempt.id,
+                executor_action: ExecutorAction::new(
+                    ExecutorActionType::ScriptRequest(ScriptRequest {
+                        script: "true".to_string(),
+                        language: ScriptRequestLanguage::Bash,
+                        context: ScriptContext::SetupScript,
+                    }),
+                    None,
+                ),
+                run_reason: ExecutionProcessRunReason::CodingAgent,
+            },
+            Uuid::new_v4(),
+            None,
+        )
+        .await
+        .unwrap()
+        .id
+    }
+
+    fn bad_request(result: Result<impl std::fmt::Debug, ApiError>) -> String {
+        match result {
+            Err(ApiError::BadRequest(message)) => message,
+            other => panic!("expected a bad request, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn a_running_plan_is_not_ready_for_review() {
+        assert_eq!(
+            bad_request(ensure_plan_awaiting_review(
+                &ExecutionProcessStatus::Running,
+                true
+            )),
+            "The plan is still being written"
+        );
+    }
+
+    #[test]
+    fn only_completed_plan_mode_runs_await_review() {
+        assert!(ensure_plan_awaiting_review(&ExecutionProcessStatus::Completed, true).is_ok());
+        for (status, plan_mode) in [
+            (ExecutionProcessStatus::Completed, false),
+            (ExecutionProcessStatus::Failed, true),
+            (ExecutionProcessStatus::Killed, true),
+        ] {
+            assert_eq!(
+                bad_request(ensure_plan_awaiting_review(&status, plan_mode)),
+                "No plan awaiting approval"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn a_plan_saved_without_revisions_becomes_the_first_revision() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let task = task_in(&db, TaskStatus::Plan, Some("1. Add the table")).await;
+        let run = plan_run(&db, &task).await;
+
+        let revision = pending_plan_revision(&db.pool, &task, run).await.unwrap();
+
+        assert_eq!(revision.revision, 1);
+        assert_eq!(revision.plan, "1. Add the table");
+        assert_eq!(revision.execution_process_id, Some(run));
+        assert_eq!(revision.status, TaskPlanRevisionStatus::Proposed);
+    }
+
+    #[tokio::test]
+    async fn a_run_that_left_no_plan_has_nothing_to_review() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let task = task_in(&db, TaskStatus::Plan, Some("  ")).await;
+        let run = plan_run(&db, &task).await;
+
+        assert_eq!(
+            bad_request(pending_plan_revision(&db.pool, &task, run).await),
+            "The plan mode run did not produce a plan"
+        );
+    }
+
+    #[tokio::test]
+    async fn approving_moves_the_proposed_plan_into_progress() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let task = task_in(&db, TaskStatus::Plan, None).await;
+        let run = plan_run(&db, &task).await;
+        TaskPlanRevision::record(&db.pool, task.id, Some(run), "1. Add the table")
+            .await
+            .unwrap();
+
+        let pending = pending_plan_revision(&db.pool, &task, run).await.unwrap();
+        approve_plan_revision(&db.pool, &task, &pending)
+            .await
+            .unwrap();
+
+        let approved = TaskPlanRevision::find_latest(&db.pool, task.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(approved.status, TaskPlanRevisionStatus::Approved);
+        let task = Task::find_by_id(&db.pool, task.id).await.unwrap().unwrap();
+        assert_eq!(task.status, TaskStatus::InProgress);
+        assert_eq!(
+            TaskPlanStep::find_by_task_id(&db.pool, task.id)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn a_plan_sent_back_for_changes_cannot_be_approved() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let task = task_in(&db, TaskStatus::Plan, None).await;
+        let run = plan_run(&db, &task).await;
+        let first = TaskPlanRevision::record(&db.pool, task.id, Some(run), "1. Add the table")
+            .await
+            .unwrap();
+        send_plan_back_for_changes(&db.pool, &task, &first, "Add a migration step")
+            .await
+            .unwrap();
+
+        assert_eq!(
+            bad_request(pending_plan_revision(&db.pool, &task, run).await),
+            "No plan awaiting approval"
+        );
+    }
+
+    #[tokio::test]
+    async fn changes_cannot_be_requested_for_a_plan_of_another_run() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let task = task_in(&db, TaskStatus::Plan, None).await;
+        let planning = plan_run(&db, &task).await;
+        TaskPlanRevision::record(&db.pool, task.id, Some(planning), "1. Add the table")
+            .await
+            .unwrap();
+        // A later run, e.g. the implementation, that presented no plan of its own
+        let later = plan_run(&db, &task).await;
+
+        assert_eq!(
+            bad_request(pending_plan_revision(&db.pool, &task, later).await),
+            "No plan awaiting approval"
+        );
+        let latest = TaskPlanRevision::find_latest(&db.pool, task.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(latest.status, TaskPlanRevisionStatus::Proposed);
+    }
+
+    #[tokio::test]
+    async fn requested_changes_are_followed_by_the_revised_plan() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let task = task_in(&db, TaskStatus::InReview, None).await;
+        let run = plan_run(&db, &task).await;
+        let first = TaskPlanRevision::record(&db.pool, task.id, Some(run), "1. Add the table")
+            .await
+            .unwrap();
+        let pending = pending_plan_revision(&db.pool, &task, run).await.unwrap();
+        assert_eq!(pending.id, first.id);
+
+        send_plan_back_for_changes(&db.pool, &task, &pending, "Add a migration step")
+            .await
+            .unwrap();
+
+        let first = TaskPlanRevision::find_by_revision(&db.pool, task.id, 1)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(first.status, TaskPlanRevisionStatus::ChangesRequested);
+        assert_eq!(first.feedback.as_deref(), Some("Add a migration step"));
+        let task = Task::find_by_id(&db.pool, task.id).await.unwrap().unwrap();
+        assert_eq!(task.status, TaskStatus::Plan);
+
+        // The follow-up run saves its revised plan on exit
+        let follow_up = plan_run(&db, &task).await;
+        let revised = "1. Add a migration\n2. Add the table";
+        TaskPlanRevision::record(&db.pool, task.id, Some(follow_up), revised)
+            .await
+            .unwrap();
+        let task = Task::find_by_id(&db.pool, task.id).await.unwrap().unwrap();
+        let pending = pending_plan_revision(&db.pool, &task, follow_up)
+            .await
+            .unwrap();
+        assert_eq!(pending.revision, 2);
+        assert_eq!(pending.plan, revised);
+        assert_eq!(pending.status, TaskPlanRevisionStatus::Proposed);
+        assert_eq!(task.plan.as_deref(), Some(revised));
+    }
+
+    #[tokio::test]
+    async fn an_unchanged_revised_plan_is_not_recorded_again() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let task = task_in(&db, TaskStatus::Plan, None).await;
+        let run = plan_run(&db, &task).await;
+        let first = TaskPlanRevision::record(&db.pool, task.id, Some(run), "1. Add the table")
+            .await
+            .unwrap();
+        send_plan_back_for_changes(&db.pool, &task, &first, "Split it up")
+            .await
+            .unwrap();
+
+        let follow_up = plan_run(&db, &task).await;
+        let again =
+            TaskPlanRevision::record(&db.pool, task.id, Some(follow_up), "1. Add the table")
+                .await
+                .unwrap();
+
+        assert_eq!(again.id, first.id);
+        assert_eq!(
+            TaskPlanRevision::find_by_task_id(&db.pool, task.id)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+        // Nothing new to review until the agent changes the plan
+        assert_eq!(
+            bad_request(pending_plan_revision(&db.pool, &task, follow_up).await),
+            "No plan awaiting approval"
+        );
+    }
+}
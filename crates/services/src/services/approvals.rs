@@ -1,14 +1,21 @@
 pub mod executor_approvals;
+pub mod policy;
+pub mod timeouts;
+pub mod webhook;
 
 use std::{collections::HashMap, sync::Arc, time::Duration as StdDuration};
 
 use dashmap::DashMap;
-use db::models::{
-    execution_process::ExecutionProcess,
-    task::{Task, TaskStatus},
+use db::{
+    DBService,
+    models::{
+        approval_record::ApprovalRecord,
+        execution_process::ExecutionProcess,
+        task::{Task, TaskStatus},
+    },
 };
 use executors::{
-    approvals::ToolCallMetadata,
+    approvals::{ApprovalTimeoutAction, ToolCallMetadata},
     logs::{
         ActionType, NormalizedEntry, NormalizedEntryType, ToolStatus,
         utils::patch::{ConversationPatch, extract_normalized_entry_from_patch},
@@ -25,12 +32,15 @@ use utils::{
 };
 use uuid::Uuid;
 
+use self::{policy::ApprovalPolicyDecision, webhook::ApprovalSigner};
+
 #[derive(Debug)]
 struct PendingApproval {
     entry_index: usize,
     entry: NormalizedEntry,
     execution_process_id: Uuid,
     tool_name: String,
+    tool_input: serde_json::Value,
     response_tx: oneshot::Sender<ApprovalStatus>,
 }
 
@@ -39,15 +49,23 @@ type ApprovalWaiter = Shared<BoxFuture<'static, ApprovalStatus>>;
 #[derive(Debug)]
 pub struct ToolContext {
     pub tool_name: String,
+    pub tool_input: serde_json::Value,
     pub execution_process_id: Uuid,
     pub plan: Option<String>,
+    /// The agent stopped waiting for this request when the server restarted, so the
+    /// decision has to be sent to it as a follow-up
+    pub interrupted: bool,
 }
 
 #[derive(Clone)]
 pub struct Approvals {
+    db: DBService,
     pending: Arc<DashMap<String, PendingApproval>>,
+    /// Requests restored from the database whose agent is no longer waiting
+    interrupted: Arc<DashMap<String, ApprovalRecord>>,
     completed: Arc<DashMap<String, ApprovalStatus>>,
     msg_stores: Arc<RwLock<HashMap<Uuid, Arc<MsgStore>>>>,
+    signer: ApprovalSigner,
 }
 
 #[derive(Debug, Error)]
@@ -67,17 +85,26 @@ pub enum ApprovalError {
 }
 
 impl Approvals {
-    pub fn new(msg_stores: Arc<RwLock<HashMap<Uuid, Arc<MsgStore>>>>) -> Self {
+    pub fn new(db: DBService, msg_stores: Arc<RwLock<HashMap<Uuid, Arc<MsgStore>>>>) -> Self {
         Self {
+            db,
             pending: Arc::new(DashMap::new()),
+            interrupted: Arc::new(DashMap::new()),
             completed: Arc::new(DashMap::new()),
             msg_stores,
+            signer: ApprovalSigner::load_or_create(),
         }
     }
 
+    /// Signs the links and callback tokens used to answer approvals from outside the UI
+    pub fn signer(&self) -> &ApprovalSigner {
+        &self.signer
+    }
+
     pub async fn create_with_waiter(
         &self,
         request: ApprovalRequest,
+        on_timeout: ApprovalTimeoutAction,
     ) -> Result<(ApprovalRequest, ApprovalWaiter), ApprovalError> {
         let (tx, rx) = oneshot::channel();
         let waiter: ApprovalWaiter = rx
@@ -86,6 +113,15 @@ impl Approvals {
             .shared();
         let req_id = request.id.clone();
 
+        ApprovalRecord::create(
+            &self.db.pool,
+            &request,
+            on_timeout,
+            &ApprovalStatus::Pending,
+            None,
+        )
+        .await?;
+
         if let Some(store) = self.msg_store_by_id(&request.execution_process_id).await {
             // Find the matching tool use entry by name and input
             let matching_tool = find_matching_tool_use(store.clone(), &request.tool_call_id);
@@ -96,6 +132,7 @@ impl Approvals {
                         approval_id: req_id.clone(),
                         requested_at: request.created_at,
                         timeout_at: request.timeout_at,
+                        on_timeout,
                     })
                     .ok_or(ApprovalError::NoToolUseEntry)?;
                 store.push_patch(ConversationPatch::replace(idx, approval_entry));
@@ -107,6 +144,7 @@ impl Approvals {
                         entry: matching_tool,
                         execution_process_id: request.execution_process_id,
                         tool_name: request.tool_name.clone(),
+                        tool_input: request.tool_input.clone(),
                         response_tx: tx,
                     },
                 );
@@ -130,20 +168,27 @@ impl Approvals {
             );
         }
 
-        self.spawn_timeout_watcher(req_id.clone(), request.timeout_at, waiter.clone());
+        self.spawn_timeout_watcher(
+            req_id.clone(),
+            request.timeout_at,
+            on_timeout,
+            waiter.clone(),
+        );
         Ok((request, waiter))
     }
 
-    #[tracing::instrument(skip(self, id, req))]
+    #[tracing::instrument(skip(self, id, req, responded_by))]
     pub async fn respond(
         &self,
-        pool: &SqlitePool,
         id: &str,
         req: ApprovalResponse,
+        responded_by: Option<&str>,
     ) -> Result<(ApprovalStatus, ToolContext), ApprovalError> {
+        let pool = &self.db.pool;
         if let Some((_, p)) = self.pending.remove(id) {
             self.completed.insert(id.to_string(), req.status.clone());
             let _ = p.response_tx.send(req.status.clone());
+            ApprovalRecord::resolve(pool, id, &req.status, responded_by).await?;
 
             if let Some(store) = self.msg_store_by_id(&p.execution_process_id).await {
                 let status = ToolStatus::from_approval_status(&req.status).ok_or(
@@ -173,25 +218,35 @@ impl Approvals {
 
             let tool_ctx = ToolContext {
                 tool_name: p.tool_name,
+                tool_input: p.tool_input,
                 execution_process_id: p.execution_process_id,
                 plan,
+                interrupted: false,
             };
+            self.remember_if_requested(id, &req, &tool_ctx).await;
 
             // If approved or denied, and task is still InReview, move back to InProgress
             if matches!(
                 req.status,
                 ApprovalStatus::Approved | ApprovalStatus::Denied { .. }
-            ) && let Ok(ctx) =
-                ExecutionProcess::load_context(pool, tool_ctx.execution_process_id).await
-                && ctx.task.status == TaskStatus::InReview
-                && let Err(e) = Task::update_status(pool, ctx.task.id, TaskStatus::InProgress).await
-            {
-                tracing::warn!(
-                    "Failed to update task status to InProgress after approval response: {}",
-                    e
-                );
+            ) {
+                ensure_task_in_progress(pool, tool_ctx.execution_process_id).await;
             }
 
+            Ok((req.status, tool_ctx))
+        } else if let Some((_, record)) = self.interrupted.remove(id) {
+            self.completed.insert(id.to_string(), req.status.clone());
+            ApprovalRecord::resolve(pool, id, &req.status, responded_by).await?;
+
+            let tool_ctx = ToolContext {
+                tool_name: record.tool_name,
+                tool_input: record.tool_input.0,
+                execution_process_id: record.execution_process_id,
+                plan: None,
+                interrupted: true,
+            };
+            self.remember_if_requested(id, &req, &tool_ctx).await;
+
             Ok((req.status, tool_ctx))
         } else if self.completed.contains_key(id) {
             Err(ApprovalError::AlreadyCompleted)
@@ -200,16 +255,100 @@ impl Approvals {
         }
     }
 
+    /// Persist an approval as an allow rule when the user asked to remember it.
+    /// Plan approvals are always asked for, so they are never remembered.
+    async fn remember_if_requested(
+        &self,
+        id: &str,
+        req: &ApprovalResponse,
+        tool_ctx: &ToolContext,
+    ) {
+        let Some(scope) = req.remember else {
+            return;
+        };
+        if !matches!(req.status, ApprovalStatus::Approved) || tool_ctx.tool_name == "ExitPlanMode" {
+            return;
+        }
+        let pool = &self.db.pool;
+        match ExecutionProcess::load_context(pool, tool_ctx.execution_process_id).await {
+            Ok(ctx) => {
+                match policy::remember_approval(
+                    pool,
+                    &ctx,
+                    &tool_ctx.tool_name,
+                    &tool_ctx.tool_input,
+                    scope,
+                )
+                .await
+                {
+                    Ok(Some(_)) => {}
+                    Ok(None) => tracing::info!(
+                        "Not remembering approval {} for '{}': no command or path to scope it to",
+                        id,
+                        tool_ctx.tool_name
+                    ),
+                    Err(e) => tracing::warn!("Failed to remember approval {}: {}", id, e),
+                }
+            }
+            Err(e) => tracing::warn!("Failed to load context to remember approval: {}", e),
+        }
+    }
+
+    /// Reload requests that were still pending when the server stopped. Their agents
+    /// are gone, so they stay answerable until they time out and the answer is passed
+    /// on as a follow-up. Call at startup, after orphaned executions are cleaned up.
+    pub async fn restore_pending(&self) -> Result<usize, ApprovalError> {
+        let pool = &self.db.pool;
+        let mut restored = 0;
+        for record in ApprovalRecord::find_pending(pool).await? {
+            // Paused requests wait for a human however long it takes
+            if record.timeout_action != ApprovalTimeoutAction::Pause {
+                if record.timeout_at <= chrono::Utc::now() {
+                    ApprovalRecord::resolve(pool, &record.id, &ApprovalStatus::TimedOut, None)
+                        .await?;
+                    continue;
+                }
+                self.spawn_interrupted_timeout(record.id.clone(), record.timeout_at);
+            }
+            self.interrupted.insert(record.id.clone(), record);
+            restored += 1;
+        }
+        Ok(restored)
+    }
+
+    fn spawn_interrupted_timeout(&self, id: String, timeout_at: chrono::DateTime<chrono::Utc>) {
+        let interrupted = self.interrupted.clone();
+        let completed = self.completed.clone();
+        let pool = self.db.pool.clone();
+        let to_wait = (timeout_at - chrono::Utc::now())
+            .to_std()
+            .unwrap_or_else(|_| StdDuration::from_secs(0));
+
+        tokio::spawn(async move {
+            tokio::time::sleep(to_wait).await;
+            if interrupted.remove(&id).is_some() {
+                completed.insert(id.clone(), ApprovalStatus::TimedOut);
+                if let Err(e) =
+                    ApprovalRecord::resolve(&pool, &id, &ApprovalStatus::TimedOut, None).await
+                {
+                    tracing::warn!("Failed to record timeout of approval '{}': {}", id, e);
+                }
+            }
+        });
+    }
+
     #[tracing::instrument(skip(self, id, timeout_at, waiter))]
     fn spawn_timeout_watcher(
         &self,
         id: String,
         timeout_at: chrono::DateTime<chrono::Utc>,
+        on_timeout: ApprovalTimeoutAction,
         waiter: ApprovalWaiter,
     ) {
         let pending = self.pending.clone();
         let completed = self.completed.clone();
         let msg_stores = self.msg_stores.clone();
+        let pool = self.db.pool.clone();
 
         let now = chrono::Utc::now();
         let to_wait = (timeout_at - now)
@@ -222,16 +361,27 @@ impl Approvals {
                 biased;
 
                 resolved = waiter.clone() => resolved,
-                _ = tokio::time::sleep_until(deadline) => ApprovalStatus::TimedOut,
+                _ = tokio::time::sleep_until(deadline) => match on_timeout {
+                    ApprovalTimeoutAction::Deny => ApprovalStatus::TimedOut,
+                    ApprovalTimeoutAction::Approve => ApprovalStatus::Approved,
+                    // Keep the agent waiting until someone answers
+                    ApprovalTimeoutAction::Pause => waiter.clone().await,
+                },
             };
 
-            let is_timeout = matches!(&status, ApprovalStatus::TimedOut);
             completed.insert(id.clone(), status.clone());
 
-            if is_timeout && let Some((_, pending_approval)) = pending.remove(&id) {
+            // Still pending means nobody answered and the timeout outcome applies
+            if let Some((_, pending_approval)) = pending.remove(&id) {
                 if pending_approval.response_tx.send(status.clone()).is_err() {
                     tracing::debug!("approval '{}' timeout notification receiver dropped", id);
                 }
+                if matches!(status, ApprovalStatus::Approved) {
+                    ensure_task_in_progress(&pool, pending_approval.execution_process_id).await;
+                }
+                if let Err(e) = ApprovalRecord::resolve(&pool, &id, &status, None).await {
+                    tracing::warn!("Failed to record timeout of approval '{}': {}", id, e);
+                }
 
                 let store = {
                     let map = msg_stores.read().await;
@@ -239,9 +389,10 @@ impl Approvals {
                 };
 
                 if let Some(store) = store {
-                    if let Some(updated_entry) = pending_approval
-                        .entry
-                        .with_tool_status(ToolStatus::TimedOut)
+                    let tool_status =
+                        ToolStatus::from_approval_status(&status).unwrap_or(ToolStatus::TimedOut);
+                    if let Some(updated_entry) =
+                        pending_approval.entry.with_tool_status(tool_status)
                     {
                         store.push_patch(ConversationPatch::replace(
                             pending_approval.entry_index,
@@ -263,11 +414,59 @@ impl Approvals {
         });
     }
 
+    /// Record an approval policy decision on the tool use entry, optionally also
+    /// settling its status when the policy resolved the call without a human.
+    pub async fn record_policy_decision(
+        &self,
+        execution_process_id: Uuid,
+        tool_call_id: &str,
+        decision: &ApprovalPolicyDecision,
+        status: Option<ToolStatus>,
+    ) {
+        let Some(store) = self.msg_store_by_id(&execution_process_id).await else {
+            tracing::warn!(
+                "No msg_store found for execution_process_id: {}",
+                execution_process_id
+            );
+            return;
+        };
+        let Some((idx, mut entry)) = find_matching_tool_use(store.clone(), tool_call_id) else {
+            tracing::debug!(
+                "No tool use entry to record approval policy decision for tool call '{}'",
+                tool_call_id
+            );
+            return;
+        };
+
+        if let Some(serde_json::Value::Object(metadata)) = entry.metadata.as_mut()
+            && let Ok(value) = serde_json::to_value(decision)
+        {
+            metadata.insert("approval_policy".to_string(), value);
+        }
+        if let Some(status) = status
+            && let Some(updated) = entry.with_tool_status(status)
+        {
+            entry = updated;
+        }
+        store.push_patch(ConversationPatch::replace(idx, entry));
+    }
+
     async fn msg_store_by_id(&self, execution_process_id: &Uuid) -> Option<Arc<MsgStore>> {
         let map = self.msg_stores.read().await;
         map.get(execution_process_id).cloned()
     }
 
+    /// Execution process and tool name of an unanswered request, including requests
+    /// interrupted by a restart
+    pub fn unanswered_request(&self, id: &str) -> Option<(Uuid, String)> {
+        if let Some(pending) = self.pending.get(id) {
+            return Some((pending.execution_process_id, pending.tool_name.clone()));
+        }
+        self.interrupted
+            .get(id)
+            .map(|record| (record.execution_process_id, record.tool_name.clone()))
+    }
+
     /// Find a pending ExitPlanMode approval for the given execution process
     pub fn find_pending_exit_plan_mode(
         &self,
@@ -307,6 +506,18 @@ pub(crate) async fn ensure_task_in_review(pool: &SqlitePool, execution_process_i
     }
 }
 
+async fn ensure_task_in_progress(pool: &SqlitePool, execution_process_id: Uuid) {
+    if let Ok(ctx) = ExecutionProcess::load_context(pool, execution_process_id).await
+        && ctx.task.status == TaskStatus::InReview
+        && let Err(e) = Task::update_status(pool, ctx.task.id, TaskStatus::InProgress).await
+    {
+        tracing::warn!(
+            "Failed to update task status to InProgress after approval response: {}",
+            e
+        );
+    }
+}
+
 /// Find a matching tool use entry that hasn't been assigned to an approval yet
 /// Matches by tool call id from tool metadata
 fn find_matching_tool_use(
@@ -349,11 +560,240 @@ fn find_matching_tool_use(
 mod tests {
     use std::sync::Arc;
 
-    use executors::logs::{ActionType, NormalizedEntry, NormalizedEntryType, ToolStatus};
+    use db::{
+        models::{
+            approval_record::ApprovalRecordStatus,
+            execution_process::{CreateExecutionProcess, ExecutionProcessRunReason},
+            task::CreateTask,
+            task_attempt::{CreateTaskAttempt, TaskAttempt},
+        },
+        test_utils::create_project,
+    };
+    use executors::{
+        actions::{
+            ExecutorAction, ExecutorActionType,
+            script::{ScriptContext, ScriptRequest, ScriptRequestLanguage},
+        },
+        executors::BaseCodingAgent,
+        logs::{ActionType, NormalizedEntry, NormalizedEntryType, ToolStatus},
+    };
     use utils::msg_store::MsgStore;
 
     use super::*;
 
+    /// Approvals backed by an in-memory database with one execution process, as a
+    /// freshly started server would see it
+    async fn restarted_approvals() -> (Approvals, Uuid, Uuid) {
+        let db = DBService::new_in_memory().await.unwrap();
+        let project = create_project(&db.pool, "/tmp/approvals").await;
+        let task = Task::create(
+            &db.pool,
+            &CreateTask::from_title_description(project.id, "Approve me".to_string(), None),
+            Uuid::new_v4(),
+        )
+        .await
+        .unwrap();
+        let attempt = TaskAttempt::create(
+            &db.pool,
+            &CreateTaskAttempt {
+                executor: BaseCodingAgent::ClaudeCode,
+                base_branch: "main".to_string(),
+                branch: "approve-me".to_string(),
+                stacked_on_attempt_id: None,
+            },
+            Uuid::new_v4(),
+            task.id,
+        )
+        .await
+        .unwrap();
+        let process = ExecutionProcess::create(
+            &db.pool,
+            &CreateExecutionProcess {
+                task_attempt_id: attempt.id,
+                executor_action: ExecutorAction::new(
+                    ExecutorActionType::ScriptRequest(ScriptRequest {
+                        script: "true".to_string(),
+                        language: ScriptRequestLanguage::Bash,
+                        context: ScriptContext::SetupScript,
+                    }),
+                    None,
+                ),
+                run_reason: ExecutionProcessRunReason::SetupScript,
+            },
+            Uuid::new_v4(),
+            None,
+        )
+        .await
+        .unwrap();
+
+        let approvals = Approvals {
+            db,
+            pending: Arc::new(DashMap::new()),
+            interrupted: Arc::new(DashMap::new()),
+            completed: Arc::new(DashMap::new()),
+            msg_stores: Arc::new(RwLock::new(HashMap::new())),
+            signer: ApprovalSigner::new([7; 32]),
+        };
+        (approvals, attempt.id, process.id)
+    }
+
+    /// A request left pending in the database by a previous server run
+    async fn leftover_request(
+        approvals: &Approvals,
+        execution_process_id: Uuid,
+        timeout_in: chrono::Duration,
+        on_timeout: ApprovalTimeoutAction,
+    ) -> String {
+        let request = ApprovalRequest {
+            id: Uuid::new_v4().to_string(),
+            tool_name: "Bash".to_string(),
+            tool_input: serde_json::json!({"command": "cargo test"}),
+            tool_call_id: Uuid::new_v4().to_string(),
+            execution_process_id,
+            created_at: chrono::Utc::now(),
+            timeout_at: chrono::Utc::now() + timeout_in,
+        };
+        ApprovalRecord::create(
+            &approvals.db.pool,
+            &request,
+            on_timeout,
+            &ApprovalStatus::Pending,
+            None,
+        )
+        .await
+        .unwrap();
+        request.id
+    }
+
+    async fn record_status(
+        approvals: &Approvals,
+        attempt_id: Uuid,
+        id: &str,
+    ) -> ApprovalRecordStatus {
+        ApprovalRecord::find_by_task_attempt_id(&approvals.db.pool, attempt_id)
+            .await
+            .unwrap()
+            .into_iter()
+            .find(|record| record.id == id)
+            .unwrap()
+            .status
+    }
+
+    fn approve(execution_process_id: Uuid) -> ApprovalResponse {
+        ApprovalResponse {
+            execution_process_id,
+            status: ApprovalStatus::Approved,
+            remember: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn restore_pending_keeps_unexpired_requests_answerable() {
+        let (approvals, _, process_id) = restarted_approvals().await;
+        let id = leftover_request(
+            &approvals,
+            process_id,
+            chrono::Duration::minutes(10),
+            ApprovalTimeoutAction::Deny,
+        )
+        .await;
+
+        assert_eq!(approvals.restore_pending().await.unwrap(), 1);
+        assert_eq!(
+            approvals.unanswered_request(&id),
+            Some((process_id, "Bash".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn restore_pending_times_out_expired_requests() {
+        let (approvals, attempt_id, process_id) = restarted_approvals().await;
+        let id = leftover_request(
+            &approvals,
+            process_id,
+            chrono::Duration::minutes(-1),
+            ApprovalTimeoutAction::Deny,
+        )
+        .await;
+
+        assert_eq!(approvals.restore_pending().await.unwrap(), 0);
+        assert_eq!(approvals.unanswered_request(&id), None);
+        assert_eq!(
+            record_status(&approvals, attempt_id, &id).await,
+            ApprovalRecordStatus::TimedOut
+        );
+    }
+
+    #[tokio::test]
+    async fn restore_pending_keeps_paused_requests_past_their_timeout() {
+        let (approvals, _, process_id) = restarted_approvals().await;
+        let id = leftover_request(
+            &approvals,
+            process_id,
+            chrono::Duration::minutes(-1),
+            ApprovalTimeoutAction::Pause,
+        )
+        .await;
+
+        assert_eq!(approvals.restore_pending().await.unwrap(), 1);
+        assert!(approvals.unanswered_request(&id).is_some());
+    }
+
+    #[tokio::test]
+    async fn answering_an_interrupted_request_asks_for_a_follow_up() {
+        let (approvals, attempt_id, process_id) = restarted_approvals().await;
+        let id = leftover_request(
+            &approvals,
+            process_id,
+            chrono::Duration::minutes(10),
+            ApprovalTimeoutAction::Deny,
+        )
+        .await;
+        approvals.restore_pending().await.unwrap();
+
+        let (status, tool_ctx) = approvals
+            .respond(&id, approve(process_id), Some("reviewer"))
+            .await
+            .unwrap();
+
+        assert!(matches!(status, ApprovalStatus::Approved));
+        assert!(tool_ctx.interrupted);
+        assert_eq!(tool_ctx.tool_name, "Bash");
+        assert_eq!(tool_ctx.execution_process_id, process_id);
+        assert_eq!(
+            record_status(&approvals, attempt_id, &id).await,
+            ApprovalRecordStatus::Approved
+        );
+        assert!(matches!(
+            approvals.respond(&id, approve(process_id), None).await,
+            Err(ApprovalError::AlreadyCompleted)
+        ));
+    }
+
+    #[tokio::test]
+    async fn interrupted_request_times_out_while_waiting() {
+        let (approvals, attempt_id, process_id) = restarted_approvals().await;
+        let id = leftover_request(
+            &approvals,
+            process_id,
+            chrono::Duration::milliseconds(50),
+            ApprovalTimeoutAction::Deny,
+        )
+        .await;
+        approvals.restore_pending().await.unwrap();
+
+        tokio::time::sleep(StdDuration::from_millis(300)).await;
+
+        assert!(matches!(
+            approvals.respond(&id, approve(process_id), None).await,
+            Err(ApprovalError::AlreadyCompleted)
+        ));
+        assert_eq!(
+            record_status(&approvals, attempt_id, &id).await,
+            ApprovalRecordStatus::TimedOut
+        );
+    }
+
     fn create_tool_use_entry(
         tool_name: &str,
         file_path: &str,
@@ -418,6 +858,7 @@ mod tests {
                 approval_id: "test-id".to_string(),
                 requested_at: chrono::Utc::now(),
                 timeout_at: chrono::Utc::now(),
+                on_timeout: ApprovalTimeoutAction::Deny,
             },
         );
         store.push_patch(
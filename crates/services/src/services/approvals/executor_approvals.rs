@@ -1,28 +1,146 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use db::{self, DBService};
-use executors::approvals::{ExecutorApprovalError, ExecutorApprovalService};
+use db::{
+    self, DBService,
+    models::{
+        approval_record::ApprovalRecord,
+        approval_rule::ApprovalRuleAction,
+        execution_process::{ExecutionContext, ExecutionProcess},
+    },
+};
+use executors::{
+    approvals::{ApprovalTimeoutAction, ExecutorApprovalError, ExecutorApprovalService},
+    logs::ToolStatus,
+};
 use serde_json::Value;
+use tokio::sync::RwLock;
 use utils::approvals::{ApprovalRequest, ApprovalStatus, CreateApprovalRequest};
 use uuid::Uuid;
 
-use crate::services::approvals::Approvals;
+use crate::services::{
+    approvals::{
+        Approvals,
+        policy::{ApprovalPolicy, ApprovalPolicyDecision},
+        timeouts::ApprovalTimeout,
+        webhook::{self, ApprovalWebhookPayload},
+    },
+    config::Config,
+    notification::NotificationService,
+};
 
 pub struct ExecutorApprovalBridge {
     approvals: Approvals,
     db: DBService,
+    config: Arc<RwLock<Config>>,
     execution_process_id: Uuid,
 }
 
 impl ExecutorApprovalBridge {
-    pub fn new(approvals: Approvals, db: DBService, execution_process_id: Uuid) -> Arc<Self> {
+    pub fn new(
+        approvals: Approvals,
+        db: DBService,
+        config: Arc<RwLock<Config>>,
+        execution_process_id: Uuid,
+    ) -> Arc<Self> {
         Arc::new(Self {
             approvals,
             db,
+            config,
             execution_process_id,
         })
     }
+
+    /// Evaluate the approval rules for the process's project and executor profile.
+    /// Errors loading the policy fall back to asking a human.
+    async fn policy_decision(
+        &self,
+        ctx: &ExecutionContext,
+        tool_name: &str,
+        tool_input: &Value,
+    ) -> Option<ApprovalPolicyDecision> {
+        let profile = ctx.execution_process.executor_profile_id();
+        match ApprovalPolicy::load(
+            &self.db.pool,
+            ctx.task.project_id,
+            ctx.task_attempt.id,
+            profile.as_ref(),
+        )
+        .await
+        {
+            Ok(policy) => policy.evaluate(tool_name, tool_input),
+            Err(e) => {
+                tracing::warn!("Failed to load approval rules: {}", e);
+                None
+            }
+        }
+    }
+
+    /// The configured timeout, with the project and profile overrides applied
+    async fn approval_timeout(&self, ctx: Option<&ExecutionContext>) -> ApprovalTimeout {
+        let defaults = {
+            let config = self.config.read().await;
+            ApprovalTimeout::new(
+                i64::from(config.approval_timeout_seconds),
+                config.approval_timeout_action,
+            )
+        };
+        let Some(ctx) = ctx else {
+            return defaults;
+        };
+        let profile = ctx.execution_process.executor_profile_id();
+        ApprovalTimeout::resolve(
+            &self.db.pool,
+            defaults,
+            ctx.task.project_id,
+            profile.as_ref(),
+        )
+        .await
+        .unwrap_or_else(|e| {
+            tracing::warn!("Failed to load approval timeout settings: {}", e);
+            defaults
+        })
+    }
+
+    async fn notify(&self, ctx: Option<&ExecutionContext>, title: &str, message: &str) {
+        let notifications = self.config.read().await.notifications.clone();
+        let title = match ctx {
+            Some(ctx) => format!("{title}: {}", ctx.task.title),
+            None => title.to_string(),
+        };
+        NotificationService::notify(notifications, &title, message).await;
+    }
+
+    /// Push the request to the approval webhook, if one is configured
+    async fn send_webhook(
+        &self,
+        ctx: Option<&ExecutionContext>,
+        request: &ApprovalRequest,
+        on_timeout: ApprovalTimeoutAction,
+    ) {
+        let webhook_config = self.config.read().await.approval_webhook.clone();
+        let Some(url) = webhook_config.url.filter(|url| !url.trim().is_empty()) else {
+            return;
+        };
+        let base_url = match webhook_config.public_url {
+            Some(public_url) => public_url,
+            None => match utils::port_file::read_port_file("anyon").await {
+                Ok(port) => format!("http://127.0.0.1:{port}"),
+                Err(e) => {
+                    tracing::warn!("Cannot build approval links without the server port: {}", e);
+                    return;
+                }
+            },
+        };
+        let payload = ApprovalWebhookPayload::new(
+            self.approvals.signer(),
+            &base_url,
+            request,
+            on_timeout,
+            ctx,
+        );
+        tokio::spawn(async move { webhook::send_webhook(&url, &payload).await });
+    }
 }
 
 #[async_trait]
@@ -33,8 +151,10 @@ impl ExecutorApprovalService for ExecutorApprovalBridge {
         tool_input: Value,
         tool_call_id: &str,
     ) -> Result<ApprovalStatus, ExecutorApprovalError> {
-        super::ensure_task_in_review(&self.db.pool, self.execution_process_id).await;
-
+        let ctx = ExecutionProcess::load_context(&self.db.pool, self.execution_process_id)
+            .await
+            .inspect_err(|e| tracing::warn!("Failed to load context for approval request: {}", e))
+            .ok();
         let request = ApprovalRequest::from_create(
             CreateApprovalRequest {
                 tool_name: tool_name.to_string(),
@@ -44,13 +164,95 @@ impl ExecutorApprovalService for ExecutorApprovalBridge {
             self.execution_process_id,
         );
 
-        let (_, waiter) = self
+        if let Some(ctx) = &ctx
+            && let Some(decision) = self
+                .policy_decision(ctx, tool_name, &request.tool_input)
+                .await
+        {
+            let (status, tool_status) = match decision.action {
+                ApprovalRuleAction::Allow => (Some(ApprovalStatus::Approved), None),
+                ApprovalRuleAction::Deny => {
+                    let reason = Some(format!("Denied by approval rule: {}", decision.rule));
+                    (
+                        Some(ApprovalStatus::Denied {
+                            reason: reason.clone(),
+                        }),
+                        Some(ToolStatus::Denied { reason }),
+                    )
+                }
+                // Fall through to a human decision, noting which rule asked for it
+                ApprovalRuleAction::Ask => (None, None),
+            };
+            self.approvals
+                .record_policy_decision(
+                    self.execution_process_id,
+                    tool_call_id,
+                    &decision,
+                    tool_status,
+                )
+                .await;
+            if let Some(status) = status {
+                if let Err(e) = ApprovalRecord::create(
+                    &self.db.pool,
+                    &request,
+                    ApprovalTimeoutAction::default(),
+                    &status,
+                    Some(decision.rule_id),
+                )
+                .await
+                {
+                    tracing::warn!("Failed to record approval rule decision: {}", e);
+                }
+                return Ok(status);
+            }
+        }
+
+        super::ensure_task_in_review(&self.db.pool, self.execution_process_id).await;
+
+        let timeout = self.approval_timeout(ctx.as_ref()).await;
+        let request = request.with_timeout(timeout.seconds);
+        let (request, waiter) = self
             .approvals
-            .create_with_waiter(request)
+            .create_with_waiter(request, timeout.action)
             .await
             .map_err(ExecutorApprovalError::request_failed)?;
 
-        let status = waiter.clone().await;
+        self.send_webhook(ctx.as_ref(), &request, timeout.action).await;
+
+        self.notify(
+            ctx.as_ref(),
+            "Approval needed",
+            &format!("The agent is waiting for approval to use {tool_name}"),
+        )
+        .await;
+
+        let status = match timeout.reminder_lead() {
+            Some(lead) => {
+                let remind_in =
+                    std::time::Duration::from_secs(timeout.seconds as u64).saturating_sub(lead);
+                tokio::select! {
+                    status = waiter.clone() => status,
+                    _ = tokio::time::sleep(remind_in) => {
+                        let outcome = match timeout.action {
+                            ApprovalTimeoutAction::Deny => "be denied",
+                            ApprovalTimeoutAction::Approve => "be approved automatically",
+                            ApprovalTimeoutAction::Pause => "keep the agent paused until you answer",
+                        };
+                        self.notify(
+                            ctx.as_ref(),
+                            "Approval expiring",
+                            &format!(
+                                "The approval for {tool_name} expires in {} minutes and will then {outcome}",
+                                lead.as_secs().div_ceil(60)
+                            ),
+                        )
+                        .await;
+                        waiter.clone().await
+                    }
+                }
+            }
+            None => waiter.clone().await,
+        };
 
         if matches!(status, ApprovalStatus::Pending) {
             return Err(ExecutorApprovalError::request_failed(
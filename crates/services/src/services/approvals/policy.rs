@@ -0,0 +1,682 @@
+//! Rule-based approval policy evaluated before a tool call waits for a human.
+//!
+//! Rules come from the `approval_rules` table. The most specific scope wins: rules
+//! remembered for the attempt, then rules for the project and executor profile, then
+//! project-only, then executor-only, then global rules. Within a scope rules are tried
+//! in `position` order and the first match decides. Deny rules are the exception: a
+//! matching deny rule in any scope wins over allow and ask rules, so a remembered
+//! approval can never unlock a call a broader rule forbids.
+//!
+//! Input patterns are searched for in the one field a call acts on: its command,
+//! file path or URL. Free text the agent writes, such as a description, is never
+//! matched, and when the field is missing only deny and ask rules apply.
+
+use db::models::{
+    approval_rule::{ApprovalRule, ApprovalRuleAction, CreateApprovalRule},
+    execution_process::ExecutionContext,
+};
+use executors::{executors::BaseCodingAgent, profile::ExecutorProfileId};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::SqlitePool;
+use utils::approvals::ApprovalRememberScope;
+use uuid::Uuid;
+
+/// Outcome of the policy for one tool call, recorded on the tool use entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApprovalPolicyDecision {
+    pub action: ApprovalRuleAction,
+    pub rule_id: Uuid,
+    /// Human-readable summary of the rule that matched
+    pub rule: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ApprovalPolicy {
+    rules: Vec<ApprovalRule>,
+}
+
+impl ApprovalPolicy {
+    pub fn new(mut rules: Vec<ApprovalRule>, profile: Option<&ExecutorProfileId>) -> Self {
+        rules.retain(|rule| applies_to_profile(rule, profile));
+        // Stable sort keeps `position` order within each scope
+        rules.sort_by_key(|rule| std::cmp::Reverse(specificity(rule)));
+        Self { rules }
+    }
+
+    /// Load the rules that apply to an attempt of a project and an executor profile.
+    pub async fn load(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        task_attempt_id: Uuid,
+        profile: Option<&ExecutorProfileId>,
+    ) -> Result<Self, sqlx::Error> {
+        let rules = ApprovalRule::find_applicable(pool, project_id, task_attempt_id).await?;
+        Ok(Self::new(rules, profile))
+    }
+
+    /// The decision of the first matching rule, or `None` when no rule matches and
+    /// the call falls back to asking a human. Any matching deny rule is checked
+    /// first. Plan approvals (`ExitPlanMode`) are always left to a human.
+    pub fn evaluate(&self, tool_name: &str, tool_input: &Value) -> Option<ApprovalPolicyDecision> {
+        if tool_name == "ExitPlanMode" {
+            return None;
+        }
+        let mut matching = self
+            .rules
+            .iter()
+            .filter(|rule| rule_matches(rule, tool_name, tool_input));
+        let first = matching.next()?;
+        let rule = if first.action == ApprovalRuleAction::Deny {
+            first
+        } else {
+            matching
+                .find(|rule| rule.action == ApprovalRuleAction::Deny)
+                .unwrap_or(first)
+        };
+        Some(ApprovalPolicyDecision {
+            action: rule.action,
+            rule_id: rule.id,
+            rule: describe_rule(rule),
+        })
+    }
+}
+
+/// Whether a rule may match its input pattern against this field. Only fields saying
+/// what a call acts on are allowed, not free text the agent writes.
+pub fn is_subject_field(field: &str) -> bool {
+    SUBJECT_KEYS.contains(&field)
+}
+
+/// Check that a rule's patterns compile, returning the regex error otherwise.
+pub fn validate_patterns(
+    tool_pattern: &str,
+    input_pattern: Option<&str>,
+) -> Result<(), regex::Error> {
+    tool_regex(tool_pattern)?;
+    if let Some(pattern) = input_pattern {
+        Regex::new(pattern)?;
+    }
+    Ok(())
+}
+
+pub fn describe_rule(rule: &ApprovalRule) -> String {
+    if let Some(description) = rule.description.as_deref().filter(|d| !d.trim().is_empty()) {
+        return description.to_string();
+    }
+    let action = match rule.action {
+        ApprovalRuleAction::Allow => "allow",
+        ApprovalRuleAction::Deny => "deny",
+        ApprovalRuleAction::Ask => "ask",
+    };
+    match &rule.input_pattern {
+        Some(input) => format!("{action} {} matching /{input}/", rule.tool_pattern),
+        None => format!("{action} {}", rule.tool_pattern),
+    }
+}
+
+/// Input fields holding what a tool call acts on, in order of preference: the command
+/// of a shell call, the file of an edit or read, or the URL of a fetch
+pub const SUBJECT_KEYS: [&str; 5] = ["command", "file_path", "notebook_path", "path", "url"];
+
+/// Persist an approval as an allow rule for the same tool and the same command, file
+/// or URL. Returns `None` without remembering anything when the input has no such
+/// field to pin the rule to, since a tool-wide rule would allow far more than what
+/// was approved.
+pub async fn remember_approval(
+    pool: &SqlitePool,
+    ctx: &ExecutionContext,
+    tool_name: &str,
+    tool_input: &Value,
+    scope: ApprovalRememberScope,
+) -> Result<Option<ApprovalRule>, sqlx::Error> {
+    let (project_id, task_attempt_id) = match scope {
+        ApprovalRememberScope::Attempt => (Some(ctx.task.project_id), Some(ctx.task_attempt.id)),
+        ApprovalRememberScope::Project => (Some(ctx.task.project_id), None),
+        ApprovalRememberScope::Global => (None, None),
+    };
+    let Some(rule) = remembered_rule(project_id, task_attempt_id, tool_name, tool_input) else {
+        return Ok(None);
+    };
+    ApprovalRule::create(pool, &rule).await.map(Some)
+}
+
+fn remembered_rule(
+    project_id: Option<Uuid>,
+    task_attempt_id: Option<Uuid>,
+    tool_name: &str,
+    tool_input: &Value,
+) -> Option<CreateApprovalRule> {
+    let subject = input_subject(tool_input);
+    // Without a subject only inputs with nothing to match against are safe to
+    // remember tool-wide
+    if subject.is_none() && !string_values(tool_input).is_empty() {
+        return None;
+    }
+    let description = match &subject {
+        Some((_, subject)) => format!("Always allow {tool_name}: {subject}"),
+        None => format!("Always allow {tool_name}"),
+    };
+
+    Some(CreateApprovalRule {
+        project_id,
+        task_attempt_id,
+        executor: None,
+        variant: None,
+        tool_pattern: regex::escape(tool_name),
+        input_pattern: subject
+            .as_ref()
+            .map(|(_, subject)| format!("^{}$", regex::escape(subject))),
+        input_field: subject.map(|(field, _)| field.to_string()),
+        action: ApprovalRuleAction::Allow,
+        description: Some(description),
+    })
+}
+
+fn tool_regex(pattern: &str) -> Result<Regex, regex::Error> {
+    Regex::new(&format!("^(?:{pattern})$"))
+}
+
+fn applies_to_profile(rule: &ApprovalRule, profile: Option<&ExecutorProfileId>) -> bool {
+    scope_matches_profile(rule.executor.as_ref(), rule.variant.as_deref(), profile)
+}
+
+/// Whether an executor/variant scope covers a profile; an empty scope covers all
+pub(super) fn scope_matches_profile(
+    executor: Option<&BaseCodingAgent>,
+    variant: Option<&str>,
+    profile: Option<&ExecutorProfileId>,
+) -> bool {
+    match (executor, profile) {
+        (None, _) => variant.is_none(),
+        (Some(_), None) => false,
+        (Some(executor), Some(profile)) => {
+            *executor == profile.executor
+                && variant.is_none_or(|v| profile.variant.as_deref() == Some(v))
+        }
+    }
+}
+
+fn specificity(rule: &ApprovalRule) -> u8 {
+    let attempt = if rule.task_attempt_id.is_some() { 4 } else { 0 };
+    let project = if rule.project_id.is_some() { 2 } else { 0 };
+    let executor = if rule.executor.is_some() { 1 } else { 0 };
+    attempt + project + executor
+}
+
+fn rule_matches(rule: &ApprovalRule, tool_name: &str, tool_input: &Value) -> bool {
+    let tool_matches = match tool_regex(&rule.tool_pattern) {
+        Ok(re) => re.is_match(tool_name),
+        Err(e) => {
+            tracing::warn!(
+                "Skipping approval rule {} with invalid tool pattern: {}",
+                rule.id,
+                e
+            );
+            return false;
+        }
+    };
+    if !tool_matches {
+        return false;
+    }
+    let Some(input_pattern) = &rule.input_pattern else {
+        return true;
+    };
+    let re = match Regex::new(input_pattern) {
+        Ok(re) => re,
+        Err(e) => {
+            tracing::warn!(
+                "Skipping approval rule {} with invalid input pattern: {}",
+                rule.id,
+                e
+            );
+            return false;
+        }
+    };
+    let subject = match &rule.input_field {
+        Some(field) => subject_value(tool_input, field),
+        None => input_subject(tool_input).map(|(_, subject)| subject),
+    };
+    match subject {
+        Some(subject) => re.is_match(&subject),
+        // Without the field the rule can't tell what the call does, so only rules
+        // that hold the call back apply
+        None => rule.action != ApprovalRuleAction::Allow,
+    }
+}
+
+/// The field a tool call acts on and its value. Input patterns are only matched
+/// against it, never against free text the agent writes such as a `description`.
+fn input_subject(tool_input: &Value) -> Option<(&'static str, String)> {
+    SUBJECT_KEYS
+        .iter()
+        .find_map(|key| subject_value(tool_input, key).map(|value| (*key, value)))
+}
+
+/// A string field, or a command given as an argument list, joined with spaces
+fn subject_value(tool_input: &Value, key: &str) -> Option<String> {
+    match tool_input.get(key)? {
+        Value::String(s) => Some(s.clone()),
+        Value::Array(items) => items
+            .iter()
+            .map(Value::as_str)
+            .collect::<Option<Vec<_>>>()
+            .map(|parts| parts.join(" ")),
+        _ => None,
+    }
+}
+
+/// All string leaves of a tool input
+fn string_values(value: &Value) -> Vec<&str> {
+    match value {
+        Value::String(s) => vec![s.as_str()],
+        Value::Array(items) => items.iter().flat_map(string_values).collect(),
+        Value::Object(map) => map.values().flat_map(string_values).collect(),
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use serde_json::json;
+
+    use super::*;
+
+    fn rule(
+        project_id: Option<Uuid>,
+        executor: Option<BaseCodingAgent>,
+        tool_pattern: &str,
+        input_pattern: Option<&str>,
+        action: ApprovalRuleAction,
+        position: i64,
+    ) -> ApprovalRule {
+        ApprovalRule {
+            id: Uuid::new_v4(),
+            project_id,
+            task_attempt_id: None,
+            executor,
+            variant: None,
+            tool_pattern: tool_pattern.to_string(),
+            input_pattern: input_pattern.map(str::to_string),
+            input_field: None,
+            action,
+            description: None,
+            position,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn claude() -> ExecutorProfileId {
+        ExecutorProfileId {
+            executor: BaseCodingAgent::ClaudeCode,
+            variant: None,
+        }
+    }
+
+    fn action(policy: &ApprovalPolicy, tool: &str, input: Value) -> Option<ApprovalRuleAction> {
+        policy.evaluate(tool, &input).map(|d| d.action)
+    }
+
+    #[test]
+    fn tool_pattern_must_match_the_whole_tool_name() {
+        let policy = ApprovalPolicy::new(
+            vec![rule(
+                None,
+                None,
+                "Read|Grep",
+                None,
+                ApprovalRuleAction::Allow,
+                0,
+            )],
+            Some(&claude()),
+        );
+
+        assert_eq!(
+            action(&policy, "Read", json!({"file_path": "a.rs"})),
+            Some(ApprovalRuleAction::Allow)
+        );
+        assert_eq!(action(&policy, "ReadMany", json!({})), None);
+    }
+
+    #[test]
+    fn input_pattern_matches_the_file_path_of_file_tools() {
+        let policy = ApprovalPolicy::new(
+            vec![rule(
+                None,
+                None,
+                "Edit",
+                Some(r"\.lock$"),
+                ApprovalRuleAction::Ask,
+                0,
+            )],
+            Some(&claude()),
+        );
+
+        assert_eq!(
+            action(
+                &policy,
+                "Edit",
+                json!({"file_path": "Cargo.lock", "new_string": "x"})
+            ),
+            Some(ApprovalRuleAction::Ask)
+        );
+        assert_eq!(
+            action(
+                &policy,
+                "Edit",
+                json!({"file_path": "Cargo.toml", "old_string": "Cargo.lock"})
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn a_matching_description_does_not_allow_a_harmful_command() {
+        let policy = ApprovalPolicy::new(
+            vec![rule(
+                None,
+                None,
+                "Bash",
+                Some(r"^cargo (test|check)"),
+                ApprovalRuleAction::Allow,
+                0,
+            )],
+            Some(&claude()),
+        );
+
+        assert_eq!(
+            action(
+                &policy,
+                "Bash",
+                json!({"command": "rm -rf ~", "description": "cargo test"})
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn commands_given_as_argument_lists_are_matched_joined() {
+        let policy = ApprovalPolicy::new(
+            vec![rule(
+                None,
+                None,
+                "bash",
+                Some(r"^cargo test$"),
+                ApprovalRuleAction::Allow,
+                0,
+            )],
+            None,
+        );
+
+        assert_eq!(
+            action(&policy, "bash", json!({"command": ["cargo", "test"]})),
+            Some(ApprovalRuleAction::Allow)
+        );
+    }
+
+    #[test]
+    fn input_rules_fail_closed_without_a_subject_field() {
+        let policy = ApprovalPolicy::new(
+            vec![
+                rule(
+                    None,
+                    None,
+                    "edit",
+                    Some(r"^src/"),
+                    ApprovalRuleAction::Allow,
+                    0,
+                ),
+                rule(
+                    None,
+                    None,
+                    "fetch",
+                    Some(r"^https://internal"),
+                    ApprovalRuleAction::Deny,
+                    1,
+                ),
+            ],
+            None,
+        );
+
+        assert_eq!(
+            action(
+                &policy,
+                "edit",
+                json!({"file_changes": {"/etc/passwd": {}}, "reason": "src/"})
+            ),
+            None
+        );
+        assert_eq!(
+            action(&policy, "fetch", json!({"prompt": "summarize"})),
+            Some(ApprovalRuleAction::Deny)
+        );
+    }
+
+    #[test]
+    fn first_matching_rule_in_position_order_decides() {
+        let policy = ApprovalPolicy::new(
+            vec![
+                rule(
+                    None,
+                    None,
+                    "Bash",
+                    Some(r"^git push"),
+                    ApprovalRuleAction::Ask,
+                    0,
+                ),
+                rule(None, None, "Bash", None, ApprovalRuleAction::Allow, 1),
+            ],
+            Some(&claude()),
+        );
+
+        assert_eq!(
+            action(&policy, "Bash", json!({"command": "git push origin main"})),
+            Some(ApprovalRuleAction::Ask)
+        );
+        assert_eq!(
+            action(&policy, "Bash", json!({"command": "git status"})),
+            Some(ApprovalRuleAction::Allow)
+        );
+    }
+
+    #[test]
+    fn more_specific_scope_is_tried_first() {
+        let project = Some(Uuid::new_v4());
+        let policy = ApprovalPolicy::new(
+            vec![
+                rule(None, None, "Bash", None, ApprovalRuleAction::Ask, 0),
+                rule(
+                    project,
+                    None,
+                    "Bash",
+                    Some(r"^cargo (test|check)"),
+                    ApprovalRuleAction::Allow,
+                    0,
+                ),
+            ],
+            Some(&claude()),
+        );
+
+        assert_eq!(
+            action(&policy, "Bash", json!({"command": "cargo test -p db"})),
+            Some(ApprovalRuleAction::Allow)
+        );
+        assert_eq!(
+            action(&policy, "Bash", json!({"command": "make"})),
+            Some(ApprovalRuleAction::Ask)
+        );
+    }
+
+    #[test]
+    fn deny_wins_over_an_earlier_allow_in_the_same_scope() {
+        let policy = ApprovalPolicy::new(
+            vec![
+                rule(None, None, "Bash", None, ApprovalRuleAction::Allow, 0),
+                rule(
+                    None,
+                    None,
+                    "Bash",
+                    Some("rm -rf"),
+                    ApprovalRuleAction::Deny,
+                    1,
+                ),
+            ],
+            Some(&claude()),
+        );
+
+        assert_eq!(
+            action(&policy, "Bash", json!({"command": "cd / && rm -rf tmp"})),
+            Some(ApprovalRuleAction::Deny)
+        );
+        assert_eq!(
+            action(&policy, "Bash", json!({"command": "ls"})),
+            Some(ApprovalRuleAction::Allow)
+        );
+    }
+
+    #[test]
+    fn deny_wins_over_a_more_specific_allow() {
+        let project = Some(Uuid::new_v4());
+        let policy = ApprovalPolicy::new(
+            vec![
+                rule(
+                    None,
+                    None,
+                    "Bash",
+                    Some("rm -rf"),
+                    ApprovalRuleAction::Deny,
+                    0,
+                ),
+                rule(project, None, "Bash", None, ApprovalRuleAction::Allow, 0),
+            ],
+            Some(&claude()),
+        );
+
+        let decision = policy
+            .evaluate("Bash", &json!({"command": "rm -rf target"}))
+            .unwrap();
+        assert_eq!(decision.action, ApprovalRuleAction::Deny);
+        assert_eq!(decision.rule, "deny Bash matching /rm -rf/");
+    }
+
+    #[test]
+    fn rules_for_another_executor_do_not_apply() {
+        let policy = ApprovalPolicy::new(
+            vec![rule(
+                None,
+                Some(BaseCodingAgent::Codex),
+                "Bash",
+                None,
+                ApprovalRuleAction::Allow,
+                0,
+            )],
+            Some(&claude()),
+        );
+
+        assert_eq!(action(&policy, "Bash", json!({"command": "ls"})), None);
+    }
+
+    #[test]
+    fn plan_approvals_are_never_decided_by_rules() {
+        let policy = ApprovalPolicy::new(
+            vec![rule(None, None, ".*", None, ApprovalRuleAction::Allow, 0)],
+            Some(&claude()),
+        );
+
+        assert_eq!(
+            action(&policy, "ExitPlanMode", json!({"plan": "do it"})),
+            None
+        );
+    }
+
+    #[test]
+    fn remembered_command_only_allows_the_same_command() {
+        let input = json!({"command": "cargo test", "description": "run tests"});
+        let created = remembered_rule(None, None, "Bash", &input).unwrap();
+        assert_eq!(created.input_pattern.as_deref(), Some("^cargo test$"));
+
+        let policy = ApprovalPolicy::new(
+            vec![rule(
+                None,
+                None,
+                &created.tool_pattern,
+                created.input_pattern.as_deref(),
+                created.action,
+                0,
+            )],
+            None,
+        );
+        assert_eq!(
+            action(&policy, "Bash", input),
+            Some(ApprovalRuleAction::Allow)
+        );
+        assert_eq!(
+            action(
+                &policy,
+                "Bash",
+                json!({"command": "cargo test && rm -rf /"})
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn remembered_file_tool_is_pinned_to_the_file_path() {
+        let created = remembered_rule(
+            None,
+            None,
+            "Edit",
+            &json!({"file_path": "src/main.rs", "old_string": "a", "new_string": "b"}),
+        )
+        .unwrap();
+        assert_eq!(created.input_pattern.as_deref(), Some(r"^src/main\.rs$"));
+        assert_eq!(created.input_field.as_deref(), Some("file_path"));
+    }
+
+    #[test]
+    fn remembered_rules_only_match_the_field_they_were_granted_for() {
+        let created = remembered_rule(None, None, "Grep", &json!({"path": "src"})).unwrap();
+        assert_eq!(created.input_field.as_deref(), Some("path"));
+
+        let mut remembered = rule(
+            None,
+            None,
+            &created.tool_pattern,
+            created.input_pattern.as_deref(),
+            created.action,
+            0,
+        );
+        remembered.input_field = created.input_field;
+        let policy = ApprovalPolicy::new(vec![remembered], None);
+
+        assert_eq!(
+            action(&policy, "Grep", json!({"path": "src", "pattern": "TODO"})),
+            Some(ApprovalRuleAction::Allow)
+        );
+        // The same text in another field does not count as the granted subject
+        assert_eq!(
+            action(&policy, "Grep", json!({"command": "src", "path": "/etc"})),
+            None
+        );
+        assert_eq!(action(&policy, "Grep", json!({"command": "src"})), None);
+    }
+
+    #[test]
+    fn approval_without_a_subject_is_not_remembered() {
+        assert!(
+            remembered_rule(
+                None,
+                None,
+                "mcp__db__query",
+                &json!({"sql": "DROP TABLE x"})
+            )
+            .is_none()
+        );
+
+        let created = remembered_rule(None, None, "TodoRead", &json!({})).unwrap();
+        assert_eq!(created.input_pattern, None);
+    }
+}
@@ -0,0 +1,200 @@
+//! How long a tool approval waits for an answer and what happens when nobody answers.
+//!
+//! The app config supplies the defaults. Overrides in `approval_timeout_settings` are
+//! applied from the broadest scope to the most specific one, each replacing only the
+//! settings it sets.
+
+use std::time::Duration;
+
+use db::models::approval_timeout_setting::ApprovalTimeoutSetting;
+use executors::{approvals::ApprovalTimeoutAction, profile::ExecutorProfileId};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+use super::policy::scope_matches_profile;
+
+/// Longest lead time for the reminder sent before an approval expires
+const MAX_REMINDER_LEAD: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ApprovalTimeout {
+    pub seconds: i64,
+    pub action: ApprovalTimeoutAction,
+}
+
+impl ApprovalTimeout {
+    /// A non-positive timeout, e.g. from a hand-edited config file, falls back to the
+    /// built-in default instead of expiring every approval at once.
+    pub fn new(seconds: i64, action: ApprovalTimeoutAction) -> Self {
+        let seconds = if seconds > 0 {
+            seconds
+        } else {
+            utils::approvals::APPROVAL_TIMEOUT_SECONDS
+        };
+        Self { seconds, action }
+    }
+
+    /// Apply the overrides that cover a project and executor profile to the defaults.
+    pub async fn resolve(
+        pool: &SqlitePool,
+        defaults: Self,
+        project_id: Uuid,
+        profile: Option<&ExecutorProfileId>,
+    ) -> Result<Self, sqlx::Error> {
+        let settings = ApprovalTimeoutSetting::find_applicable(pool, project_id).await?;
+        Ok(defaults.with_overrides(settings, profile))
+    }
+
+    fn with_overrides(
+        mut self,
+        mut settings: Vec<ApprovalTimeoutSetting>,
+        profile: Option<&ExecutorProfileId>,
+    ) -> Self {
+        settings
+            .retain(|s| scope_matches_profile(s.executor.as_ref(), s.variant.as_deref(), profile));
+        settings.sort_by_key(|s| {
+            let project = if s.project_id.is_some() { 2 } else { 0 };
+            let executor = if s.executor.is_some() { 1 } else { 0 };
+            project + executor
+        });
+        for setting in settings {
+            if let Some(seconds) = setting.timeout_seconds.filter(|s| *s > 0) {
+                self.seconds = seconds;
+            }
+            if let Some(action) = setting.timeout_action {
+                self.action = action;
+            }
+        }
+        self
+    }
+
+    /// How long before the deadline to remind the user, if the window allows one
+    pub fn reminder_lead(&self) -> Option<Duration> {
+        let total = Duration::from_secs(u64::try_from(self.seconds).ok()?);
+        let lead = (total / 5).min(MAX_REMINDER_LEAD);
+        (lead >= Duration::from_secs(10)).then_some(lead)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use executors::executors::BaseCodingAgent;
+
+    use super::*;
+
+    fn setting(
+        project_id: Option<Uuid>,
+        executor: Option<BaseCodingAgent>,
+        timeout_seconds: Option<i64>,
+        timeout_action: Option<ApprovalTimeoutAction>,
+    ) -> ApprovalTimeoutSetting {
+        ApprovalTimeoutSetting {
+            id: Uuid::new_v4(),
+            project_id,
+            executor,
+            variant: None,
+            timeout_seconds,
+            timeout_action,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn defaults() -> ApprovalTimeout {
+        ApprovalTimeout::new(3600, ApprovalTimeoutAction::Deny)
+    }
+
+    fn codex() -> ExecutorProfileId {
+        ExecutorProfileId {
+            executor: BaseCodingAgent::Codex,
+            variant: None,
+        }
+    }
+
+    #[test]
+    fn zero_default_timeout_falls_back_to_the_built_in_one() {
+        let timeout = ApprovalTimeout::new(0, ApprovalTimeoutAction::Deny);
+        assert_eq!(timeout.seconds, utils::approvals::APPROVAL_TIMEOUT_SECONDS);
+    }
+
+    #[test]
+    fn defaults_apply_without_overrides() {
+        assert_eq!(
+            defaults().with_overrides(vec![], Some(&codex())),
+            defaults()
+        );
+    }
+
+    #[test]
+    fn project_override_replaces_global_override() {
+        let project = Some(Uuid::new_v4());
+        let settings = vec![
+            setting(project, None, Some(600), None),
+            setting(None, None, Some(60), Some(ApprovalTimeoutAction::Approve)),
+        ];
+
+        assert_eq!(
+            defaults().with_overrides(settings, None),
+            ApprovalTimeout::new(600, ApprovalTimeoutAction::Approve)
+        );
+    }
+
+    #[test]
+    fn override_replaces_only_the_settings_it_sets() {
+        let project = Some(Uuid::new_v4());
+        let settings = vec![
+            setting(project, Some(BaseCodingAgent::Codex), Some(120), None),
+            setting(project, None, Some(600), Some(ApprovalTimeoutAction::Pause)),
+        ];
+
+        assert_eq!(
+            defaults().with_overrides(settings, Some(&codex())),
+            ApprovalTimeout::new(120, ApprovalTimeoutAction::Pause)
+        );
+    }
+
+    #[test]
+    fn overrides_for_another_executor_are_ignored() {
+        let settings = vec![setting(
+            None,
+            Some(BaseCodingAgent::ClaudeCode),
+            Some(60),
+            Some(ApprovalTimeoutAction::Approve),
+        )];
+
+        assert_eq!(
+            defaults().with_overrides(settings.clone(), Some(&codex())),
+            defaults()
+        );
+        assert_eq!(defaults().with_overrides(settings, None), defaults());
+    }
+
+    #[test]
+    fn non_positive_timeout_override_is_ignored() {
+        let settings = vec![setting(None, None, Some(0), None)];
+
+        assert_eq!(defaults().with_overrides(settings, None), defaults());
+    }
+
+    #[test]
+    fn reminder_lead_is_a_fifth_of_the_window_up_to_five_minutes() {
+        assert_eq!(
+            ApprovalTimeout::new(300, ApprovalTimeoutAction::Deny).reminder_lead(),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(defaults().reminder_lead(), Some(MAX_REMINDER_LEAD));
+    }
+
+    #[test]
+    fn short_windows_get_no_reminder() {
+        assert_eq!(
+            ApprovalTimeout::new(30, ApprovalTimeoutAction::Deny).reminder_lead(),
+            None
+        );
+        assert_eq!(
+            ApprovalTimeout::new(-1, ApprovalTimeoutAction::Deny).reminder_lead(),
+            None
+        );
+    }
+}
@@ -0,0 +1,326 @@
+//! Answering approval requests from outside the UI.
+//!
+//! Each request can be pushed to an outgoing webhook together with signed links to
+//! approve or deny it and a signed token for the callback endpoint. Tokens are bound to
+//! the approval id and the action they allow, and expire with the request. They are
+//! single use because an approval can only be answered while it is still pending.
+
+use std::time::Duration;
+
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::{DateTime, Utc};
+use db::models::execution_process::ExecutionContext;
+use executors::approvals::ApprovalTimeoutAction;
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::Sha256;
+use thiserror::Error;
+use utils::{
+    approvals::{ApprovalRememberScope, ApprovalRequest, ApprovalStatus},
+    assets::approval_signing_key_path,
+};
+use uuid::Uuid;
+
+type HmacSha256 = Hmac<Sha256>;
+
+const KEY_LEN: usize = 32;
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Links of requests that never time out stay valid this long
+const PAUSED_LINK_LIFETIME_DAYS: i64 = 7;
+
+#[derive(Debug, Error)]
+pub enum ApprovalLinkError {
+    #[error("malformed approval token")]
+    Malformed,
+    #[error("approval token expired")]
+    Expired,
+    #[error("invalid approval token signature")]
+    InvalidSignature,
+}
+
+/// What a signed token allows its holder to do with one approval request
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalLinkAction {
+    Approve,
+    Deny,
+    /// Any answer, through the callback endpoint
+    Respond,
+}
+
+impl ApprovalLinkAction {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Approve => "approve",
+            Self::Deny => "deny",
+            Self::Respond => "respond",
+        }
+    }
+
+    /// Whether a token for this action may be used to answer with `status`
+    pub fn permits(&self, status: &ApprovalStatus) -> bool {
+        match self {
+            Self::Approve => matches!(status, ApprovalStatus::Approved),
+            Self::Deny => matches!(status, ApprovalStatus::Denied { .. }),
+            Self::Respond => matches!(
+                status,
+                ApprovalStatus::Approved | ApprovalStatus::Denied { .. }
+            ),
+        }
+    }
+}
+
+/// Signs and verifies approval tokens with a key kept in the asset directory
+#[derive(Clone)]
+pub struct ApprovalSigner {
+    key: [u8; KEY_LEN],
+}
+
+impl std::fmt::Debug for ApprovalSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ApprovalSigner").finish_non_exhaustive()
+    }
+}
+
+impl ApprovalSigner {
+    pub fn new(key: [u8; KEY_LEN]) -> Self {
+        Self { key }
+    }
+
+    /// Load the signing key, creating it on first use. Falls back to a key that only
+    /// lives as long as the process, so links stop working after a restart.
+    pub fn load_or_create() -> Self {
+        let path = approval_signing_key_path();
+        if let Ok(bytes) = std::fs::read(&path)
+            && let Ok(key) = <[u8; KEY_LEN]>::try_from(bytes.as_slice())
+        {
+            return Self::new(key);
+        }
+
+        let mut key = [0u8; KEY_LEN];
+        rand::thread_rng().fill_bytes(&mut key);
+        if let Err(e) = write_key(&path, &key) {
+            tracing::warn!(
+                "Failed to store approval signing key at {:?}, approval links will not survive a restart: {}",
+                path,
+                e
+            );
+        }
+        Self::new(key)
+    }
+
+    /// Token allowing `action` on an approval until `expires_at`
+    pub fn sign(
+        &self,
+        approval_id: &str,
+        action: ApprovalLinkAction,
+        expires_at: DateTime<Utc>,
+    ) -> String {
+        let expires = expires_at.timestamp();
+        let signature = self
+            .mac(approval_id, action, expires)
+            .finalize()
+            .into_bytes();
+        format!("{expires}.{}", URL_SAFE_NO_PAD.encode(signature))
+    }
+
+    pub fn verify(
+        &self,
+        approval_id: &str,
+        action: ApprovalLinkAction,
+        token: &str,
+    ) -> Result<(), ApprovalLinkError> {
+        let (expires, signature) = token.split_once('.').ok_or(ApprovalLinkError::Malformed)?;
+        let expires: i64 = expires.parse().map_err(|_| ApprovalLinkError::Malformed)?;
+        let signature = URL_SAFE_NO_PAD
+            .decode(signature)
+            .map_err(|_| ApprovalLinkError::Malformed)?;
+
+        self.mac(approval_id, action, expires)
+            .verify_slice(&signature)
+            .map_err(|_| ApprovalLinkError::InvalidSignature)?;
+        if Utc::now().timestamp() >= expires {
+            return Err(ApprovalLinkError::Expired);
+        }
+        Ok(())
+    }
+
+    fn mac(&self, approval_id: &str, action: ApprovalLinkAction, expires: i64) -> HmacSha256 {
+        let mut mac =
+            HmacSha256::new_from_slice(&self.key).expect("HMAC accepts keys of any length");
+        mac.update(format!("{approval_id}.{}.{expires}", action.as_str()).as_bytes());
+        mac
+    }
+}
+
+fn write_key(path: &std::path::Path, key: &[u8]) -> std::io::Result<()> {
+    #[cfg(unix)]
+    {
+        use std::{io::Write, os::unix::fs::OpenOptionsExt};
+        let mut file = std::fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .mode(0o600)
+            .open(path)?;
+        file.write_all(key)
+    }
+    #[cfg(not(unix))]
+    {
+        std::fs::write(path, key)
+    }
+}
+
+/// Body of the callback endpoint, for chat tools that answer on a user's behalf
+#[derive(Debug, Clone, Deserialize)]
+pub struct ApprovalCallback {
+    /// `callback_token` from the webhook payload
+    pub token: String,
+    pub status: ApprovalStatus,
+    /// Recorded in the approval history as the responder
+    pub responded_by: Option<String>,
+    #[serde(default)]
+    pub remember: Option<ApprovalRememberScope>,
+}
+
+/// JSON posted to the approval webhook for each request that waits for a human
+#[derive(Debug, Clone, Serialize)]
+pub struct ApprovalWebhookPayload {
+    pub event: String,
+    pub approval_id: String,
+    pub tool_name: String,
+    pub tool_input: Value,
+    pub execution_process_id: Uuid,
+    pub task_id: Option<Uuid>,
+    pub task_title: Option<String>,
+    pub task_attempt_id: Option<Uuid>,
+    pub project_id: Option<Uuid>,
+    pub branch: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub timeout_at: DateTime<Utc>,
+    pub on_timeout: ApprovalTimeoutAction,
+    /// Signed one-time links a person can open to answer the request
+    pub approve_url: String,
+    pub deny_url: String,
+    /// Endpoint accepting an [`ApprovalCallback`] with `callback_token`
+    pub callback_url: String,
+    pub callback_token: String,
+}
+
+impl ApprovalWebhookPayload {
+    pub fn new(
+        signer: &ApprovalSigner,
+        base_url: &str,
+        request: &ApprovalRequest,
+        on_timeout: ApprovalTimeoutAction,
+        ctx: Option<&ExecutionContext>,
+    ) -> Self {
+        let expires_at = match on_timeout {
+            ApprovalTimeoutAction::Pause => {
+                request.timeout_at + chrono::Duration::days(PAUSED_LINK_LIFETIME_DAYS)
+            }
+            _ => request.timeout_at,
+        };
+        let base_url = base_url.trim_end_matches('/');
+        let id = &request.id;
+        let link = |action: ApprovalLinkAction| {
+            format!(
+                "{base_url}/api/approvals/{id}/link?action={}&token={}",
+                action.as_str(),
+                signer.sign(id, action, expires_at)
+            )
+        };
+
+        Self {
+            event: "approval_requested".to_string(),
+            approval_id: id.clone(),
+            tool_name: request.tool_name.clone(),
+            tool_input: request.tool_input.clone(),
+            execution_process_id: request.execution_process_id,
+            task_id: ctx.map(|ctx| ctx.task.id),
+            task_title: ctx.map(|ctx| ctx.task.title.clone()),
+            task_attempt_id: ctx.map(|ctx| ctx.task_attempt.id),
+            project_id: ctx.map(|ctx| ctx.task.project_id),
+            branch: ctx.map(|ctx| ctx.task_attempt.branch.clone()),
+            created_at: request.created_at,
+            timeout_at: request.timeout_at,
+            on_timeout,
+            approve_url: link(ApprovalLinkAction::Approve),
+            deny_url: link(ApprovalLinkAction::Deny),
+            callback_url: format!("{base_url}/api/approvals/{id}/callback"),
+            callback_token: signer.sign(id, ApprovalLinkAction::Respond, expires_at),
+        }
+    }
+}
+
+/// Post the payload to the webhook. Failures are logged; the request still waits in
+/// the UI.
+pub async fn send_webhook(url: &str, payload: &ApprovalWebhookPayload) {
+    let client = reqwest::Client::new();
+    match client
+        .post(url)
+        .timeout(WEBHOOK_TIMEOUT)
+        .json(payload)
+        .send()
+        .await
+    {
+        Ok(response) if !response.status().is_success() => {
+            tracing::warn!(
+                "Approval webhook for {} returned {}",
+                payload.approval_id,
+                response.status()
+            );
+        }
+        Ok(_) => {}
+        Err(e) => {
+            tracing::warn!(
+                "Failed to send approval webhook for {}: {}",
+                payload.approval_id,
+                e
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokens_are_bound_to_approval_and_action() {
+        let signer = ApprovalSigner::new([7; KEY_LEN]);
+        let expires_at = Utc::now() + chrono::Duration::minutes(5);
+        let token = signer.sign("a1", ApprovalLinkAction::Approve, expires_at);
+
+        assert!(
+            signer
+                .verify("a1", ApprovalLinkAction::Approve, &token)
+                .is_ok()
+        );
+        assert!(matches!(
+            signer.verify("a1", ApprovalLinkAction::Deny, &token),
+            Err(ApprovalLinkError::InvalidSignature)
+        ));
+        assert!(matches!(
+            signer.verify("a2", ApprovalLinkAction::Approve, &token),
+            Err(ApprovalLinkError::InvalidSignature)
+        ));
+        assert!(matches!(
+            ApprovalSigner::new([8; KEY_LEN]).verify("a1", ApprovalLinkAction::Approve, &token),
+            Err(ApprovalLinkError::InvalidSignature)
+        ));
+
+        let expired = signer.sign(
+            "a1",
+            ApprovalLinkAction::Approve,
+            Utc::now() - chrono::Duration::seconds(1),
+        );
+        assert!(matches!(
+            signer.verify("a1", ApprovalLinkAction::Approve, &expired),
+            Err(ApprovalLinkError::Expired)
+        ));
+    }
+}
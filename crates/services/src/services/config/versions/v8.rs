@@ -1,5 +1,7 @@
 use anyhow::Error;
-use executors::{executors::BaseCodingAgent, profile::ExecutorProfileId};
+use executors::{
+    approvals::ApprovalTimeoutAction, executors::BaseCodingAgent, profile::ExecutorProfileId,
+};
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 pub use v7::{
@@ -13,6 +15,29 @@ fn default_git_branch_prefix() -> String {
     "vk".to_string()
 }
 
+fn default_approval_timeout_seconds() -> u32 {
+    utils::approvals::APPROVAL_TIMEOUT_SECONDS as u32
+}
+
+/// Outgoing webhook for tool approval requests
+#[derive(Clone, Debug, Default, Serialize, Deserialize, TS)]
+pub struct ApprovalWebhookConfig {
+    /// Receives a JSON payload with signed approve/deny links for each approval request
+    pub url: Option<String>,
+    /// Address the links should point to when the server is reached through a tunnel or
+    /// proxy; defaults to the local address
+    pub public_url: Option<String>,
+}
+
+/// Semantic search over project documents
+#[derive(Clone, Debug, Default, Serialize, Deserialize, TS)]
+pub struct DocumentSearchConfig {
+    /// Shell command that embeds texts with a local model. It gets a JSON array of
+    /// strings on stdin and prints a JSON array of vectors, one per string. The
+    /// built-in embedder, which only compares wording, is used when unset.
+    pub embedding_command: Option<String>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, TS)]
 pub struct Config {
     pub config_version: String,
@@ -33,6 +58,23 @@ pub struct Config {
     pub git_branch_prefix: String,
     #[serde(default)]
     pub showcases: ShowcaseState,
+    /// Send new PR review comments to the agent as soon as they are ingested
+    #[serde(default)]
+    pub auto_address_review_comments: bool,
+    /// Send the output of failing PR checks to the agent when CI goes red
+    #[serde(default)]
+    pub auto_fix_failed_checks: bool,
+    /// How long tool approvals wait for an answer, unless a project or profile overrides it.
+    /// Must be positive: saving 0 is rejected, and a 0 in the file uses the default.
+    #[serde(default = "default_approval_timeout_seconds")]
+    pub approval_timeout_seconds: u32,
+    /// What happens to tool approvals nobody answers in time
+    #[serde(default)]
+    pub approval_timeout_action: ApprovalTimeoutAction,
+    #[serde(default)]
+    pub approval_webhook: ApprovalWebhookConfig,
+    #[serde(default)]
+    pub document_search: DocumentSearchConfig,
 }
 
 impl Config {
@@ -56,6 +98,12 @@ impl Config {
             language: old_config.language,
             git_branch_prefix: old_config.git_branch_prefix,
             showcases: old_config.showcases,
+            auto_address_review_comments: false,
+            auto_fix_failed_checks: false,
+            approval_timeout_seconds: default_approval_timeout_seconds(),
+            approval_timeout_action: ApprovalTimeoutAction::default(),
+            approval_webhook: ApprovalWebhookConfig::default(),
+            document_search: DocumentSearchConfig::default(),
         }
     }
 
@@ -104,6 +152,12 @@ impl Default for Config {
             language: UiLanguage::default(),
             git_branch_prefix: default_git_branch_prefix(),
             showcases: ShowcaseState::default(),
+            auto_address_review_comments: false,
+            auto_fix_failed_checks: false,
+            approval_timeout_seconds: default_approval_timeout_seconds(),
+            approval_timeout_action: ApprovalTimeoutAction::default(),
+            approval_webhook: ApprovalWebhookConfig::default(),
+            document_search: DocumentSearchConfig::default(),
         }
     }
 }
@@ -2,6 +2,7 @@ use std::{
     collections::HashMap,
     path::{Path, PathBuf},
     sync::Arc,
+    time::Duration,
 };
 
 use anyhow::{Error as AnyhowError, anyhow};
@@ -17,6 +18,7 @@ use db::{
         executor_session::{CreateExecutorSession, ExecutorSession},
         task::{Task, TaskStatus},
         task_attempt::{TaskAttempt, TaskAttemptError},
+        task_plan_step::TaskPlanStep,
     },
 };
 use executors::{
@@ -25,8 +27,14 @@ use executors::{
         coding_agent_initial::CodingAgentInitialRequest,
         script::{ScriptContext, ScriptRequest, ScriptRequestLanguage},
     },
-    executors::{ExecutorError, StandardCodingAgentExecutor},
-    logs::{NormalizedEntry, NormalizedEntryError, NormalizedEntryType, utils::ConversationPatch},
+    executors::{BaseCodingAgent, ExecutorError, StandardCodingAgentExecutor},
+    logs::{
+        ActionType, NormalizedEntry, NormalizedEntryError, NormalizedEntryType,
+        utils::{
+            ConversationPatch,
+            patch::{extract_normalized_entry_from_patch, normalized_entries_from_patches},
+        },
+    },
     profile::{ExecutorConfigs, ExecutorProfileId},
 };
 use futures::{StreamExt, future};
@@ -41,14 +49,18 @@ use utils::{
 use uuid::Uuid;
 
 use crate::services::{
+    documents::DocumentService,
     git::{GitService, GitServiceError},
     image::ImageService,
-    planning,
+    plan_progress,
     share::SharePublisher,
     worktree_manager::{WorktreeError, WorktreeManager},
 };
 pub type ContainerRef = String;
 
+/// Upper bound on waiting for stored logs to be normalized again
+const NORMALIZATION_TIMEOUT: Duration = Duration::from_secs(60);
+
 /// Data needed for background worktree cleanup (doesn't require DB access)
 #[derive(Debug, Clone)]
 pub struct WorktreeCleanupData {
@@ -314,116 +326,155 @@ pub trait ContainerService {
             )
         } else {
             // Fallback: load from DB and normalize
-            let log_records =
-                match ExecutionProcessLogs::find_by_execution_id(&self.db().pool, *id).await {
-                    Ok(records) if !records.is_empty() => records,
-                    Ok(_) => return None, // No logs exist
-                    Err(e) => {
-                        tracing::error!("Failed to fetch logs for execution {}: {}", id, e);
-                        return None;
-                    }
-                };
+            let temp_store = self.normalize_stored_logs(id).await?;
+            Some(
+                temp_store
+                    .history_plus_stream()
+                    .filter(|msg| future::ready(matches!(msg, Ok(LogMsg::JsonPatch(..)))))
+                    .chain(futures::stream::once(async {
+                        Ok::<_, std::io::Error>(LogMsg::Finished)
+                    }))
+                    .boxed(),
+            )
+        }
+    }
 
-            let raw_messages = match ExecutionProcessLogs::parse_logs(&log_records) {
-                Ok(msgs) => msgs,
+    /// Replay an execution's stored logs into a fresh store and spawn the executor's
+    /// normalizer on it. The normalizer tasks hold the store until they have read the
+    /// whole log, so the caller's handle is the last one left once they are done.
+    async fn normalize_stored_logs(&self, id: &Uuid) -> Option<Arc<MsgStore>> {
+        let log_records =
+            match ExecutionProcessLogs::find_by_execution_id(&self.db().pool, *id).await {
+                Ok(records) if !records.is_empty() => records,
+                Ok(_) => return None, // No logs exist
                 Err(e) => {
-                    tracing::error!("Failed to parse logs for execution {}: {}", id, e);
+                    tracing::error!("Failed to fetch logs for execution {}: {}", id, e);
                     return None;
                 }
             };
 
-            // Create temporary store and populate
-            // Include JsonPatch messages (already normalized) and Stdout/Stderr (need normalization)
-            let temp_store = Arc::new(MsgStore::new());
-            for msg in raw_messages {
-                if matches!(
-                    msg,
-                    LogMsg::Stdout(_) | LogMsg::Stderr(_) | LogMsg::JsonPatch(_)
-                ) {
-                    temp_store.push(msg);
-                }
+        let raw_messages = match ExecutionProcessLogs::parse_logs(&log_records) {
+            Ok(msgs) => msgs,
+            Err(e) => {
+                tracing::error!("Failed to parse logs for execution {}: {}", id, e);
+                return None;
             }
-            temp_store.push_finished();
+        };
 
-            let process = match ExecutionProcess::find_by_id(&self.db().pool, *id).await {
-                Ok(Some(process)) => process,
-                Ok(None) => {
-                    tracing::error!("No execution process found for ID: {}", id);
-                    return None;
-                }
-                Err(e) => {
-                    tracing::error!("Failed to fetch execution process {}: {}", id, e);
-                    return None;
-                }
-            };
+        // Create temporary store and populate
+        // Include JsonPatch messages (already normalized) and Stdout/Stderr (need normalization)
+        let temp_store = Arc::new(MsgStore::new());
+        for msg in raw_messages {
+            if matches!(
+                msg,
+                LogMsg::Stdout(_) | LogMsg::Stderr(_) | LogMsg::JsonPatch(_)
+            ) {
+                temp_store.push(msg);
+            }
+        }
+        temp_store.push_finished();
 
-            // Get the task attempt to determine correct directory
-            let task_attempt = match process.parent_task_attempt(&self.db().pool).await {
-                Ok(Some(task_attempt)) => task_attempt,
-                Ok(None) => {
-                    tracing::error!("No task attempt found for ID: {}", process.task_attempt_id);
-                    return None;
-                }
-                Err(e) => {
-                    tracing::error!(
-                        "Failed to fetch task attempt {}: {}",
-                        process.task_attempt_id,
-                        e
-                    );
-                    return None;
-                }
-            };
+        let process = match ExecutionProcess::find_by_id(&self.db().pool, *id).await {
+            Ok(Some(process)) => process,
+            Ok(None) => {
+                tracing::error!("No execution process found for ID: {}", id);
+                return None;
+            }
+            Err(e) => {
+                tracing::error!("Failed to fetch execution process {}: {}", id, e);
+                return None;
+            }
+        };
 
-            if let Err(err) = self.ensure_container_exists(&task_attempt).await {
-                tracing::warn!(
-                    "Failed to recreate worktree before log normalization for task attempt {}: {}",
-                    task_attempt.id,
-                    err
+        // Get the task attempt to determine correct directory
+        let task_attempt = match process.parent_task_attempt(&self.db().pool).await {
+            Ok(Some(task_attempt)) => task_attempt,
+            Ok(None) => {
+                tracing::error!("No task attempt found for ID: {}", process.task_attempt_id);
+                return None;
+            }
+            Err(e) => {
+                tracing::error!(
+                    "Failed to fetch task attempt {}: {}",
+                    process.task_attempt_id,
+                    e
                 );
+                return None;
             }
+        };
 
-            let current_dir = self.task_attempt_to_current_dir(&task_attempt);
+        if let Err(err) = self.ensure_container_exists(&task_attempt).await {
+            tracing::warn!(
+                "Failed to recreate worktree before log normalization for task attempt {}: {}",
+                task_attempt.id,
+                err
+            );
+        }
 
-            let executor_action = if let Ok(executor_action) = process.executor_action() {
-                executor_action
-            } else {
-                tracing::error!(
-                    "Failed to parse executor action: {:?}",
+        let current_dir = self.task_attempt_to_current_dir(&task_attempt);
+
+        let executor_action = if let Ok(executor_action) = process.executor_action() {
+            executor_action
+        } else {
+            tracing::error!(
+                "Failed to parse executor action: {:?}",
+                process.executor_action()
+            );
+            return None;
+        };
+
+        // Spawn normalizer on populated store
+        match executor_action.typ() {
+            ExecutorActionType::CodingAgentInitialRequest(request) => {
+                let executor = ExecutorConfigs::get_cached()
+                    .get_coding_agent_or_default(&request.executor_profile_id);
+                executor.normalize_logs(temp_store.clone(), &current_dir);
+            }
+            ExecutorActionType::CodingAgentFollowUpRequest(request) => {
+                let executor = ExecutorConfigs::get_cached()
+                    .get_coding_agent_or_default(&request.executor_profile_id);
+                executor.normalize_logs(temp_store.clone(), &current_dir);
+            }
+            _ => {
+                tracing::debug!(
+                    "Executor action doesn't support log normalization: {:?}",
                     process.executor_action()
                 );
                 return None;
-            };
+            }
+        }
+        Some(temp_store)
+    }
 
-            // Spawn normalizer on populated store
-            match executor_action.typ() {
-                ExecutorActionType::CodingAgentInitialRequest(request) => {
-                    let executor = ExecutorConfigs::get_cached()
-                        .get_coding_agent_or_default(&request.executor_profile_id);
-                    executor.normalize_logs(temp_store.clone(), &current_dir);
-                }
-                ExecutorActionType::CodingAgentFollowUpRequest(request) => {
-                    let executor = ExecutorConfigs::get_cached()
-                        .get_coding_agent_or_default(&request.executor_profile_id);
-                    executor.normalize_logs(temp_store.clone(), &current_dir);
-                }
-                _ => {
-                    tracing::debug!(
-                        "Executor action doesn't support log normalization: {:?}",
-                        process.executor_action()
-                    );
-                    return None;
+    /// Snapshot of an execution's normalized conversation. Logs of executions that are
+    /// no longer in memory are normalized again, waiting for the normalizer to finish.
+    async fn normalized_entries(&self, id: &Uuid) -> Option<Vec<NormalizedEntry>> {
+        let store = match self.get_msg_store_by_id(id).await {
+            Some(store) => store,
+            None => {
+                let store = self.normalize_stored_logs(id).await?;
+                let deadline = tokio::time::Instant::now() + NORMALIZATION_TIMEOUT;
+                while Arc::strong_count(&store) > 1 {
+                    if tokio::time::Instant::now() >= deadline {
+                        tracing::warn!(
+                            "Normalizing logs of execution {} did not finish in {:?}, returning partial conversation",
+                            id,
+                            NORMALIZATION_TIMEOUT
+                        );
+                        break;
+                    }
+                    tokio::time::sleep(Duration::from_millis(20)).await;
                 }
+                store
             }
-            Some(
-                temp_store
-                    .history_plus_stream()
-                    .filter(|msg| future::ready(matches!(msg, Ok(LogMsg::JsonPatch(..)))))
-                    .chain(futures::stream::once(async {
-                        Ok::<_, std::io::Error>(LogMsg::Finished)
-                    }))
-                    .boxed(),
-            )
-        }
+        };
+
+        let history = store.get_history();
+        let patches = history.iter().filter_map(|msg| match msg {
+            LogMsg::JsonPatch(patch) => Some(patch),
+            _ => None,
+        });
+        Some(normalized_entries_from_patches(patches))
     }
 
     fn spawn_stream_raw_logs_to_db(&self, execution_id: &Uuid) -> JoinHandle<()> {
@@ -500,6 +551,57 @@ pub trait ContainerService {
         })
     }
 
+    /// Check off the task's plan steps as the agent's todo list advances
+    fn spawn_plan_progress_tracker(&self, execution_id: &Uuid, task_id: Uuid) -> JoinHandle<()> {
+        let execution_id = *execution_id;
+        let msg_stores = self.msg_stores().clone();
+        let db = self.db().clone();
+
+        tokio::spawn(async move {
+            // Only tasks with an approved plan have steps to check off
+            match TaskPlanStep::find_by_task_id(&db.pool, task_id).await {
+                Ok(steps) if !steps.is_empty() => {}
+                Ok(_) => return,
+                Err(e) => {
+                    tracing::error!("Failed to load plan steps of task {}: {}", task_id, e);
+                    return;
+                }
+            }
+
+            let store = {
+                let map = msg_stores.read().await;
+                map.get(&execution_id).cloned()
+            };
+            let Some(store) = store else {
+                return;
+            };
+
+            let mut stream = store.history_plus_stream();
+            while let Some(Ok(msg)) = stream.next().await {
+                match msg {
+                    LogMsg::JsonPatch(patch) => {
+                        if let Some((_, entry)) = extract_normalized_entry_from_patch(&patch)
+                            && let NormalizedEntryType::ToolUse {
+                                action_type: ActionType::TodoManagement { todos, .. },
+                                ..
+                            } = entry.entry_type
+                            && let Err(e) =
+                                plan_progress::record_todos(&db.pool, task_id, &todos).await
+                        {
+                            tracing::error!(
+                                "Failed to update plan progress of task {}: {}",
+                                task_id,
+                                e
+                            );
+                        }
+                    }
+                    LogMsg::Finished => break,
+                    _ => {}
+                }
+            }
+        })
+    }
+
     async fn start_attempt(
         &self,
         task_attempt: &TaskAttempt,
@@ -534,11 +636,14 @@ pub trait ContainerService {
         );
         let mut prompt = ImageService::canonicalise_image_paths(&task.to_prompt(), &worktree_path);
 
-        if planning::is_planning_conversation_task(&task) {
+        if !task.kind.sends_initial_prompt() {
             // Planning Conversation은 초기 프롬프트를 비움
             prompt = String::new();
-        } else if task.status == TaskStatus::Plan {
+        } else if task.status == TaskStatus::Plan
+            && executor_profile_id.executor == BaseCodingAgent::ClaudeCode
+        {
             // Plan 칸반으로 이동한 경우: 워크플로우 커맨드를 바로 초기 프롬프트로 사용
+            // (다른 실행기는 PLAN 변형이 계획 지시를 프롬프트에 덧붙임)
             let mut base_prompt = format!(
                 "/anyon:custom:workflows:story-implementation-plan {}",
                 task.title
@@ -554,6 +659,16 @@ pub trait ContainerService {
             prompt = ImageService::canonicalise_image_paths(&base_prompt, &worktree_path);
         }
 
+        if task.kind.sends_initial_prompt() {
+            prompt = DocumentService::add_task_documents_to_prompt(
+                &self.db().pool,
+                &project,
+                task.id,
+                prompt,
+            )
+            .await;
+        }
+
         let cleanup_action = self.cleanup_action(project.cleanup_script);
 
         // Choose whether to execute the setup_script or coding agent first
@@ -759,6 +874,9 @@ pub trait ContainerService {
         }
 
         self.spawn_stream_raw_logs_to_db(&execution_process.id);
+        if run_reason == &ExecutionProcessRunReason::CodingAgent {
+            self.spawn_plan_progress_tracker(&execution_process.id, task.id);
+        }
         Ok(execution_process)
     }
 
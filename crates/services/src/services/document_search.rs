@@ -0,0 +1,789 @@
+//! Search over a project's documents. Full-text search ranks documents with SQLite
+//! FTS5, weighing titles, headings and code blocks above the rest of the text.
+//! Semantic search compares embeddings of document sections with the query's.
+//! Both indexes are brought up to date with the files on every search, which only
+//! re-indexes the documents whose contents changed.
+
+use std::{
+    collections::{HashMap, HashSet},
+    process::Stdio,
+};
+
+use db::models::{
+    document_search::{
+        DocumentEmbedding, DocumentSearchFields, DocumentSearchIndex, NewDocumentEmbedding,
+    },
+    project::Project,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::SqlitePool;
+use tokio::{io::AsyncWriteExt, process::Command};
+use ts_rs::TS;
+use utils::shell::get_shell_command;
+use uuid::Uuid;
+
+use crate::services::{
+    config::DocumentSearchConfig,
+    documents::{Document, DocumentError, DocumentService},
+};
+
+pub const DEFAULT_SEARCH_LIMIT: i64 = 20;
+
+const HASHING_MODEL: &str = "hashing-v1";
+const HASHING_DIMENSIONS: usize = 256;
+/// Sections longer than this are split at paragraph breaks before embedding
+const MAX_CHUNK_CHARS: usize = 1500;
+const SNIPPET_CHARS: usize = 240;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum DocumentSearchMode {
+    /// Documents containing the query's words
+    #[default]
+    Text,
+    /// Document sections closest in meaning to the query
+    Semantic,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct DocumentSearchResult {
+    pub document_id: Uuid,
+    pub title: String,
+    pub category: String,
+    /// Heading of the matching section, for semantic matches
+    pub heading: Option<String>,
+    /// Matching passage; in text mode the matched terms are wrapped in `**`
+    pub snippet: String,
+    /// Higher is better. Scores are only comparable within one search.
+    pub score: f64,
+}
+
+/// Section of a document that is embedded on its own
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Chunk {
+    heading: Option<String>,
+    content: String,
+}
+
+fn heading_text(line: &str) -> Option<&str> {
+    let trimmed = line.trim_start();
+    let level = trimmed.chars().take_while(|c| *c == '#').count();
+    if !(1..=6).contains(&level) {
+        return None;
+    }
+    let rest = &trimmed[level..];
+    (rest.is_empty() || rest.starts_with(char::is_whitespace))
+        .then(|| rest.trim().trim_end_matches('#').trim())
+}
+
+fn is_fence(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    trimmed.starts_with("```") || trimmed.starts_with("~~~")
+}
+
+/// Split a document into the fields of the full-text index
+fn search_fields(document: &Document) -> DocumentSearchFields {
+    let mut fields = DocumentSearchFields {
+        title: document.title.clone(),
+        ..Default::default()
+    };
+    let mut in_code_block = false;
+    for line in document.content.lines() {
+        let (field, text) = if is_fence(line) {
+            in_code_block = !in_code_block;
+            continue;
+        } else if in_code_block {
+            (&mut fields.code, line)
+        } else if let Some(heading) = heading_text(line) {
+            (&mut fields.headings, heading)
+        } else {
+            (&mut fields.body, line)
+        };
+        field.push_str(text);
+        field.push('\n');
+    }
+    fields
+}
+
+/// Sections of a document under each heading, with long ones split at paragraphs
+fn chunks(content: &str) -> Vec<Chunk> {
+    let mut sections: Vec<Chunk> = vec![Chunk {
+        heading: None,
+        content: String::new(),
+    }];
+    let mut in_code_block = false;
+    for line in content.lines() {
+        if is_fence(line) {
+            in_code_block = !in_code_block;
+        } else if !in_code_block && let Some(heading) = heading_text(line) {
+            sections.push(Chunk {
+                heading: Some(heading.to_string()),
+                content: String::new(),
+            });
+            continue;
+        }
+        let section = sections.last_mut().expect("sections start non-empty");
+        section.content.push_str(line);
+        section.content.push('\n');
+    }
+
+    let mut chunks = Vec::new();
+    for section in sections {
+        let mut current = String::new();
+        for paragraph in section.content.split("\n\n").map(str::trim) {
+            if paragraph.is_empty() {
+                continue;
+            }
+            if !current.is_empty() && current.len() + paragraph.len() > MAX_CHUNK_CHARS {
+                chunks.push(Chunk {
+                    heading: section.heading.clone(),
+                    content: std::mem::take(&mut current),
+                });
+            }
+            if !current.is_empty() {
+                current.push_str("\n\n");
+            }
+            current.push_str(paragraph);
+        }
+        if !current.is_empty() || section.heading.is_some() {
+            chunks.push(Chunk {
+                heading: section.heading,
+                content: current,
+            });
+        }
+    }
+    chunks
+}
+
+/// FTS5 query matching documents with all of the query's words, or any of them,
+/// as prefixes so that `retry` also finds `retries`
+fn match_query(query: &str, any: bool) -> Option<String> {
+    let terms: Vec<String> = query
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|term| !term.is_empty())
+        .map(|term| format!("\"{}\"*", term.to_lowercase()))
+        .collect();
+    (!terms.is_empty()).then(|| terms.join(if any { " OR " } else { " " }))
+}
+
+fn content_hash(document: &Document) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(document.title.as_bytes());
+    hasher.update([0]);
+    hasher.update(document.category.as_bytes());
+    hasher.update([0]);
+    hasher.update(document.content.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+fn snippet(text: &str) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    match text.char_indices().nth(SNIPPET_CHARS) {
+        Some((end, _)) => format!("{}…", &text[..end]),
+        None => text,
+    }
+}
+
+/// Turns texts into vectors for semantic search
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Embedder {
+    /// Built-in feature hashing of words and character trigrams. It needs no model
+    /// and matches related word forms, but not synonyms.
+    Hashing,
+    /// Shell command running a local model; see [`DocumentSearchConfig`]
+    Command(String),
+}
+
+impl Embedder {
+    pub fn from_config(config: &DocumentSearchConfig) -> Self {
+        match config.embedding_command.as_deref().map(str::trim) {
+            Some(command) if !command.is_empty() => Self::Command(command.to_string()),
+            _ => Self::Hashing,
+        }
+    }
+
+    /// Name the embeddings are stored under, so that changing the embedder re-embeds
+    fn model(&self) -> String {
+        match self {
+            Self::Hashing => HASHING_MODEL.to_string(),
+            Self::Command(command) => {
+                let hash = format!("{:x}", Sha256::digest(command.as_bytes()));
+                format!("command:{}", &hash[..16])
+            }
+        }
+    }
+
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, DocumentError> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+        let vectors = match self {
+            Self::Hashing => texts.iter().map(|text| hashing_embedding(text)).collect(),
+            Self::Command(command) => command_embeddings(command, texts).await?,
+        };
+        Ok(vectors.into_iter().map(normalized).collect())
+    }
+}
+
+fn fnv1a(text: &str) -> u64 {
+    text.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+fn hashing_embedding(text: &str) -> Vec<f32> {
+    let mut vector = vec![0.0; HASHING_DIMENSIONS];
+    let mut add = |feature: &str, weight: f32| {
+        let hash = fnv1a(feature);
+        let sign = if hash >> 63 == 0 { 1.0 } else { -1.0 };
+        vector[(hash % HASHING_DIMENSIONS as u64) as usize] += sign * weight;
+    };
+    for word in text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.chars().count() >= 2)
+    {
+        let word = word.to_lowercase();
+        add(&word, 1.0);
+        let padded: Vec<char> = format!("^{word}$").chars().collect();
+        for trigram in padded.windows(3) {
+            add(&trigram.iter().collect::<String>(), 0.5);
+        }
+    }
+    vector
+}
+
+async fn command_embeddings(
+    command: &str,
+    texts: &[String],
+) -> Result<Vec<Vec<f32>>, DocumentError> {
+    let (shell, shell_arg) = get_shell_command();
+    let mut child = Command::new(shell)
+        .arg(shell_arg)
+        .arg(command)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()?;
+
+    let input = serde_json::to_vec(texts).map_err(|e| DocumentError::Embedding(e.to_string()))?;
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    let writer = tokio::spawn(async move {
+        let result = stdin.write_all(&input).await;
+        drop(stdin);
+        result
+    });
+    let output = child.wait_with_output().await?;
+    writer
+        .await
+        .map_err(|e| DocumentError::Embedding(e.to_string()))??;
+
+    if !output.status.success() {
+        return Err(DocumentError::Embedding(format!(
+            "embedding command exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    let vectors: Vec<Vec<f32>> = serde_json::from_slice(&output.stdout).map_err(|e| {
+        DocumentError::Embedding(format!("embedding command printed invalid JSON: {e}"))
+    })?;
+    if vectors.len() != texts.len() {
+        return Err(DocumentError::Embedding(format!(
+            "embedding command returned {} vectors for {} texts",
+            vectors.len(),
+            texts.len()
+        )));
+    }
+    Ok(vectors)
+}
+
+fn normalized(mut vector: Vec<f32>) -> Vec<f32> {
+    let norm = vector.iter().map(|value| value * value).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        vector.iter_mut().for_each(|value| *value /= norm);
+    }
+    vector
+}
+
+/// Cosine similarity of two normalized vectors
+fn similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() {
+        return 0.0;
+    }
+    a.iter().zip(b).map(|(a, b)| a * b).sum()
+}
+
+pub struct DocumentSearchService;
+
+impl DocumentSearchService {
+    /// Documents containing the query's words, best first. Falls back to documents
+    /// containing any of them when none contains all.
+    pub async fn search(
+        pool: &SqlitePool,
+        project: &Project,
+        query: &str,
+        limit: i64,
+    ) -> Result<Vec<DocumentSearchResult>, DocumentError> {
+        let documents = Self::sync_index(pool, project).await?;
+
+        let mut hits = Vec::new();
+        for any in [false, true] {
+            let Some(match_query) = match_query(query, any) else {
+                return Ok(Vec::new());
+            };
+            hits = DocumentSearchIndex::search(pool, project.id, &match_query, limit).await?;
+            if !hits.is_empty() {
+                break;
+            }
+        }
+
+        Ok(hits
+            .into_iter()
+            .filter_map(|hit| {
+                let document = documents.get(&hit.document_id)?;
+                Some(DocumentSearchResult {
+                    document_id: document.id,
+                    title: document.title.clone(),
+                    category: document.category.clone(),
+                    heading: None,
+                    snippet: hit.snippet,
+                    score: -hit.rank,
+                })
+            })
+            .collect())
+    }
+
+    /// Documents with the sections closest to the query, best first
+    pub async fn semantic_search(
+        pool: &SqlitePool,
+        project: &Project,
+        query: &str,
+        limit: i64,
+        embedder: &Embedder,
+    ) -> Result<Vec<DocumentSearchResult>, DocumentError> {
+        if query.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+        let documents = Self::sync_index(pool, project).await?;
+        let embeddings = Self::sync_embeddings(pool, project, &documents, embedder).await?;
+        let query_vector = embedder
+            .embed(&[query.to_string()])
+            .await?
+            .pop()
+            .unwrap_or_default();
+
+        let mut best: HashMap<Uuid, (f32, &DocumentEmbedding)> = HashMap::new();
+        for embedding in &embeddings {
+            let score = similarity(&query_vector, &embedding.vector());
+            let entry = best
+                .entry(embedding.document_id)
+                .or_insert((score, embedding));
+            if score > entry.0 {
+                *entry = (score, embedding);
+            }
+        }
+        let mut ranked: Vec<_> = best
+            .into_values()
+            .filter(|(score, _)| *score > 0.0)
+            .collect();
+        ranked.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+        Ok(ranked
+            .into_iter()
+            .filter_map(|(score, embedding)| {
+                let document = documents.get(&embedding.document_id)?;
+                let text = if embedding.content.is_empty() {
+                    embedding.heading.as_deref().unwrap_or_default()
+                } else {
+                    embedding.content.as_str()
+                };
+                Some(DocumentSearchResult {
+                    document_id: document.id,
+                    title: document.title.clone(),
+                    category: document.category.clone(),
+                    heading: embedding.heading.clone(),
+                    snippet: snippet(text),
+                    score: score as f64,
+                })
+            })
+            .take(limit.max(0) as usize)
+            .collect())
+    }
+
+    /// Re-index the documents that changed since they were indexed and drop the
+    /// ones that are gone
+    async fn sync_index(
+        pool: &SqlitePool,
+        project: &Project,
+    ) -> Result<HashMap<Uuid, Document>, DocumentError> {
+        let documents = DocumentService::all(pool, project).await?;
+        let mut indexed = DocumentSearchIndex::content_hashes(pool, project.id).await?;
+        for document in &documents {
+            let hash = content_hash(document);
+            if indexed.remove(&document.id).as_ref() != Some(&hash) {
+                DocumentSearchIndex::upsert(
+                    pool,
+                    document.id,
+                    project.id,
+                    &hash,
+                    &search_fields(document),
+                )
+                .await?;
+            }
+        }
+        for removed in indexed.into_keys() {
+            DocumentSearchIndex::remove(pool, removed).await?;
+        }
+        Ok(documents
+            .into_iter()
+            .map(|document| (document.id, document))
+            .collect())
+    }
+
+    /// Embed the sections of the documents that changed since they were embedded,
+    /// in one batch, and return all of the project's embeddings
+    async fn sync_embeddings(
+        pool: &SqlitePool,
+        project: &Project,
+        documents: &HashMap<Uuid, Document>,
+        embedder: &Embedder,
+    ) -> Result<Vec<DocumentEmbedding>, DocumentError> {
+        let model = embedder.model();
+        let embeddings = DocumentEmbedding::find_by_project_id(pool, project.id, &model).await?;
+        let embedded: HashSet<(Uuid, &str)> = embeddings
+            .iter()
+            .map(|embedding| (embedding.document_id, embedding.content_hash.as_str()))
+            .collect();
+
+        let stale: Vec<(&Document, String, Vec<Chunk>)> = documents
+            .values()
+            .filter_map(|document| {
+                let hash = content_hash(document);
+                if embedded.contains(&(document.id, hash.as_str())) {
+                    return None;
+                }
+                let chunks = chunks(&document.content);
+                (!chunks.is_empty()).then_some((document, hash, chunks))
+            })
+            .collect();
+        if stale.is_empty() {
+            return Ok(embeddings);
+        }
+
+        let texts: Vec<String> = stale
+            .iter()
+            .flat_map(|(document, _, chunks)| {
+                chunks.iter().map(move |chunk| {
+                    [
+                        Some(document.title.as_str()),
+                        chunk.heading.as_deref(),
+                        Some(chunk.content.as_str()),
+                    ]
+                    .into_iter()
+                    .flatten()
+                    .collect::<Vec<_>>()
+                    .join("\n")
+                })
+            })
+            .collect();
+        let mut vectors = embedder.embed(&texts).await?.into_iter();
+        for (document, hash, chunks) in stale {
+            let chunks: Vec<NewDocumentEmbedding> = chunks
+                .into_iter()
+                .zip(vectors.by_ref())
+                .map(|(chunk, vector)| NewDocumentEmbedding {
+                    heading: chunk.heading,
+                    content: chunk.content,
+                    vector,
+                })
+                .collect();
+            DocumentEmbedding::replace_for_document(
+                pool,
+                document.id,
+                project.id,
+                &model,
+                &hash,
+                &chunks,
+            )
+            .await?;
+        }
+
+        Ok(DocumentEmbedding::find_by_project_id(pool, project.id, &model).await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use db::{DBService, test_utils::create_project};
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::services::documents::{CreateDocument, UpdateDocument};
+
+    fn document(content: &str) -> Document {
+        Document {
+            id: Uuid::nil(),
+            project_id: Uuid::nil(),
+            title: "Billing".to_string(),
+            content: content.to_string(),
+            category: "technology".to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    async fn project(db: &DBService, repo: &TempDir) -> Project {
+        create_project(&db.pool, repo.path()).await
+    }
+
+    async fn create(db: &DBService, project: &Project, title: &str, content: &str) -> Document {
+        DocumentService::create(
+            &db.pool,
+            project,
+            &CreateDocument {
+                title: title.to_string(),
+                content: content.to_string(),
+                category: "technology".to_string(),
+            },
+        )
+        .await
+        .unwrap()
+    }
+
+    fn titles(results: Vec<DocumentSearchResult>) -> Vec<String> {
+        results.into_iter().map(|result| result.title).collect()
+    }
+
+    #[test]
+    fn search_fields_separate_headings_code_and_body() {
+        let fields = search_fields(&document(
+            "Intro text.\n\n## Retries\nFailed charges are retried.\n\n```rust\n# not a heading\nretry();\n```\n# Refunds #\nManual only.",
+        ));
+
+        assert_eq!(fields.title, "Billing");
+        assert_eq!(fields.headings, "Retries\nRefunds\n");
+        assert_eq!(fields.code, "# not a heading\nretry();\n");
+        assert_eq!(
+            fields.body,
+            "Intro text.\n\nFailed charges are retried.\n\nManual only.\n"
+        );
+    }
+
+    #[test]
+    fn chunks_start_at_headings_outside_code_blocks() {
+        let sections = chunks(
+            "Intro text.\n\n## Retries\nFailed charges are retried.\n\n```sh\n# not a heading\n```\n# Refunds #\n",
+        );
+
+        assert_eq!(
+            sections,
+            vec![
+                Chunk {
+                    heading: None,
+                    content: "Intro text.".to_string(),
+                },
+                Chunk {
+                    heading: Some("Retries".to_string()),
+                    content: "Failed charges are retried.\n\n```sh\n# not a heading\n```"
+                        .to_string(),
+                },
+                Chunk {
+                    heading: Some("Refunds".to_string()),
+                    content: String::new(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn long_sections_are_split_at_paragraphs() {
+        let paragraph = "word ".repeat(140).trim().to_string();
+        let content = format!("## Notes\n{paragraph}\n\n{paragraph}\n\n{paragraph}");
+
+        let sections = chunks(&content);
+
+        assert_eq!(sections.len(), 2);
+        assert!(
+            sections
+                .iter()
+                .all(|chunk| chunk.heading.as_deref() == Some("Notes")
+                    && chunk.content.len() <= MAX_CHUNK_CHARS)
+        );
+        assert_eq!(sections[0].content, format!("{paragraph}\n\n{paragraph}"));
+        assert_eq!(sections[1].content, paragraph);
+    }
+
+    #[test]
+    fn match_queries_use_word_prefixes() {
+        assert_eq!(
+            match_query("billing retries!", false).as_deref(),
+            Some("\"billing\"* \"retries\"*")
+        );
+        assert_eq!(
+            match_query("Billing retries", true).as_deref(),
+            Some("\"billing\"* OR \"retries\"*")
+        );
+        assert_eq!(match_query(" -- ", true), None);
+    }
+
+    #[tokio::test]
+    async fn title_matches_rank_above_body_matches() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let repo = TempDir::new().unwrap();
+        let project = project(&db, &repo).await;
+        create(
+            &db,
+            &project,
+            "Billing",
+            "Failed charges go through retries.",
+        )
+        .await;
+        create(&db, &project, "Retries", "How background jobs back off.").await;
+
+        let results = DocumentSearchService::search(&db.pool, &project, "retries", 10)
+            .await
+            .unwrap();
+
+        assert_eq!(titles(results), vec!["Retries", "Billing"]);
+    }
+
+    #[tokio::test]
+    async fn documents_with_any_word_match_when_none_has_all() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let repo = TempDir::new().unwrap();
+        let project = project(&db, &repo).await;
+        create(&db, &project, "Billing", "Failed charges are retried.").await;
+        create(&db, &project, "Onboarding", "The welcome screen.").await;
+        create(&db, &project, "Hiring", "Interview loop.").await;
+
+        let mut found = titles(
+            DocumentSearchService::search(&db.pool, &project, "charges welcome", 10)
+                .await
+                .unwrap(),
+        );
+        found.sort();
+
+        assert_eq!(found, vec!["Billing", "Onboarding"]);
+    }
+
+    #[tokio::test]
+    async fn edited_documents_are_reindexed() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let repo = TempDir::new().unwrap();
+        let project = project(&db, &repo).await;
+        let billing = create(&db, &project, "Billing", "Charges are retried.").await;
+
+        DocumentService::update(
+            &db.pool,
+            &project,
+            billing.id,
+            &UpdateDocument {
+                title: None,
+                content: Some("Charges are refunded.".to_string()),
+                category: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        assert!(
+            DocumentSearchService::search(&db.pool, &project, "retried", 10)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(
+            titles(
+                DocumentSearchService::search(&db.pool, &project, "refunded", 10)
+                    .await
+                    .unwrap()
+            ),
+            vec!["Billing"]
+        );
+    }
+
+    #[tokio::test]
+    async fn deleted_documents_leave_the_index() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let repo = TempDir::new().unwrap();
+        let project = project(&db, &repo).await;
+        let charges = create(&db, &project, "Charges", "Failed charges are retried.").await;
+        create(&db, &project, "Refunds", "Refunds reverse charges.").await;
+        DocumentSearchService::search(&db.pool, &project, "charges", 10)
+            .await
+            .unwrap();
+
+        DocumentService::delete(&db.pool, &project, charges.id)
+            .await
+            .unwrap();
+
+        let indexed: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM document_search")
+            .fetch_one(&db.pool)
+            .await
+            .unwrap();
+        assert_eq!(indexed, 1);
+        assert_eq!(
+            titles(
+                DocumentSearchService::search(&db.pool, &project, "charges", 1)
+                    .await
+                    .unwrap()
+            ),
+            vec!["Refunds"]
+        );
+    }
+
+    #[test]
+    fn hashing_embeddings_are_closer_for_related_wording() {
+        let query = normalized(hashing_embedding("billing retries"));
+        let related = normalized(hashing_embedding("Failed charges are retried by billing"));
+        let unrelated = normalized(hashing_embedding("Design of the onboarding screen"));
+
+        assert!(similarity(&query, &related) > similarity(&query, &unrelated));
+    }
+
+    #[tokio::test]
+    async fn semantic_search_returns_the_closest_section() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let repo = TempDir::new().unwrap();
+        let project = project(&db, &repo).await;
+        create(
+            &db,
+            &project,
+            "Payments",
+            "## Onboarding\nThe welcome screen design.\n\n## Retries\nFailed charges are retried nightly.",
+        )
+        .await;
+        create(&db, &project, "Hiring", "Interview loop and offers.").await;
+
+        let results = DocumentSearchService::semantic_search(
+            &db.pool,
+            &project,
+            "retry failed charges",
+            10,
+            &Embedder::Hashing,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(results[0].title, "Payments");
+        assert_eq!(results[0].heading.as_deref(), Some("Retries"));
+        assert_eq!(results[0].snippet, "Failed charges are retried nightly.");
+    }
+
+    #[tokio::test]
+    async fn command_embeddings_must_match_the_texts() {
+        let embedder = Embedder::Command("echo '[[1.0, 0.0]]'".to_string());
+        assert_ne!(embedder.model(), Embedder::Hashing.model());
+
+        let vectors = embedder.embed(&["one".to_string()]).await.unwrap();
+        assert_eq!(vectors, vec![vec![1.0, 0.0]]);
+
+        let result = embedder
+            .embed(&["one".to_string(), "two".to_string()])
+            .await;
+        assert!(matches!(result, Err(DocumentError::Embedding(_))));
+    }
+}
@@ -0,0 +1,79 @@
+//! Filling in document templates. Templates are markdown with `{{name}}`
+//! placeholders for details of the project the document is created in.
+
+use chrono::{NaiveDate, Utc};
+use db::models::project::Project;
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+
+lazy_static! {
+    static ref PLACEHOLDER: Regex = Regex::new(r"\{\{\s*([a-z_]+)\s*\}\}").unwrap();
+}
+
+/// Values of the placeholders templates can use
+#[derive(Debug, Clone)]
+pub struct TemplateContext {
+    pub project_name: String,
+    /// Name of the repository folder
+    pub repo_name: String,
+    pub repo_path: String,
+    pub date: NaiveDate,
+}
+
+impl TemplateContext {
+    pub fn for_project(project: &Project) -> Self {
+        Self {
+            project_name: project.name.clone(),
+            repo_name: project
+                .git_repo_path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            repo_path: project.git_repo_path.to_string_lossy().to_string(),
+            date: Utc::now().date_naive(),
+        }
+    }
+
+    fn value(&self, placeholder: &str) -> Option<String> {
+        match placeholder {
+            "project_name" => Some(self.project_name.clone()),
+            "repo_name" => Some(self.repo_name.clone()),
+            "repo_path" => Some(self.repo_path.clone()),
+            "date" => Some(self.date.format("%Y-%m-%d").to_string()),
+            _ => None,
+        }
+    }
+}
+
+/// Replace the placeholders of a template. Unknown placeholders are left as they are.
+pub fn render(template: &str, context: &TemplateContext) -> String {
+    PLACEHOLDER
+        .replace_all(template, |captures: &Captures| {
+            context
+                .value(&captures[1])
+                .unwrap_or_else(|| captures[0].to_string())
+        })
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_known_placeholders() {
+        let context = TemplateContext {
+            project_name: "Anyon".to_string(),
+            repo_name: "anyon".to_string(),
+            repo_path: "/work/anyon".to_string(),
+            date: NaiveDate::from_ymd_opt(2025, 12, 17).unwrap(),
+        };
+        assert_eq!(
+            render(
+                "# {{project_name}} ({{ repo_name }})\n{{date}} {{repo_path}} {{owner}}",
+                &context
+            ),
+            "# Anyon (anyon)\n2025-12-17 /work/anyon {{owner}}"
+        );
+    }
+}
@@ -0,0 +1,1146 @@
+//! Markdown documents in a project's `anyon-docs` folder: PRD, design and technical
+//! docs. The files are the source of truth for contents; SQLite gives each document
+//! a stable id and keeps a revision history of its saves.
+//!
+//! Ids follow renames made through the app. A file renamed or moved outside the app
+//! keeps its id only when its contents still match the document's latest revision;
+//! otherwise it shows up as a new document and links to the old one are dropped.
+
+use std::{
+    collections::{HashMap, hash_map::DefaultHasher},
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use chrono::{DateTime, Utc};
+use db::models::{
+    document::DocumentEntry,
+    document_revision::{DocumentRevision, DocumentRevisionSource, DocumentRevisionSummary},
+    document_template::{CONVERSATION_PACK_ID, DocumentTemplate},
+    project::Project,
+    task_document::TaskDocument,
+};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use thiserror::Error;
+use tokio::fs::{self, OpenOptions};
+use ts_rs::TS;
+use utils::diff::create_unified_diff;
+use uuid::Uuid;
+
+use crate::services::document_templates::{self, TemplateContext};
+
+pub const DOCS_FOLDER: &str = "anyon-docs";
+
+// Category mapping
+const DEFAULT_CATEGORY: &str = "planning";
+const CATEGORY_FOLDERS: &[(&str, &str)] = &[
+    ("planning", "plan"),
+    ("design", "design"),
+    ("technology", "tech"),
+    ("conversation", "conversation"),
+];
+
+/// Folders listed when no category is requested; conversation docs have their own tab
+const DEFAULT_LIST_FOLDERS: &[&str] = &["plan", "design", "tech"];
+
+/// Linked documents larger than this are referenced by path in prompts instead of inlined
+const MAX_INLINE_PROMPT_BYTES: usize = 32 * 1024;
+
+// Per-project locks held while a file and its stored entry change together, and while
+// scans match files to entries, so a scan never sees a renamed file without its entry
+lazy_static::lazy_static! {
+    static ref DOCUMENT_WRITE_LOCKS: Mutex<HashMap<Uuid, Arc<tokio::sync::Mutex<()>>>> =
+        Mutex::new(HashMap::new());
+}
+
+fn write_lock(project: &Project) -> Arc<tokio::sync::Mutex<()>> {
+    DOCUMENT_WRITE_LOCKS
+        .lock()
+        .unwrap()
+        .entry(project.id)
+        .or_insert_with(|| Arc::new(tokio::sync::Mutex::new(())))
+        .clone()
+}
+
+#[derive(Debug, Error)]
+pub enum DocumentError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("Document not found")]
+    NotFound,
+    #[error("Document revision not found")]
+    RevisionNotFound,
+    #[error("Document template not found")]
+    TemplateNotFound,
+    #[error("Document with this title already exists")]
+    AlreadyExists,
+    #[error("문서 저장 경로에 쓸 수 없습니다: {0}")]
+    NotWritable(String),
+    #[error("Document embedding failed: {0}")]
+    Embedding(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct Document {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub title: String,
+    pub content: String,
+    pub category: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct DocumentListResponse {
+    pub items: Vec<Document>,
+    pub total: i64,
+}
+
+#[derive(Debug, Deserialize, TS)]
+#[ts(export)]
+pub struct CreateDocument {
+    pub title: String,
+    pub content: String,
+    pub category: String,
+}
+
+#[derive(Debug, Deserialize, TS)]
+#[ts(export)]
+pub struct UpdateDocument {
+    pub title: Option<String>,
+    pub content: Option<String>,
+    pub category: Option<String>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct InstantiateDocumentTemplate {
+    pub template_id: Uuid,
+    /// Defaults to the template's title
+    pub title: Option<String>,
+    /// Defaults to the template's category
+    pub category: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, TS)]
+pub struct DocumentRevisionDiff {
+    pub from: i64,
+    pub to: i64,
+    /// Unified diff of the content; empty when it did not change
+    pub unified_diff: String,
+}
+
+fn category_to_folder(category: &str) -> &'static str {
+    for (cat, folder) in CATEGORY_FOLDERS {
+        if *cat == category {
+            return folder;
+        }
+    }
+    CATEGORY_FOLDERS
+        .iter()
+        .find(|(cat, _)| *cat == DEFAULT_CATEGORY)
+        .map(|(_, folder)| *folder)
+        .unwrap_or("plan")
+}
+
+fn folder_to_category(folder: &str) -> &'static str {
+    for (cat, f) in CATEGORY_FOLDERS {
+        if *f == folder {
+            return cat;
+        }
+    }
+    DEFAULT_CATEGORY
+}
+
+// Ids documents were addressed by before they were stored, derived from the file path
+fn legacy_path_id(path: &Path) -> Uuid {
+    let mut hasher = DefaultHasher::new();
+    path.to_string_lossy().hash(&mut hasher);
+    let hash = hasher.finish();
+
+    // Create UUID from hash bytes
+    let bytes: [u8; 16] = {
+        let mut b = [0u8; 16];
+        b[0..8].copy_from_slice(&hash.to_le_bytes());
+        b[8..16].copy_from_slice(&hash.to_be_bytes());
+        b
+    };
+    Uuid::from_bytes(bytes)
+}
+
+// Sanitize filename
+fn sanitize_filename(title: &str) -> String {
+    title
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' || c == ' ' || c == '.' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+/// Path of a document as stored in `documents.path`
+fn relative_path(folder: &str, filename: &str) -> String {
+    format!("{folder}/{filename}")
+}
+
+/// Docs base path of a project
+pub fn docs_path(project: &Project) -> PathBuf {
+    project.git_repo_path.join(DOCS_FOLDER)
+}
+
+// Writeability check: ensure directory exists and is writable by doing a probe write.
+async fn ensure_writable_dir(path: &Path) -> Result<(), DocumentError> {
+    fs::create_dir_all(path).await?;
+    let probe = path.join(".write-test.tmp");
+    match OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(&probe)
+        .await
+    {
+        Ok(_) => {
+            let _ = fs::remove_file(&probe).await;
+            Ok(())
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => Err(
+            DocumentError::NotWritable(path.to_string_lossy().to_string()),
+        ),
+        Err(e) => Err(DocumentError::Io(e)),
+    }
+}
+
+// Ensure the conversation docs of the planning conversation pack exist
+async fn ensure_conversation_docs(
+    pool: &SqlitePool,
+    project: &Project,
+) -> Result<(), DocumentError> {
+    let path = docs_path(project).join(category_to_folder("conversation"));
+    ensure_writable_dir(&path).await?;
+
+    let context = TemplateContext::for_project(project);
+    for template in DocumentTemplate::find_by_pack_id(pool, CONVERSATION_PACK_ID)
+        .await?
+        .into_iter()
+        .filter(|template| template.category == "conversation")
+    {
+        let file_path = path.join(format!("{}.md", sanitize_filename(&template.title)));
+        if !file_path.exists() {
+            fs::write(
+                &file_path,
+                document_templates::render(&template.content, &context),
+            )
+            .await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// A document file on disk together with its stored id
+struct DocumentFile {
+    entry: DocumentEntry,
+    folder: &'static str,
+    filename: String,
+}
+
+impl DocumentFile {
+    fn path(&self, project: &Project) -> PathBuf {
+        docs_path(project).join(self.folder).join(&self.filename)
+    }
+
+    async fn read(&self, project: &Project) -> Result<Document, DocumentError> {
+        let file_path = self.path(project);
+        let content = fs::read_to_string(&file_path).await?;
+        let metadata = fs::metadata(&file_path).await?;
+
+        let title = self
+            .filename
+            .strip_suffix(".md")
+            .unwrap_or(&self.filename)
+            .to_string();
+
+        let created_at = metadata
+            .created()
+            .map(DateTime::<Utc>::from)
+            .unwrap_or(self.entry.created_at);
+        let updated_at = metadata
+            .modified()
+            .map(DateTime::<Utc>::from)
+            .unwrap_or_else(|_| Utc::now());
+
+        Ok(Document {
+            id: self.entry.id,
+            project_id: project.id,
+            title,
+            content,
+            category: folder_to_category(self.folder).to_string(),
+            created_at,
+            updated_at,
+        })
+    }
+}
+
+pub struct DocumentService;
+
+impl DocumentService {
+    /// Documents of a category, or of the planning, design and technology categories
+    pub async fn list(
+        pool: &SqlitePool,
+        project: &Project,
+        category: Option<&str>,
+    ) -> Result<Vec<Document>, DocumentError> {
+        // If requesting conversation docs, ensure fixed files exist
+        if category == Some("conversation") {
+            ensure_conversation_docs(pool, project).await?;
+        }
+        let folders = match category {
+            Some(category) => vec![category_to_folder(category)],
+            None => DEFAULT_LIST_FOLDERS.to_vec(),
+        };
+
+        let mut items = Vec::new();
+        for file in Self::scan(pool, project)
+            .await?
+            .into_iter()
+            .filter(|file| folders.contains(&file.folder))
+        {
+            match file.read(project).await {
+                Ok(doc) => items.push(doc),
+                Err(e) => tracing::warn!("Failed to read document {}: {:?}", file.filename, e),
+            }
+        }
+        Ok(items)
+    }
+
+    /// Documents of every category, conversation docs included
+    pub async fn all(pool: &SqlitePool, project: &Project) -> Result<Vec<Document>, DocumentError> {
+        let mut items = Vec::new();
+        for file in Self::scan(pool, project).await? {
+            match file.read(project).await {
+                Ok(doc) => items.push(doc),
+                Err(e) => tracing::warn!("Failed to read document {}: {:?}", file.filename, e),
+            }
+        }
+        Ok(items)
+    }
+
+    pub async fn get(
+        pool: &SqlitePool,
+        project: &Project,
+        document_id: Uuid,
+    ) -> Result<Document, DocumentError> {
+        // Ensure conversation docs are present before search (covers direct get by ID)
+        ensure_conversation_docs(pool, project).await?;
+        Self::find(pool, project, document_id)
+            .await?
+            .read(project)
+            .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        project: &Project,
+        data: &CreateDocument,
+    ) -> Result<Document, DocumentError> {
+        let folder = category_to_folder(&data.category);
+        let dir = docs_path(project).join(folder);
+        ensure_writable_dir(&dir).await?;
+        let filename = format!("{}.md", sanitize_filename(&data.title));
+        let file_path = dir.join(&filename);
+
+        let lock = write_lock(project);
+        let guard = lock.lock().await;
+        if file_path.exists() {
+            return Err(DocumentError::AlreadyExists);
+        }
+        fs::write(&file_path, &data.content).await?;
+
+        let path = relative_path(folder, &filename);
+        // A document whose file was removed outside the app may still hold the path
+        DocumentEntry::delete_by_path(pool, project.id, &path).await?;
+        let entry = DocumentEntry::create(pool, Uuid::new_v4(), project.id, &path).await?;
+        drop(guard);
+
+        let document = DocumentFile {
+            entry,
+            folder,
+            filename,
+        }
+        .read(project)
+        .await?;
+        Self::record(pool, &document, DocumentRevisionSource::Edit, None).await?;
+        Ok(document)
+    }
+
+    /// Create a document from a template, filling in the project's details
+    pub async fn create_from_template(
+        pool: &SqlitePool,
+        project: &Project,
+        data: &InstantiateDocumentTemplate,
+    ) -> Result<Document, DocumentError> {
+        let template = DocumentTemplate::find_by_id(pool, data.template_id)
+            .await?
+            .ok_or(DocumentError::TemplateNotFound)?;
+        let context = TemplateContext::for_project(project);
+        Self::create(
+            pool,
+            project,
+            &CreateDocument {
+                title: data.title.clone().unwrap_or(template.title),
+                content: document_templates::render(&template.content, &context),
+                category: data.category.clone().unwrap_or(template.category),
+            },
+        )
+        .await
+    }
+
+    /// Create the documents of a template pack, e.g. for a new project. Templates
+    /// whose document already exists are skipped.
+    pub async fn apply_template_pack(
+        pool: &SqlitePool,
+        project: &Project,
+        pack_id: Uuid,
+    ) -> Result<Vec<Document>, DocumentError> {
+        let mut documents = Vec::new();
+        for template in DocumentTemplate::find_by_pack_id(pool, pack_id).await? {
+            let data = InstantiateDocumentTemplate {
+                template_id: template.id,
+                title: None,
+                category: None,
+            };
+            match Self::create_from_template(pool, project, &data).await {
+                Ok(document) => documents.push(document),
+                Err(DocumentError::AlreadyExists) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(documents)
+    }
+
+    /// Save new contents, renaming or moving the file when the title or category
+    /// changes. The document keeps its id either way.
+    pub async fn update(
+        pool: &SqlitePool,
+        project: &Project,
+        document_id: Uuid,
+        data: &UpdateDocument,
+    ) -> Result<Document, DocumentError> {
+        Self::save(
+            pool,
+            project,
+            document_id,
+            data,
+            DocumentRevisionSource::Edit,
+            None,
+        )
+        .await
+    }
+
+    /// Delete the file together with the document's history
+    pub async fn delete(
+        pool: &SqlitePool,
+        project: &Project,
+        document_id: Uuid,
+    ) -> Result<(), DocumentError> {
+        let file = Self::find(pool, project, document_id).await?;
+        let path = file.path(project);
+        if let Some(dir) = path.parent() {
+            ensure_writable_dir(dir).await?;
+        }
+        let lock = write_lock(project);
+        let _guard = lock.lock().await;
+        fs::remove_file(&path).await?;
+        DocumentEntry::delete(pool, file.entry.id).await?;
+        Ok(())
+    }
+
+    /// Revisions of a document, newest first. The newest always matches the file.
+    pub async fn revisions(
+        pool: &SqlitePool,
+        project: &Project,
+        document_id: Uuid,
+    ) -> Result<Vec<DocumentRevisionSummary>, DocumentError> {
+        Self::sync_history(pool, project, document_id).await?;
+        Ok(DocumentRevision::find_summaries(pool, document_id).await?)
+    }
+
+    pub async fn revision(
+        pool: &SqlitePool,
+        project: &Project,
+        document_id: Uuid,
+        revision: i64,
+    ) -> Result<DocumentRevision, DocumentError> {
+        Self::sync_history(pool, project, document_id).await?;
+        DocumentRevision::find_by_revision(pool, document_id, revision)
+            .await?
+            .ok_or(DocumentError::RevisionNotFound)
+    }
+
+    /// Diff between two revisions; `to` defaults to the current contents
+    pub async fn diff(
+        pool: &SqlitePool,
+        project: &Project,
+        document_id: Uuid,
+        from: i64,
+        to: Option<i64>,
+    ) -> Result<DocumentRevisionDiff, DocumentError> {
+        let current = Self::sync_history(pool, project, document_id).await?;
+        let from = DocumentRevision::find_by_revision(pool, document_id, from)
+            .await?
+            .ok_or(DocumentError::RevisionNotFound)?;
+        let to = match to {
+            Some(to) => DocumentRevision::find_by_revision(pool, document_id, to).await?,
+            None => Some(current),
+        }
+        .ok_or(DocumentError::RevisionNotFound)?;
+
+        let unified_diff = if from.content == to.content {
+            String::new()
+        } else {
+            create_unified_diff(&format!("{}.md", to.title), &from.content, &to.content)
+        };
+        Ok(DocumentRevisionDiff {
+            from: from.revision,
+            to: to.revision,
+            unified_diff,
+        })
+    }
+
+    /// Bring back the title, category and content of a past revision as a new revision
+    pub async fn restore(
+        pool: &SqlitePool,
+        project: &Project,
+        document_id: Uuid,
+        revision: i64,
+    ) -> Result<Document, DocumentError> {
+        let past = Self::revision(pool, project, document_id, revision).await?;
+        Self::save(
+            pool,
+            project,
+            document_id,
+            &UpdateDocument {
+                title: Some(past.title),
+                content: Some(past.content),
+                category: Some(past.category),
+            },
+            DocumentRevisionSource::Restore,
+            Some(past.revision),
+        )
+        .await
+    }
+
+    /// Documents linked to a task, in link order. Links to removed files are skipped.
+    pub async fn linked_to_task(
+        pool: &SqlitePool,
+        project: &Project,
+        task_id: Uuid,
+    ) -> Result<Vec<Document>, DocumentError> {
+        let mut documents = Vec::new();
+        for file in Self::linked_files(pool, project, task_id).await? {
+            documents.push(file.read(project).await?);
+        }
+        Ok(documents)
+    }
+
+    /// Prompt section with the contents of a task's linked documents, for the first
+    /// coding agent run of its attempts. `None` when no linked document has content.
+    pub async fn task_prompt_section(
+        pool: &SqlitePool,
+        project: &Project,
+        task_id: Uuid,
+    ) -> Result<Option<String>, DocumentError> {
+        let mut sections = Vec::new();
+        for file in Self::linked_files(pool, project, task_id).await? {
+            let document = file.read(project).await?;
+            let content = document.content.trim();
+            if content.is_empty() {
+                continue;
+            }
+            let body = if content.len() > MAX_INLINE_PROMPT_BYTES {
+                format!(
+                    "Too long to include here, read it from `{}`.",
+                    file.path(project).display()
+                )
+            } else {
+                content.to_string()
+            };
+            sections.push(format!(
+                "### {} ({})\n\n{}",
+                document.title, document.category, body
+            ));
+        }
+        if sections.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(format!(
+            "## Project documents\n\nThis task is based on the project documents below. Follow them, and point out where the task contradicts them.\n\n{}",
+            sections.join("\n\n")
+        )))
+    }
+
+    /// Append the task's linked documents to the first prompt of an attempt. The
+    /// prompt is left as it is when the documents cannot be read.
+    pub async fn add_task_documents_to_prompt(
+        pool: &SqlitePool,
+        project: &Project,
+        task_id: Uuid,
+        prompt: String,
+    ) -> String {
+        match Self::task_prompt_section(pool, project, task_id).await {
+            Ok(Some(documents)) => format!("{prompt}\n\n{documents}"),
+            Ok(None) => prompt,
+            Err(e) => {
+                tracing::warn!("Failed to load documents linked to task {}: {}", task_id, e);
+                prompt
+            }
+        }
+    }
+
+    async fn linked_files(
+        pool: &SqlitePool,
+        project: &Project,
+        task_id: Uuid,
+    ) -> Result<Vec<DocumentFile>, DocumentError> {
+        let links = TaskDocument::find_by_task_id(pool, task_id).await?;
+        if links.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut files: HashMap<Uuid, DocumentFile> = Self::scan(pool, project)
+            .await?
+            .into_iter()
+            .map(|file| (file.entry.id, file))
+            .collect();
+        Ok(links
+            .iter()
+            .filter_map(|link| files.remove(&link.document_id))
+            .collect())
+    }
+
+    async fn save(
+        pool: &SqlitePool,
+        project: &Project,
+        document_id: Uuid,
+        data: &UpdateDocument,
+        source: DocumentRevisionSource,
+        restored_from: Option<i64>,
+    ) -> Result<Document, DocumentError> {
+        // Ensure conversation docs directory exists (for moves or new category)
+        ensure_conversation_docs(pool, project).await?;
+
+        let file = Self::find(pool, project, document_id).await?;
+        let current = file.read(project).await?;
+        Self::record(pool, &current, DocumentRevisionSource::External, None).await?;
+
+        let title = data.title.as_deref().unwrap_or(&current.title);
+        let content = data.content.as_deref().unwrap_or(&current.content);
+        let category = data.category.as_deref().unwrap_or(&current.category);
+
+        let folder = category_to_folder(category);
+        let dir = docs_path(project).join(folder);
+        ensure_writable_dir(&dir).await?;
+        let filename = format!("{}.md", sanitize_filename(title));
+        let old_path = file.path(project);
+        let new_path = dir.join(&filename);
+
+        let mut entry = file.entry;
+        let lock = write_lock(project);
+        let guard = lock.lock().await;
+        if new_path != old_path {
+            // Case-only renames point at the same file on case-insensitive filesystems
+            let same_file = old_path
+                .to_string_lossy()
+                .eq_ignore_ascii_case(&new_path.to_string_lossy());
+            if new_path.exists() && !same_file {
+                return Err(DocumentError::AlreadyExists);
+            }
+            fs::rename(&old_path, &new_path).await?;
+
+            let path = relative_path(folder, &filename);
+            DocumentEntry::delete_by_path(pool, project.id, &path).await?;
+            DocumentEntry::update_path(pool, entry.id, &path).await?;
+            entry.path = path;
+        }
+        drop(guard);
+        fs::write(&new_path, content).await?;
+
+        let document = DocumentFile {
+            entry,
+            folder,
+            filename,
+        }
+        .read(project)
+        .await?;
+        Self::record(pool, &document, source, restored_from).await?;
+        Ok(document)
+    }
+
+    /// Record edits made on disk since the last revision, returning the newest one
+    async fn sync_history(
+        pool: &SqlitePool,
+        project: &Project,
+        document_id: Uuid,
+    ) -> Result<DocumentRevision, DocumentError> {
+        let current = Self::find(pool, project, document_id)
+            .await?
+            .read(project)
+            .await?;
+        Self::record(pool, &current, DocumentRevisionSource::External, None).await
+    }
+
+    /// Add a revision unless the document is unchanged since its latest one
+    async fn record(
+        pool: &SqlitePool,
+        document: &Document,
+        source: DocumentRevisionSource,
+        restored_from: Option<i64>,
+    ) -> Result<DocumentRevision, DocumentError> {
+        if let Some(latest) = DocumentRevision::find_latest(pool, document.id).await?
+            && latest.title == document.title
+            && latest.category == document.category
+            && latest.content == document.content
+        {
+            return Ok(latest);
+        }
+        Ok(DocumentRevision::create(
+            pool,
+            document.id,
+            &document.title,
+            &document.category,
+            &document.content,
+            source,
+            restored_from,
+        )
+        .await?)
+    }
+
+    async fn find(
+        pool: &SqlitePool,
+        project: &Project,
+        document_id: Uuid,
+    ) -> Result<DocumentFile, DocumentError> {
+        Self::scan(pool, project)
+            .await?
+            .into_iter()
+            .find(|file| file.entry.id == document_id)
+            .ok_or(DocumentError::NotFound)
+    }
+
+    /// Markdown files in all category folders, storing ids for files seen for the
+    /// first time, e.g. ones added by an agent or from before ids were stored. Waits
+    /// for renames and other file changes made through the app to finish.
+    async fn scan(
+        pool: &SqlitePool,
+        project: &Project,
+    ) -> Result<Vec<DocumentFile>, DocumentError> {
+        let lock = write_lock(project);
+        let _guard = lock.lock().await;
+        let mut entries: HashMap<String, DocumentEntry> =
+            DocumentEntry::find_by_project_id(pool, project.id)
+                .await?
+                .into_iter()
+                .map(|entry| (entry.path.clone(), entry))
+                .collect();
+        let docs_path = docs_path(project);
+
+        let mut files = Vec::new();
+        let mut new_files = Vec::new();
+        for folder in CATEGORY_FOLDERS.iter().map(|(_, folder)| *folder) {
+            let mut dir_entries = match fs::read_dir(docs_path.join(folder)).await {
+                Ok(e) => e,
+                Err(_) => continue,
+            };
+
+            while let Ok(Some(dir_entry)) = dir_entries.next_entry().await {
+                let path = dir_entry.path();
+                if path.extension().is_none_or(|ext| ext != "md") {
+                    continue;
+                }
+                let Some(filename) = path.file_name().and_then(|n| n.to_str()) else {
+                    continue;
+                };
+
+                match entries.remove(&relative_path(folder, filename)) {
+                    Some(entry) => files.push(DocumentFile {
+                        entry,
+                        folder,
+                        filename: filename.to_string(),
+                    }),
+                    None => new_files.push((folder, filename.to_string(), path)),
+                }
+            }
+        }
+
+        // What is left in `entries` are documents whose file is gone
+        for (folder, filename, path) in new_files {
+            let relative = relative_path(folder, &filename);
+            let entry = match Self::moved_document(pool, &mut entries, &path).await? {
+                Some(mut entry) => {
+                    DocumentEntry::update_path(pool, entry.id, &relative).await?;
+                    entry.path = relative;
+                    entry
+                }
+                None => {
+                    // Keep the id existing links use, unless another document took it
+                    let mut id = legacy_path_id(&path);
+                    if DocumentEntry::find_by_id(pool, id).await?.is_some() {
+                        id = Uuid::new_v4();
+                    }
+                    DocumentEntry::find_or_create(pool, id, project.id, &relative).await?
+                }
+            };
+            files.push(DocumentFile {
+                entry,
+                folder,
+                filename,
+            });
+        }
+        Ok(files)
+    }
+
+    /// The document a new file at `path` was renamed or moved from outside the app:
+    /// the only one among those whose file is gone with the same latest contents
+    async fn moved_document(
+        pool: &SqlitePool,
+        missing: &mut HashMap<String, DocumentEntry>,
+        path: &Path,
+    ) -> Result<Option<DocumentEntry>, DocumentError> {
+        if missing.is_empty() {
+            return Ok(None);
+        }
+        let content = match fs::read_to_string(path).await {
+            Ok(content) if !content.trim().is_empty() => content,
+            _ => return Ok(None),
+        };
+
+        let mut matches = Vec::new();
+        for (old_path, entry) in missing.iter() {
+            if let Some(latest) = DocumentRevision::find_latest(pool, entry.id).await?
+                && latest.content == content
+            {
+                matches.push(old_path.clone());
+            }
+        }
+        match matches.as_slice() {
+            [old_path] => Ok(missing.remove(old_path)),
+            _ => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use db::{
+        DBService,
+        models::task::{CreateTask, Task},
+        test_utils::create_project,
+    };
+    use tempfile::TempDir;
+
+    use super::*;
+
+    async fn project(db: &DBService, repo: &TempDir) -> Project {
+        create_project(&db.pool, repo.path()).await
+    }
+
+    async fn create(db: &DBService, project: &Project, title: &str, content: &str) -> Document {
+        DocumentService::create(
+            &db.pool,
+            project,
+            &CreateDocument {
+                title: title.to_string(),
+                content: content.to_string(),
+                category: "planning".to_string(),
+            },
+        )
+        .await
+        .unwrap()
+    }
+
+    async fn task_linked_to(db: &DBService, project: &Project, documents: &[&Document]) -> Uuid {
+        let task = Task::create(
+            &db.pool,
+            &CreateTask::from_title_description(project.id, "Build it".to_string(), None),
+            Uuid::new_v4(),
+        )
+        .await
+        .unwrap();
+        let document_ids: Vec<Uuid> = documents.iter().map(|document| document.id).collect();
+        TaskDocument::replace_for_task(&db.pool, task.id, &document_ids)
+            .await
+            .unwrap();
+        task.id
+    }
+
+    fn update_content(content: &str) -> UpdateDocument {
+        UpdateDocument {
+            title: None,
+            content: Some(content.to_string()),
+            category: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn renames_in_the_app_keep_the_id() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let repo = TempDir::new().unwrap();
+        let project = project(&db, &repo).await;
+        let document = create(&db, &project, "Roadmap", "# Roadmap").await;
+
+        let moved = DocumentService::update(
+            &db.pool,
+            &project,
+            document.id,
+            &UpdateDocument {
+                title: Some("Plan".to_string()),
+                content: None,
+                category: Some("design".to_string()),
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(moved.id, document.id);
+        assert!(docs_path(&project).join("design/Plan.md").exists());
+        assert!(!docs_path(&project).join("plan/Roadmap.md").exists());
+    }
+
+    #[tokio::test]
+    async fn renames_outside_the_app_keep_the_id_when_contents_match() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let repo = TempDir::new().unwrap();
+        let project = project(&db, &repo).await;
+        let kept = create(&db, &project, "Roadmap", "# Roadmap").await;
+        let edited = create(&db, &project, "Pricing", "# Pricing").await;
+        let docs = docs_path(&project);
+        fs::create_dir_all(docs.join("tech")).await.unwrap();
+
+        fs::rename(docs.join("plan/Roadmap.md"), docs.join("tech/Roadmap.md"))
+            .await
+            .unwrap();
+        fs::remove_file(docs.join("plan/Pricing.md")).await.unwrap();
+        fs::write(docs.join("plan/Prices.md"), "# Prices, reworked")
+            .await
+            .unwrap();
+
+        let documents = DocumentService::all(&db.pool, &project).await.unwrap();
+        let id_of = |title: &str| {
+            documents
+                .iter()
+                .find(|document| document.title == title)
+                .unwrap()
+                .id
+        };
+        assert_eq!(id_of("Roadmap"), kept.id);
+        assert_ne!(id_of("Prices"), edited.id);
+    }
+
+    #[tokio::test]
+    async fn concurrent_scans_store_one_id_per_file() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let repo = TempDir::new().unwrap();
+        let project = project(&db, &repo).await;
+        let dir = docs_path(&project).join("plan");
+        fs::create_dir_all(&dir).await.unwrap();
+        fs::write(dir.join("Added by agent.md"), "# Notes")
+            .await
+            .unwrap();
+
+        let (first, second) = tokio::join!(
+            DocumentService::all(&db.pool, &project),
+            DocumentService::all(&db.pool, &project)
+        );
+
+        assert_eq!(first.unwrap()[0].id, second.unwrap()[0].id);
+        assert_eq!(
+            DocumentEntry::find_by_project_id(&db.pool, project.id)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn edits_on_disk_are_recorded_as_revisions() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let repo = TempDir::new().unwrap();
+        let project = project(&db, &repo).await;
+        let document = create(&db, &project, "Roadmap", "v1").await;
+        DocumentService::update(&db.pool, &project, document.id, &update_content("v2"))
+            .await
+            .unwrap();
+        fs::write(docs_path(&project).join("plan/Roadmap.md"), "v3")
+            .await
+            .unwrap();
+
+        let revisions = DocumentService::revisions(&db.pool, &project, document.id)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            revisions
+                .iter()
+                .map(|revision| (revision.revision, revision.source))
+                .collect::<Vec<_>>(),
+            vec![
+                (3, DocumentRevisionSource::External),
+                (2, DocumentRevisionSource::Edit),
+                (1, DocumentRevisionSource::Edit),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn diff_compares_revisions_or_the_current_contents() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let repo = TempDir::new().unwrap();
+        let project = project(&db, &repo).await;
+        let document = create(&db, &project, "Roadmap", "line\n").await;
+        DocumentService::update(
+            &db.pool,
+            &project,
+            document.id,
+            &update_content("changed\n"),
+        )
+        .await
+        .unwrap();
+
+        let diff = DocumentService::diff(&db.pool, &project, document.id, 1, None)
+            .await
+            .unwrap();
+        assert_eq!((diff.from, diff.to), (1, 2));
+        assert!(diff.unified_diff.contains("-line"));
+        assert!(diff.unified_diff.contains("+changed"));
+
+        let unchanged = DocumentService::diff(&db.pool, &project, document.id, 2, Some(2))
+            .await
+            .unwrap();
+        assert!(unchanged.unified_diff.is_empty());
+        assert!(matches!(
+            DocumentService::diff(&db.pool, &project, document.id, 7, None).await,
+            Err(DocumentError::RevisionNotFound)
+        ));
+    }
+
+    #[tokio::test]
+    async fn restore_brings_back_a_past_revision_as_a_new_one() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let repo = TempDir::new().unwrap();
+        let project = project(&db, &repo).await;
+        let document = create(&db, &project, "Roadmap", "v1").await;
+        DocumentService::update(
+            &db.pool,
+            &project,
+            document.id,
+            &UpdateDocument {
+                title: Some("Plan".to_string()),
+                content: Some("v2".to_string()),
+                category: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let restored = DocumentService::restore(&db.pool, &project, document.id, 1)
+            .await
+            .unwrap();
+
+        assert_eq!(restored.id, document.id);
+        assert_eq!(restored.title, "Roadmap");
+        assert_eq!(restored.content, "v1");
+        let latest = DocumentService::revision(&db.pool, &project, document.id, 3)
+            .await
+            .unwrap();
+        assert_eq!(latest.source, DocumentRevisionSource::Restore);
+        assert_eq!(latest.restored_from, Some(1));
+    }
+
+    #[tokio::test]
+    async fn prompt_section_follows_link_order_and_skips_empty_documents() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let repo = TempDir::new().unwrap();
+        let project = project(&db, &repo).await;
+        let prd = create(&db, &project, "PRD", "Users can export reports.").await;
+        let design = create(&db, &project, "Design", "Export button in the header.").await;
+        let empty = create(&db, &project, "Notes", "  \n").await;
+        let task_id = task_linked_to(&db, &project, &[&design, &empty, &prd]).await;
+
+        let section = DocumentService::task_prompt_section(&db.pool, &project, task_id)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let design_at = section.find("### Design (planning)").unwrap();
+        let prd_at = section.find("### PRD (planning)").unwrap();
+        assert!(design_at < prd_at);
+        assert!(section.contains("Users can export reports."));
+        assert!(!section.contains("### Notes"));
+    }
+
+    #[tokio::test]
+    async fn oversized_documents_are_referenced_by_path() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let repo = TempDir::new().unwrap();
+        let project = project(&db, &repo).await;
+        let fits = "a".repeat(MAX_INLINE_PROMPT_BYTES);
+        let too_long = "b".repeat(MAX_INLINE_PROMPT_BYTES + 1);
+        let small = create(&db, &project, "Small", &fits).await;
+        let large = create(&db, &project, "Large", &too_long).await;
+        let task_id = task_linked_to(&db, &project, &[&small, &large]).await;
+
+        let section = DocumentService::task_prompt_section(&db.pool, &project, task_id)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert!(section.contains(&fits));
+        assert!(!section.contains(&too_long));
+        let large_path = docs_path(&project).join("plan/Large.md");
+        assert!(section.contains(&format!(
+            "Too long to include here, read it from `{}`.",
+            large_path.display()
+        )));
+    }
+
+    #[tokio::test]
+    async fn linked_documents_are_appended_to_the_initial_prompt() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let repo = TempDir::new().unwrap();
+        let project = project(&db, &repo).await;
+        let prd = create(&db, &project, "PRD", "Users can export reports.").await;
+        let linked = task_linked_to(&db, &project, &[&prd]).await;
+        let unlinked = task_linked_to(&db, &project, &[]).await;
+
+        let prompt = DocumentService::add_task_documents_to_prompt(
+            &db.pool,
+            &project,
+            linked,
+            "Add CSV export".to_string(),
+        )
+        .await;
+        assert!(prompt.starts_with("Add CSV export\n\n## Project documents\n"));
+        assert!(prompt.ends_with("### PRD (planning)\n\nUsers can export reports."));
+
+        let prompt = DocumentService::add_task_documents_to_prompt(
+            &db.pool,
+            &project,
+            unlinked,
+            "Add CSV export".to_string(),
+        )
+        .await;
+        assert_eq!(prompt, "Add CSV export");
+    }
+}
@@ -472,6 +472,54 @@ impl DraftsService {
         Ok(Self::draft_to_response(draft))
     }
 
+    /// Append an app-generated prompt (PR feedback, CI failures) to the attempt's
+    /// follow-up draft and queue it, keeping whatever the user already typed. The
+    /// follow-up starts right away when the attempt is idle, otherwise after the
+    /// current run.
+    pub async fn queue_follow_up_prompt(
+        &self,
+        container: &(dyn ContainerService + Send + Sync),
+        task_attempt: &TaskAttempt,
+        prompt: &str,
+    ) -> Result<DraftResponse, DraftsServiceError> {
+        let existing =
+            Draft::find_by_task_attempt_and_type(self.pool(), task_attempt.id, DraftType::FollowUp)
+                .await?
+                .map(|d| d.prompt)
+                .unwrap_or_default();
+        let prompt = Self::append_to_draft(&existing, prompt);
+
+        self.save_follow_up_draft(
+            task_attempt,
+            &UpdateFollowUpDraftRequest {
+                prompt: Some(prompt),
+                variant: None,
+                image_ids: None,
+                version: None,
+            },
+        )
+        .await?;
+        self.set_follow_up_queue(
+            container,
+            task_attempt,
+            &SetQueueRequest {
+                queued: true,
+                expected_queued: Some(false),
+                expected_version: None,
+            },
+        )
+        .await
+    }
+
+    /// Follow-up prompt with `prompt` added after what the user already typed
+    fn append_to_draft(existing: &str, prompt: &str) -> String {
+        if existing.trim().is_empty() {
+            prompt.to_string()
+        } else {
+            format!("{}\n\n{prompt}", existing.trim_end())
+        }
+    }
+
     pub async fn get_draft(
         &self,
         task_attempt_id: Uuid,
@@ -480,3 +528,24 @@ impl DraftsService {
         self.fetch_draft_response(task_attempt_id, draft_type).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn app_prompt_replaces_an_empty_draft() {
+        assert_eq!(
+            DraftsService::append_to_draft("  \n", "Fix the review comments"),
+            "Fix the review comments"
+        );
+    }
+
+    #[test]
+    fn app_prompt_goes_after_what_the_user_typed() {
+        assert_eq!(
+            DraftsService::append_to_draft("Also rename the flag\n\n", "Fix the review comments"),
+            "Also rename the flag\n\nFix the review comments"
+        );
+    }
+}
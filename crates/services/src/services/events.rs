@@ -1,10 +1,15 @@
-use std::{str::FromStr, sync::Arc};
+use std::{
+    collections::HashMap,
+    str::FromStr,
+    sync::{Arc, Weak},
+};
 
 use db::{
     DBService,
     models::{
         draft::{Draft, DraftType},
         execution_process::ExecutionProcess,
+        merge::Merge,
         shared_task::SharedTask as SharedDbTask,
         task::Task,
         task_attempt::TaskAttempt,
@@ -12,10 +17,12 @@ use db::{
 };
 use serde_json::json;
 use sqlx::{Error as SqlxError, Sqlite, SqlitePool, decode::Decode, sqlite::SqliteOperation};
-use tokio::sync::RwLock;
+use tokio::sync::{Mutex, RwLock};
 use utils::msg_store::MsgStore;
 use uuid::Uuid;
 
+#[path = "events/document_watcher.rs"]
+mod document_watcher;
 #[path = "events/patches.rs"]
 pub mod patches;
 #[path = "events/streams.rs"]
@@ -23,8 +30,10 @@ mod streams;
 #[path = "events/types.rs"]
 pub mod types;
 
+use document_watcher::DocumentWatcher;
 pub use patches::{
-    draft_patch, execution_process_patch, shared_task_patch, task_attempt_patch, task_patch,
+    document_patch, draft_patch, execution_process_patch, pr_merge_patch, shared_task_patch,
+    task_attempt_patch, task_patch,
 };
 pub use types::{EventError, EventPatch, EventPatchInner, HookTables, RecordTypes};
 
@@ -34,6 +43,8 @@ pub struct EventService {
     db: DBService,
     #[allow(dead_code)]
     entry_count: Arc<RwLock<usize>>,
+    /// Docs folder watchers by project, alive while a document stream uses them
+    document_watchers: Arc<Mutex<HashMap<Uuid, Weak<DocumentWatcher>>>>,
 }
 
 impl EventService {
@@ -43,6 +54,7 @@ impl EventService {
             msg_store,
             db,
             entry_count,
+            document_watchers: Arc::default(),
         }
     }
 
@@ -184,6 +196,23 @@ impl EventService {
                                     // Deletions handled in preupdate hook for reliable data capture
                                     return;
                                 }
+                                (HookTables::Merges, SqliteOperation::Delete) => {
+                                    // Merges only go away with their attempt
+                                    return;
+                                }
+                                (HookTables::Merges, _) => {
+                                    match Merge::find_by_rowid(&db.pool, rowid).await {
+                                        Ok(Some(Merge::Pr(pr_merge))) => {
+                                            RecordTypes::PrMerge(pr_merge)
+                                        }
+                                        // Direct merges are not streamed
+                                        Ok(_) => return,
+                                        Err(e) => {
+                                            tracing::error!("Failed to fetch merge: {:?}", e);
+                                            return;
+                                        }
+                                    }
+                                }
                                 (HookTables::SharedTasks, _) => {
                                     match SharedDbTask::find_by_rowid(&db.pool, rowid).await {
                                         Ok(Some(task)) => RecordTypes::SharedTask(task),
@@ -310,6 +339,11 @@ impl EventService {
                                     msg_store_for_hook.push_patch(patch);
                                     return;
                                 }
+                                RecordTypes::PrMerge(pr_merge) => {
+                                    let patch = pr_merge_patch::replace(pr_merge);
+                                    msg_store_for_hook.push_patch(patch);
+                                    return;
+                                }
                                 RecordTypes::SharedTask(task) => {
                                     let patch = match hook.operation {
                                         SqliteOperation::Insert => shared_task_patch::add(task),
@@ -0,0 +1,195 @@
+use std::collections::HashMap;
+
+use db::models::project::Project;
+use futures::StreamExt;
+use notify_debouncer_full::DebouncedEvent;
+use sqlx::SqlitePool;
+use tokio::{
+    fs,
+    sync::broadcast,
+    task::{JoinHandle, spawn_blocking},
+};
+use utils::log_msg::LogMsg;
+use uuid::Uuid;
+
+use super::{patches::document_patch, types::EventError};
+use crate::services::{
+    documents::{Document, DocumentService, docs_path},
+    filesystem_watcher,
+};
+
+/// Watches a project's docs folder and broadcasts a patch for every document that
+/// is added, changed or removed, whether by the app, an agent or an editor.
+/// Watching stops when the watcher is dropped.
+pub struct DocumentWatcher {
+    sender: broadcast::Sender<LogMsg>,
+    task: JoinHandle<()>,
+}
+
+impl Drop for DocumentWatcher {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+impl DocumentWatcher {
+    pub async fn start(pool: SqlitePool, project: Project) -> Result<Self, EventError> {
+        let root = docs_path(&project);
+        fs::create_dir_all(&root).await?;
+        let (debouncer, mut watcher_rx, _) =
+            spawn_blocking(move || filesystem_watcher::async_watcher(root))
+                .await
+                .map_err(anyhow::Error::from)??;
+        let mut known: HashMap<Uuid, Document> = DocumentService::all(&pool, &project)
+            .await?
+            .into_iter()
+            .map(|document| (document.id, document))
+            .collect();
+
+        let (sender, _) = broadcast::channel(256);
+        let task_sender = sender.clone();
+        let task = tokio::spawn(async move {
+            let _debouncer_guard = debouncer;
+            while let Some(result) = watcher_rx.next().await {
+                match result {
+                    Ok(events) if events.iter().any(touches_documents) => {}
+                    Ok(_) => continue,
+                    Err(errors) => {
+                        for error in errors {
+                            tracing::warn!("Docs folder watcher error: {error}");
+                        }
+                        continue;
+                    }
+                }
+                // Rescanning is cheap for a docs folder and keeps renames and
+                // whole-folder moves consistent with how documents are listed. The
+                // scan waits for a save in progress, so a file renamed by the app is
+                // never seen without its stored id.
+                match DocumentService::all(&pool, &project).await {
+                    Ok(documents) => {
+                        for patch in apply_changes(&mut known, documents) {
+                            let _ = task_sender.send(LogMsg::JsonPatch(patch));
+                        }
+                    }
+                    Err(e) => {
+                        tracing::warn!("Failed to rescan documents of project {}: {e}", project.id)
+                    }
+                }
+            }
+        });
+
+        Ok(Self { sender, task })
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<LogMsg> {
+        self.sender.subscribe()
+    }
+}
+
+/// Whether an event may change a document: markdown files, or folders holding them
+fn touches_documents(event: &DebouncedEvent) -> bool {
+    event
+        .paths
+        .iter()
+        .any(|path| path.extension().is_none_or(|ext| ext == "md"))
+}
+
+/// Patches turning the known documents into the current ones, which become known
+fn apply_changes(
+    known: &mut HashMap<Uuid, Document>,
+    documents: Vec<Document>,
+) -> Vec<json_patch::Patch> {
+    let mut current: HashMap<Uuid, Document> = documents
+        .into_iter()
+        .map(|document| (document.id, document))
+        .collect();
+    let mut patches: Vec<json_patch::Patch> = known
+        .keys()
+        .filter(|id| !current.contains_key(id))
+        .map(|id| document_patch::remove(*id))
+        .collect();
+    for document in current.values() {
+        match known.get(&document.id) {
+            None => patches.push(document_patch::add(document)),
+            Some(previous) if previous != document => {
+                patches.push(document_patch::replace(document))
+            }
+            Some(_) => {}
+        }
+    }
+    std::mem::swap(known, &mut current);
+    patches
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+
+    fn document(title: &str, content: &str) -> Document {
+        Document {
+            id: Uuid::new_v4(),
+            project_id: Uuid::nil(),
+            title: title.to_string(),
+            content: content.to_string(),
+            category: "planning".to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn known(documents: &[&Document]) -> HashMap<Uuid, Document> {
+        documents
+            .iter()
+            .map(|document| (document.id, (*document).clone()))
+            .collect()
+    }
+
+    #[test]
+    fn new_files_are_added() {
+        let roadmap = document("Roadmap", "# Roadmap");
+        let mut known = HashMap::new();
+
+        let patches = apply_changes(&mut known, vec![roadmap.clone()]);
+
+        assert_eq!(patches, vec![document_patch::add(&roadmap)]);
+        assert_eq!(known.get(&roadmap.id), Some(&roadmap));
+    }
+
+    #[test]
+    fn changed_files_are_replaced() {
+        let roadmap = document("Roadmap", "# Roadmap");
+        let mut known = known(&[&roadmap]);
+        let edited = Document {
+            content: "# Roadmap\n\nQ3".to_string(),
+            ..roadmap.clone()
+        };
+
+        let patches = apply_changes(&mut known, vec![edited.clone()]);
+
+        assert_eq!(patches, vec![document_patch::replace(&edited)]);
+        assert_eq!(known.get(&roadmap.id), Some(&edited));
+    }
+
+    #[test]
+    fn missing_files_are_removed() {
+        let roadmap = document("Roadmap", "# Roadmap");
+        let design = document("Design", "# Design");
+        let mut known = known(&[&roadmap, &design]);
+
+        let patches = apply_changes(&mut known, vec![design.clone()]);
+
+        assert_eq!(patches, vec![document_patch::remove(roadmap.id)]);
+        assert!(!known.contains_key(&roadmap.id));
+    }
+
+    #[test]
+    fn unchanged_files_send_nothing() {
+        let roadmap = document("Roadmap", "# Roadmap");
+        let mut known = known(&[&roadmap]);
+
+        assert!(apply_changes(&mut known, vec![roadmap.clone()]).is_empty());
+        assert_eq!(known.len(), 1);
+    }
+}
@@ -1,6 +1,7 @@
 use db::models::{
     draft::{Draft, DraftType},
     execution_process::ExecutionProcess,
+    merge::PrMerge,
     shared_task::SharedTask as DbSharedTask,
     task::TaskWithAttemptStatus,
     task_attempt::TaskAttempt,
@@ -8,6 +9,8 @@ use db::models::{
 use json_patch::{AddOperation, Patch, PatchOperation, RemoveOperation, ReplaceOperation};
 use uuid::Uuid;
 
+use crate::services::documents::Document;
+
 // Shared helper to escape JSON Pointer segments
 fn escape_pointer_segment(s: &str) -> String {
     s.replace('~', "~0").replace('/', "~1")
@@ -241,3 +244,64 @@ pub mod task_attempt_patch {
         })])
     }
 }
+
+/// Helper functions for creating PR merge (status and CI checks) patches
+pub mod pr_merge_patch {
+    use super::*;
+
+    fn pr_merge_path(pr_number: i64) -> String {
+        format!("/pr_merges/{pr_number}")
+    }
+
+    /// Replace the state of a specific PR. PRs are keyed by number, since an attempt
+    /// can have opened several.
+    pub fn replace(pr_merge: &PrMerge) -> Patch {
+        Patch(vec![PatchOperation::Replace(ReplaceOperation {
+            path: pr_merge_path(pr_merge.pr_info.number)
+                .try_into()
+                .expect("PR merge path should be valid"),
+            value: serde_json::to_value(pr_merge).expect("PR merge serialization should not fail"),
+        })])
+    }
+}
+
+/// Helper functions for creating document patches
+pub mod document_patch {
+    use super::*;
+
+    fn document_path(document_id: Uuid) -> String {
+        format!(
+            "/documents/{}",
+            escape_pointer_segment(&document_id.to_string())
+        )
+    }
+
+    /// Create patch for a document that appeared in the docs folder
+    pub fn add(document: &Document) -> Patch {
+        Patch(vec![PatchOperation::Add(AddOperation {
+            path: document_path(document.id)
+                .try_into()
+                .expect("Document path should be valid"),
+            value: serde_json::to_value(document).expect("Document serialization should not fail"),
+        })])
+    }
+
+    /// Create patch for a document whose file changed
+    pub fn replace(document: &Document) -> Patch {
+        Patch(vec![PatchOperation::Replace(ReplaceOperation {
+            path: document_path(document.id)
+                .try_into()
+                .expect("Document path should be valid"),
+            value: serde_json::to_value(document).expect("Document serialization should not fail"),
+        })])
+    }
+
+    /// Create patch for a document whose file is gone
+    pub fn remove(document_id: Uuid) -> Patch {
+        Patch(vec![PatchOperation::Remove(RemoveOperation {
+            path: document_path(document_id)
+                .try_into()
+                .expect("Document path should be valid"),
+        })])
+    }
+}
@@ -1,21 +1,27 @@
+use std::sync::{Arc, Weak};
+
 use db::models::{
     draft::{Draft, DraftType},
     execution_process::ExecutionProcess,
+    merge::{Merge, PrMerge},
     project::Project,
     shared_task::SharedTask,
     task::{Task, TaskWithAttemptStatus},
 };
 use futures::StreamExt;
 use serde_json::json;
-use tokio_stream::wrappers::BroadcastStream;
+use sqlx::SqlitePool;
+use tokio_stream::wrappers::{BroadcastStream, errors::BroadcastStreamRecvError};
 use utils::log_msg::LogMsg;
 use uuid::Uuid;
 
 use super::{
     EventService,
+    document_watcher::DocumentWatcher,
     patches::execution_process_patch,
     types::{EventError, EventPatch, RecordTypes},
 };
+use crate::services::documents::{DocumentError, DocumentService};
 
 impl EventService {
     /// Stream raw task messages for a specific project with initial snapshot
@@ -218,6 +224,66 @@ impl EventService {
         Ok(combined_stream)
     }
 
+    /// Stream a project's documents, keyed by id, with initial snapshot. Changes
+    /// to the files in its docs folder are streamed as they happen.
+    pub async fn stream_documents_raw(
+        &self,
+        project: &Project,
+    ) -> Result<futures::stream::BoxStream<'static, Result<LogMsg, std::io::Error>>, EventError>
+    {
+        let watcher = self.document_watcher(project).await?;
+        // Subscribe before taking the snapshot so no change falls in between
+        let receiver = watcher.subscribe();
+
+        let initial_msg = documents_snapshot(&self.db.pool, project).await?;
+
+        let pool = self.db.pool.clone();
+        let project = project.clone();
+        let live_stream = BroadcastStream::new(receiver).filter_map(move |msg_result| {
+            // The stream owns a handle on the watcher, which stops with the last stream
+            let _watcher = &watcher;
+            let pool = pool.clone();
+            let project = project.clone();
+            async move {
+                match msg_result {
+                    Ok(msg) => Some(Ok(msg)),
+                    // Skipped changes can't be replayed, so the client starts over
+                    // from a fresh snapshot
+                    Err(BroadcastStreamRecvError::Lagged(skipped)) => {
+                        tracing::debug!(
+                            "Document stream of project {} skipped {skipped} changes",
+                            project.id
+                        );
+                        Some(
+                            documents_snapshot(&pool, &project)
+                                .await
+                                .map_err(std::io::Error::other),
+                        )
+                    }
+                }
+            }
+        });
+
+        let initial_stream = futures::stream::once(async move { Ok(initial_msg) });
+        Ok(initial_stream.chain(live_stream).boxed())
+    }
+
+    /// Watcher of a project's docs folder, shared by the streams of the project
+    async fn document_watcher(
+        &self,
+        project: &Project,
+    ) -> Result<Arc<DocumentWatcher>, EventError> {
+        let mut watchers = self.document_watchers.lock().await;
+        if let Some(watcher) = watchers.get(&project.id).and_then(Weak::upgrade) {
+            return Ok(watcher);
+        }
+        let watcher =
+            Arc::new(DocumentWatcher::start(self.db.pool.clone(), project.clone()).await?);
+        watchers.retain(|_, watcher| watcher.strong_count() > 0);
+        watchers.insert(project.id, Arc::downgrade(&watcher));
+        Ok(watcher)
+    }
+
     /// Stream execution processes for a specific task attempt with initial snapshot (raw LogMsg format for WebSocket)
     pub async fn stream_execution_processes_for_attempt_raw(
         &self,
@@ -446,4 +512,79 @@ impl EventService {
         let combined_stream = initial_stream.chain(filtered_stream).boxed();
         Ok(combined_stream)
     }
+
+    /// Stream the status and CI checks of a task attempt's PRs, keyed by PR number,
+    /// with initial snapshot (raw LogMsg)
+    pub async fn stream_pr_merge_for_attempt_raw(
+        &self,
+        task_attempt_id: Uuid,
+    ) -> Result<futures::stream::BoxStream<'static, Result<LogMsg, std::io::Error>>, EventError>
+    {
+        let pr_merges: serde_json::Map<String, serde_json::Value> =
+            Merge::find_by_task_attempt_id(&self.db.pool, task_attempt_id)
+                .await?
+                .into_iter()
+                .filter_map(|merge| match merge {
+                    Merge::Pr(pr_merge) => Some((
+                        pr_merge.pr_info.number.to_string(),
+                        serde_json::to_value(pr_merge).unwrap(),
+                    )),
+                    Merge::Direct(_) => None,
+                })
+                .collect();
+
+        let initial_patch = json!([
+            {
+                "op": "replace",
+                "path": "/pr_merges",
+                "value": pr_merges
+            }
+        ]);
+        let initial_msg = LogMsg::JsonPatch(serde_json::from_value(initial_patch).unwrap());
+
+        let filtered_stream = BroadcastStream::new(self.msg_store.get_receiver()).filter_map(
+            move |msg_result| async move {
+                match msg_result {
+                    Ok(LogMsg::JsonPatch(patch)) => {
+                        if let Some(patch_op) = patch.0.first()
+                            && patch_op.path().starts_with("/pr_merges/")
+                            && let json_patch::PatchOperation::Replace(op) = patch_op
+                            && serde_json::from_value::<PrMerge>(op.value.clone())
+                                .is_ok_and(|pr_merge| pr_merge.task_attempt_id == task_attempt_id)
+                        {
+                            return Some(Ok(LogMsg::JsonPatch(patch)));
+                        }
+                        None
+                    }
+                    Ok(other) => Some(Ok(other)),
+                    Err(_) => None,
+                }
+            },
+        );
+
+        let initial_stream = futures::stream::once(async move { Ok(initial_msg) });
+        let combined_stream = initial_stream.chain(filtered_stream).boxed();
+        Ok(combined_stream)
+    }
+}
+
+/// Patch replacing the client's documents with the current ones, keyed by id
+async fn documents_snapshot(pool: &SqlitePool, project: &Project) -> Result<LogMsg, DocumentError> {
+    let documents_map: serde_json::Map<String, serde_json::Value> =
+        DocumentService::all(pool, project)
+            .await?
+            .into_iter()
+            .map(|document| {
+                (
+                    document.id.to_string(),
+                    serde_json::to_value(document).unwrap(),
+                )
+            })
+            .collect();
+    let patch = json!([{
+        "op": "replace",
+        "path": "/documents",
+        "value": documents_map
+    }]);
+    Ok(LogMsg::JsonPatch(serde_json::from_value(patch).unwrap()))
 }
@@ -2,6 +2,7 @@ use anyhow::Error as AnyhowError;
 use db::models::{
     draft::{Draft, DraftType},
     execution_process::ExecutionProcess,
+    merge::PrMerge,
     shared_task::SharedTask,
     task::Task,
     task_attempt::TaskAttempt,
@@ -13,6 +14,8 @@ use thiserror::Error;
 use ts_rs::TS;
 use uuid::Uuid;
 
+use crate::services::{documents::DocumentError, filesystem_watcher::FilesystemWatcherError};
+
 #[derive(Debug, Error)]
 pub enum EventError {
     #[error(transparent)]
@@ -20,6 +23,12 @@ pub enum EventError {
     #[error(transparent)]
     Parse(#[from] serde_json::Error),
     #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Document(#[from] DocumentError),
+    #[error(transparent)]
+    Watcher(#[from] FilesystemWatcherError),
+    #[error(transparent)]
     Other(#[from] AnyhowError), // Catches any unclassified errors
 }
 
@@ -35,6 +44,8 @@ pub enum HookTables {
     Drafts,
     #[strum(to_string = "shared_tasks")]
     SharedTasks,
+    #[strum(to_string = "merges")]
+    Merges,
 }
 
 #[derive(Serialize, Deserialize, TS)]
@@ -46,6 +57,7 @@ pub enum RecordTypes {
     Draft(Draft),
     RetryDraft(Draft),
     SharedTask(SharedTask),
+    PrMerge(PrMerge),
     DeletedTask {
         rowid: i64,
         project_id: Option<Uuid>,
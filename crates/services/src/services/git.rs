@@ -1,14 +1,17 @@
 use std::{collections::HashMap, path::Path};
 
 use chrono::{DateTime, Utc};
+use db::models::project_commit_settings::{CommitSigning, ProjectCommitSettings};
 use git2::{
     BranchType, Delta, DiffFindOptions, DiffOptions, Error as GitError, Reference, Remote,
     Repository, Sort,
 };
 use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
 use thiserror::Error;
 use ts_rs::TS;
-use utils::diff::{Diff, DiffChangeKind, FileDiffDetails};
+use utils::diff::{self as diff_utils, Diff, DiffChangeKind, FileDiffDetails};
+use uuid::Uuid;
 
 mod cli;
 
@@ -38,6 +41,8 @@ pub enum GitServiceError {
     WorktreeDirty(String, String),
     #[error("Rebase in progress; resolve or abort it before retrying")]
     RebaseInProgress,
+    #[error("Invalid change selection: {0}")]
+    InvalidSelection(String),
 }
 /// Service for managing Git operations in task execution workflows
 #[derive(Clone)]
@@ -57,6 +62,61 @@ pub enum ConflictOp {
     Revert,
 }
 
+/// A file (and optionally a subset of its hunks) picked from a diff
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct DiffSelection {
+    /// Path of the file as reported by the diff (new path for renames)
+    pub path: String,
+    /// Zero-based hunk indices to keep; `None` takes the whole file
+    pub hunks: Option<Vec<usize>>,
+}
+
+/// Identity and signing applied to commits made through [`GitService::commit_with_options`]
+#[derive(Debug, Clone, Default)]
+pub struct CommitOptions {
+    /// Author and committer; the ambient git identity when `None`
+    pub identity: Option<CommitIdentity>,
+    /// Add a `Co-authored-by` trailer for the ambient identity when `identity` replaces it
+    pub co_author_user: bool,
+    pub signing: CommitSigning,
+    /// Overrides `user.signingkey`
+    pub signing_key: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitIdentity {
+    pub name: String,
+    pub email: String,
+}
+
+impl CommitOptions {
+    /// Options from the project's commit settings; the defaults when it has none
+    pub async fn for_project(pool: &SqlitePool, project_id: Uuid) -> Result<Self, sqlx::Error> {
+        Ok(ProjectCommitSettings::find_by_project_id(pool, project_id)
+            .await?
+            .map(|settings| Self::from(&settings))
+            .unwrap_or_default())
+    }
+}
+
+impl From<&ProjectCommitSettings> for CommitOptions {
+    fn from(settings: &ProjectCommitSettings) -> Self {
+        let identity = match (&settings.author_name, &settings.author_email) {
+            (Some(name), Some(email)) => Some(CommitIdentity {
+                name: name.clone(),
+                email: email.clone(),
+            }),
+            _ => None,
+        };
+        Self {
+            identity,
+            co_author_user: settings.co_author_user,
+            signing: settings.signing,
+            signing_key: settings.signing_key.clone(),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, TS)]
 pub struct GitBranch {
     pub name: String,
@@ -268,6 +328,16 @@ impl GitService {
     }
 
     pub fn commit(&self, path: &Path, message: &str) -> Result<bool, GitServiceError> {
+        self.commit_with_options(path, message, &CommitOptions::default())
+    }
+
+    /// Stage and commit all changes using the given identity and signing settings.
+    pub fn commit_with_options(
+        &self,
+        path: &Path,
+        message: &str,
+        options: &CommitOptions,
+    ) -> Result<bool, GitServiceError> {
         // Use Git CLI to respect sparse-checkout semantics for staging and commit
         let git = GitCli::new();
         let has_changes = git
@@ -280,13 +350,87 @@ impl GitService {
 
         git.add_all(path)
             .map_err(|e| GitServiceError::InvalidRepository(format!("git add failed: {e}")))?;
+        // Read the user's identity before a fallback identity may be written
+        let message = self.message_with_trailer(path, message, options)?;
         // Only ensure identity once we know we're about to commit
         self.ensure_cli_commit_identity(path)?;
-        git.commit(path, message)
+        git.commit_with_options(path, &message, options)
             .map_err(|e| GitServiceError::InvalidRepository(format!("git commit failed: {e}")))?;
         Ok(true)
     }
 
+    /// `message` with the `Co-authored-by` trailer from [`Self::co_author_trailer`], if any
+    fn message_with_trailer(
+        &self,
+        path: &Path,
+        message: &str,
+        options: &CommitOptions,
+    ) -> Result<String, GitServiceError> {
+        Ok(match self.co_author_trailer(path, options)? {
+            Some(trailer) => format!("{}\n\n{trailer}", message.trim_end()),
+            None => message.to_string(),
+        })
+    }
+
+    /// Write a commit of `tree` on top of `parent` without updating any ref, with the
+    /// identity from `options`. Signed commits go through the git CLI, since libgit2
+    /// cannot sign.
+    fn write_commit(
+        &self,
+        repo: &Repository,
+        tree: &git2::Tree,
+        parent: &git2::Commit,
+        message: &str,
+        options: &CommitOptions,
+    ) -> Result<git2::Oid, GitServiceError> {
+        let repo_path = repo.workdir().unwrap_or(repo.path());
+        let message = self.message_with_trailer(repo_path, message, options)?;
+        if options.signing != CommitSigning::None {
+            self.ensure_cli_commit_identity(repo_path)?;
+            let sha = GitCli::new()
+                .commit_tree(
+                    repo_path,
+                    &tree.id().to_string(),
+                    &parent.id().to_string(),
+                    &message,
+                    options,
+                )
+                .map_err(|e| {
+                    GitServiceError::InvalidRepository(format!("git commit-tree failed: {e}"))
+                })?;
+            return Ok(git2::Oid::from_str(&sha)?);
+        }
+        let signature = match &options.identity {
+            Some(identity) => git2::Signature::now(&identity.name, &identity.email)?,
+            None => self.signature_with_fallback(repo)?,
+        };
+        Ok(repo.commit(None, &signature, &signature, &message, tree, &[parent])?)
+    }
+
+    /// `Co-authored-by` trailer crediting the repository's configured identity when
+    /// commits are attributed to a different author.
+    fn co_author_trailer(
+        &self,
+        path: &Path,
+        options: &CommitOptions,
+    ) -> Result<Option<String>, GitServiceError> {
+        let Some(identity) = &options.identity else {
+            return Ok(None);
+        };
+        if !options.co_author_user {
+            return Ok(None);
+        }
+        let cfg = self.open_repo(path)?.config()?;
+        let (Ok(name), Ok(email)) = (cfg.get_string("user.name"), cfg.get_string("user.email"))
+        else {
+            return Ok(None);
+        };
+        if email.eq_ignore_ascii_case(&identity.email) {
+            return Ok(None);
+        }
+        Ok(Some(format!("Co-authored-by: {name} <{email}>")))
+    }
+
     /// Get diffs between branches or worktree changes
     pub fn get_diffs(
         &self,
@@ -770,6 +914,7 @@ impl GitService {
         task_branch_name: &str,
         base_branch_name: &str,
         commit_message: &str,
+        options: &CommitOptions,
     ) -> Result<String, GitServiceError> {
         // Open the repositories
         let task_repo = self.open_repo(task_worktree_path)?;
@@ -806,13 +951,16 @@ impl GitService {
                 }
 
                 // Use CLI merge in base context
+                let commit_message =
+                    self.message_with_trailer(&base_checkout_path, commit_message, options)?;
                 self.ensure_cli_commit_identity(&base_checkout_path)?;
                 let sha = git_cli
                     .merge_squash_commit(
                         &base_checkout_path,
                         base_branch_name,
                         task_branch_name,
-                        commit_message,
+                        &commit_message,
+                        options,
                     )
                     .map_err(|e| {
                         GitServiceError::InvalidRepository(format!("CLI merge failed: {e}"))
@@ -838,14 +986,13 @@ impl GitService {
                 let task_commit = task_branch.get().peel_to_commit()?;
 
                 // Create the squash commit in-memory (no checkout) and update the base branch ref
-                let signature = self.signature_with_fallback(&task_repo)?;
                 let squash_commit_id = self.perform_squash_merge(
                     &task_repo,
                     &base_commit,
                     &task_commit,
-                    &signature,
                     commit_message,
                     base_branch_name,
+                    options,
                 )?;
 
                 // Update the task branch to the new squash commit so follow-up
@@ -862,6 +1009,277 @@ impl GitService {
             }
         }
     }
+
+    /// Create a commit on top of `base_commit` that contains only the selected
+    /// changes from `diffs` (as returned by [`Self::get_diffs`] for the worktree).
+    /// The commit is not referenced by any branch; it is meant to be cherry-picked.
+    pub fn create_partial_commit(
+        &self,
+        worktree_path: &Path,
+        base_commit: &Commit,
+        diffs: &[Diff],
+        selection: &[DiffSelection],
+        message: &str,
+        options: &CommitOptions,
+    ) -> Result<Commit, GitServiceError> {
+        if selection.is_empty() {
+            return Err(GitServiceError::InvalidSelection(
+                "no files selected".to_string(),
+            ));
+        }
+
+        let repo = self.open_repo(worktree_path)?;
+        let base = repo.find_commit(base_commit.as_oid())?;
+        let base_tree = base.tree()?;
+        let mut index = git2::Index::new()?;
+        index.read_tree(&base_tree)?;
+
+        for selected in selection {
+            let diff = diffs
+                .iter()
+                .find(|d| Self::diff_path(d) == selected.path)
+                .ok_or_else(|| {
+                    GitServiceError::InvalidSelection(format!(
+                        "'{}' has no changes in this attempt",
+                        selected.path
+                    ))
+                })?;
+
+            if let Some(hunks) = &selected.hunks {
+                if !matches!(diff.change, DiffChangeKind::Modified) {
+                    return Err(GitServiceError::InvalidSelection(format!(
+                        "hunks can only be selected for modified files, '{}' is not",
+                        selected.path
+                    )));
+                }
+                let (Some(old), Some(new)) = (&diff.old_content, &diff.new_content) else {
+                    return Err(GitServiceError::InvalidSelection(format!(
+                        "contents of '{}' are not available for hunk selection",
+                        selected.path
+                    )));
+                };
+                let total = diff_utils::count_diff_hunks(old, new);
+                if let Some(bad) = hunks.iter().find(|h| **h >= total) {
+                    return Err(GitServiceError::InvalidSelection(format!(
+                        "'{}' has {total} hunks, hunk {bad} does not exist",
+                        selected.path
+                    )));
+                }
+                let content = diff_utils::apply_selected_hunks(old, new, hunks);
+                let mode = Self::worktree_file_mode(worktree_path, &base_tree, &selected.path)?;
+                Self::stage_blob(&repo, &mut index, &selected.path, content.as_bytes(), mode)?;
+                continue;
+            }
+
+            // Whole file: drop the old path for moves/deletions, take the new file as-is
+            if let Some(old_path) = &diff.old_path
+                && matches!(
+                    diff.change,
+                    DiffChangeKind::Deleted | DiffChangeKind::Renamed
+                )
+            {
+                index.remove_path(Path::new(old_path))?;
+            }
+            if let Some(new_path) = &diff.new_path {
+                let source = worktree_path.join(new_path);
+                let mode = Self::worktree_file_mode(worktree_path, &base_tree, new_path)?;
+                let bytes = if mode == 0o120000 {
+                    std::fs::read_link(&source)?
+                        .to_string_lossy()
+                        .into_owned()
+                        .into_bytes()
+                } else {
+                    std::fs::read(&source)?
+                };
+                Self::stage_blob(&repo, &mut index, new_path, &bytes, mode)?;
+            }
+        }
+
+        let tree_oid = index.write_tree_to(&repo)?;
+        if tree_oid == base_tree.id() {
+            return Err(GitServiceError::InvalidSelection(
+                "selected changes are empty".to_string(),
+            ));
+        }
+        let tree = repo.find_tree(tree_oid)?;
+        let oid = self.write_commit(&repo, &tree, &base, message, options)?;
+        Ok(Commit::new(oid))
+    }
+
+    /// Git file mode of a worktree file, as `git add` would record it. Where the
+    /// filesystem has no executable bit, the mode from `base_tree` is kept.
+    fn worktree_file_mode(
+        worktree_path: &Path,
+        base_tree: &git2::Tree,
+        path: &str,
+    ) -> Result<u32, GitServiceError> {
+        let metadata = std::fs::symlink_metadata(worktree_path.join(path))?;
+        if metadata.file_type().is_symlink() {
+            return Ok(0o120000);
+        }
+        #[cfg(unix)]
+        let executable = {
+            use std::os::unix::fs::PermissionsExt;
+            Some(metadata.permissions().mode() & 0o111 != 0)
+        };
+        #[cfg(not(unix))]
+        let executable = None;
+        Ok(match executable {
+            Some(true) => 0o100755,
+            Some(false) => 0o100644,
+            None => base_tree
+                .get_path(Path::new(path))
+                .map(|entry| entry.filemode() as u32)
+                .unwrap_or(0o100644),
+        })
+    }
+
+    /// Write `content` as a blob and stage it at `path` with the given file mode.
+    fn stage_blob(
+        repo: &Repository,
+        index: &mut git2::Index,
+        path: &str,
+        content: &[u8],
+        mode: u32,
+    ) -> Result<(), GitServiceError> {
+        let blob_oid = repo.blob(content)?;
+        let entry = git2::IndexEntry {
+            ctime: git2::IndexTime::new(0, 0),
+            mtime: git2::IndexTime::new(0, 0),
+            dev: 0,
+            ino: 0,
+            mode,
+            uid: 0,
+            gid: 0,
+            file_size: content.len() as u32,
+            id: blob_oid,
+            flags: 0,
+            flags_extended: 0,
+            path: path.as_bytes().to_vec(),
+        };
+        index.add(&entry)?;
+        Ok(())
+    }
+
+    /// Cherry-pick `commit` onto a local branch. Nothing is left behind on
+    /// conflicts: the pick is computed in memory when the branch is not checked
+    /// out, and aborted in the checkout otherwise. Returns the new branch tip.
+    pub fn cherry_pick_onto_branch(
+        &self,
+        repo_path: &Path,
+        branch_name: &str,
+        commit: &Commit,
+        options: &CommitOptions,
+    ) -> Result<String, GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        let branch = repo
+            .find_branch(branch_name, BranchType::Local)
+            .map_err(|_| GitServiceError::BranchNotFound(branch_name.to_string()))?;
+
+        if let Some(checkout_path) = self.find_checkout_path_for_branch(repo_path, branch_name)? {
+            let git = GitCli::new();
+            if git.has_staged_changes(&checkout_path).map_err(|e| {
+                GitServiceError::InvalidRepository(format!("git diff --cached failed: {e}"))
+            })? {
+                return Err(GitServiceError::WorktreeDirty(
+                    branch_name.to_string(),
+                    "staged changes present".to_string(),
+                ));
+            }
+            self.ensure_cli_commit_identity(&checkout_path)?;
+            return match git.cherry_pick(&checkout_path, &commit.to_string(), options) {
+                Ok(sha) => Ok(sha),
+                Err(GitCliError::CommandFailed(stderr)) if Self::looks_like_conflict(&stderr) => {
+                    let conflicts = git.get_conflicted_files(&checkout_path).unwrap_or_default();
+                    let _ = git.abort_cherry_pick(&checkout_path);
+                    Err(GitServiceError::MergeConflicts(format!(
+                        "Selected changes conflict with '{branch_name}'. Conflicted files: {}.",
+                        conflicts.join(", ")
+                    )))
+                }
+                Err(e) => Err(GitServiceError::InvalidRepository(format!(
+                    "git cherry-pick failed: {e}"
+                ))),
+            };
+        }
+
+        let ours = branch.get().peel_to_commit()?;
+        let theirs = repo.find_commit(commit.as_oid())?;
+        let mut index = repo.cherrypick_commit(&theirs, &ours, 0, None)?;
+        if index.has_conflicts() {
+            let conflicts = index
+                .conflicts()?
+                .filter_map(|c| c.ok())
+                .filter_map(|c| c.our.or(c.their).or(c.ancestor))
+                .map(|entry| String::from_utf8_lossy(&entry.path).to_string())
+                .collect::<Vec<_>>();
+            return Err(GitServiceError::MergeConflicts(format!(
+                "Selected changes conflict with '{branch_name}'. Conflicted files: {}.",
+                conflicts.join(", ")
+            )));
+        }
+
+        let tree = repo.find_tree(index.write_tree_to(&repo)?)?;
+        let message = theirs.message().unwrap_or("Apply selected changes");
+        // The picked commit already carries any trailer, so only identity and signing apply
+        let options = CommitOptions {
+            co_author_user: false,
+            ..options.clone()
+        };
+        let oid = self.write_commit(&repo, &tree, &ours, message, &options)?;
+        let refname = format!("refs/heads/{branch_name}");
+        repo.reference(&refname, oid, true, "Apply selected changes")?;
+        Ok(oid.to_string())
+    }
+
+    /// Cherry-pick `commit` into a worktree. On conflicts the cherry-pick is left
+    /// in progress so it can be resolved or aborted like any other
+    /// [`ConflictOp::CherryPick`]. Returns the new HEAD.
+    pub fn cherry_pick_into_worktree(
+        &self,
+        worktree_path: &Path,
+        commit: &Commit,
+        options: &CommitOptions,
+    ) -> Result<String, GitServiceError> {
+        let repo = self.open_repo(worktree_path)?;
+        self.check_worktree_clean(&repo)?;
+        if let Some(op) = self.detect_conflict_op(worktree_path)? {
+            return Err(match op {
+                ConflictOp::Rebase => GitServiceError::RebaseInProgress,
+                op => GitServiceError::InvalidRepository(format!(
+                    "{op:?} in progress; resolve or abort it before retrying"
+                )),
+            });
+        }
+
+        self.ensure_cli_commit_identity(worktree_path)?;
+        let git = GitCli::new();
+        match git.cherry_pick(worktree_path, &commit.to_string(), options) {
+            Ok(sha) => Ok(sha),
+            Err(GitCliError::CommandFailed(stderr)) if Self::looks_like_conflict(&stderr) => {
+                let conflicts = git.get_conflicted_files(worktree_path).unwrap_or_default();
+                let branch = repo
+                    .head()
+                    .ok()
+                    .and_then(|h| h.shorthand().map(|s| s.to_string()))
+                    .unwrap_or_else(|| "(unknown)".to_string());
+                Err(GitServiceError::MergeConflicts(format!(
+                    "Cherry-pick into '{branch}' stopped on conflicts. Conflicted files: {}. Resolve conflicts and then continue or abort.",
+                    conflicts.join(", ")
+                )))
+            }
+            Err(e) => Err(GitServiceError::InvalidRepository(format!(
+                "git cherry-pick failed: {e}"
+            ))),
+        }
+    }
+
+    fn looks_like_conflict(stderr: &str) -> bool {
+        stderr.contains("could not apply")
+            || stderr.contains("CONFLICT")
+            || stderr.to_lowercase().contains("resolve all conflicts")
+    }
+
     fn get_branch_status_inner(
         &self,
         repo: &Repository,
@@ -1156,6 +1574,19 @@ impl GitService {
         Ok(())
     }
 
+    /// Add a worktree with a detached HEAD at the given branch or commit
+    pub fn add_detached_worktree(
+        &self,
+        repo_path: &Path,
+        worktree_path: &Path,
+        commit_ish: &str,
+    ) -> Result<(), GitServiceError> {
+        let git = GitCli::new();
+        git.worktree_add_detached(repo_path, worktree_path, commit_ish)
+            .map_err(|e| GitServiceError::InvalidRepository(e.to_string()))?;
+        Ok(())
+    }
+
     /// Remove a worktree
     pub fn remove_worktree(
         &self,
@@ -1246,9 +1677,9 @@ impl GitService {
         repo: &Repository,
         base_commit: &git2::Commit,
         task_commit: &git2::Commit,
-        signature: &git2::Signature,
         commit_message: &str,
         base_branch_name: &str,
+        options: &CommitOptions,
     ) -> Result<git2::Oid, GitServiceError> {
         // In-memory merge to detect conflicts without touching the working tree
         let mut merge_opts = git2::MergeOptions::new();
@@ -1269,14 +1700,8 @@ impl GitService {
         let tree = repo.find_tree(tree_id)?;
 
         // Create a squash commit: use merged tree with base_commit as sole parent
-        let squash_commit_id = repo.commit(
-            None,           // Don't update any reference yet
-            signature,      // Author
-            signature,      // Committer
-            commit_message, // Custom message
-            &tree,          // Merged tree content
-            &[base_commit], // Single parent: base branch commit
-        )?;
+        let squash_commit_id =
+            self.write_commit(repo, &tree, base_commit, commit_message, options)?;
 
         // Update the base branch reference to point to the new commit
         let refname = format!("refs/heads/{base_branch_name}");
@@ -1293,6 +1718,7 @@ impl GitService {
         new_base_branch: &str,
         old_base_branch: &str,
         task_branch: &str,
+        options: &CommitOptions,
     ) -> Result<String, GitServiceError> {
         let worktree_repo = Repository::open(worktree_path)?;
         let main_repo = self.open_repo(repo_path)?;
@@ -1319,17 +1745,20 @@ impl GitService {
         // Ensure identity for any commits produced by rebase
         self.ensure_cli_commit_identity(worktree_path)?;
         // Use git CLI rebase to carry out the operation safely
-        match git.rebase_onto(worktree_path, new_base_branch, old_base_branch, task_branch) {
+        match git.rebase_onto(
+            worktree_path,
+            new_base_branch,
+            old_base_branch,
+            task_branch,
+            options,
+        ) {
             Ok(()) => {}
             Err(GitCliError::RebaseInProgress) => {
                 return Err(GitServiceError::RebaseInProgress);
             }
             Err(GitCliError::CommandFailed(stderr)) => {
                 // If the CLI indicates conflicts, return a concise, actionable error.
-                let looks_like_conflict = stderr.contains("could not apply")
-                    || stderr.contains("CONFLICT")
-                    || stderr.to_lowercase().contains("resolve all conflicts");
-                if looks_like_conflict {
+                if Self::looks_like_conflict(&stderr) {
                     // Determine current attempt branch name for clarity
                     let attempt_branch = worktree_repo
                         .head()
@@ -1412,6 +1841,32 @@ impl GitService {
         }
     }
 
+    /// Remote-tracking branch for `branch_name`: its upstream, or the branch of the same
+    /// name on the default remote. Remote branch names are returned unchanged.
+    pub fn remote_tracking_branch(
+        &self,
+        repo_path: &Path,
+        branch_name: &str,
+    ) -> Result<Option<String>, GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        let Ok(local) = repo.find_branch(branch_name, BranchType::Local) else {
+            return Ok(repo
+                .find_branch(branch_name, BranchType::Remote)
+                .is_ok()
+                .then(|| branch_name.to_string()));
+        };
+        if let Ok(upstream) = local.upstream()
+            && let Ok(Some(name)) = upstream.name()
+        {
+            return Ok(Some(name.to_string()));
+        }
+        let candidate = format!("{}/{branch_name}", self.default_remote_name(&repo));
+        Ok(repo
+            .find_branch(&candidate, BranchType::Remote)
+            .is_ok()
+            .then_some(candidate))
+    }
+
     pub fn rename_local_branch(
         &self,
         worktree_path: &Path,
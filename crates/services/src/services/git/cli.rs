@@ -21,10 +21,11 @@ use std::{
     process::Command,
 };
 
+use db::models::project_commit_settings::CommitSigning;
 use thiserror::Error;
 use utils::shell::resolve_executable_path_blocking; // TODO: make GitCli async
 
-use crate::services::git::Commit;
+use crate::services::git::{Commit, CommitOptions};
 
 #[derive(Debug, Error)]
 pub enum GitCliError {
@@ -106,6 +107,30 @@ impl GitCli {
         Ok(())
     }
 
+    /// Run `git -C <repo> worktree add --detach <path> <commit-ish>`, checking out
+    /// the commit without a branch so a branch checked out elsewhere can be used
+    pub fn worktree_add_detached(
+        &self,
+        repo_path: &Path,
+        worktree_path: &Path,
+        commit_ish: &str,
+    ) -> Result<(), GitCliError> {
+        self.ensure_available()?;
+
+        let args: Vec<OsString> = vec![
+            "worktree".into(),
+            "add".into(),
+            "--detach".into(),
+            worktree_path.as_os_str().into(),
+            OsString::from(commit_ish),
+        ];
+        self.git(repo_path, args)?;
+
+        let _ = self.git(worktree_path, ["sparse-checkout", "reapply"]);
+
+        Ok(())
+    }
+
     /// Run `git -C <repo> worktree remove <path>`
     pub fn worktree_remove(
         &self,
@@ -292,6 +317,99 @@ impl GitCli {
         self.git(worktree_path, ["commit", "-m", message])?;
         Ok(())
     }
+
+    /// Commit staged changes, overriding author/committer and signing as requested.
+    /// Signing uses the user's own gpg/ssh setup; only the format and key are set here.
+    pub fn commit_with_options(
+        &self,
+        worktree_path: &Path,
+        message: &str,
+        options: &CommitOptions,
+    ) -> Result<(), GitCliError> {
+        let (envs, mut args) = Self::commit_settings(options, true);
+        args.extend(["commit".into(), "-m".into(), OsString::from(message)]);
+        if Self::signs(options) {
+            args.push("-S".into());
+        }
+
+        self.git_with_env(worktree_path, args, &envs)?;
+        Ok(())
+    }
+
+    /// Create a commit object for `tree` on top of `parent` without touching any ref.
+    /// Returns the new commit sha.
+    pub fn commit_tree(
+        &self,
+        repo_path: &Path,
+        tree: &str,
+        parent: &str,
+        message: &str,
+        options: &CommitOptions,
+    ) -> Result<String, GitCliError> {
+        let (envs, mut args) = Self::commit_settings(options, true);
+        args.extend([
+            "commit-tree".into(),
+            tree.into(),
+            "-p".into(),
+            parent.into(),
+            "-m".into(),
+            OsString::from(message),
+        ]);
+        if Self::signs(options) {
+            args.push("-S".into());
+        }
+        Ok(self
+            .git_with_env(repo_path, args, &envs)?
+            .trim()
+            .to_string())
+    }
+
+    /// Environment and `-c` arguments applying `options` to commits git creates. With
+    /// `author` unset only the committer is overridden, for commits that are replayed
+    /// (rebase, cherry-pick) and keep their original author.
+    fn commit_settings(
+        options: &CommitOptions,
+        author: bool,
+    ) -> (Vec<(OsString, OsString)>, Vec<OsString>) {
+        let mut envs: Vec<(OsString, OsString)> = Vec::new();
+        if let Some(identity) = &options.identity {
+            let roles: &[&str] = if author {
+                &["AUTHOR", "COMMITTER"]
+            } else {
+                &["COMMITTER"]
+            };
+            for role in roles {
+                envs.push((
+                    format!("GIT_{role}_NAME").into(),
+                    identity.name.clone().into(),
+                ));
+                envs.push((
+                    format!("GIT_{role}_EMAIL").into(),
+                    identity.email.clone().into(),
+                ));
+            }
+        }
+
+        let mut args: Vec<OsString> = Vec::new();
+        let format = match options.signing {
+            CommitSigning::None => None,
+            CommitSigning::Gpg => Some("openpgp"),
+            CommitSigning::Ssh => Some("ssh"),
+        };
+        if let Some(format) = format {
+            args.push("-c".into());
+            args.push(format!("gpg.format={format}").into());
+            if let Some(key) = &options.signing_key {
+                args.push("-c".into());
+                args.push(format!("user.signingkey={key}").into());
+            }
+        }
+        (envs, args)
+    }
+
+    fn signs(options: &CommitOptions) -> bool {
+        options.signing != CommitSigning::None
+    }
     /// Fetch a branch to the given remote using native git authentication.
     pub fn fetch_with_refspec(
         &self,
@@ -393,12 +511,14 @@ impl GitCli {
     }
 
     /// Perform `git rebase --onto <new_base> <old_base>` on <task_branch> in `worktree_path`.
+    /// Rebased commits keep their author; committer and signing follow `options`.
     pub fn rebase_onto(
         &self,
         worktree_path: &Path,
         new_base: &str,
         old_base: &str,
         task_branch: &str,
+        options: &CommitOptions,
     ) -> Result<(), GitCliError> {
         // If a rebase is in progress, refuse to proceed. The caller can
         // choose to abort or continue; we avoid destructive actions here.
@@ -410,10 +530,18 @@ impl GitCli {
             .merge_base(worktree_path, old_base, task_branch)
             .unwrap_or(old_base.to_string());
 
-        self.git(
-            worktree_path,
-            ["rebase", "--onto", new_base, &merge_base, task_branch],
-        )?;
+        let (envs, mut args) = Self::commit_settings(options, false);
+        args.push("rebase".into());
+        if Self::signs(options) {
+            args.push("--gpg-sign".into());
+        }
+        args.extend([
+            "--onto".into(),
+            new_base.into(),
+            merge_base.into(),
+            task_branch.into(),
+        ]);
+        self.git_with_env(worktree_path, args, &envs)?;
         Ok(())
     }
 
@@ -504,11 +632,12 @@ impl GitCli {
         base_branch: &str,
         from_branch: &str,
         message: &str,
+        options: &CommitOptions,
     ) -> Result<String, GitCliError> {
         self.git(repo_path, ["checkout", base_branch]).map(|_| ())?;
         self.git(repo_path, ["merge", "--squash", "--no-commit", from_branch])
             .map(|_| ())?;
-        self.git(repo_path, ["commit", "-m", message]).map(|_| ())?;
+        self.commit_with_options(repo_path, message, options)?;
         let sha = self
             .git(repo_path, ["rev-parse", "HEAD"])?
             .trim()
@@ -516,6 +645,28 @@ impl GitCli {
         Ok(sha)
     }
 
+    /// Cherry-pick a single commit onto the current HEAD. The picked commit keeps its
+    /// author; committer and signing follow `options`. Returns the new HEAD sha.
+    pub fn cherry_pick(
+        &self,
+        worktree_path: &Path,
+        sha: &str,
+        options: &CommitOptions,
+    ) -> Result<String, GitCliError> {
+        let (envs, mut args) = Self::commit_settings(options, false);
+        args.push("cherry-pick".into());
+        if Self::signs(options) {
+            args.push("-S".into());
+        }
+        args.push(sha.into());
+        self.git_with_env(worktree_path, args, &envs)?;
+        let head = self
+            .git(worktree_path, ["rev-parse", "HEAD"])?
+            .trim()
+            .to_string();
+        Ok(head)
+    }
+
     /// Update a ref to a specific sha in the repo.
     pub fn update_ref(
         &self,
@@ -1,7 +1,8 @@
 use std::time::Duration;
 
 use backon::{ExponentialBuilder, Retryable};
-use db::models::merge::PullRequestInfo;
+use chrono::{DateTime, Utc};
+use db::models::merge::{CiCheck, PullRequestInfo};
 use regex::Regex;
 use thiserror::Error;
 use tokio::task;
@@ -110,6 +111,19 @@ pub struct CreatePrRequest {
     pub base_branch: String,
 }
 
+/// An inline review comment on a pull request
+#[derive(Debug, Clone)]
+pub struct ReviewComment {
+    pub id: i64,
+    pub author: String,
+    pub body: String,
+    pub path: Option<String>,
+    pub line: Option<i64>,
+    pub diff_hunk: Option<String>,
+    pub url: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone)]
 pub struct GitHubService {
     gh_cli: GhCli,
@@ -236,6 +250,45 @@ impl GitHubService {
         .await
     }
 
+    /// List inline review comments on a pull request
+    pub async fn list_review_comments(
+        &self,
+        repo_info: &GitHubRepoInfo,
+        pr_number: i64,
+    ) -> Result<Vec<ReviewComment>, GitHubServiceError> {
+        (|| async {
+            let owner = repo_info.owner.clone();
+            let repo = repo_info.repo_name.clone();
+            let cli = self.gh_cli.clone();
+            let comments = task::spawn_blocking(move || {
+                cli.list_review_comments(&owner, &repo, pr_number)
+            })
+            .await
+            .map_err(|err| {
+                GitHubServiceError::PullRequest(format!(
+                    "Failed to execute GitHub CLI for PR #{pr_number} review comments: {err}"
+                ))
+            })?;
+            comments.map_err(GitHubServiceError::from)
+        })
+        .retry(
+            &ExponentialBuilder::default()
+                .with_min_delay(Duration::from_secs(1))
+                .with_max_delay(Duration::from_secs(30))
+                .with_max_times(3)
+                .with_jitter(),
+        )
+        .when(|err: &GitHubServiceError| err.should_retry())
+        .notify(|err: &GitHubServiceError, dur: Duration| {
+            tracing::warn!(
+                "GitHub API call failed, retrying after {:.2}s: {}",
+                dur.as_secs_f64(),
+                err
+            );
+        })
+        .await
+    }
+
     /// List all pull requests for a branch (including closed/merged)
     pub async fn list_all_prs_for_branch(
         &self,
@@ -279,4 +332,34 @@ impl GitHubService {
         })
         .await
     }
+
+    /// Fetch the failed-step logs for a check that ran on GitHub Actions.
+    /// Returns `None` for checks reported by other CI providers.
+    pub async fn failed_check_log(
+        &self,
+        repo_info: &GitHubRepoInfo,
+        check: &CiCheck,
+    ) -> Result<Option<String>, GitHubServiceError> {
+        let Some(run_id) = check.details_url.as_deref().and_then(Self::actions_run_id) else {
+            return Ok(None);
+        };
+        let owner = repo_info.owner.clone();
+        let repo = repo_info.repo_name.clone();
+        let cli = self.gh_cli.clone();
+        let log = task::spawn_blocking(move || cli.failed_run_log(&owner, &repo, run_id))
+            .await
+            .map_err(|err| {
+                GitHubServiceError::PullRequest(format!(
+                    "Failed to execute GitHub CLI for run {run_id} logs: {err}"
+                ))
+            })??;
+        Ok(Some(log))
+    }
+
+    /// Extract the workflow run id from an Actions details URL
+    /// (`https://github.com/<owner>/<repo>/actions/runs/<run_id>/job/<job_id>`).
+    fn actions_run_id(details_url: &str) -> Option<u64> {
+        let (_, rest) = details_url.split_once("/actions/runs/")?;
+        rest.split('/').next()?.parse().ok()
+    }
 }
@@ -10,12 +10,12 @@ use std::{
 };
 
 use chrono::{DateTime, Utc};
-use db::models::merge::{MergeStatus, PullRequestInfo};
+use db::models::merge::{CiCheck, CiStatus, MergeStatus, PullRequestInfo};
 use serde_json::Value;
 use thiserror::Error;
 use utils::shell::resolve_executable_path_blocking;
 
-use crate::services::github::{CreatePrRequest, GitHubRepoInfo};
+use crate::services::github::{CreatePrRequest, GitHubRepoInfo, ReviewComment};
 
 /// High-level errors originating from the GitHub CLI.
 #[derive(Debug, Error)]
@@ -141,7 +141,7 @@ impl GhCli {
             "--repo",
             &format!("{owner}/{repo}"),
             "--json",
-            "number,url,state,mergedAt,mergeCommit",
+            "number,url,state,mergedAt,mergeCommit,statusCheckRollup",
         ])?;
         Self::parse_pr_view(&raw)
     }
@@ -167,6 +167,38 @@ impl GhCli {
         ])?;
         Self::parse_pr_list(&raw)
     }
+
+    /// List inline review comments on a pull request.
+    pub fn list_review_comments(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: i64,
+    ) -> Result<Vec<ReviewComment>, GhCliError> {
+        let raw = self.run([
+            "api",
+            "--paginate",
+            &format!("repos/{owner}/{repo}/pulls/{pr_number}/comments"),
+        ])?;
+        Self::parse_review_comments(&raw)
+    }
+
+    /// Fetch the logs of the failed steps of a GitHub Actions workflow run.
+    pub fn failed_run_log(
+        &self,
+        owner: &str,
+        repo: &str,
+        run_id: u64,
+    ) -> Result<String, GhCliError> {
+        self.run([
+            "run",
+            "view",
+            &run_id.to_string(),
+            "--repo",
+            &format!("{owner}/{repo}"),
+            "--log-failed",
+        ])
+    }
 }
 
 impl GhCli {
@@ -207,6 +239,8 @@ impl GhCli {
             status: MergeStatus::Open,
             merged_at: None,
             merge_commit_sha: None,
+            ci_status: None,
+            ci_checks: Vec::new(),
         })
     }
 
@@ -243,6 +277,71 @@ impl GhCli {
             .collect()
     }
 
+    fn parse_review_comments(raw: &str) -> Result<Vec<ReviewComment>, GhCliError> {
+        // `--paginate` prints one JSON array per page back to back
+        let mut comments = Vec::new();
+        for page in serde_json::Deserializer::from_str(raw.trim()).into_iter::<Value>() {
+            let page = page.map_err(|err| {
+                GhCliError::UnexpectedOutput(format!(
+                    "Failed to parse PR review comments response: {err}; raw: {raw}"
+                ))
+            })?;
+            let arr = page.as_array().ok_or_else(|| {
+                GhCliError::UnexpectedOutput(format!(
+                    "PR review comments response is not an array: {page:#?}"
+                ))
+            })?;
+            for item in arr {
+                comments.push(Self::extract_review_comment(item).ok_or_else(|| {
+                    GhCliError::UnexpectedOutput(format!(
+                        "PR review comment missing required fields: {item:#?}"
+                    ))
+                })?);
+            }
+        }
+        Ok(comments)
+    }
+
+    fn extract_review_comment(value: &Value) -> Option<ReviewComment> {
+        let id = value.get("id")?.as_i64()?;
+        let body = value.get("body")?.as_str()?.to_string();
+        let author = value
+            .get("user")
+            .and_then(|u| u.get("login"))
+            .and_then(Value::as_str)
+            .unwrap_or("unknown")
+            .to_string();
+        let created_at = value
+            .get("created_at")
+            .and_then(Value::as_str)
+            .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+            .map(|dt| dt.with_timezone(&Utc))?;
+        // `line` is null once the commented line is outdated; fall back to the original line
+        let line = value
+            .get("line")
+            .and_then(Value::as_i64)
+            .or_else(|| value.get("original_line").and_then(Value::as_i64));
+        Some(ReviewComment {
+            id,
+            author,
+            body,
+            path: value
+                .get("path")
+                .and_then(Value::as_str)
+                .map(|s| s.to_string()),
+            line,
+            diff_hunk: value
+                .get("diff_hunk")
+                .and_then(Value::as_str)
+                .map(|s| s.to_string()),
+            url: value
+                .get("html_url")
+                .and_then(Value::as_str)
+                .map(|s| s.to_string()),
+            created_at,
+        })
+    }
+
     fn extract_pr_info(value: &Value) -> Option<PullRequestInfo> {
         let number = value.get("number")?.as_i64()?;
         let url = value.get("url")?.as_str()?.to_string();
@@ -261,6 +360,11 @@ impl GhCli {
             .and_then(|v| v.get("oid"))
             .and_then(Value::as_str)
             .map(|s| s.to_string());
+        let ci_checks: Vec<CiCheck> = value
+            .get("statusCheckRollup")
+            .and_then(Value::as_array)
+            .map(|items| items.iter().filter_map(Self::extract_ci_check).collect())
+            .unwrap_or_default();
         Some(PullRequestInfo {
             number,
             url,
@@ -272,6 +376,146 @@ impl GhCli {
             },
             merged_at,
             merge_commit_sha,
+            ci_status: CiStatus::aggregate(&ci_checks),
+            ci_checks,
+        })
+    }
+
+    /// Parse an entry of `statusCheckRollup`, which is either a check run
+    /// (Actions and other apps) or a legacy commit status context.
+    fn extract_ci_check(value: &Value) -> Option<CiCheck> {
+        let field = |key: &str| value.get(key).and_then(Value::as_str);
+        let (name, status, details_url) = if let Some(context) = field("context") {
+            let status = match field("state")?.to_ascii_uppercase().as_str() {
+                "SUCCESS" => CiStatus::Success,
+                "PENDING" | "EXPECTED" => CiStatus::Pending,
+                _ => CiStatus::Failure,
+            };
+            (context, status, field("targetUrl"))
+        } else {
+            let status = if field("status")?.eq_ignore_ascii_case("COMPLETED") {
+                match field("conclusion")
+                    .unwrap_or_default()
+                    .to_ascii_uppercase()
+                    .as_str()
+                {
+                    "SUCCESS" | "NEUTRAL" | "SKIPPED" => CiStatus::Success,
+                    _ => CiStatus::Failure,
+                }
+            } else {
+                CiStatus::Pending
+            };
+            (field("name")?, status, field("detailsUrl"))
+        };
+        Some(CiCheck {
+            name: name.to_string(),
+            status,
+            details_url: details_url
+                .filter(|url| !url.is_empty())
+                .map(|url| url.to_string()),
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn pr_with_checks(checks: Value) -> PullRequestInfo {
+        GhCli::extract_pr_info(&json!({
+            "number": 7,
+            "url": "https://github.com/owner/repo/pull/7",
+            "state": "OPEN",
+            "statusCheckRollup": checks,
+        }))
+        .unwrap()
+    }
+
+    fn check_run(name: &str, status: &str, conclusion: &str) -> Value {
+        json!({
+            "__typename": "CheckRun",
+            "name": name,
+            "status": status,
+            "conclusion": conclusion,
+            "detailsUrl": format!("https://ci.example.com/{name}"),
+        })
+    }
+
+    fn status_context(context: &str, state: &str) -> Value {
+        json!({
+            "__typename": "StatusContext",
+            "context": context,
+            "state": state,
+            "targetUrl": "",
+        })
+    }
+
+    #[test]
+    fn pr_without_checks_has_no_ci_status() {
+        let pr = pr_with_checks(json!([]));
+        assert!(pr.ci_checks.is_empty());
+        assert_eq!(pr.ci_status, None);
+    }
+
+    #[test]
+    fn check_runs_and_status_contexts_are_both_parsed() {
+        let pr = pr_with_checks(json!([
+            check_run("build", "COMPLETED", "SUCCESS"),
+            status_context("ci/legacy", "PENDING"),
+        ]));
+        assert_eq!(
+            pr.ci_checks,
+            vec![
+                CiCheck {
+                    name: "build".to_string(),
+                    status: CiStatus::Success,
+                    details_url: Some("https://ci.example.com/build".to_string()),
+                },
+                CiCheck {
+                    name: "ci/legacy".to_string(),
+                    status: CiStatus::Pending,
+                    details_url: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn neutral_and_skipped_runs_count_as_passing() {
+        let pr = pr_with_checks(json!([
+            check_run("build", "COMPLETED", "SUCCESS"),
+            check_run("docs", "COMPLETED", "NEUTRAL"),
+            check_run("deploy", "COMPLETED", "SKIPPED"),
+        ]));
+        assert_eq!(pr.ci_status, Some(CiStatus::Success));
+    }
+
+    #[test]
+    fn unfinished_checks_keep_the_pr_pending() {
+        let pr = pr_with_checks(json!([
+            check_run("build", "COMPLETED", "SUCCESS"),
+            check_run("test", "IN_PROGRESS", ""),
+        ]));
+        assert_eq!(pr.ci_status, Some(CiStatus::Pending));
+    }
+
+    #[test]
+    fn any_failure_fails_the_pr() {
+        let pr = pr_with_checks(json!([
+            check_run("test", "IN_PROGRESS", ""),
+            check_run("lint", "COMPLETED", "TIMED_OUT"),
+            status_context("ci/legacy", "SUCCESS"),
+        ]));
+        assert_eq!(pr.ci_status, Some(CiStatus::Failure));
+        assert_eq!(
+            pr.ci_checks
+                .iter()
+                .filter(|check| check.status == CiStatus::Failure)
+                .map(|check| check.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["lint"]
+        );
+    }
+}
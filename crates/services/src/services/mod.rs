@@ -5,6 +5,9 @@ pub mod auth;
 pub mod config;
 pub mod container;
 pub mod diff_stream;
+pub mod document_search;
+pub mod document_templates;
+pub mod documents;
 pub mod drafts;
 pub mod events;
 pub mod file_ranker;
@@ -16,8 +19,13 @@ pub mod github;
 pub mod image;
 pub mod notification;
 pub mod oauth_credentials;
-pub mod planning;
+pub mod plan_progress;
+pub mod pr_checks;
 pub mod pr_monitor;
+pub mod pr_review;
 pub mod remote_client;
 pub mod share;
+pub mod stacked_branches;
+pub mod subtasks;
+pub mod task_breakdown;
 pub mod worktree_manager;
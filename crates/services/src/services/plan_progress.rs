@@ -0,0 +1,261 @@
+//! Approved plans as checklists. The steps are parsed from the plan's markdown and
+//! checked off as the agent's todo list advances during the implementation.
+
+use std::collections::HashSet;
+
+use db::models::task_plan_step::{TaskPlanStep, TaskPlanStepStatus};
+use executors::logs::TodoItem;
+use lazy_static::lazy_static;
+use regex::Regex;
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+lazy_static! {
+    static ref CHECKBOX_ITEM: Regex = Regex::new(r"^\s*[-*+]\s+\[[ xX]\]\s+(.+)$").unwrap();
+    static ref NUMBERED_ITEM: Regex = Regex::new(r"^\d+[.)]\s+(.+)$").unwrap();
+    static ref NUMBERED_HEADING: Regex =
+        Regex::new(r"^#{2,6}\s+(?:(?i:step)\s+)?\d+[.):]?\s+(.+)$").unwrap();
+    static ref BULLET_ITEM: Regex = Regex::new(r"^[-*+]\s+(.+)$").unwrap();
+}
+
+/// Share of a todo's or step's words the other must contain to count as the same step
+const MIN_SIMILARITY: f64 = 0.5;
+
+/// Steps of a plan, in order: its checkbox items, or else its top-level numbered
+/// items, numbered headings or bullets, whichever comes first in that list
+pub fn parse_plan_steps(plan: &str) -> Vec<String> {
+    let mut kinds: [Vec<String>; 4] = Default::default();
+    let mut in_code_block = false;
+    for line in plan.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block {
+            continue;
+        }
+        let patterns: [&Regex; 4] = [
+            &CHECKBOX_ITEM,
+            &NUMBERED_ITEM,
+            &NUMBERED_HEADING,
+            &BULLET_ITEM,
+        ];
+        for (steps, pattern) in kinds.iter_mut().zip(patterns) {
+            if let Some(captures) = pattern.captures(line) {
+                let title = clean_title(&captures[1]);
+                if !title.is_empty() {
+                    steps.push(title);
+                }
+                break;
+            }
+        }
+    }
+    kinds
+        .into_iter()
+        .find(|steps| !steps.is_empty())
+        .unwrap_or_default()
+}
+
+fn clean_title(text: &str) -> String {
+    text.replace("**", "")
+        .trim()
+        .trim_end_matches(':')
+        .trim()
+        .to_string()
+}
+
+/// Status changes the agent's todo list implies for the steps. Todos are matched
+/// to steps by their wording, or by position when the list mirrors the plan.
+/// Steps only move forward, since agents rewrite their todo lists as they go.
+pub fn step_updates(steps: &[TaskPlanStep], todos: &[TodoItem]) -> Vec<(Uuid, TaskPlanStepStatus)> {
+    let step_words: Vec<HashSet<String>> = steps.iter().map(|s| words(&s.title)).collect();
+    let mut matched: Vec<Option<usize>> = vec![None; steps.len()];
+    for (todo_index, todo) in todos.iter().enumerate() {
+        let todo_words = words(&todo.content);
+        let best = step_words
+            .iter()
+            .enumerate()
+            .filter(|(step_index, _)| matched[*step_index].is_none())
+            .map(|(step_index, words)| (step_index, similarity(words, &todo_words)))
+            .filter(|(_, score)| *score >= MIN_SIMILARITY)
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+        if let Some((step_index, _)) = best {
+            matched[step_index] = Some(todo_index);
+        }
+    }
+    if matched.iter().all(Option::is_none) && todos.len() == steps.len() {
+        matched = (0..steps.len()).map(Some).collect();
+    }
+
+    steps
+        .iter()
+        .zip(matched)
+        .filter_map(|(step, todo_index)| {
+            let status = todo_status(&todos[todo_index?].status);
+            (status > step.status).then_some((step.id, status))
+        })
+        .collect()
+}
+
+fn todo_status(status: &str) -> TaskPlanStepStatus {
+    match status.to_lowercase().replace(['-', ' '], "_").as_str() {
+        "completed" | "complete" | "done" => TaskPlanStepStatus::Completed,
+        "in_progress" | "inprogress" | "active" => TaskPlanStepStatus::InProgress,
+        _ => TaskPlanStepStatus::Pending,
+    }
+}
+
+fn words(text: &str) -> HashSet<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.chars().count() >= 3)
+        .map(str::to_lowercase)
+        .collect()
+}
+
+fn similarity(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    let smaller = a.len().min(b.len());
+    if smaller == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / smaller as f64
+}
+
+/// Replace the task's checklist with the steps of a newly approved plan
+pub async fn save_plan_steps(
+    pool: &SqlitePool,
+    task_id: Uuid,
+    plan: &str,
+) -> Result<Vec<TaskPlanStep>, sqlx::Error> {
+    TaskPlanStep::replace_for_task(pool, task_id, &parse_plan_steps(plan)).await
+}
+
+/// Check off the steps the agent's latest todo list reports progress on
+pub async fn record_todos(
+    pool: &SqlitePool,
+    task_id: Uuid,
+    todos: &[TodoItem],
+) -> Result<(), sqlx::Error> {
+    let steps = TaskPlanStep::find_by_task_id(pool, task_id).await?;
+    TaskPlanStep::update_statuses(pool, task_id, &step_updates(&steps, todos)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+
+    fn step(title: &str, status: TaskPlanStepStatus) -> TaskPlanStep {
+        TaskPlanStep {
+            id: Uuid::new_v4(),
+            task_id: Uuid::nil(),
+            position: 0,
+            title: title.to_string(),
+            status,
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn todo(content: &str, status: &str) -> TodoItem {
+        TodoItem {
+            content: content.to_string(),
+            status: status.to_string(),
+            priority: None,
+        }
+    }
+
+    fn plan_steps() -> Vec<TaskPlanStep> {
+        vec![
+            step("Add the migration", TaskPlanStepStatus::Completed),
+            step("Expose the REST endpoint", TaskPlanStepStatus::Pending),
+            step("Update the frontend card", TaskPlanStepStatus::Pending),
+        ]
+    }
+
+    #[test]
+    fn numbered_items_are_steps_outside_code_blocks() {
+        let plan = "# Plan\n\nSome context.\n\n1. **Add the migration**: new table\n   - detail\n2. Expose the endpoint\n\n```sh\n3. not a step\n```\n- a note";
+        assert_eq!(
+            parse_plan_steps(plan),
+            vec!["Add the migration: new table", "Expose the endpoint"]
+        );
+    }
+
+    #[test]
+    fn checkbox_items_win_over_numbered_items() {
+        assert_eq!(
+            parse_plan_steps("## Steps\n- [x] Write tests\n- [ ] Ship it\n1. ignored"),
+            vec!["Write tests", "Ship it"]
+        );
+    }
+
+    #[test]
+    fn numbered_headings_and_bullets_are_steps_without_lists() {
+        assert_eq!(
+            parse_plan_steps("## Step 1: Schema\ntext\n## Step 2: Routes\n- a note"),
+            vec!["Schema", "Routes"]
+        );
+        assert_eq!(
+            parse_plan_steps("Plan:\n- Schema\n- Routes\n  - nested"),
+            vec!["Schema", "Routes"]
+        );
+        assert!(parse_plan_steps("Just change the colour.").is_empty());
+    }
+
+    #[test]
+    fn todos_match_steps_by_wording() {
+        let steps = plan_steps();
+        let updates = step_updates(
+            &steps,
+            &[
+                todo("Expose REST endpoint for tasks", "in_progress"),
+                todo("Frontend card update", "done"),
+            ],
+        );
+        assert_eq!(
+            updates,
+            vec![
+                (steps[1].id, TaskPlanStepStatus::InProgress),
+                (steps[2].id, TaskPlanStepStatus::Completed),
+            ]
+        );
+    }
+
+    #[test]
+    fn steps_never_move_backwards() {
+        let steps = plan_steps();
+        assert!(step_updates(&steps, &[todo("Add migration", "pending")]).is_empty());
+    }
+
+    #[test]
+    fn unrelated_todos_in_the_plan_shape_match_by_position() {
+        let steps = plan_steps();
+        let updates = step_updates(
+            &steps,
+            &[
+                todo("Schema", "completed"),
+                todo("Route", "completed"),
+                todo("UI", "in_progress"),
+            ],
+        );
+        assert_eq!(
+            updates,
+            vec![
+                (steps[1].id, TaskPlanStepStatus::Completed),
+                (steps[2].id, TaskPlanStepStatus::InProgress),
+            ]
+        );
+    }
+
+    #[test]
+    fn unrelated_todos_of_another_length_are_ignored() {
+        let steps = plan_steps();
+        assert!(
+            step_updates(
+                &steps,
+                &[todo("Schema", "completed"), todo("Route", "completed")]
+            )
+            .is_empty()
+        );
+    }
+}
@@ -0,0 +1,115 @@
+//! CI check results for attempt pull requests.
+//!
+//! The PR monitor records check status on the PR merge row. When checks fail, the
+//! failing check output can be sent to the agent as a queued follow-up.
+
+use db::{
+    DBService,
+    models::{
+        merge::{CiCheck, CiStatus, Merge, PrMerge},
+        task_attempt::TaskAttempt,
+    },
+};
+use thiserror::Error;
+use uuid::Uuid;
+
+use super::{
+    container::ContainerService,
+    drafts::{DraftsService, DraftsServiceError},
+    github::{GitHubRepoInfo, GitHubService, GitHubServiceError},
+};
+
+/// Only the tail of a failed run's log is included in the prompt
+const MAX_LOG_LINES: usize = 150;
+
+#[derive(Debug, Error)]
+pub enum PrChecksError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error(transparent)]
+    GitHubService(#[from] GitHubServiceError),
+    #[error(transparent)]
+    Drafts(#[from] DraftsServiceError),
+    #[error("No failed checks on the attempt's pull request")]
+    NoFailedChecks,
+}
+
+#[derive(Clone)]
+pub struct PrChecksService {
+    db: DBService,
+}
+
+impl PrChecksService {
+    pub fn new(db: DBService) -> Self {
+        Self { db }
+    }
+
+    /// The most recent PR opened from the attempt, if any
+    pub async fn latest_pr(&self, task_attempt_id: Uuid) -> Result<Option<PrMerge>, sqlx::Error> {
+        let merges = Merge::find_by_task_attempt_id(&self.db.pool, task_attempt_id).await?;
+        Ok(merges.into_iter().find_map(|merge| match merge {
+            Merge::Pr(pr_merge) => Some(pr_merge),
+            Merge::Direct(_) => None,
+        }))
+    }
+
+    /// Send the output of the PR's failing checks to the agent as a queued follow-up.
+    pub async fn send_failures_to_agent(
+        &self,
+        drafts: &DraftsService,
+        container: &(dyn ContainerService + Send + Sync),
+        task_attempt: &TaskAttempt,
+    ) -> Result<Vec<CiCheck>, PrChecksError> {
+        let Some(pr_merge) = self.latest_pr(task_attempt.id).await? else {
+            return Err(PrChecksError::NoFailedChecks);
+        };
+        let failed: Vec<CiCheck> = pr_merge
+            .pr_info
+            .ci_checks
+            .iter()
+            .filter(|check| check.status == CiStatus::Failure)
+            .cloned()
+            .collect();
+        if failed.is_empty() {
+            return Err(PrChecksError::NoFailedChecks);
+        }
+
+        let prompt = self.follow_up_prompt(&pr_merge, &failed).await?;
+        drafts
+            .queue_follow_up_prompt(container, task_attempt, &prompt)
+            .await?;
+        Ok(failed)
+    }
+
+    /// Format failing checks, with the tail of their logs where available, as a
+    /// follow-up prompt.
+    async fn follow_up_prompt(
+        &self,
+        pr_merge: &PrMerge,
+        failed: &[CiCheck],
+    ) -> Result<String, PrChecksError> {
+        let github_service = GitHubService::new()?;
+        let repo_info = GitHubRepoInfo::from_remote_url(&pr_merge.pr_info.url)?;
+
+        let mut prompt = format!(
+            "CI checks failed on pull request #{}. Fix the failures below:\n",
+            pr_merge.pr_info.number
+        );
+        for check in failed {
+            prompt.push_str(&format!("\n## {}\n", check.name));
+            if let Some(url) = &check.details_url {
+                prompt.push_str(&format!("Details: {url}\n"));
+            }
+            match github_service.failed_check_log(&repo_info, check).await {
+                Ok(Some(log)) => {
+                    let lines: Vec<&str> = log.lines().collect();
+                    let tail = &lines[lines.len().saturating_sub(MAX_LOG_LINES)..];
+                    prompt.push_str(&format!("```\n{}\n```\n", tail.join("\n")));
+                }
+                Ok(None) => {}
+                Err(e) => tracing::warn!("Failed to fetch logs for check '{}': {}", check.name, e),
+            }
+        }
+        Ok(prompt)
+    }
+}
@@ -3,7 +3,7 @@ use std::time::Duration;
 use db::{
     DBService,
     models::{
-        merge::{Merge, MergeStatus, PrMerge},
+        merge::{CiStatus, Merge, MergeStatus, PrMerge, PullRequestInfo},
         task::{Task, TaskStatus},
         task_attempt::{TaskAttempt, TaskAttemptError},
     },
@@ -11,13 +11,17 @@ use db::{
 use serde_json::json;
 use sqlx::error::Error as SqlxError;
 use thiserror::Error;
-use tokio::time::interval;
+use tokio::{sync::mpsc::UnboundedSender, time::interval};
 use tracing::{debug, error, info};
+use uuid::Uuid;
 
 use crate::services::{
     analytics::AnalyticsContext,
+    git::GitService,
     github::{GitHubRepoInfo, GitHubService, GitHubServiceError},
+    pr_review::PrReviewService,
     share::SharePublisher,
+    stacked_branches::StackedBranchService,
 };
 
 #[derive(Debug, Error)]
@@ -30,25 +34,40 @@ enum PrMonitorError {
     Sqlx(#[from] SqlxError),
 }
 
+/// Notifications from the PR monitor that may warrant a follow-up to the agent
+#[derive(Debug, Clone, Copy)]
+pub enum PrMonitorEvent {
+    /// New review comments were ingested for the attempt
+    ReviewComments(Uuid),
+    /// The checks on the attempt's PR went from not failing to failing
+    ChecksFailed(Uuid),
+}
+
 /// Service to monitor GitHub PRs and update task status when they are merged
 pub struct PrMonitorService {
     db: DBService,
+    git: GitService,
     poll_interval: Duration,
     analytics: Option<AnalyticsContext>,
     publisher: Option<SharePublisher>,
+    events_tx: Option<UnboundedSender<PrMonitorEvent>>,
 }
 
 impl PrMonitorService {
     pub async fn spawn(
         db: DBService,
+        git: GitService,
         analytics: Option<AnalyticsContext>,
         publisher: Option<SharePublisher>,
+        events_tx: Option<UnboundedSender<PrMonitorEvent>>,
     ) -> tokio::task::JoinHandle<()> {
         let service = Self {
             db,
+            git,
             poll_interval: Duration::from_secs(60), // Check every minute
             analytics,
             publisher,
+            events_tx,
         };
         tokio::spawn(async move {
             service.start().await;
@@ -108,6 +127,11 @@ impl PrMonitorService {
             pr_merge.pr_info.number, pr_status.status
         );
 
+        if matches!(&pr_status.status, MergeStatus::Open) {
+            self.record_ci_status(pr_merge, &pr_status).await?;
+            self.sync_review_comments(pr_merge).await;
+        }
+
         // Update the PR status in the database
         if !matches!(&pr_status.status, MergeStatus::Open) {
             // Update merge status with the latest information from GitHub
@@ -130,6 +154,8 @@ impl PrMonitorService {
                 );
                 Task::update_status(&self.db.pool, task_attempt.task_id, TaskStatus::Done).await?;
 
+                self.promote_stacked_attempts(&task_attempt).await;
+
                 // Track analytics event
                 if let Some(analytics) = &self.analytics
                     && let Ok(Some(task)) =
@@ -162,4 +188,82 @@ impl PrMonitorService {
 
         Ok(())
     }
+
+    /// Store the latest check results for an open PR when they changed.
+    async fn record_ci_status(
+        &self,
+        pr_merge: &PrMerge,
+        latest: &PullRequestInfo,
+    ) -> Result<(), PrMonitorError> {
+        let previous = &pr_merge.pr_info;
+        if previous.ci_status == latest.ci_status && previous.ci_checks == latest.ci_checks {
+            return Ok(());
+        }
+
+        Merge::update_ci_status(
+            &self.db.pool,
+            pr_merge.id,
+            latest.ci_status,
+            &latest.ci_checks,
+        )
+        .await?;
+
+        if latest.ci_status == Some(CiStatus::Failure)
+            && previous.ci_status != Some(CiStatus::Failure)
+        {
+            info!(
+                "Checks failed on PR #{} for attempt {}",
+                pr_merge.pr_info.number, pr_merge.task_attempt_id
+            );
+            self.notify(PrMonitorEvent::ChecksFailed(pr_merge.task_attempt_id));
+        }
+        Ok(())
+    }
+
+    fn notify(&self, event: PrMonitorEvent) {
+        if let Some(tx) = &self.events_tx {
+            let _ = tx.send(event);
+        }
+    }
+
+    /// Pull new review comments for an open PR into the attempt.
+    async fn sync_review_comments(&self, pr_merge: &PrMerge) {
+        match PrReviewService::new(self.db.clone())
+            .sync_comments(pr_merge)
+            .await
+        {
+            Ok(created) if !created.is_empty() => {
+                info!(
+                    "Ingested {} new review comments from PR #{}",
+                    created.len(),
+                    pr_merge.pr_info.number
+                );
+                self.notify(PrMonitorEvent::ReviewComments(pr_merge.task_attempt_id));
+            }
+            Ok(_) => {}
+            Err(e) => error!(
+                "Failed to sync review comments for PR #{}: {}",
+                pr_merge.pr_info.number, e
+            ),
+        }
+    }
+
+    /// Rebase attempts stacked on a merged attempt onto the merged attempt's target.
+    async fn promote_stacked_attempts(&self, task_attempt: &TaskAttempt) {
+        match StackedBranchService::new(self.db.clone(), self.git.clone())
+            .promote_dependents_after_remote_merge(task_attempt)
+            .await
+        {
+            Ok(outcomes) if !outcomes.is_empty() => info!(
+                "Restacked {} attempts after PR merge of attempt {}",
+                outcomes.len(),
+                task_attempt.id
+            ),
+            Ok(_) => {}
+            Err(e) => error!(
+                "Failed to restack attempts stacked on {}: {}",
+                task_attempt.id, e
+            ),
+        }
+    }
 }
@@ -0,0 +1,363 @@
+//! Pull request review comments fed back into the attempt.
+//!
+//! Comments on an attempt's open PR are ingested by the PR monitor. They can then
+//! be sent to the agent as a queued follow-up (manually or automatically), and once
+//! the agent has finished, the fix is pushed back to the PR branch.
+
+use std::path::PathBuf;
+
+use db::{
+    DBService,
+    models::{
+        merge::{Merge, MergeStatus, PrMerge},
+        pr_review_comment::{CreatePrReviewComment, PrReviewComment, ReviewCommentStatus},
+        task_attempt::TaskAttempt,
+    },
+};
+use thiserror::Error;
+use uuid::Uuid;
+
+use super::{
+    container::ContainerService,
+    drafts::{DraftsService, DraftsServiceError},
+    git::{GitService, GitServiceError},
+    github::{GitHubRepoInfo, GitHubService, GitHubServiceError, ReviewComment},
+};
+
+#[derive(Debug, Error)]
+pub enum PrReviewError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error(transparent)]
+    GitHubService(#[from] GitHubServiceError),
+    #[error(transparent)]
+    GitService(#[from] GitServiceError),
+    #[error(transparent)]
+    Drafts(#[from] DraftsServiceError),
+    #[error("No pending review comments to send")]
+    NothingToSend,
+}
+
+#[derive(Clone)]
+pub struct PrReviewService {
+    db: DBService,
+}
+
+impl PrReviewService {
+    pub fn new(db: DBService) -> Self {
+        Self { db }
+    }
+
+    /// Fetch review comments for an open PR and store the ones not seen before.
+    /// Returns the newly ingested comments.
+    pub async fn sync_comments(
+        &self,
+        pr_merge: &PrMerge,
+    ) -> Result<Vec<PrReviewComment>, PrReviewError> {
+        let github_service = GitHubService::new()?;
+        let repo_info = GitHubRepoInfo::from_remote_url(&pr_merge.pr_info.url)?;
+        let comments = github_service
+            .list_review_comments(&repo_info, pr_merge.pr_info.number)
+            .await?;
+        self.ingest_comments(pr_merge, comments).await
+    }
+
+    /// Store review comments fetched for `pr_merge`, skipping the ones ingested
+    /// before. Returns the newly ingested comments.
+    pub async fn ingest_comments(
+        &self,
+        pr_merge: &PrMerge,
+        comments: Vec<ReviewComment>,
+    ) -> Result<Vec<PrReviewComment>, PrReviewError> {
+        let mut created = Vec::new();
+        for comment in comments {
+            if let Some(row) = PrReviewComment::create_if_new(
+                &self.db.pool,
+                &CreatePrReviewComment {
+                    merge_id: pr_merge.id,
+                    task_attempt_id: pr_merge.task_attempt_id,
+                    github_comment_id: comment.id,
+                    author: comment.author,
+                    body: comment.body,
+                    path: comment.path,
+                    line: comment.line,
+                    diff_hunk: comment.diff_hunk,
+                    url: comment.url,
+                    commented_at: comment.created_at,
+                },
+            )
+            .await?
+            {
+                created.push(row);
+            }
+        }
+        Ok(created)
+    }
+
+    /// Sync comments for the attempt's open PR, if it has one.
+    pub async fn sync_for_attempt(&self, task_attempt_id: Uuid) -> Result<(), PrReviewError> {
+        let merges = Merge::find_by_task_attempt_id(&self.db.pool, task_attempt_id).await?;
+        for merge in merges {
+            if let Merge::Pr(pr_merge) = merge
+                && matches!(pr_merge.pr_info.status, MergeStatus::Open)
+            {
+                self.sync_comments(&pr_merge).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Send pending review comments (all of them, or only `comment_ids`) to the agent
+    /// as a queued follow-up.
+    pub async fn send_to_agent(
+        &self,
+        drafts: &DraftsService,
+        container: &(dyn ContainerService + Send + Sync),
+        task_attempt: &TaskAttempt,
+        comment_ids: Option<&[Uuid]>,
+    ) -> Result<Vec<PrReviewComment>, PrReviewError> {
+        let comments: Vec<PrReviewComment> = PrReviewComment::find_by_task_attempt_and_status(
+            &self.db.pool,
+            task_attempt.id,
+            ReviewCommentStatus::Pending,
+        )
+        .await?
+        .into_iter()
+        .filter(|c| comment_ids.is_none_or(|ids| ids.contains(&c.id)))
+        .collect();
+        if comments.is_empty() {
+            return Err(PrReviewError::NothingToSend);
+        }
+
+        drafts
+            .queue_follow_up_prompt(container, task_attempt, &Self::follow_up_prompt(&comments))
+            .await?;
+
+        for comment in &comments {
+            PrReviewComment::update_status(&self.db.pool, comment.id, ReviewCommentStatus::Sent)
+                .await?;
+        }
+        Ok(comments)
+    }
+
+    /// Push the attempt branch once the agent has worked through sent comments, and
+    /// mark those comments as addressed. Does nothing when no comments are in flight.
+    pub async fn push_addressed(
+        &self,
+        git: &GitService,
+        task_attempt: &TaskAttempt,
+    ) -> Result<usize, PrReviewError> {
+        let sent = PrReviewComment::find_by_task_attempt_and_status(
+            &self.db.pool,
+            task_attempt.id,
+            ReviewCommentStatus::Sent,
+        )
+        .await?;
+        if sent.is_empty() {
+            return Ok(0);
+        }
+        let Some(container_ref) = &task_attempt.container_ref else {
+            return Ok(0);
+        };
+
+        git.push_to_github(&PathBuf::from(container_ref), &task_attempt.branch)?;
+        for comment in &sent {
+            PrReviewComment::update_status(
+                &self.db.pool,
+                comment.id,
+                ReviewCommentStatus::Addressed,
+            )
+            .await?;
+        }
+        Ok(sent.len())
+    }
+
+    /// Format review comments as a follow-up prompt with file/line context.
+    pub fn follow_up_prompt(comments: &[PrReviewComment]) -> String {
+        let mut prompt = String::from(
+            "The pull request for this task received review comments. Address each of them:\n",
+        );
+        for (idx, comment) in comments.iter().enumerate() {
+            prompt.push_str(&format!("\n{}. ", idx + 1));
+            match (&comment.path, comment.line) {
+                (Some(path), Some(line)) => prompt.push_str(&format!("`{path}` line {line}")),
+                (Some(path), None) => prompt.push_str(&format!("`{path}`")),
+                _ => prompt.push_str("General comment"),
+            }
+            prompt.push_str(&format!(" (by @{}):\n", comment.author));
+            if let Some(hunk) = &comment.diff_hunk {
+                prompt.push_str(&format!("```diff\n{}\n```\n", hunk.trim_end()));
+            }
+            for line in comment.body.trim().lines() {
+                prompt.push_str(&format!("> {line}\n"));
+            }
+        }
+        prompt
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use db::{
+        models::{
+            task::{CreateTask, Task},
+            task_attempt::CreateTaskAttempt,
+        },
+        test_utils::create_project,
+    };
+    use executors::executors::BaseCodingAgent;
+
+    use super::*;
+
+    async fn open_pr(db: &DBService) -> PrMerge {
+        let project = create_project(&db.pool, "/tmp/review").await;
+        let task = Task::create(
+            &db.pool,
+            &CreateTask::from_title_description(project.id, "Review me".to_string(), None),
+            Uuid::new_v4(),
+        )
+        .await
+        .unwrap();
+        let attempt = TaskAttempt::create(
+            &db.pool,
+            &CreateTaskAttempt {
+                executor: BaseCodingAgent::ClaudeCode,
+                base_branch: "main".to_string(),
+                branch: "review-me".to_string(),
+                stacked_on_attempt_id: None,
+            },
+            Uuid::new_v4(),
+            task.id,
+        )
+        .await
+        .unwrap();
+        Merge::create_pr(
+            &db.pool,
+            attempt.id,
+            "main",
+            7,
+            "https://github.com/owner/repo/pull/7",
+        )
+        .await
+        .unwrap()
+    }
+
+    fn fetched(id: i64, body: &str) -> ReviewComment {
+        ReviewComment {
+            id,
+            author: "octocat".to_string(),
+            body: body.to_string(),
+            path: Some("src/lib.rs".to_string()),
+            line: Some(id),
+            diff_hunk: None,
+            url: None,
+            created_at: Utc.timestamp_opt(1_700_000_000 + id, 0).unwrap(),
+        }
+    }
+
+    fn stored(path: Option<&str>, line: Option<i64>, diff_hunk: Option<&str>) -> PrReviewComment {
+        PrReviewComment {
+            id: Uuid::new_v4(),
+            merge_id: Uuid::new_v4(),
+            task_attempt_id: Uuid::new_v4(),
+            github_comment_id: 1,
+            author: "octocat".to_string(),
+            body: "Please handle the error\ninstead of unwrapping.\n".to_string(),
+            path: path.map(str::to_string),
+            line,
+            diff_hunk: diff_hunk.map(str::to_string),
+            url: None,
+            status: ReviewCommentStatus::Pending,
+            commented_at: Utc::now(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn ingest_stores_new_comments_as_pending() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let pr = open_pr(&db).await;
+
+        let created = PrReviewService::new(db.clone())
+            .ingest_comments(&pr, vec![fetched(1, "first"), fetched(2, "second")])
+            .await
+            .unwrap();
+
+        assert_eq!(
+            created.iter().map(|c| c.body.as_str()).collect::<Vec<_>>(),
+            vec!["first", "second"]
+        );
+        assert!(
+            created
+                .iter()
+                .all(|c| c.status == ReviewCommentStatus::Pending
+                    && c.merge_id == pr.id
+                    && c.task_attempt_id == pr.task_attempt_id)
+        );
+    }
+
+    #[tokio::test]
+    async fn ingest_skips_comments_seen_before() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let pr = open_pr(&db).await;
+        let service = PrReviewService::new(db.clone());
+        service
+            .ingest_comments(&pr, vec![fetched(1, "first")])
+            .await
+            .unwrap();
+        PrReviewComment::update_status(
+            &db.pool,
+            PrReviewComment::find_by_task_attempt_id(&db.pool, pr.task_attempt_id)
+                .await
+                .unwrap()[0]
+                .id,
+            ReviewCommentStatus::Sent,
+        )
+        .await
+        .unwrap();
+
+        let created = service
+            .ingest_comments(&pr, vec![fetched(1, "first, edited"), fetched(2, "second")])
+            .await
+            .unwrap();
+
+        assert_eq!(created.len(), 1);
+        assert_eq!(created[0].github_comment_id, 2);
+        let all = PrReviewComment::find_by_task_attempt_id(&db.pool, pr.task_attempt_id)
+            .await
+            .unwrap();
+        assert_eq!(all.len(), 2);
+        // A re-fetched comment keeps the status it was already in
+        assert_eq!(all[0].status, ReviewCommentStatus::Sent);
+    }
+
+    #[test]
+    fn follow_up_prompt_numbers_comments_with_their_location() {
+        let prompt = PrReviewService::follow_up_prompt(&[
+            stored(Some("src/lib.rs"), Some(12), None),
+            stored(Some("README.md"), None, None),
+            stored(None, None, None),
+        ]);
+
+        assert!(prompt.starts_with("The pull request for this task received review comments."));
+        assert!(prompt.contains("\n1. `src/lib.rs` line 12 (by @octocat):\n"));
+        assert!(prompt.contains("\n2. `README.md` (by @octocat):\n"));
+        assert!(prompt.contains("\n3. General comment (by @octocat):\n"));
+    }
+
+    #[test]
+    fn follow_up_prompt_quotes_the_body_after_the_diff_hunk() {
+        let prompt = PrReviewService::follow_up_prompt(&[stored(
+            Some("src/lib.rs"),
+            Some(3),
+            Some("@@ -1,3 +1,3 @@\n-let x = a;\n+let x = a.unwrap();\n"),
+        )]);
+
+        assert!(prompt.ends_with(
+            "```diff\n@@ -1,3 +1,3 @@\n-let x = a;\n+let x = a.unwrap();\n```\n\
+             > Please handle the error\n> instead of unwrapping.\n"
+        ));
+    }
+}
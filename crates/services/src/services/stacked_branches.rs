@@ -0,0 +1,371 @@
+//! Stacked attempt branches.
+//!
+//! An attempt can be created on top of another in-flight attempt's branch. When
+//! the lower branch is rewritten (rebased) or lands in its own target (merged),
+//! the attempts stacked on it are rebased so that they only carry their own
+//! commits, and their `target_branch` is kept pointing at the right place.
+
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
+
+use db::{
+    DBService,
+    models::{
+        merge::{Merge, MergeStatus},
+        project::Project,
+        task::Task,
+        task_attempt::{TaskAttempt, TaskAttemptError},
+    },
+};
+use sqlx::Error as SqlxError;
+use thiserror::Error;
+use uuid::Uuid;
+
+use super::{
+    git::{CommitOptions, GitService, GitServiceError},
+    worktree_manager::{WorktreeError, WorktreeManager},
+};
+
+#[derive(Debug, Error)]
+pub enum StackedBranchError {
+    #[error(transparent)]
+    Database(#[from] SqlxError),
+    #[error(transparent)]
+    TaskAttempt(#[from] TaskAttemptError),
+    #[error(transparent)]
+    GitService(#[from] GitServiceError),
+    #[error(transparent)]
+    Worktree(#[from] WorktreeError),
+    #[error("Attempt to stack on was not found")]
+    ParentNotFound,
+    #[error("Cannot stack on an attempt from another project")]
+    ParentInOtherProject,
+    #[error("Cannot stack on an attempt whose worktree has been cleaned up")]
+    ParentWorktreeDeleted,
+    #[error("Cannot stack on an attempt that has already been merged")]
+    ParentMerged,
+    #[error("Cannot stack on attempt {0}: its stack loops back on itself")]
+    Cycle(Uuid),
+}
+
+#[derive(Debug, Clone)]
+pub enum RestackStatus {
+    /// Branch was rebased onto the new base
+    Rebased { head_oid: String },
+    /// Only the stored target was updated (no branch or worktree to rebase yet)
+    Retargeted,
+    /// Rebase stopped on conflicts; the worktree is left mid-rebase for the user
+    Conflicts { message: String },
+    /// Rebase could not be attempted (e.g. dirty worktree)
+    Skipped { reason: String },
+}
+
+#[derive(Debug, Clone)]
+pub struct RestackOutcome {
+    pub attempt_id: Uuid,
+    pub branch: String,
+    pub target_branch: String,
+    pub status: RestackStatus,
+}
+
+#[derive(Clone)]
+pub struct StackedBranchService {
+    db: DBService,
+    git: GitService,
+}
+
+impl StackedBranchService {
+    pub fn new(db: DBService, git: GitService) -> Self {
+        Self { db, git }
+    }
+
+    /// Check that a new attempt in `project_id` can be stacked on `parent_id` and return
+    /// the parent, whose branch the new attempt starts from.
+    pub async fn stack_parent(
+        &self,
+        project_id: Uuid,
+        parent_id: Uuid,
+    ) -> Result<TaskAttempt, StackedBranchError> {
+        let pool = &self.db.pool;
+        let parent = TaskAttempt::find_by_id(pool, parent_id)
+            .await?
+            .ok_or(StackedBranchError::ParentNotFound)?;
+        let parent_task = parent
+            .parent_task(pool)
+            .await?
+            .ok_or(TaskAttemptError::TaskNotFound)?;
+        if parent_task.project_id != project_id {
+            return Err(StackedBranchError::ParentInOtherProject);
+        }
+        if parent.worktree_deleted {
+            return Err(StackedBranchError::ParentWorktreeDeleted);
+        }
+        let merged = Merge::find_by_task_attempt_id(pool, parent.id)
+            .await?
+            .iter()
+            .any(|merge| match merge {
+                Merge::Direct(_) => true,
+                Merge::Pr(pr) => matches!(pr.pr_info.status, MergeStatus::Merged),
+            });
+        if merged {
+            return Err(StackedBranchError::ParentMerged);
+        }
+
+        // Refuse to build on a stack whose links loop, since restacking would never end
+        let mut seen = HashSet::from([parent.id]);
+        let mut next = parent.stacked_on_attempt_id;
+        while let Some(id) = next {
+            if !seen.insert(id) {
+                return Err(StackedBranchError::Cycle(parent.id));
+            }
+            next = TaskAttempt::find_by_id(pool, id)
+                .await?
+                .and_then(|ancestor| ancestor.stacked_on_attempt_id);
+        }
+
+        Ok(parent)
+    }
+
+    /// Walk up the stack from `attempt`, returning the attempts it is stacked on
+    /// (closest first).
+    pub async fn ancestors(
+        &self,
+        attempt: &TaskAttempt,
+    ) -> Result<Vec<TaskAttempt>, StackedBranchError> {
+        let mut ancestors = Vec::new();
+        let mut next = attempt.stacked_on_attempt_id;
+        while let Some(id) = next {
+            // Guard against cycles in corrupted data
+            if id == attempt.id || ancestors.iter().any(|a: &TaskAttempt| a.id == id) {
+                break;
+            }
+            let Some(parent) = TaskAttempt::find_by_id(&self.db.pool, id).await? else {
+                break;
+            };
+            next = parent.stacked_on_attempt_id;
+            ancestors.push(parent);
+        }
+        Ok(ancestors)
+    }
+
+    /// Attempts stacked directly on `attempt`.
+    pub async fn dependents(
+        &self,
+        attempt: &TaskAttempt,
+    ) -> Result<Vec<TaskAttempt>, StackedBranchError> {
+        Ok(TaskAttempt::find_stacked_on(&self.db.pool, attempt.id, &attempt.branch).await?)
+    }
+
+    /// Drop `attempt` out of its stack when its target no longer points at the branch
+    /// of the attempt it was stacked on.
+    pub async fn detach_if_retargeted(
+        &self,
+        attempt: &TaskAttempt,
+        new_target_branch: &str,
+    ) -> Result<(), StackedBranchError> {
+        let Some(parent_id) = attempt.stacked_on_attempt_id else {
+            return Ok(());
+        };
+        let still_stacked = TaskAttempt::find_by_id(&self.db.pool, parent_id)
+            .await?
+            .is_some_and(|parent| parent.branch == new_target_branch);
+        if !still_stacked {
+            TaskAttempt::update_stacked_on(&self.db.pool, attempt.id, None, new_target_branch)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Rebase every attempt stacked on `parent` after the parent branch was rewritten.
+    /// `old_parent_head` is the parent branch tip before the rewrite.
+    pub async fn restack_dependents(
+        &self,
+        parent: &TaskAttempt,
+        old_parent_head: &str,
+    ) -> Result<Vec<RestackOutcome>, StackedBranchError> {
+        self.restack(
+            parent,
+            old_parent_head,
+            Some(&parent.branch),
+            &parent.branch,
+            Some(parent.id),
+        )
+        .await
+    }
+
+    /// Move attempts stacked on `parent` onto the parent's own target once the parent
+    /// has been merged. The dependents inherit the parent's position in the stack.
+    pub async fn promote_dependents_after_merge(
+        &self,
+        parent: &TaskAttempt,
+        old_parent_head: &str,
+    ) -> Result<Vec<RestackOutcome>, StackedBranchError> {
+        self.restack(
+            parent,
+            old_parent_head,
+            Some(&parent.target_branch),
+            &parent.target_branch,
+            parent.stacked_on_attempt_id,
+        )
+        .await
+    }
+
+    /// Same as [`Self::promote_dependents_after_merge`] for merges that happened on the
+    /// remote (e.g. a merged pull request), where the local parent branch is untouched.
+    /// The local target branch does not contain the merge yet, so dependents are rebased
+    /// onto its remote-tracking branch (fetched first), or only retargeted when the
+    /// target has none.
+    pub async fn promote_dependents_after_remote_merge(
+        &self,
+        parent: &TaskAttempt,
+    ) -> Result<Vec<RestackOutcome>, StackedBranchError> {
+        if self.dependents(parent).await?.is_empty() {
+            return Ok(Vec::new());
+        }
+        let repo_path = self.project(parent).await?.git_repo_path;
+        let old_parent_head = self.git.get_branch_oid(&repo_path, &parent.branch)?;
+        let merged_base = self
+            .git
+            .remote_tracking_branch(&repo_path, &parent.target_branch)?;
+        self.restack(
+            parent,
+            &old_parent_head,
+            merged_base.as_deref(),
+            &parent.target_branch,
+            parent.stacked_on_attempt_id,
+        )
+        .await
+    }
+
+    /// Rebase the dependents of `parent` onto `rebase_onto` (only retarget them when
+    /// `None`) and record `new_target` as their target branch.
+    async fn restack(
+        &self,
+        parent: &TaskAttempt,
+        old_parent_head: &str,
+        rebase_onto: Option<&str>,
+        new_target: &str,
+        new_stacked_on: Option<Uuid>,
+    ) -> Result<Vec<RestackOutcome>, StackedBranchError> {
+        let dependents = self.dependents(parent).await?;
+        if dependents.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let project = self.project(parent).await?;
+        let repo_path = project.git_repo_path;
+        let commit_options = CommitOptions::for_project(&self.db.pool, project.id).await?;
+        let mut outcomes = Vec::new();
+
+        for child in dependents {
+            let old_child_head = self.git.get_branch_oid(&repo_path, &child.branch).ok();
+            let status = match rebase_onto {
+                Some(onto) => {
+                    self.rebase_child(&repo_path, &child, old_parent_head, onto, &commit_options)
+                        .await
+                }
+                None => RestackStatus::Retargeted,
+            };
+
+            if !matches!(status, RestackStatus::Skipped { .. }) {
+                TaskAttempt::update_stacked_on(&self.db.pool, child.id, new_stacked_on, new_target)
+                    .await?;
+            }
+
+            let rebased = matches!(status, RestackStatus::Rebased { .. });
+            outcomes.push(RestackOutcome {
+                attempt_id: child.id,
+                branch: child.branch.clone(),
+                target_branch: new_target.to_string(),
+                status,
+            });
+
+            // The child's branch was rewritten too, so its own dependents need the same treatment
+            if rebased && let Some(old_child_head) = old_child_head {
+                let nested = Box::pin(self.restack_dependents(&child, &old_child_head)).await?;
+                outcomes.extend(nested);
+            }
+        }
+
+        Ok(outcomes)
+    }
+
+    async fn rebase_child(
+        &self,
+        repo_path: &Path,
+        child: &TaskAttempt,
+        old_parent_head: &str,
+        new_base: &str,
+        commit_options: &CommitOptions,
+    ) -> RestackStatus {
+        let Some(container_ref) = &child.container_ref else {
+            return RestackStatus::Retargeted;
+        };
+        if !self
+            .git
+            .check_branch_exists(repo_path, &child.branch)
+            .unwrap_or(false)
+        {
+            return RestackStatus::Retargeted;
+        }
+
+        let worktree_path = PathBuf::from(container_ref);
+        if let Err(e) =
+            WorktreeManager::ensure_worktree_exists(repo_path, &child.branch, &worktree_path).await
+        {
+            tracing::warn!(
+                "Failed to prepare worktree for stacked attempt {}: {}",
+                child.id,
+                e
+            );
+            return RestackStatus::Skipped {
+                reason: e.to_string(),
+            };
+        }
+
+        match self.git.rebase_branch(
+            repo_path,
+            &worktree_path,
+            new_base,
+            old_parent_head,
+            &child.branch,
+            commit_options,
+        ) {
+            Ok(head_oid) => {
+                tracing::info!(
+                    "Rebased stacked attempt {} ('{}') onto '{}'",
+                    child.id,
+                    child.branch,
+                    new_base
+                );
+                RestackStatus::Rebased { head_oid }
+            }
+            Err(GitServiceError::MergeConflicts(message)) => {
+                tracing::warn!(
+                    "Stacked attempt {} has conflicts after rebasing onto '{}'",
+                    child.id,
+                    new_base
+                );
+                RestackStatus::Conflicts { message }
+            }
+            Err(e) => {
+                tracing::warn!("Failed to rebase stacked attempt {}: {}", child.id, e);
+                RestackStatus::Skipped {
+                    reason: e.to_string(),
+                }
+            }
+        }
+    }
+
+    async fn project(&self, attempt: &TaskAttempt) -> Result<Project, StackedBranchError> {
+        let task = Task::find_by_id(&self.db.pool, attempt.task_id)
+            .await?
+            .ok_or(TaskAttemptError::TaskNotFound)?;
+        let project = Project::find_by_id(&self.db.pool, task.project_id)
+            .await?
+            .ok_or(TaskAttemptError::ProjectNotFound)?;
+        Ok(project)
+    }
+}
@@ -0,0 +1,292 @@
+//! Creating a set of related tasks at once, such as the subtasks an agent splits a
+//! task into or the tasks of a document breakdown. A task may only depend on tasks
+//! earlier in the set, which rules out dependency cycles.
+
+use db::models::{
+    task::{CreateTask, Task, TaskStatus},
+    task_dependency::TaskDependency,
+    task_document::TaskDocument,
+};
+use executors::executors::BaseCodingAgent;
+use serde::{Deserialize, Serialize};
+use sqlx::SqliteConnection;
+use thiserror::Error;
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum InvalidSubtasks {
+    #[error("No tasks given")]
+    Empty,
+    #[error("Task {0} has no title")]
+    MissingTitle(usize),
+    #[error("Task {index} can only depend on earlier tasks, not {dependency}")]
+    InvalidDependency { index: usize, dependency: usize },
+}
+
+#[derive(Debug, Error)]
+pub enum SubtaskError {
+    #[error(transparent)]
+    Invalid(#[from] InvalidSubtasks),
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+}
+
+/// A task to create as part of a set
+#[derive(Debug, Clone)]
+pub struct NewSubtask {
+    pub title: String,
+    pub description: Option<String>,
+    pub suggested_executor: Option<BaseCodingAgent>,
+    /// Indices of earlier tasks in the set that must be done first
+    pub depends_on: Vec<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, TS)]
+pub struct Subtask {
+    #[serde(flatten)]
+    #[ts(flatten)]
+    pub task: Task,
+    /// Ids of the subtasks that must be done first
+    pub depends_on: Vec<Uuid>,
+}
+
+/// Check that every task has a title and only depends on tasks before it. Takes the
+/// title and dependency indices of each task in order.
+pub fn validate_subtasks<'a>(
+    subtasks: impl IntoIterator<Item = (&'a str, &'a [usize])>,
+) -> Result<(), InvalidSubtasks> {
+    let mut count = 0;
+    for (index, (title, depends_on)) in subtasks.into_iter().enumerate() {
+        if title.trim().is_empty() {
+            return Err(InvalidSubtasks::MissingTitle(index));
+        }
+        if let Some(&dependency) = depends_on.iter().find(|&&d| d >= index) {
+            return Err(InvalidSubtasks::InvalidDependency { index, dependency });
+        }
+        count += 1;
+    }
+    if count == 0 {
+        return Err(InvalidSubtasks::Empty);
+    }
+    Ok(())
+}
+
+/// Create the tasks as children of `parent_task_attempt`, each linked to `document_id`
+/// when given, along with their dependencies. Runs on the caller's connection so the
+/// caller decides the transaction; on error nothing should be committed.
+pub async fn create_subtasks(
+    conn: &mut SqliteConnection,
+    project_id: Uuid,
+    parent_task_attempt: Uuid,
+    document_id: Option<Uuid>,
+    subtasks: &[NewSubtask],
+) -> Result<Vec<Subtask>, SubtaskError> {
+    validate_subtasks(
+        subtasks
+            .iter()
+            .map(|s| (s.title.as_str(), s.depends_on.as_slice())),
+    )?;
+
+    let mut created: Vec<Subtask> = Vec::with_capacity(subtasks.len());
+    for subtask in subtasks {
+        let create = CreateTask {
+            project_id,
+            title: subtask.title.trim().to_string(),
+            description: subtask.description.clone(),
+            status: Some(TaskStatus::Todo),
+            kind: None,
+            suggested_executor: subtask.suggested_executor,
+            parent_task_attempt: Some(parent_task_attempt),
+            image_ids: None,
+            shared_task_id: None,
+        };
+        let task = Task::create(&mut *conn, &create, Uuid::new_v4()).await?;
+        if let Some(document_id) = document_id {
+            TaskDocument::create(&mut *conn, task.id, document_id).await?;
+        }
+
+        let mut depends_on = Vec::with_capacity(subtask.depends_on.len());
+        for &index in &subtask.depends_on {
+            let dependency_id = created[index].task.id;
+            if depends_on.contains(&dependency_id) {
+                continue;
+            }
+            TaskDependency::create(&mut *conn, task.id, dependency_id).await?;
+            depends_on.push(dependency_id);
+        }
+        created.push(Subtask { task, depends_on });
+    }
+    Ok(created)
+}
+
+#[cfg(test)]
+mod tests {
+    use db::{
+        DBService,
+        models::task_attempt::{CreateTaskAttempt, TaskAttempt},
+        test_utils::create_project,
+    };
+
+    use super::*;
+
+    fn new(title: &str, depends_on: &[usize]) -> NewSubtask {
+        NewSubtask {
+            title: title.to_string(),
+            description: None,
+            suggested_executor: None,
+            depends_on: depends_on.to_vec(),
+        }
+    }
+
+    fn validate(subtasks: &[NewSubtask]) -> Result<(), InvalidSubtasks> {
+        validate_subtasks(
+            subtasks
+                .iter()
+                .map(|s| (s.title.as_str(), s.depends_on.as_slice())),
+        )
+    }
+
+    /// A project with one task and attempt to create subtasks under
+    async fn parent(db: &DBService) -> (Uuid, Uuid) {
+        let project = create_project(&db.pool, "/tmp/subtasks").await;
+        let task = Task::create(
+            &db.pool,
+            &CreateTask::from_title_description(project.id, "Parent".to_string(), None),
+            Uuid::new_v4(),
+        )
+        .await
+        .unwrap();
+        let attempt = TaskAttempt::create(
+            &db.pool,
+            &CreateTaskAttempt {
+                executor: BaseCodingAgent::ClaudeCode,
+                base_branch: "main".to_string(),
+                branch: "parent".to_string(),
+                stacked_on_attempt_id: None,
+            },
+            Uuid::new_v4(),
+            task.id,
+        )
+        .await
+        .unwrap();
+        (project.id, attempt.id)
+    }
+
+    #[test]
+    fn dependencies_on_earlier_tasks_are_valid() {
+        assert_eq!(
+            validate(&[new("Schema", &[]), new("API", &[0]), new("UI", &[0, 1])]),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn dependencies_on_the_task_itself_or_later_ones_are_rejected() {
+        assert_eq!(
+            validate(&[new("Schema", &[0])]),
+            Err(InvalidSubtasks::InvalidDependency {
+                index: 0,
+                dependency: 0
+            })
+        );
+        assert_eq!(
+            validate(&[new("Schema", &[]), new("API", &[2]), new("UI", &[])]),
+            Err(InvalidSubtasks::InvalidDependency {
+                index: 1,
+                dependency: 2
+            })
+        );
+    }
+
+    #[test]
+    fn empty_sets_and_blank_titles_are_rejected() {
+        assert_eq!(validate(&[]), Err(InvalidSubtasks::Empty));
+        assert_eq!(
+            validate(&[new("Schema", &[]), new("  ", &[])]),
+            Err(InvalidSubtasks::MissingTitle(1))
+        );
+    }
+
+    #[tokio::test]
+    async fn creates_tasks_with_their_dependencies() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let (project_id, attempt_id) = parent(&db).await;
+        let mut tx = db.pool.begin().await.unwrap();
+
+        let created = create_subtasks(
+            &mut tx,
+            project_id,
+            attempt_id,
+            None,
+            &[new("Schema", &[]), new(" API ", &[0, 0])],
+        )
+        .await
+        .unwrap();
+        tx.commit().await.unwrap();
+
+        assert_eq!(created[1].task.title, "API");
+        assert_eq!(created[1].task.parent_task_attempt, Some(attempt_id));
+        assert_eq!(created[1].depends_on, vec![created[0].task.id]);
+        let stored = TaskDependency::find_by_task_id(&db.pool, created[1].task.id)
+            .await
+            .unwrap();
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].depends_on_task_id, created[0].task.id);
+    }
+
+    #[tokio::test]
+    async fn suggested_executors_are_stored_on_the_task() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let (project_id, attempt_id) = parent(&db).await;
+        let subtask = NewSubtask {
+            description: Some("Add the endpoint".to_string()),
+            suggested_executor: Some(BaseCodingAgent::Codex),
+            ..new("API", &[])
+        };
+        let mut tx = db.pool.begin().await.unwrap();
+
+        let created = create_subtasks(&mut tx, project_id, attempt_id, None, &[subtask])
+            .await
+            .unwrap();
+        tx.commit().await.unwrap();
+
+        let stored = Task::find_by_id(&db.pool, created[0].task.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.suggested_executor, Some(BaseCodingAgent::Codex));
+        assert_eq!(stored.description.as_deref(), Some("Add the endpoint"));
+    }
+
+    #[tokio::test]
+    async fn a_failure_part_way_leaves_no_tasks_behind() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let (project_id, attempt_id) = parent(&db).await;
+        let tasks_before = Task::find_by_project_id_with_attempt_status(&db.pool, project_id)
+            .await
+            .unwrap()
+            .len();
+
+        // Linking to a document that does not exist fails after the first insert
+        let mut tx = db.pool.begin().await.unwrap();
+        let result = create_subtasks(
+            &mut tx,
+            project_id,
+            attempt_id,
+            Some(Uuid::new_v4()),
+            &[new("Schema", &[]), new("API", &[0])],
+        )
+        .await;
+        drop(tx);
+
+        assert!(matches!(result, Err(SubtaskError::Database(_))));
+        assert_eq!(
+            Task::find_by_project_id_with_attempt_status(&db.pool, project_id)
+                .await
+                .unwrap()
+                .len(),
+            tasks_before
+        );
+    }
+}
@@ -0,0 +1,225 @@
+//! Turning a planning document, such as a PRD or an epics file, into kanban tasks.
+//! A coding agent reads the document and replies with a JSON breakdown, which is
+//! previewed and edited before the tasks are created.
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use ts_rs::TS;
+
+use crate::services::{
+    documents::Document,
+    subtasks::{InvalidSubtasks, NewSubtask, validate_subtasks},
+};
+
+#[derive(Debug, Error)]
+pub enum TaskBreakdownError {
+    #[error("The agent's reply contains no JSON task breakdown")]
+    MissingJson,
+    #[error("The agent's task breakdown is not valid JSON: {0}")]
+    InvalidJson(#[from] serde_json::Error),
+    #[error(transparent)]
+    Invalid(#[from] InvalidSubtasks),
+}
+
+/// A task the breakdown suggests creating
+#[derive(Debug, Clone, Serialize, Deserialize, TS, PartialEq)]
+pub struct ProposedTask {
+    pub title: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub acceptance_criteria: Vec<String>,
+    /// Indices of earlier tasks in the breakdown that must be done first
+    #[serde(default)]
+    pub depends_on: Vec<usize>,
+}
+
+impl ProposedTask {
+    /// Description of the created task, with the acceptance criteria as a checklist
+    pub fn task_description(&self) -> Option<String> {
+        let description = self
+            .description
+            .as_deref()
+            .map(str::trim)
+            .filter(|d| !d.is_empty());
+        let criteria: Vec<String> = self
+            .acceptance_criteria
+            .iter()
+            .map(|c| c.trim())
+            .filter(|c| !c.is_empty())
+            .map(|c| format!("- [ ] {c}"))
+            .collect();
+
+        match (description, criteria.is_empty()) {
+            (None, true) => None,
+            (Some(description), true) => Some(description.to_string()),
+            (description, false) => Some(format!(
+                "{}## Acceptance criteria\n{}",
+                description.map(|d| format!("{d}\n\n")).unwrap_or_default(),
+                criteria.join("\n")
+            )),
+        }
+    }
+
+    /// The task to create for this proposal
+    pub fn to_subtask(&self) -> NewSubtask {
+        NewSubtask {
+            title: self.title.clone(),
+            description: self.task_description(),
+            suggested_executor: None,
+            depends_on: self.depends_on.clone(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct BreakdownReply {
+    tasks: Vec<ProposedTask>,
+}
+
+pub fn breakdown_task_title(document: &Document) -> String {
+    format!("Break down {} into tasks", document.title)
+}
+
+/// Instructions for the agent. The document itself reaches the agent as a linked
+/// document of the breakdown task.
+pub fn breakdown_task_description(document: &Document) -> String {
+    format!(
+        r#"Split the `{title}` project document below into kanban tasks a coding agent can implement one at a time. Do not change any files.
+
+Read the relevant code first so the tasks fit the existing project. Each task should be small enough for a single pull request, and its description should say what to build and where.
+
+Reply with the breakdown as a single fenced JSON block in this shape, in implementation order:
+
+```json
+{{
+  "tasks": [
+    {{
+      "title": "Short imperative title",
+      "description": "What to build and why, with pointers to the relevant code",
+      "acceptance_criteria": ["Observable outcome that shows the task is done"],
+      "depends_on": [0]
+    }}
+  ]
+}}
+```
+
+`depends_on` lists the indices of earlier tasks in the list that must be done first."#,
+        title = document.title
+    )
+}
+
+/// Extract the breakdown from the agent's reply. The last fenced `json` block wins,
+/// so a reply refined through follow-ups yields the latest version.
+pub fn parse_breakdown(reply: &str) -> Result<Vec<ProposedTask>, TaskBreakdownError> {
+    let json = last_json_block(reply)
+        .or_else(|| {
+            let trimmed = reply.trim();
+            trimmed.starts_with('{').then_some(trimmed)
+        })
+        .ok_or(TaskBreakdownError::MissingJson)?;
+    let tasks = serde_json::from_str::<BreakdownReply>(json)?.tasks;
+    validate_breakdown(&tasks)?;
+    Ok(tasks)
+}
+
+/// Extract the breakdown from the agent's replies, oldest first. The newest reply
+/// with a JSON block decides, so closing remarks after the breakdown are skipped.
+pub fn parse_latest_breakdown<'a>(
+    replies: impl IntoIterator<Item = &'a str>,
+) -> Result<Vec<ProposedTask>, TaskBreakdownError> {
+    let replies: Vec<&str> = replies.into_iter().collect();
+    for reply in replies.into_iter().rev() {
+        match parse_breakdown(reply) {
+            Err(TaskBreakdownError::MissingJson) => continue,
+            result => return result,
+        }
+    }
+    Err(TaskBreakdownError::MissingJson)
+}
+
+pub fn validate_breakdown(tasks: &[ProposedTask]) -> Result<(), TaskBreakdownError> {
+    validate_subtasks(
+        tasks
+            .iter()
+            .map(|task| (task.title.as_str(), task.depends_on.as_slice())),
+    )?;
+    Ok(())
+}
+
+fn last_json_block(text: &str) -> Option<&str> {
+    let start = text.rfind("```json")? + "```json".len();
+    let body = &text[start..];
+    let end = body.find("```")?;
+    Some(body[..end].trim())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_last_json_block_and_checks_dependencies() {
+        let reply = r#"First draft:
+```json
+{"tasks": [{"title": "Old"}]}
+```
+Revised:
+```json
+{"tasks": [
+  {"title": "Add schema", "acceptance_criteria": ["Migration runs"]},
+  {"title": "Add endpoint", "description": "REST route", "depends_on": [0]}
+]}
+```"#;
+        let tasks = parse_breakdown(reply).unwrap();
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(tasks[1].depends_on, vec![0]);
+        assert_eq!(
+            tasks[0].task_description().as_deref(),
+            Some("## Acceptance criteria\n- [ ] Migration runs")
+        );
+
+        let forward = r#"```json
+{"tasks": [{"title": "A", "depends_on": [1]}, {"title": "B"}]}
+```"#;
+        assert!(matches!(
+            parse_breakdown(forward),
+            Err(TaskBreakdownError::Invalid(
+                InvalidSubtasks::InvalidDependency {
+                    index: 0,
+                    dependency: 1
+                }
+            ))
+        ));
+        assert!(matches!(
+            parse_breakdown("No tasks here"),
+            Err(TaskBreakdownError::MissingJson)
+        ));
+    }
+
+    #[test]
+    fn newest_reply_with_a_breakdown_wins() {
+        let first = r#"```json
+{"tasks": [{"title": "Old"}]}
+```"#;
+        let refined = r#"```json
+{"tasks": [{"title": "New"}]}
+```"#;
+        let tasks =
+            parse_latest_breakdown([first, refined, "Let me know if you want changes."]).unwrap();
+        assert_eq!(tasks[0].title, "New");
+
+        let broken = r#"```json
+{"tasks": [{"title": ""}]}
+```"#;
+        assert!(matches!(
+            parse_latest_breakdown([first, broken]),
+            Err(TaskBreakdownError::Invalid(InvalidSubtasks::MissingTitle(
+                0
+            )))
+        ));
+        assert!(matches!(
+            parse_latest_breakdown(["Working on it", "Done"]),
+            Err(TaskBreakdownError::MissingJson)
+        ));
+    }
+}
@@ -77,6 +77,26 @@ impl WorktreeManager {
         repo_path: &Path,
         branch_name: &str,
         worktree_path: &Path,
+    ) -> Result<(), WorktreeError> {
+        Self::ensure_checkout_exists(repo_path, branch_name, worktree_path, false).await
+    }
+
+    /// Ensure a worktree with a detached HEAD at `base_branch` exists, for work that
+    /// needs a checkout of its own but no branch. Commits made in it stay on the
+    /// detached HEAD and are gone once the worktree is cleaned up.
+    pub async fn ensure_detached_worktree_exists(
+        repo_path: &Path,
+        base_branch: &str,
+        worktree_path: &Path,
+    ) -> Result<(), WorktreeError> {
+        Self::ensure_checkout_exists(repo_path, base_branch, worktree_path, true).await
+    }
+
+    async fn ensure_checkout_exists(
+        repo_path: &Path,
+        branch_name: &str,
+        worktree_path: &Path,
+        detach: bool,
     ) -> Result<(), WorktreeError> {
         let path_str = worktree_path.to_string_lossy().to_string();
 
@@ -100,7 +120,7 @@ impl WorktreeManager {
 
         // If worktree doesn't exist or isn't properly set up, recreate it
         info!("Worktree needs recreation at path: {}", path_str);
-        Self::recreate_worktree_internal(repo_path, branch_name, worktree_path).await
+        Self::recreate_worktree_internal(repo_path, branch_name, worktree_path, detach).await
     }
 
     /// Internal worktree recreation function (always recreates)
@@ -108,6 +128,7 @@ impl WorktreeManager {
         repo_path: &Path,
         branch_name: &str,
         worktree_path: &Path,
+        detach: bool,
     ) -> Result<(), WorktreeError> {
         let path_str = worktree_path.to_string_lossy().to_string();
         let branch_name_owned = branch_name.to_string();
@@ -152,6 +173,7 @@ impl WorktreeManager {
             &worktree_path_owned,
             &worktree_name,
             &path_str,
+            detach,
         )
         .await
     }
@@ -282,6 +304,7 @@ impl WorktreeManager {
         worktree_path: &Path,
         worktree_name: &str,
         path_str: &str,
+        detach: bool,
     ) -> Result<(), WorktreeError> {
         let git_repo_path = git_repo_path.to_path_buf();
         let branch_name = branch_name.to_string();
@@ -292,7 +315,14 @@ impl WorktreeManager {
         tokio::task::spawn_blocking(move || -> Result<(), WorktreeError> {
             // Prefer git CLI for worktree add to inherit sparse-checkout semantics
             let git_service = GitService::new();
-            match git_service.add_worktree(&git_repo_path, &worktree_path, &branch_name, false) {
+            let add_worktree = || {
+                if detach {
+                    git_service.add_detached_worktree(&git_repo_path, &worktree_path, &branch_name)
+                } else {
+                    git_service.add_worktree(&git_repo_path, &worktree_path, &branch_name, false)
+                }
+            };
+            match add_worktree() {
                 Ok(()) => {
                     if !worktree_path.exists() {
                         return Err(WorktreeError::Repository(format!(
@@ -318,12 +348,7 @@ impl WorktreeManager {
                     if worktree_path.exists() {
                         std::fs::remove_dir_all(&worktree_path).map_err(WorktreeError::Io)?;
                     }
-                    if let Err(e2) = git_service.add_worktree(
-                        &git_repo_path,
-                        &worktree_path,
-                        &branch_name,
-                        false,
-                    ) {
+                    if let Err(e2) = add_worktree() {
                         return Err(WorktreeError::GitService(e2));
                     }
                     if !worktree_path.exists() {
@@ -5,7 +5,7 @@ use std::{
 };
 
 use git2::{PushOptions, Repository, build::CheckoutBuilder};
-use services::services::git::{GitCli, GitCliError, GitService};
+use services::services::git::{CommitOptions, GitCli, GitCliError, GitService};
 use tempfile::TempDir;
 // Avoid direct git CLI usage in tests; exercise GitService instead.
 
@@ -420,6 +420,7 @@ fn rebase_preserves_untracked_files() {
         "new-base",
         "old-base",
         "feature",
+        &CommitOptions::default(),
     );
     assert!(res.is_ok(), "rebase should succeed: {res:?}");
 
@@ -442,6 +443,7 @@ fn rebase_aborts_on_uncommitted_tracked_changes() {
         "new-base",
         "old-base",
         "feature",
+        &CommitOptions::default(),
     );
     assert!(res.is_err(), "rebase should fail on dirty worktree");
 
@@ -463,6 +465,7 @@ fn rebase_aborts_if_untracked_would_be_overwritten_by_base() {
         "new-base",
         "old-base",
         "feature",
+        &CommitOptions::default(),
     );
     assert!(
         res.is_err(),
@@ -493,6 +496,7 @@ fn merge_does_not_overwrite_main_repo_untracked_files() {
         "feature",
         "main",
         "squash merge",
+        &CommitOptions::default(),
     );
     assert!(
         res.is_err(),
@@ -536,6 +540,7 @@ fn merge_does_not_touch_tracked_uncommitted_changes_in_base_worktree() {
         "feature",
         "main",
         "squash merge",
+        &CommitOptions::default(),
     );
     assert!(
         res.is_ok(),
@@ -566,7 +571,14 @@ fn merge_refuses_with_staged_changes_on_base() {
     // main has staged change
     write_file(&repo_path, "staged.txt", "staged\n");
     add_path(&repo_path, "staged.txt");
-    let res = s.merge_changes(&repo_path, &worktree_path, "feature", "main", "squash");
+    let res = s.merge_changes(
+        &repo_path,
+        &worktree_path,
+        "feature",
+        "main",
+        "squash",
+        &CommitOptions::default(),
+    );
     assert!(res.is_err(), "should refuse merge due to staged changes");
     // staged file remains
     let content = std::fs::read_to_string(repo_path.join("staged.txt")).unwrap();
@@ -588,7 +600,14 @@ fn merge_preserves_unstaged_changes_on_base() {
     commit_all(&wt_repo, "feature merged");
 
     let _sha = s
-        .merge_changes(&repo_path, &worktree_path, "feature", "main", "squash")
+        .merge_changes(
+            &repo_path,
+            &worktree_path,
+            "feature",
+            "main",
+            "squash",
+            &CommitOptions::default(),
+        )
         .unwrap();
     // local edit preserved
     let loc = std::fs::read_to_string(repo_path.join("common.txt")).unwrap();
@@ -614,7 +633,14 @@ fn update_ref_does_not_destroy_feature_worktree_dirty_state() {
     write_file(&worktree_path, "dirty.txt", "unstaged\n");
     // merge from feature into main (CLI path updates task ref via update-ref)
     let sha = s
-        .merge_changes(&repo_path, &worktree_path, "feature", "main", "squash")
+        .merge_changes(
+            &repo_path,
+            &worktree_path,
+            "feature",
+            "main",
+            "squash",
+            &CommitOptions::default(),
+        )
         .unwrap();
     // uncommitted change in feature worktree preserved
     let dirty = std::fs::read_to_string(worktree_path.join("dirty.txt")).unwrap();
@@ -642,7 +668,14 @@ fn libgit2_merge_updates_base_ref_in_both_repos() {
 
     // Perform merge (squash) while main repo is NOT on base branch (libgit2 path)
     let sha = s
-        .merge_changes(&repo_path, &worktree_path, "feature", "main", "squash")
+        .merge_changes(
+            &repo_path,
+            &worktree_path,
+            "feature",
+            "main",
+            "squash",
+            &CommitOptions::default(),
+        )
         .expect("merge should succeed via libgit2 path");
 
     // Base branch ref advanced in both main and worktree repositories
@@ -664,7 +697,14 @@ fn libgit2_merge_updates_task_ref_and_feature_head_preserves_dirty() {
 
     // Perform merge (squash) from feature into main; this path uses libgit2
     let sha = s
-        .merge_changes(&repo_path, &worktree_path, "feature", "main", "squash")
+        .merge_changes(
+            &repo_path,
+            &worktree_path,
+            "feature",
+            "main",
+            "squash",
+            &CommitOptions::default(),
+        )
         .expect("merge should succeed via libgit2 path");
 
     // Dirty file preserved in worktree
@@ -697,6 +737,7 @@ fn rebase_refuses_to_abort_existing_rebase() {
             "new-base",
             "old-base",
             "feature",
+            &CommitOptions::default(),
         )
         .expect_err("first rebase should error and leave in-progress state");
 
@@ -708,6 +749,7 @@ fn rebase_refuses_to_abort_existing_rebase() {
         "new-base",
         "old-base",
         "feature",
+        &CommitOptions::default(),
     );
     assert!(res.is_err(), "should error because rebase is in progress");
     // Note: We do not auto-abort; user should resolve or abort explicitly
@@ -728,6 +770,7 @@ fn rebase_fast_forwards_when_no_unique_commits() {
             "new-base",
             "old-base",
             "feature",
+            &CommitOptions::default(),
         )
         .expect("rebase should succeed");
     let after_oid = g.get_head_info(&worktree_path).unwrap().oid;
@@ -759,6 +802,7 @@ fn rebase_applies_multiple_commits_onto_ahead_base() {
             "new-base",
             "old-base",
             "feature",
+            &CommitOptions::default(),
         )
         .expect("rebase should succeed");
 
@@ -795,6 +839,7 @@ fn merge_when_base_ahead_and_feature_ahead_fails() {
         "feature",
         "main",
         "squash merge",
+        &CommitOptions::default(),
     );
 
     assert!(
@@ -827,6 +872,7 @@ fn merge_conflict_does_not_move_base_ref() {
         "feature",
         "main",
         "squash merge",
+        &CommitOptions::default(),
     );
 
     assert!(res.is_err(), "conflicting merge should fail");
@@ -870,6 +916,7 @@ fn merge_delete_vs_modify_conflict_behaves_safely() {
         "feature",
         "main",
         "squash merge",
+        &CommitOptions::default(),
     );
 
     // Should now fail due to base branch being ahead, not due to merge conflicts
@@ -904,6 +951,7 @@ fn rebase_preserves_rename_changes() {
             "new-base",
             "old-base",
             "feature",
+            &CommitOptions::default(),
         )
         .expect("rebase should succeed");
     // after rebase, renamed file present; original absent
@@ -935,7 +983,14 @@ fn merge_refreshes_main_worktree_when_on_base() {
 
     // Merge into main (squash) and ensure main worktree is updated since it is on base
     let merge_sha = s
-        .merge_changes(&repo_path, &wt, "feature", "main", "squash")
+        .merge_changes(
+            &repo_path,
+            &wt,
+            "feature",
+            "main",
+            "squash",
+            &CommitOptions::default(),
+        )
         .unwrap();
     // Since main is on base branch and we use safe CLI merge, both working tree
     // and ref should reflect the merged content.
@@ -1105,7 +1160,14 @@ fn merge_binary_conflict_does_not_move_ref() {
     let _ = s.commit(&repo_path, "main bin").unwrap();
 
     let before = s.get_branch_oid(&repo_path, "main").unwrap();
-    let res = s.merge_changes(&repo_path, &worktree_path, "feature", "main", "merge bin");
+    let res = s.merge_changes(
+        &repo_path,
+        &worktree_path,
+        "feature",
+        "main",
+        "merge bin",
+        &CommitOptions::default(),
+    );
     assert!(res.is_err(), "binary conflict should fail");
     let after = s.get_branch_oid(&repo_path, "main").unwrap();
     assert_eq!(before, after, "main ref unchanged on conflict");
@@ -1144,6 +1206,7 @@ fn merge_rename_vs_modify_conflict_does_not_move_ref() {
         "feature",
         "main",
         "merge rename",
+        &CommitOptions::default(),
     );
     match res {
         Err(_) => {
@@ -1199,6 +1262,7 @@ fn merge_leaves_no_staged_changes_on_target_branch() {
             "feature",
             "main",
             "merge feature",
+            &CommitOptions::default(),
         )
         .expect("merge should succeed");
 
@@ -1269,6 +1333,7 @@ fn worktree_to_worktree_merge_leaves_no_staged_changes() {
         "feature-a",
         "feature-b",
         "merge feature-a into feature-b",
+        &CommitOptions::default(),
     );
 
     // Verify no staged changes were introduced
@@ -1326,6 +1391,7 @@ fn merge_into_orphaned_branch_uses_libgit2_fallback() {
             "feature",
             "orphaned-feature",
             "merge into orphaned branch",
+            &CommitOptions::default(),
         )
         .expect("libgit2 merge into orphaned branch should succeed");
 
@@ -1397,6 +1463,7 @@ fn merge_base_ahead_of_task_should_error() {
         "feature",
         "main",
         "attempt merge when base ahead",
+        &CommitOptions::default(),
     );
 
     // TDD: This test will initially fail because merge currently succeeds
@@ -6,8 +6,9 @@ use std::{
 
 use git2::{Repository, build::CheckoutBuilder};
 use services::services::{
-    git::{DiffTarget, GitCli, GitService},
+    git::{CommitIdentity, CommitOptions, DiffSelection, DiffTarget, GitCli, GitService},
     github::{GitHubRepoInfo, GitHubServiceError},
+    worktree_manager::WorktreeManager,
 };
 use tempfile::TempDir;
 use utils::diff::DiffChangeKind;
@@ -259,6 +260,42 @@ fn commit_and_is_worktree_clean() {
     );
 }
 
+#[test]
+fn commit_with_options_overrides_author_and_credits_user() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    configure_user(&repo_path, "Human Dev", "human@example.com");
+    write_file(&repo_path, "bot.txt", "from the agent\n");
+
+    let s = GitService::new();
+    let options = CommitOptions {
+        identity: Some(CommitIdentity {
+            name: "Anyon Agent".to_string(),
+            email: "bot@example.com".to_string(),
+        }),
+        co_author_user: true,
+        ..Default::default()
+    };
+    assert!(
+        s.commit_with_options(&repo_path, "agent change", &options)
+            .unwrap()
+    );
+
+    let (name, email) = get_head_author(&repo_path);
+    assert_eq!(name.as_deref(), Some("Anyon Agent"));
+    assert_eq!(email.as_deref(), Some("bot@example.com"));
+
+    let repo = Repository::open(&repo_path).unwrap();
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    assert_eq!(head.committer().email(), Some("bot@example.com"));
+    assert!(
+        head.message()
+            .unwrap()
+            .trim_end()
+            .ends_with("Co-authored-by: Human Dev <human@example.com>")
+    );
+}
+
 #[test]
 fn commit_in_detached_head_succeeds_via_service() {
     let td = TempDir::new().unwrap();
@@ -466,6 +503,132 @@ fn worktree_diff_permission_only_change() {
     assert_eq!(d.old_content, d.new_content);
 }
 
+#[test]
+fn partial_commit_cherry_picks_selected_hunks_onto_base() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let s = GitService::new();
+
+    let baseline = (1..=20).map(|i| format!("line {i}\n")).collect::<String>();
+    write_file(&repo_path, "a.txt", &baseline);
+    let _ = s.commit(&repo_path, "baseline").unwrap();
+    create_branch(&repo_path, "feature");
+    checkout_branch(&repo_path, "feature");
+
+    // Two far-apart edits produce two hunks; plus an unrelated new file
+    let edited = baseline
+        .replace("line 2\n", "line two\n")
+        .replace("line 19\n", "line nineteen\n");
+    write_file(&repo_path, "a.txt", &edited);
+    write_file(&repo_path, "b.txt", "new\n");
+
+    let base_commit = s.get_base_commit(&repo_path, "feature", "main").unwrap();
+    let diffs = s
+        .get_diffs(
+            DiffTarget::Worktree {
+                worktree_path: Path::new(&repo_path),
+                base_commit: &base_commit,
+            },
+            None,
+        )
+        .unwrap();
+    let commit = s
+        .create_partial_commit(
+            &repo_path,
+            &base_commit,
+            &diffs,
+            &[DiffSelection {
+                path: "a.txt".to_string(),
+                hunks: Some(vec![0]),
+            }],
+            "pick first hunk",
+            &CommitOptions::default(),
+        )
+        .unwrap();
+    let new_main = s
+        .cherry_pick_onto_branch(&repo_path, "main", &commit, &CommitOptions::default())
+        .unwrap();
+
+    let repo = Repository::open(&repo_path).unwrap();
+    let tree = repo
+        .find_commit(git2::Oid::from_str(&new_main).unwrap())
+        .unwrap()
+        .tree()
+        .unwrap();
+    let blob = repo
+        .find_blob(tree.get_path(Path::new("a.txt")).unwrap().id())
+        .unwrap();
+    let content = String::from_utf8_lossy(blob.content());
+    assert!(content.contains("line two\n"));
+    assert!(content.contains("line 19\n"));
+    assert!(tree.get_path(Path::new("b.txt")).is_err());
+    // The feature worktree is untouched
+    assert_eq!(fs::read_to_string(repo_path.join("a.txt")).unwrap(), edited);
+}
+
+#[cfg(unix)]
+#[test]
+fn partial_commit_keeps_executable_bit_of_selected_files() {
+    use std::os::unix::fs::PermissionsExt;
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let s = GitService::new();
+    write_file(&repo_path, "run.sh", "echo run\n");
+    let _ = s.commit(&repo_path, "add run.sh").unwrap();
+    create_branch(&repo_path, "feature");
+    checkout_branch(&repo_path, "feature");
+
+    // A new executable script and a permission-only change to an existing one
+    write_file(&repo_path, "tool.sh", "echo tool\n");
+    for path in ["tool.sh", "run.sh"] {
+        let mut perms = fs::metadata(repo_path.join(path)).unwrap().permissions();
+        perms.set_mode(perms.mode() | 0o111);
+        fs::set_permissions(repo_path.join(path), perms).unwrap();
+    }
+
+    let base_commit = s.get_base_commit(&repo_path, "feature", "main").unwrap();
+    let diffs = s
+        .get_diffs(
+            DiffTarget::Worktree {
+                worktree_path: Path::new(&repo_path),
+                base_commit: &base_commit,
+            },
+            None,
+        )
+        .unwrap();
+    let selection = ["tool.sh", "run.sh"].map(|path| DiffSelection {
+        path: path.to_string(),
+        hunks: None,
+    });
+    let commit = s
+        .create_partial_commit(
+            &repo_path,
+            &base_commit,
+            &diffs,
+            &selection,
+            "scripts",
+            &CommitOptions::default(),
+        )
+        .unwrap();
+    let new_main = s
+        .cherry_pick_onto_branch(&repo_path, "main", &commit, &CommitOptions::default())
+        .unwrap();
+
+    let repo = Repository::open(&repo_path).unwrap();
+    let tree = repo
+        .find_commit(git2::Oid::from_str(&new_main).unwrap())
+        .unwrap()
+        .tree()
+        .unwrap();
+    for path in ["tool.sh", "run.sh"] {
+        assert_eq!(
+            tree.get_path(Path::new(path)).unwrap().filemode(),
+            0o100755,
+            "{path} should stay executable"
+        );
+    }
+}
+
 #[test]
 fn github_repo_info_parses_https_and_ssh_urls() {
     let info = GitHubRepoInfo::from_remote_url("https://github.com/owner/repo.git").unwrap();
@@ -530,7 +693,14 @@ fn squash_merge_libgit2_sets_author_without_user() {
 
     // Merge feature -> main (libgit2 squash)
     let merge_sha = s
-        .merge_changes(&repo_path, &worktree_path, "feature", "main", "squash")
+        .merge_changes(
+            &repo_path,
+            &worktree_path,
+            "feature",
+            "main",
+            "squash",
+            &CommitOptions::default(),
+        )
         .unwrap();
 
     // The squash commit author should not be the feature commit's author, and must be present.
@@ -544,3 +714,74 @@ fn squash_merge_libgit2_sets_author_without_user() {
         assert_eq!(email.as_deref(), Some("noreply@anyon.dev"));
     }
 }
+
+#[test]
+fn squash_merge_uses_project_commit_identity() {
+    let options = CommitOptions {
+        identity: Some(CommitIdentity {
+            name: "Anyon Agent".to_string(),
+            email: "bot@example.com".to_string(),
+        }),
+        ..Default::default()
+    };
+    // Base branch checked out in the main repo takes the CLI path; otherwise libgit2
+    for base_checked_out in [true, false] {
+        let td = TempDir::new().unwrap();
+        let repo_path = init_repo_main(&td);
+        configure_user(&repo_path, "Human Dev", "human@example.com");
+        let worktree_path = td.path().join("wt_feature");
+        let s = GitService::new();
+        create_branch(&repo_path, "feature");
+        s.add_worktree(&repo_path, &worktree_path, "feature", false)
+            .unwrap();
+        write_file(&worktree_path, "f.txt", "feat\n");
+        s.commit(&worktree_path, "feat").unwrap();
+        if !base_checked_out {
+            create_branch(&repo_path, "dev");
+            checkout_branch(&repo_path, "dev");
+        }
+
+        let merge_sha = s
+            .merge_changes(
+                &repo_path,
+                &worktree_path,
+                "feature",
+                "main",
+                "squash",
+                &options,
+            )
+            .unwrap();
+
+        let repo = Repository::open(&repo_path).unwrap();
+        let commit = repo
+            .find_commit(git2::Oid::from_str(&merge_sha).unwrap())
+            .unwrap();
+        assert_eq!(commit.author().email(), Some("bot@example.com"));
+        assert_eq!(commit.committer().email(), Some("bot@example.com"));
+    }
+}
+
+#[tokio::test]
+async fn detached_worktrees_of_the_checked_out_branch_sit_side_by_side() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let first = td.path().join("wt_first");
+    let second = td.path().join("wt_second");
+
+    for worktree_path in [&first, &second] {
+        WorktreeManager::ensure_detached_worktree_exists(&repo_path, "main", worktree_path)
+            .await
+            .unwrap();
+    }
+    write_file(&first, "plan.md", "first\n");
+
+    let repo = Repository::open(&repo_path).unwrap();
+    let main_head = repo.head().unwrap().peel_to_commit().unwrap().id();
+    for worktree_path in [&first, &second] {
+        let worktree = Repository::open(worktree_path).unwrap();
+        assert!(worktree.head_detached().unwrap());
+        assert_eq!(worktree.head().unwrap().target(), Some(main_head));
+    }
+    assert!(!second.join("plan.md").exists());
+    assert_eq!(repo.head().unwrap().shorthand(), Some("main"));
+}
@@ -0,0 +1,374 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use db::{
+    DBService,
+    models::{
+        merge::Merge,
+        project::Project,
+        task::{CreateTask, Task},
+        task_attempt::{CreateTaskAttempt, TaskAttempt},
+    },
+    test_utils::create_project,
+};
+use executors::executors::BaseCodingAgent;
+use services::services::{
+    git::{GitCli, GitService},
+    stacked_branches::{RestackStatus, StackedBranchError, StackedBranchService},
+    worktree_manager::WorktreeManager,
+};
+use tempfile::TempDir;
+use uuid::Uuid;
+
+fn git(path: &Path, args: &[&str]) -> String {
+    GitCli::new().git(path, args).unwrap()
+}
+
+fn commit_file(path: &Path, rel: &str, content: &str, message: &str) {
+    fs::write(path.join(rel), content).unwrap();
+    git(path, &["add", rel]);
+    git(path, &["commit", "-m", message]);
+}
+
+fn configure_user(path: &Path) {
+    git(path, &["config", "user.name", "Test User"]);
+    git(path, &["config", "user.email", "test@example.com"]);
+}
+
+/// Files tracked at the tip of `branch`
+fn files_at(repo_path: &Path, branch: &str) -> Vec<String> {
+    git(repo_path, &["ls-tree", "--name-only", branch])
+        .lines()
+        .map(str::to_string)
+        .collect()
+}
+
+/// Commit subjects on `branch` that are not on `base`, oldest first
+fn subjects_between(repo_path: &Path, base: &str, branch: &str) -> Vec<String> {
+    git(
+        repo_path,
+        &[
+            "log",
+            "--reverse",
+            "--format=%s",
+            &format!("{base}..{branch}"),
+        ],
+    )
+    .lines()
+    .map(str::to_string)
+    .collect()
+}
+
+struct Stack {
+    root: TempDir,
+    db: DBService,
+    repo_path: PathBuf,
+    parent: TaskAttempt,
+    child: TaskAttempt,
+}
+
+impl Stack {
+    /// A repo on `main` with attempt branch `parent` (one commit) and attempt branch
+    /// `child` stacked on it (one more commit), each checked out in its own worktree
+    async fn new() -> Self {
+        let root = TempDir::new().unwrap();
+        let repo_path = root.path().join("repo");
+        GitService::new()
+            .initialize_repo_with_main_branch(&repo_path)
+            .unwrap();
+        configure_user(&repo_path);
+        commit_file(&repo_path, "base.txt", "base\n", "base");
+
+        let db = DBService::new_in_memory().await.unwrap();
+        let project = create_project(&db.pool, &repo_path).await;
+
+        let parent = Self::attempt(&db, &root, &repo_path, &project, "parent", "main", None).await;
+        commit_file(
+            Path::new(parent.container_ref.as_ref().unwrap()),
+            "parent.txt",
+            "parent\n",
+            "parent work",
+        );
+        let child = Self::attempt(
+            &db,
+            &root,
+            &repo_path,
+            &project,
+            "child",
+            "parent",
+            Some(parent.id),
+        )
+        .await;
+        commit_file(
+            Path::new(child.container_ref.as_ref().unwrap()),
+            "child.txt",
+            "child\n",
+            "child work",
+        );
+
+        Self {
+            root,
+            db,
+            repo_path,
+            parent,
+            child,
+        }
+    }
+
+    async fn attempt(
+        db: &DBService,
+        root: &TempDir,
+        repo_path: &Path,
+        project: &Project,
+        branch: &str,
+        base_branch: &str,
+        stacked_on_attempt_id: Option<Uuid>,
+    ) -> TaskAttempt {
+        let task = Task::create(
+            &db.pool,
+            &CreateTask::from_title_description(project.id, branch.to_string(), None),
+            Uuid::new_v4(),
+        )
+        .await
+        .unwrap();
+        let attempt = TaskAttempt::create(
+            &db.pool,
+            &CreateTaskAttempt {
+                executor: BaseCodingAgent::ClaudeCode,
+                base_branch: base_branch.to_string(),
+                branch: branch.to_string(),
+                stacked_on_attempt_id,
+            },
+            Uuid::new_v4(),
+            task.id,
+        )
+        .await
+        .unwrap();
+
+        let worktree_path = root.path().join(format!("wt-{branch}"));
+        WorktreeManager::create_worktree(repo_path, branch, &worktree_path, base_branch, true)
+            .await
+            .unwrap();
+        let container_ref = worktree_path.to_string_lossy().to_string();
+        TaskAttempt::update_container_ref(&db.pool, attempt.id, &container_ref)
+            .await
+            .unwrap();
+        configure_user(&worktree_path);
+        TaskAttempt::find_by_id(&db.pool, attempt.id)
+            .await
+            .unwrap()
+            .unwrap()
+    }
+
+    fn service(&self) -> StackedBranchService {
+        StackedBranchService::new(self.db.clone(), GitService::new())
+    }
+
+    fn head(&self, branch: &str) -> String {
+        GitService::new()
+            .get_branch_oid(&self.repo_path, branch)
+            .unwrap()
+    }
+
+    async fn reload(&self, attempt: &TaskAttempt) -> TaskAttempt {
+        TaskAttempt::find_by_id(&self.db.pool, attempt.id)
+            .await
+            .unwrap()
+            .unwrap()
+    }
+
+    /// Land the parent's changes on `main` as a single squashed commit
+    fn squash_parent_into_main(&self) {
+        git(&self.repo_path, &["merge", "--squash", "parent"]);
+        git(&self.repo_path, &["commit", "-m", "parent work (squashed)"]);
+    }
+}
+
+#[tokio::test]
+async fn restack_carries_dependents_along_with_a_rewritten_parent() {
+    let stack = Stack::new().await;
+    let old_parent_head = stack.head("parent");
+    let parent_worktree = PathBuf::from(stack.parent.container_ref.as_ref().unwrap());
+    git(
+        &parent_worktree,
+        &["commit", "--amend", "-m", "parent work, reworded"],
+    );
+
+    let outcomes = stack
+        .service()
+        .restack_dependents(&stack.parent, &old_parent_head)
+        .await
+        .unwrap();
+
+    assert_eq!(outcomes.len(), 1);
+    assert!(matches!(outcomes[0].status, RestackStatus::Rebased { .. }));
+    assert_eq!(
+        subjects_between(&stack.repo_path, "main", "child"),
+        vec!["parent work, reworded", "child work"]
+    );
+    let child = stack.reload(&stack.child).await;
+    assert_eq!(child.target_branch, "parent");
+    assert_eq!(child.stacked_on_attempt_id, Some(stack.parent.id));
+}
+
+#[tokio::test]
+async fn promote_moves_dependents_onto_the_target_after_a_local_merge() {
+    let stack = Stack::new().await;
+    let old_parent_head = stack.head("parent");
+    stack.squash_parent_into_main();
+
+    let outcomes = stack
+        .service()
+        .promote_dependents_after_merge(&stack.parent, &old_parent_head)
+        .await
+        .unwrap();
+
+    assert!(matches!(outcomes[0].status, RestackStatus::Rebased { .. }));
+    assert_eq!(
+        subjects_between(&stack.repo_path, "main", "child"),
+        vec!["child work"]
+    );
+    let child = stack.reload(&stack.child).await;
+    assert_eq!(child.target_branch, "main");
+    assert_eq!(child.stacked_on_attempt_id, None);
+}
+
+#[tokio::test]
+async fn promote_after_remote_merge_rebases_onto_the_fetched_remote_target() {
+    let stack = Stack::new().await;
+    let origin = stack.root.path().join("origin.git");
+    git(
+        stack.root.path(),
+        &["init", "--bare", origin.to_str().unwrap()],
+    );
+    git(
+        &stack.repo_path,
+        &["remote", "add", "origin", origin.to_str().unwrap()],
+    );
+    git(&stack.repo_path, &["push", "origin", "main", "parent"]);
+
+    // The pull request is merged on the remote; the local `main` never sees it
+    let other = stack.root.path().join("other");
+    git(
+        stack.root.path(),
+        &[
+            "clone",
+            "-b",
+            "main",
+            origin.to_str().unwrap(),
+            other.to_str().unwrap(),
+        ],
+    );
+    configure_user(&other);
+    git(&other, &["fetch", "origin", "parent:parent"]);
+    git(&other, &["merge", "--squash", "parent"]);
+    git(&other, &["commit", "-m", "parent work (#1)"]);
+    git(&other, &["push", "origin", "main"]);
+
+    let outcomes = stack
+        .service()
+        .promote_dependents_after_remote_merge(&stack.parent)
+        .await
+        .unwrap();
+
+    assert!(matches!(outcomes[0].status, RestackStatus::Rebased { .. }));
+    assert_eq!(
+        subjects_between(&stack.repo_path, "origin/main", "child"),
+        vec!["child work"]
+    );
+    assert!(files_at(&stack.repo_path, "child").contains(&"parent.txt".to_string()));
+    let child = stack.reload(&stack.child).await;
+    assert_eq!(child.target_branch, "main");
+    assert_eq!(child.stacked_on_attempt_id, None);
+}
+
+#[tokio::test]
+async fn promote_after_remote_merge_only_retargets_without_a_remote() {
+    let stack = Stack::new().await;
+    let child_head = stack.head("child");
+
+    let outcomes = stack
+        .service()
+        .promote_dependents_after_remote_merge(&stack.parent)
+        .await
+        .unwrap();
+
+    assert!(matches!(outcomes[0].status, RestackStatus::Retargeted));
+    assert_eq!(stack.head("child"), child_head);
+    let child = stack.reload(&stack.child).await;
+    assert_eq!(child.target_branch, "main");
+    assert_eq!(child.stacked_on_attempt_id, None);
+}
+
+#[tokio::test]
+async fn stack_parent_rejects_a_merged_attempt() {
+    let stack = Stack::new().await;
+    let project_id = stack
+        .parent
+        .parent_task(&stack.db.pool)
+        .await
+        .unwrap()
+        .unwrap()
+        .project_id;
+    let service = stack.service();
+    assert_eq!(
+        service
+            .stack_parent(project_id, stack.parent.id)
+            .await
+            .unwrap()
+            .branch,
+        "parent"
+    );
+
+    Merge::create_direct(
+        &stack.db.pool,
+        stack.parent.id,
+        "main",
+        &stack.head("parent"),
+    )
+    .await
+    .unwrap();
+
+    assert!(matches!(
+        service.stack_parent(project_id, stack.parent.id).await,
+        Err(StackedBranchError::ParentMerged)
+    ));
+    assert!(matches!(
+        service.stack_parent(Uuid::new_v4(), stack.child.id).await,
+        Err(StackedBranchError::ParentInOtherProject)
+    ));
+    assert!(matches!(
+        service.stack_parent(project_id, Uuid::new_v4()).await,
+        Err(StackedBranchError::ParentNotFound)
+    ));
+}
+
+#[tokio::test]
+async fn stack_parent_rejects_a_looping_stack() {
+    let stack = Stack::new().await;
+    let project_id = stack
+        .parent
+        .parent_task(&stack.db.pool)
+        .await
+        .unwrap()
+        .unwrap()
+        .project_id;
+    TaskAttempt::update_stacked_on(
+        &stack.db.pool,
+        stack.parent.id,
+        Some(stack.child.id),
+        "child",
+    )
+    .await
+    .unwrap();
+
+    assert!(matches!(
+        stack
+            .service()
+            .stack_parent(project_id, stack.child.id)
+            .await,
+        Err(StackedBranchError::Cycle(_))
+    ));
+}
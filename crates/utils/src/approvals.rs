@@ -29,6 +29,12 @@ impl ApprovalRequest {
             timeout_at: now + Duration::seconds(APPROVAL_TIMEOUT_SECONDS),
         }
     }
+
+    /// Set the deadline to `seconds` after the request was created
+    pub fn with_timeout(mut self, seconds: i64) -> Self {
+        self.timeout_at = self.created_at + Duration::seconds(seconds);
+        self
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
@@ -52,9 +58,26 @@ pub enum ApprovalStatus {
     TimedOut,
 }
 
+/// How widely an approval should be remembered as an allow rule
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[ts(export)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalRememberScope {
+    /// Only for the rest of this task attempt
+    Attempt,
+    /// For every attempt in this project
+    Project,
+    /// For every project
+    Global,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[ts(export)]
 pub struct ApprovalResponse {
     pub execution_process_id: Uuid,
     pub status: ApprovalStatus,
+    /// Remember an approval so matching tool calls are allowed without asking
+    #[serde(default)]
+    #[ts(optional)]
+    pub remember: Option<ApprovalRememberScope>,
 }
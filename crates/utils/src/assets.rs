@@ -36,6 +36,14 @@ pub fn credentials_path() -> std::path::PathBuf {
     asset_dir().join("credentials.json")
 }
 
+pub fn approval_signing_key_path() -> std::path::PathBuf {
+    asset_dir().join("approval_signing.key")
+}
+
+pub fn mcp_token_path() -> std::path::PathBuf {
+    asset_dir().join("mcp_token")
+}
+
 #[derive(RustEmbed)]
 #[folder = "../../assets/sounds"]
 pub struct SoundAssets;
@@ -1,7 +1,7 @@
 use std::borrow::Cow;
 
 use serde::{Deserialize, Serialize};
-use similar::{ChangeTag, TextDiff};
+use similar::{ChangeTag, DiffOp, TextDiff};
 use ts_rs::TS;
 
 // Structs compatable with props: https://github.com/MrWangJustToDo/git-diff-view
@@ -89,6 +89,40 @@ pub fn compute_line_change_counts(old: &str, new: &str) -> (usize, usize) {
     (additions, deletions)
 }
 
+/// Number of hunks (3 lines of context) in the line diff between two snapshots.
+pub fn count_diff_hunks(old: &str, new: &str) -> usize {
+    TextDiff::from_lines(old, new).grouped_ops(3).len()
+}
+
+/// Apply only the selected hunks of the `old` -> `new` line diff to `old`.
+/// Hunk indices follow the same grouping as [`create_unified_diff`] (3 lines of
+/// context); unselected hunks keep the old lines.
+pub fn apply_selected_hunks(old: &str, new: &str, hunks: &[usize]) -> String {
+    let diff = TextDiff::from_lines(old, new);
+    let selected_ops = diff
+        .grouped_ops(3)
+        .into_iter()
+        .enumerate()
+        .filter(|(idx, _)| hunks.contains(idx))
+        .flat_map(|(_, group)| group)
+        .collect::<Vec<_>>();
+
+    let old_lines = diff.old_slices();
+    let new_lines = diff.new_slices();
+    let mut result = String::with_capacity(old.len().max(new.len()));
+    for op in diff.ops() {
+        let lines = if !matches!(op, DiffOp::Equal { .. }) && selected_ops.contains(op) {
+            &new_lines[op.new_range()]
+        } else {
+            &old_lines[op.old_range()]
+        };
+        for line in lines {
+            result.push_str(line);
+        }
+    }
+    result
+}
+
 // ensure a line ends with a newline character
 fn ensure_newline(line: &str) -> Cow<'_, str> {
     if line.ends_with('\n') {
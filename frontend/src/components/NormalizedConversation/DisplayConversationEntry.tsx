@@ -643,7 +643,7 @@ function DisplayConversationEntry({
     isNormalizedEntry(entry) &&
     entry.entry_type.type === 'assistant_message' &&
     task &&
-    isPlanningTask({ title: task.title, description: task.description })
+    isPlanningTask(task)
   ) {
     // Check if this is the initial greeting before workflow execution
     const content = entry.content.toLowerCase();
@@ -7,8 +7,19 @@ import {
   useState,
 } from 'react';
 import type { ReactNode } from 'react';
-import type { ApprovalStatus, ToolStatus } from 'shared/types';
+import type {
+  ApprovalRememberScope,
+  ApprovalStatus,
+  ToolStatus,
+} from 'shared/types';
 import { Button } from '@/components/ui/button';
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from '@/components/ui/select';
 import {
   Tooltip,
   TooltipContent,
@@ -23,6 +34,13 @@ import { useKeyApproveRequest, useKeyDenyApproval, Scope } from '@/keyboard';
 import { useApprovalForm } from '@/contexts/ApprovalFormContext';
 
 const DEFAULT_DENIAL_REASON = 'User denied this tool use request.';
+const ASK_AGAIN = 'ask_again';
+
+const REMEMBER_OPTIONS: { value: ApprovalRememberScope; label: string }[] = [
+  { value: 'attempt', label: 'Always allow in this attempt' },
+  { value: 'project', label: 'Always allow in this project' },
+  { value: 'global', label: 'Always allow everywhere' },
+];
 
 // ---------- Types ----------
 interface PendingApprovalEntryProps {
@@ -131,6 +149,9 @@ const PendingApprovalEntry = ({
   const [isResponding, setIsResponding] = useState(false);
   const [hasResponded, setHasResponded] = useState(false);
   const [error, setError] = useState<string | null>(null);
+  const [remember, setRemember] = useState<ApprovalRememberScope | null>(
+    null
+  );
 
   const { clear } = useApprovalForm(pendingStatus.approval_id);
 
@@ -152,7 +173,10 @@ const PendingApprovalEntry = ({
     hasResponded
   );
 
-  const disabled = isResponding || hasResponded || timeLeft <= 0;
+  // Paused approvals stay answerable after their deadline
+  const isPaused = pendingStatus.on_timeout === 'pause' && timeLeft <= 0;
+  const disabled =
+    isResponding || hasResponded || (timeLeft <= 0 && !isPaused);
 
   const shouldEnableApprovalsScope = shouldControlScopes && !disabled;
 
@@ -206,6 +230,7 @@ const PendingApprovalEntry = ({
         await approvalsApi.respond(pendingStatus.approval_id, {
           execution_process_id: executionProcessId,
           status,
+          ...(approved && remember ? { remember } : {}),
         });
         setHasResponded(true);
         clear();
@@ -218,7 +243,7 @@ const PendingApprovalEntry = ({
         setIsResponding(false);
       }
     },
-    [disabled, executionProcessId, pendingStatus.approval_id, clear]
+    [disabled, executionProcessId, pendingStatus.approval_id, clear, remember]
   );
 
   const handleApprove = useCallback(() => respond(true), [respond]);
@@ -250,8 +275,33 @@ const PendingApprovalEntry = ({
             <div className="flex items-center justify-between gap-1.5 pl-4">
               <div className="flex items-center gap-1.5">
                 <span className="text-muted-foreground">
-                  Would you like to approve this?
+                  {isPaused
+                    ? 'Paused until you answer. Would you like to approve this?'
+                    : 'Would you like to approve this?'}
                 </span>
+                <Select
+                  value={remember ?? ASK_AGAIN}
+                  onValueChange={(value) =>
+                    setRemember(
+                      value === ASK_AGAIN
+                        ? null
+                        : (value as ApprovalRememberScope)
+                    )
+                  }
+                  disabled={disabled}
+                >
+                  <SelectTrigger className="h-7 w-auto text-xs">
+                    <SelectValue />
+                  </SelectTrigger>
+                  <SelectContent>
+                    <SelectItem value={ASK_AGAIN}>Ask again next time</SelectItem>
+                    {REMEMBER_OPTIONS.map((option) => (
+                      <SelectItem key={option.value} value={option.value}>
+                        {option.label}
+                      </SelectItem>
+                    ))}
+                  </SelectContent>
+                </Select>
               </div>
               <ActionButtons
                 disabled={disabled}
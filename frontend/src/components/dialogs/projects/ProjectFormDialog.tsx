@@ -1,6 +1,15 @@
 import { useState } from 'react';
 import { useTranslation } from 'react-i18next';
+import { useQuery } from '@tanstack/react-query';
 import { Button } from '@/components/ui/button';
+import { Label } from '@/components/ui/label';
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from '@/components/ui/select';
 import {
   Dialog,
   DialogContent,
@@ -14,6 +23,9 @@ import { generateProjectNameFromPath } from '@/utils/string';
 import NiceModal, { useModal } from '@ebay/nice-modal-react';
 import { useProjectMutations } from '@/hooks/useProjectMutations';
 import { defineModal } from '@/lib/modals';
+import { listDocumentTemplatePacks } from '@/lib/document-api';
+
+const NO_TEMPLATE_PACK = 'none';
 
 export interface ProjectFormDialogProps {
   // No props needed - this is only for creating projects now
@@ -30,6 +42,15 @@ const ProjectFormDialogImpl = NiceModal.create<ProjectFormDialogProps>(() => {
   const [repoMode, setRepoMode] = useState<'existing' | 'new'>('existing');
   const [parentPath, setParentPath] = useState('');
   const [folderName, setFolderName] = useState('');
+  const [templatePackId, setTemplatePackId] = useState(NO_TEMPLATE_PACK);
+
+  const { data: templatePacks = [] } = useQuery({
+    queryKey: ['documentTemplatePacks'],
+    queryFn: listDocumentTemplatePacks,
+    enabled: modal.visible,
+  });
+  const documentTemplatePackId =
+    templatePackId === NO_TEMPLATE_PACK ? null : templatePackId;
 
   const { createProject } = useProjectMutations({
     onCreateSuccess: () => {
@@ -63,6 +84,7 @@ const ProjectFormDialogImpl = NiceModal.create<ProjectFormDialogProps>(() => {
       dev_script: null,
       cleanup_script: null,
       copy_files: null,
+      document_template_pack_id: documentTemplatePackId,
     };
 
     createProject.mutate(createData);
@@ -93,6 +115,7 @@ const ProjectFormDialogImpl = NiceModal.create<ProjectFormDialogProps>(() => {
       dev_script: null,
       cleanup_script: null,
       copy_files: null,
+      document_template_pack_id: documentTemplatePackId,
     };
 
     createProject.mutate(createData);
@@ -104,6 +127,7 @@ const ProjectFormDialogImpl = NiceModal.create<ProjectFormDialogProps>(() => {
     setGitRepoPath('');
     setParentPath('');
     setFolderName('');
+    setTemplatePackId(NO_TEMPLATE_PACK);
     setError('');
 
     modal.resolve('canceled' as ProjectFormDialogResult);
@@ -126,6 +150,28 @@ const ProjectFormDialogImpl = NiceModal.create<ProjectFormDialogProps>(() => {
 
         <div className="mx-auto w-full max-w-2xl overflow-x-hidden px-1">
           <form onSubmit={handleSubmit} className="space-y-4">
+            {templatePacks.length > 0 && (
+              <div className="space-y-2">
+                <Label htmlFor="template-pack">
+                  {t('dialogs.createProject.templatePackLabel')}
+                </Label>
+                <Select value={templatePackId} onValueChange={setTemplatePackId}>
+                  <SelectTrigger id="template-pack">
+                    <SelectValue />
+                  </SelectTrigger>
+                  <SelectContent>
+                    <SelectItem value={NO_TEMPLATE_PACK}>
+                      {t('dialogs.createProject.noTemplatePack')}
+                    </SelectItem>
+                    {templatePacks.map((pack) => (
+                      <SelectItem key={pack.id} value={pack.id}>
+                        {pack.name}
+                      </SelectItem>
+                    ))}
+                  </SelectContent>
+                </Select>
+              </div>
+            )}
             <ProjectFormFields
               isEditing={false}
               repoMode={repoMode}
@@ -91,12 +91,14 @@ const CreateAttemptDialogImpl = NiceModal.create<CreateAttemptDialogProps>(
     }, [modal.visible]);
 
     const defaultProfile: ExecutorProfileId | null = useMemo(() => {
-      if (latestAttempt?.executor) {
-        const lastExec = latestAttempt.executor as BaseCodingAgent;
-        // If the last attempt used the same executor as the user's current preference,
-        // we assume they want to use their preferred variant as well.
-        // Otherwise, we default to the "default" variant (null) since we don't know
-        // what variant they used last time (TaskAttempt doesn't store it).
+      const lastExec =
+        (latestAttempt?.executor as BaseCodingAgent | undefined) ??
+        task?.suggested_executor;
+      if (lastExec) {
+        // If the last attempt (or, before the first one, the suggested executor) is
+        // the user's current preference, we assume they want their preferred variant
+        // as well. Otherwise, we default to the "default" variant (null) since we
+        // don't know what variant they used last time (TaskAttempt doesn't store it).
         const variant =
           config?.executor_profile?.executor === lastExec
             ? config.executor_profile.variant
@@ -108,7 +110,11 @@ const CreateAttemptDialogImpl = NiceModal.create<CreateAttemptDialogProps>(
         };
       }
       return config?.executor_profile ?? null;
-    }, [latestAttempt?.executor, config?.executor_profile]);
+    }, [
+      latestAttempt?.executor,
+      task?.suggested_executor,
+      config?.executor_profile,
+    ]);
 
     const currentBranchName: string | null = useMemo(() => {
       return branches.find((b) => b.is_current)?.name ?? null;
@@ -47,6 +47,7 @@ import {
 import { useHotkeysContext } from 'react-hotkeys-hook';
 import { cn } from '@/lib/utils';
 import type {
+  TaskKind,
   TaskStatus,
   ExecutorProfileId,
   ImageResponse,
@@ -58,6 +59,7 @@ interface Task {
   title: string;
   description: string | null;
   status: TaskStatus;
+  kind?: TaskKind;
   created_at: string;
   updated_at: string;
 }
@@ -77,6 +79,7 @@ type TaskFormValues = {
   title: string;
   description: string;
   status: TaskStatus;
+  kind: TaskKind;
   executorProfileId: ExecutorProfileId | null;
   branch: string;
   autoStart: boolean;
@@ -132,6 +135,7 @@ const TaskFormDialogImpl = NiceModal.create<TaskFormDialogProps>((props) => {
           title: props.task.title,
           description: props.task.description || '',
           status: props.task.status,
+          kind: props.task.kind ?? 'implementation',
           executorProfileId: baseProfile,
           branch: defaultBranch || '',
           autoStart: false,
@@ -142,6 +146,7 @@ const TaskFormDialogImpl = NiceModal.create<TaskFormDialogProps>((props) => {
           title: props.initialTask.title,
           description: props.initialTask.description || '',
           status: 'todo',
+          kind: props.initialTask.kind ?? 'implementation',
           executorProfileId: baseProfile,
           branch: defaultBranch || '',
           autoStart: true,
@@ -154,6 +159,7 @@ const TaskFormDialogImpl = NiceModal.create<TaskFormDialogProps>((props) => {
           title: '',
           description: '',
           status: 'todo',
+          kind: 'implementation',
           executorProfileId: baseProfile,
           branch: defaultBranch || '',
           autoStart: true,
@@ -185,6 +191,8 @@ const TaskFormDialogImpl = NiceModal.create<TaskFormDialogProps>((props) => {
         title: value.title,
         description: value.description,
         status: null,
+        kind: value.kind,
+        suggested_executor: null,
         parent_task_attempt:
           mode === 'subtask' ? props.parentTaskAttemptId : null,
         image_ids: imageIds,
@@ -501,6 +509,41 @@ const TaskFormDialogImpl = NiceModal.create<TaskFormDialogProps>((props) => {
                 )}
               </form.Field>
             )}
+
+            {/* Create mode kind */}
+            {!editMode && (
+              <form.Field name="kind">
+                {(field) => (
+                  <div className="space-y-2">
+                    <Label htmlFor="task-kind" className="text-sm font-medium">
+                      {t('taskFormDialog.kindLabel')}
+                    </Label>
+                    <Select
+                      value={field.state.value}
+                      onValueChange={(value) =>
+                        field.handleChange(value as TaskKind)
+                      }
+                      disabled={isSubmitting}
+                    >
+                      <SelectTrigger id="task-kind">
+                        <SelectValue />
+                      </SelectTrigger>
+                      <SelectContent>
+                        <SelectItem value="implementation">
+                          {t('taskFormDialog.kindOptions.implementation')}
+                        </SelectItem>
+                        <SelectItem value="review">
+                          {t('taskFormDialog.kindOptions.review')}
+                        </SelectItem>
+                        <SelectItem value="chore">
+                          {t('taskFormDialog.kindOptions.chore')}
+                        </SelectItem>
+                      </SelectContent>
+                    </Select>
+                  </div>
+                )}
+              </form.Field>
+            )}
           </div>
 
           {/* Create mode dropdowns */}
@@ -1,7 +1,7 @@
 import { useDocumentContext } from '@/contexts/DocumentContext';
 import { DocumentCanvasHeader } from './DocumentCanvasHeader';
 import { DocumentEditor } from './DocumentEditor';
-import { useCallback, useState, useEffect } from 'react';
+import { useCallback, useState, useEffect, useRef } from 'react';
 import { useDocumentAutosave } from '@/hooks/useDocumentAutosave';
 
 interface DocumentCanvasProps {
@@ -19,17 +19,26 @@ export function DocumentCanvas({
   // Local content state for immediate updates
   const [localContent, setLocalContent] = useState('');
 
-  // Sync local content with open document
+  // Content of the open document as last received from the server
+  const syncedRef = useRef<{ id?: string; content: string }>({ content: '' });
+
+  // Sync local content with open document. When the file changes outside the
+  // app, take the new content unless there are unsaved local edits.
   useEffect(() => {
-    if (openDocument) {
-      setLocalContent(openDocument.content);
-    }
-  }, [openDocument?.id]); // Only sync when document ID changes
+    if (!openDocument) return;
+    const synced = syncedRef.current;
+    const switched = synced.id !== openDocument.id;
+    syncedRef.current = { id: openDocument.id, content: openDocument.content };
+    setLocalContent((local) =>
+      switched || local === synced.content ? openDocument.content : local
+    );
+  }, [openDocument?.id, openDocument?.content]);
 
   // Auto-save with debounce
   const { saveStatus, isSaving } = useDocumentAutosave({
     docId: openDocument?.id,
     content: localContent,
+    savedContent: openDocument?.content,
     onSave: updateDoc,
     debounceMs: 1500,
     enabled: !!openDocument,
@@ -139,6 +139,22 @@ export function TaskCard({
               : task.description}
           </p>
         )}
+        {task.plan_progress && (
+          <div className="flex items-center gap-2 text-xs text-muted-foreground">
+            <div className="h-1 flex-1 overflow-hidden rounded-full bg-muted">
+              <div
+                className="h-full bg-info"
+                style={{ width: `${task.plan_progress.percent}%` }}
+              />
+            </div>
+            <span>
+              {t('planProgress', {
+                current: task.plan_progress.current_step,
+                total: task.plan_progress.total_steps,
+              })}
+            </span>
+          </div>
+        )}
       </div>
     </KanbanCard>
   );
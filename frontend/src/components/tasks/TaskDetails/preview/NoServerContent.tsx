@@ -129,6 +129,8 @@ export function NoServerContent({
         title: COMPANION_INSTALL_TASK_TITLE,
         description: COMPANION_INSTALL_TASK_DESCRIPTION,
         status: null,
+        kind: 'chore',
+        suggested_executor: null,
         parent_task_attempt: null,
         image_ids: null,
         shared_task_id: null,
@@ -7,6 +7,7 @@ import {
   listDocuments,
   updateDocument,
 } from '@/lib/document-api';
+import { useProjectDocuments } from '@/hooks/useProjectDocuments';
 import { toast } from 'sonner';
 
 interface DocumentContextType {
@@ -67,6 +68,33 @@ export function DocumentProvider({ children, projectId, category }: DocumentProv
     loadDocuments();
   }, [loadDocuments]);
 
+  // Follow changes to the docs folder, including edits made outside the app
+  const { documents: streamedDocuments } = useProjectDocuments(projectId);
+  useEffect(() => {
+    if (!streamedDocuments) return;
+    const inView = streamedDocuments.filter((doc) =>
+      category ? doc.category === category : doc.category !== 'conversation'
+    );
+    setDocuments((prev) => {
+      // Keep the current order and add new documents at the end
+      const byId = new Map(inView.map((doc) => [doc.id, doc]));
+      const kept = prev.flatMap((doc) => {
+        const next = byId.get(doc.id);
+        byId.delete(doc.id);
+        return next ? [next] : [];
+      });
+      return [...kept, ...byId.values()];
+    });
+    setOpenDocument((prev) => {
+      if (!prev) return prev;
+      const next = streamedDocuments.find((doc) => doc.id === prev.id);
+      if (!next) return null;
+      return next.content === prev.content && next.title === prev.title
+        ? prev
+        : next;
+    });
+  }, [streamedDocuments, category]);
+
   // Open a document
   const openDoc = useCallback(
     async (docId: string) => {
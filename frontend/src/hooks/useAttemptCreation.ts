@@ -24,6 +24,7 @@ export function useAttemptCreation({
         task_id: taskId,
         executor_profile_id: profile,
         base_branch: baseBranch,
+        stacked_on_attempt_id: null,
       }),
     onSuccess: (newAttempt: TaskAttempt) => {
       queryClient.setQueryData(
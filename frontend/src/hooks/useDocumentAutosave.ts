@@ -5,6 +5,8 @@ export type DocumentSaveStatus = 'idle' | 'saving' | 'saved' | 'error';
 interface UseDocumentAutosaveOptions {
   docId?: string;
   content: string;
+  /** Content known to be on disk, e.g. after an edit made outside the app */
+  savedContent?: string;
   onSave: (docId: string, content: string) => Promise<void>;
   debounceMs?: number;
   enabled?: boolean;
@@ -13,6 +15,7 @@ interface UseDocumentAutosaveOptions {
 export function useDocumentAutosave({
   docId,
   content,
+  savedContent,
   onSave,
   debounceMs = 1500,
   enabled = true,
@@ -23,6 +26,13 @@ export function useDocumentAutosave({
   const saveTimeoutRef = useRef<number | undefined>(undefined);
   const initialContentSetRef = useRef(false);
 
+  // Don't save content back that came from disk
+  useEffect(() => {
+    if (savedContent !== undefined) {
+      lastSavedContentRef.current = savedContent;
+    }
+  }, [savedContent]);
+
   useEffect(() => {
     // Skip if disabled or no docId
     if (!enabled || !docId) return;
@@ -0,0 +1,44 @@
+import { useCallback, useMemo } from 'react';
+import { useJsonPatchWsStream } from './useJsonPatchWsStream';
+import { type ApiDocument, type Document, mapApiDocument } from '@/types/document';
+
+type DocumentsState = {
+  documents: Record<string, ApiDocument>;
+};
+
+export interface UseProjectDocumentsResult {
+  /** Undefined until the first snapshot arrives */
+  documents: Document[] | undefined;
+  isConnected: boolean;
+  error: string | null;
+}
+
+/**
+ * Stream the documents of a project's docs folder via WebSocket (JSON Patch).
+ * Server sends initial snapshot: replace /documents with an object keyed by id.
+ * Edits to the files, also those made outside the app, arrive at /documents/<id>
+ * via add/replace/remove operations.
+ */
+export const useProjectDocuments = (
+  projectId: string | undefined
+): UseProjectDocumentsResult => {
+  const endpoint = projectId
+    ? `/api/projects/${encodeURIComponent(projectId)}/documents/stream/ws`
+    : undefined;
+
+  const initialData = useCallback((): DocumentsState => ({ documents: {} }), []);
+
+  const { data, isConnected, error } = useJsonPatchWsStream(
+    endpoint,
+    !!projectId,
+    initialData
+  );
+
+  const documents = useMemo(
+    () =>
+      data ? Object.values(data.documents).map(mapApiDocument) : undefined,
+    [data]
+  );
+
+  return { documents, isConnected, error };
+};
@@ -4,6 +4,8 @@ import { isTauri, getSidecarPort } from './tauri';
 import {
   ApprovalStatus,
   ApiResponse,
+  ApplyChangesRequest,
+  ApplyChangesResponse,
   BranchStatus,
   Config,
   CommitInfo,
@@ -25,6 +27,10 @@ import {
   Task,
   TaskAttempt,
   TaskRelationships,
+  TaskPlanRevision,
+  TaskPlanRevisionDiff,
+  TaskPlanStep,
+  RequestPlanChanges,
   Tag,
   TagSearchParams,
   TaskWithAttemptStatus,
@@ -43,6 +49,21 @@ import {
   GitOperationError,
   ApprovalResponse,
   RebaseTaskAttemptRequest,
+  PrReviewComment,
+  CiCheck,
+  ProjectCommitSettings,
+  UpdateProjectCommitSettings,
+  ProjectMcpServersResponse,
+  UpdateProjectMcpServers,
+  ApprovalRecord,
+  ApprovalRule,
+  CreateApprovalRule,
+  UpdateApprovalRule,
+  ApprovalTimeoutSetting,
+  CreateApprovalTimeoutSetting,
+  UpdateApprovalTimeoutSetting,
+  SendReviewCommentsRequest,
+  DismissReviewCommentsRequest,
   ChangeTargetBranchRequest,
   ChangeTargetBranchResponse,
   RenameBranchRequest,
@@ -328,6 +349,40 @@ export const projectsApi = {
     return handleApiResponse<GitBranch[]>(response);
   },
 
+  getCommitSettings: async (
+    id: string
+  ): Promise<ProjectCommitSettings | null> => {
+    const response = await makeRequest(`/api/projects/${id}/commit-settings`);
+    return handleApiResponse<ProjectCommitSettings | null>(response);
+  },
+
+  updateCommitSettings: async (
+    id: string,
+    data: UpdateProjectCommitSettings
+  ): Promise<ProjectCommitSettings> => {
+    const response = await makeRequest(`/api/projects/${id}/commit-settings`, {
+      method: 'PUT',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<ProjectCommitSettings>(response);
+  },
+
+  getMcpServers: async (id: string): Promise<ProjectMcpServersResponse> => {
+    const response = await makeRequest(`/api/projects/${id}/mcp-servers`);
+    return handleApiResponse<ProjectMcpServersResponse>(response);
+  },
+
+  updateMcpServers: async (
+    id: string,
+    data: UpdateProjectMcpServers
+  ): Promise<ProjectMcpServersResponse> => {
+    const response = await makeRequest(`/api/projects/${id}/mcp-servers`, {
+      method: 'PUT',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<ProjectMcpServersResponse>(response);
+  },
+
   searchFiles: async (
     id: string,
     query: string,
@@ -469,6 +524,43 @@ export const tasksApi = {
     });
     return handleApiResponse<void>(response);
   },
+
+  requestPlanChanges: async (
+    taskId: string,
+    data: RequestPlanChanges
+  ): Promise<TaskPlanRevision> => {
+    const response = await makeRequest(
+      `/api/tasks/${taskId}/plan/request-changes`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<TaskPlanRevision>(response);
+  },
+
+  getPlanRevisions: async (taskId: string): Promise<TaskPlanRevision[]> => {
+    const response = await makeRequest(`/api/tasks/${taskId}/plan/revisions`);
+    return handleApiResponse<TaskPlanRevision[]>(response);
+  },
+
+  diffPlanRevisions: async (
+    taskId: string,
+    from: number,
+    to?: number
+  ): Promise<TaskPlanRevisionDiff> => {
+    const params = new URLSearchParams({ from: String(from) });
+    if (to !== undefined) params.set('to', String(to));
+    const response = await makeRequest(
+      `/api/tasks/${taskId}/plan/revisions/diff?${params}`
+    );
+    return handleApiResponse<TaskPlanRevisionDiff>(response);
+  },
+
+  getPlanSteps: async (taskId: string): Promise<TaskPlanStep[]> => {
+    const response = await makeRequest(`/api/tasks/${taskId}/plan/steps`);
+    return handleApiResponse<TaskPlanStep[]>(response);
+  },
 };
 
 // Task Attempts APIs
@@ -683,6 +775,22 @@ export const attemptsApi = {
     return handleApiResponse<void>(response);
   },
 
+  applyChanges: async (
+    attemptId: string,
+    data: ApplyChangesRequest
+  ): Promise<Result<ApplyChangesResponse, GitOperationError>> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/apply-changes`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponseAsResult<ApplyChangesResponse, GitOperationError>(
+      response
+    );
+  },
+
   createPR: async (
     attemptId: string,
     data: CreateGitHubPrRequest
@@ -704,6 +812,54 @@ export const attemptsApi = {
     return handleApiResponse<void>(response);
   },
 
+  getReviewComments: async (
+    attemptId: string,
+    refresh = false
+  ): Promise<PrReviewComment[]> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/review-comments?refresh=${refresh}`
+    );
+    return handleApiResponse<PrReviewComment[]>(response);
+  },
+
+  sendReviewComments: async (
+    attemptId: string,
+    data: SendReviewCommentsRequest
+  ): Promise<PrReviewComment[]> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/review-comments/send`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<PrReviewComment[]>(response);
+  },
+
+  dismissReviewComments: async (
+    attemptId: string,
+    data: DismissReviewCommentsRequest
+  ): Promise<PrReviewComment[]> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/review-comments/dismiss`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<PrReviewComment[]>(response);
+  },
+
+  sendFailedChecks: async (attemptId: string): Promise<CiCheck[]> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/pr/checks/send-failures`,
+      {
+        method: 'POST',
+      }
+    );
+    return handleApiResponse<CiCheck[]>(response);
+  },
+
   setupGhCli: async (attemptId: string): Promise<ExecutionProcess> => {
     const response = await makeRequest(
       `/api/task-attempts/${attemptId}/gh-cli-setup`,
@@ -832,6 +988,90 @@ export const tagsApi = {
   },
 };
 
+// Approval rule APIs
+export const approvalRulesApi = {
+  list: async (params?: {
+    projectId?: string;
+    taskAttemptId?: string;
+  }): Promise<ApprovalRule[]> => {
+    const query = new URLSearchParams();
+    if (params?.projectId) query.set('project_id', params.projectId);
+    if (params?.taskAttemptId) {
+      query.set('task_attempt_id', params.taskAttemptId);
+    }
+    const queryString = query.toString();
+    const response = await makeRequest(
+      `/api/approval-rules${queryString ? `?${queryString}` : ''}`
+    );
+    return handleApiResponse<ApprovalRule[]>(response);
+  },
+
+  create: async (data: CreateApprovalRule): Promise<ApprovalRule> => {
+    const response = await makeRequest('/api/approval-rules', {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<ApprovalRule>(response);
+  },
+
+  update: async (
+    ruleId: string,
+    data: UpdateApprovalRule
+  ): Promise<ApprovalRule> => {
+    const response = await makeRequest(`/api/approval-rules/${ruleId}`, {
+      method: 'PUT',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<ApprovalRule>(response);
+  },
+
+  delete: async (ruleId: string): Promise<void> => {
+    const response = await makeRequest(`/api/approval-rules/${ruleId}`, {
+      method: 'DELETE',
+    });
+    return handleApiResponse<void>(response);
+  },
+};
+
+// Approval timeout override APIs
+export const approvalTimeoutsApi = {
+  list: async (projectId?: string): Promise<ApprovalTimeoutSetting[]> => {
+    const queryParam = projectId
+      ? `?project_id=${encodeURIComponent(projectId)}`
+      : '';
+    const response = await makeRequest(`/api/approval-timeouts${queryParam}`);
+    return handleApiResponse<ApprovalTimeoutSetting[]>(response);
+  },
+
+  create: async (
+    data: CreateApprovalTimeoutSetting
+  ): Promise<ApprovalTimeoutSetting> => {
+    const response = await makeRequest('/api/approval-timeouts', {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<ApprovalTimeoutSetting>(response);
+  },
+
+  update: async (
+    settingId: string,
+    data: UpdateApprovalTimeoutSetting
+  ): Promise<ApprovalTimeoutSetting> => {
+    const response = await makeRequest(`/api/approval-timeouts/${settingId}`, {
+      method: 'PUT',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<ApprovalTimeoutSetting>(response);
+  },
+
+  delete: async (settingId: string): Promise<void> => {
+    const response = await makeRequest(`/api/approval-timeouts/${settingId}`, {
+      method: 'DELETE',
+    });
+    return handleApiResponse<void>(response);
+  },
+};
+
 // MCP Servers APIs
 export const mcpServersApi = {
   load: async (query: McpServerQuery): Promise<GetMcpServerResponse> => {
@@ -963,6 +1203,13 @@ export const approvalsApi = {
 
     return handleApiResponse<ApprovalStatus>(res);
   },
+
+  listForAttempt: async (attemptId: string): Promise<ApprovalRecord[]> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/approvals`
+    );
+    return handleApiResponse<ApprovalRecord[]>(response);
+  },
 };
 
 // OAuth API
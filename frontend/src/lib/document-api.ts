@@ -8,6 +8,26 @@ import {
   type CreateDocumentDto,
   type UpdateDocumentDto,
 } from '@/types/document';
+import type {
+  ApplyTaskBreakdown,
+  CreateDocumentTemplate,
+  CreateDocumentTemplatePack,
+  DocumentRevision,
+  DocumentRevisionDiff,
+  DocumentRevisionSummary,
+  DocumentSearchMode,
+  DocumentSearchResult,
+  DocumentTemplate,
+  DocumentTemplatePack,
+  DocumentTemplatePackWithTemplates,
+  InstantiateDocumentTemplate,
+  StartTaskBreakdown,
+  Subtask,
+  TaskBreakdownPreview,
+  UpdateDocumentTemplate,
+  UpdateDocumentTemplatePack,
+  UpdateTaskDocuments,
+} from 'shared/types';
 
 interface ListDocumentsParams {
   category?: DocumentCategory;
@@ -84,3 +104,200 @@ export async function deleteDocument(
   });
   await handleApiResponse<void>(response);
 }
+
+export async function listDocumentRevisions(
+  projectId: string,
+  documentId: string
+): Promise<DocumentRevisionSummary[]> {
+  const response = await makeRequest(
+    buildUrl(projectId, `/${documentId}/revisions`)
+  );
+  return handleApiResponse<DocumentRevisionSummary[]>(response);
+}
+
+export async function getDocumentRevision(
+  projectId: string,
+  documentId: string,
+  revision: number
+): Promise<DocumentRevision> {
+  const response = await makeRequest(
+    buildUrl(projectId, `/${documentId}/revisions/${revision}`)
+  );
+  return handleApiResponse<DocumentRevision>(response);
+}
+
+export async function diffDocumentRevisions(
+  projectId: string,
+  documentId: string,
+  from: number,
+  to?: number
+): Promise<DocumentRevisionDiff> {
+  const searchParams = new URLSearchParams({ from: from.toString() });
+  if (to !== undefined) searchParams.set('to', to.toString());
+  const response = await makeRequest(
+    buildUrl(projectId, `/${documentId}/revisions/diff?${searchParams}`)
+  );
+  return handleApiResponse<DocumentRevisionDiff>(response);
+}
+
+export async function restoreDocumentRevision(
+  projectId: string,
+  documentId: string,
+  revision: number
+): Promise<Document> {
+  const response = await makeRequest(
+    buildUrl(projectId, `/${documentId}/revisions/${revision}/restore`),
+    { method: 'POST' }
+  );
+  const payload = await handleApiResponse<ApiDocument>(response);
+  return mapApiDocument(payload);
+}
+
+export async function createDocumentFromTemplate(
+  projectId: string,
+  data: InstantiateDocumentTemplate
+): Promise<Document> {
+  const response = await makeRequest(buildUrl(projectId, '/from-template'), {
+    method: 'POST',
+    body: JSON.stringify(data),
+  });
+  const payload = await handleApiResponse<ApiDocument>(response);
+  return mapApiDocument(payload);
+}
+
+export async function searchDocuments(
+  projectId: string,
+  query: string,
+  mode: DocumentSearchMode = 'text',
+  limit?: number
+): Promise<DocumentSearchResult[]> {
+  const searchParams = new URLSearchParams({ q: query, mode });
+  if (limit !== undefined) searchParams.set('limit', limit.toString());
+  const response = await makeRequest(buildUrl(projectId, `/search?${searchParams}`));
+  return handleApiResponse<DocumentSearchResult[]>(response);
+}
+
+export async function listDocumentTemplatePacks(): Promise<
+  DocumentTemplatePackWithTemplates[]
+> {
+  const response = await makeRequest('/api/document-templates/packs');
+  return handleApiResponse<DocumentTemplatePackWithTemplates[]>(response);
+}
+
+export async function createDocumentTemplatePack(
+  data: CreateDocumentTemplatePack
+): Promise<DocumentTemplatePack> {
+  const response = await makeRequest('/api/document-templates/packs', {
+    method: 'POST',
+    body: JSON.stringify(data),
+  });
+  return handleApiResponse<DocumentTemplatePack>(response);
+}
+
+export async function updateDocumentTemplatePack(
+  packId: string,
+  data: UpdateDocumentTemplatePack
+): Promise<DocumentTemplatePack> {
+  const response = await makeRequest(`/api/document-templates/packs/${packId}`, {
+    method: 'PUT',
+    body: JSON.stringify(data),
+  });
+  return handleApiResponse<DocumentTemplatePack>(response);
+}
+
+export async function deleteDocumentTemplatePack(packId: string): Promise<void> {
+  const response = await makeRequest(`/api/document-templates/packs/${packId}`, {
+    method: 'DELETE',
+  });
+  await handleApiResponse<void>(response);
+}
+
+export async function createDocumentTemplate(
+  packId: string,
+  data: CreateDocumentTemplate
+): Promise<DocumentTemplate> {
+  const response = await makeRequest(
+    `/api/document-templates/packs/${packId}/templates`,
+    {
+      method: 'POST',
+      body: JSON.stringify(data),
+    }
+  );
+  return handleApiResponse<DocumentTemplate>(response);
+}
+
+export async function updateDocumentTemplate(
+  templateId: string,
+  data: UpdateDocumentTemplate
+): Promise<DocumentTemplate> {
+  const response = await makeRequest(`/api/document-templates/${templateId}`, {
+    method: 'PUT',
+    body: JSON.stringify(data),
+  });
+  return handleApiResponse<DocumentTemplate>(response);
+}
+
+export async function deleteDocumentTemplate(templateId: string): Promise<void> {
+  const response = await makeRequest(`/api/document-templates/${templateId}`, {
+    method: 'DELETE',
+  });
+  await handleApiResponse<void>(response);
+}
+
+export async function getTaskDocuments(taskId: string): Promise<Document[]> {
+  const response = await makeRequest(`/api/tasks/${taskId}/documents`);
+  const payload = await handleApiResponse<ApiDocument[]>(response);
+  return payload.map(mapApiDocument);
+}
+
+export async function updateTaskDocuments(
+  taskId: string,
+  data: UpdateTaskDocuments
+): Promise<Document[]> {
+  const response = await makeRequest(`/api/tasks/${taskId}/documents`, {
+    method: 'PUT',
+    body: JSON.stringify(data),
+  });
+  const payload = await handleApiResponse<ApiDocument[]>(response);
+  return payload.map(mapApiDocument);
+}
+
+export async function startTaskBreakdown(
+  projectId: string,
+  documentId: string,
+  data: StartTaskBreakdown = { executor_profile_id: null, base_branch: null }
+): Promise<TaskBreakdownPreview> {
+  const response = await makeRequest(
+    buildUrl(projectId, `/${documentId}/breakdown`),
+    {
+      method: 'POST',
+      body: JSON.stringify(data),
+    }
+  );
+  return handleApiResponse<TaskBreakdownPreview>(response);
+}
+
+export async function getTaskBreakdown(
+  projectId: string,
+  breakdownId: string
+): Promise<TaskBreakdownPreview> {
+  const response = await makeRequest(
+    `/api/projects/${projectId}/task-breakdowns/${breakdownId}`
+  );
+  return handleApiResponse<TaskBreakdownPreview>(response);
+}
+
+export async function applyTaskBreakdown(
+  projectId: string,
+  breakdownId: string,
+  data: ApplyTaskBreakdown
+): Promise<Subtask[]> {
+  const response = await makeRequest(
+    `/api/projects/${projectId}/task-breakdowns/${breakdownId}/apply`,
+    {
+      method: 'POST',
+      body: JSON.stringify(data),
+    }
+  );
+  return handleApiResponse<Subtask[]>(response);
+}
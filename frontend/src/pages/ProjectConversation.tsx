@@ -1,11 +1,18 @@
 import { useCallback, useEffect, useMemo, useRef, useState } from 'react';
 import { useParams } from 'react-router-dom';
-import { AlertTriangle, MessageSquare } from 'lucide-react';
+import { AlertTriangle, MessageSquare, Plus } from 'lucide-react';
 
 import { ResizableHandle, ResizablePanel, ResizablePanelGroup } from '@/components/ui/resizable';
 import { NewCard, NewCardHeader } from '@/components/ui/new-card';
 import { Button } from '@/components/ui/button';
 import { Loader } from '@/components/ui/loader';
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from '@/components/ui/select';
 import TaskAttemptPanel from '@/components/panels/TaskAttemptPanel';
 import { Alert, AlertDescription, AlertTitle } from '@/components/ui/alert';
 import { useProjectTasks } from '@/hooks/useProjectTasks';
@@ -25,8 +32,8 @@ import { cn } from '@/lib/utils';
 import { ConversationDocsPanel } from '@/components/conversation/ConversationDocsPanel';
 import {
   PLANNING_AUTOSEND_PROMPT,
-  PLANNING_TASK_DESCRIPTION,
   PLANNING_TASK_TITLE,
+  isPlanningTask,
 } from '@/constants/planning';
 
 function GitErrorBanner() {
@@ -44,7 +51,9 @@ function GitErrorBanner() {
 function usePlanningSession(
   projectId?: string,
   activated?: boolean,
-  activationNonce?: number
+  activationNonce?: number,
+  selectedTaskId?: string | null,
+  onCreated?: (taskId: string) => void
 ) {
   const { config } = useUserSystem();
   const { tasksById, isLoading: tasksLoading, error: tasksError } =
@@ -58,22 +67,26 @@ function usePlanningSession(
   const creationRequestedRef = useRef<number | null>(null);
   const createInFlightRef = useRef(false);
 
-  const planningTask = useMemo(() => {
-    const planningTasks = Object.values(tasksById).filter(
-      (task) => task.title === PLANNING_TASK_TITLE
-    );
-
-    if (!planningTasks.length) return undefined;
-
-    return planningTasks.sort((a, b) => {
-      const diff =
-        new Date(b.created_at as string).getTime() -
-        new Date(a.created_at as string).getTime();
-      if (diff !== 0) return diff;
-      return b.id.localeCompare(a.id);
-    })[0];
+  // Every planning conversation of the project, newest first
+  const planningTasks = useMemo(() => {
+    return Object.values(tasksById)
+      .filter(isPlanningTask)
+      .sort((a, b) => {
+        const diff =
+          new Date(b.created_at as string).getTime() -
+          new Date(a.created_at as string).getTime();
+        if (diff !== 0) return diff;
+        return b.id.localeCompare(a.id);
+      });
   }, [tasksById]);
 
+  const planningTask = useMemo(
+    () =>
+      planningTasks.find((task) => task.id === selectedTaskId) ??
+      planningTasks[0],
+    [planningTasks, selectedTaskId]
+  );
+
   const { data: attempts = [], isLoading: attemptsLoading } = useTaskAttempts(
     planningTask?.id,
     { enabled: !!planningTask?.id }
@@ -102,6 +115,8 @@ function usePlanningSession(
   useEffect(() => {
     if (!projectId) return;
     if (!activated) return;
+    // Resuming an existing conversation does not create a new one
+    if (!activationNonce) return;
     if (creating) return;
     if (creationRequestedRef.current === activationNonce) return;
     if (createInFlightRef.current) return;
@@ -129,22 +144,18 @@ function usePlanningSession(
 
     const createPlanningTask = async () => {
       try {
-        // Stop old planning attempt if it exists
-        if (planningTask && latestAttempt) {
-          try {
-            await attemptsApi.stop(latestAttempt.id);
-          } catch (err) {
-            // Continue anyway - stopping old attempt is not critical
-            console.warn('Failed to stop old planning attempt:', err);
-          }
-        }
-
-        await tasksApi.createAndStart({
+        // Other conversations keep running; a new one only adds a session
+        const title = planningTasks.length
+          ? `${PLANNING_TASK_TITLE} ${planningTasks.length + 1}`
+          : PLANNING_TASK_TITLE;
+        const created = await tasksApi.createAndStart({
           task: {
             project_id: projectId,
-            title: PLANNING_TASK_TITLE,
-            description: PLANNING_TASK_DESCRIPTION,
+            title,
+            description: null,
             status: 'inprogress',
+            kind: 'planning_conversation',
+            suggested_executor: null,
             parent_task_attempt: null,
             image_ids: null,
             shared_task_id: null,
@@ -152,6 +163,7 @@ function usePlanningSession(
           executor_profile_id: config.executor_profile,
           base_branch: baseBranch,
         });
+        onCreated?.(created.id);
       } catch (err) {
         const msg =
           (err as { message?: string }).message ??
@@ -172,8 +184,8 @@ function usePlanningSession(
     creating,
     projectId,
     activationNonce,
-    planningTask,
-    latestAttempt,
+    planningTasks.length,
+    onCreated,
   ]);
 
   const isLoading =
@@ -192,6 +204,7 @@ function usePlanningSession(
     : null;
 
   return {
+    tasks: planningTasks,
     task: planningTask,
     attempt: latestAttempt,
     isLoading,
@@ -206,21 +219,27 @@ function usePlanningSession(
 }
 
 function PlanningChatPane({
+  tasks,
   task,
   attempt,
   isLoading,
   error,
   onRetry,
   onReset,
+  onSelect,
+  onNew,
   autoPromptError,
   onRetryAutoPrompt,
 }: {
+  tasks: TaskWithAttemptStatus[];
   task: TaskWithAttemptStatus | undefined;
   attempt: TaskAttempt | undefined;
   isLoading: boolean;
   error: string | null;
   onRetry: () => void;
   onReset: () => void;
+  onSelect: (taskId: string) => void;
+  onNew: () => void;
   autoPromptError: string | null;
   onRetryAutoPrompt: () => void;
 }) {
@@ -270,13 +289,36 @@ function PlanningChatPane({
             <NewCard className="h-full min-h-0 flex flex-col bg-diagonal-lines bg-muted border-0">
               <NewCardHeader
                 className="shrink-0"
-                title={
-                  <div className="flex items-center gap-2 text-sm font-semibold text-muted-foreground">
-                    <MessageSquare className="h-4 w-4" />
-                    <span>기획 대화</span>
+                actions={
+                  <div className="flex items-center gap-2">
+                    {tasks.length > 1 && (
+                      <Select value={task.id} onValueChange={onSelect}>
+                        <SelectTrigger className="h-8 w-48 text-xs">
+                          <SelectValue />
+                        </SelectTrigger>
+                        <SelectContent>
+                          {tasks.map((conversation) => (
+                            <SelectItem
+                              key={conversation.id}
+                              value={conversation.id}
+                            >
+                              {conversation.title}
+                            </SelectItem>
+                          ))}
+                        </SelectContent>
+                      </Select>
+                    )}
+                    <Button size="sm" variant="outline" onClick={onNew}>
+                      <Plus className="h-4 w-4 mr-1" />새 대화
+                    </Button>
                   </div>
                 }
-              />
+              >
+                <div className="flex items-center gap-2 text-sm font-semibold text-muted-foreground">
+                  <MessageSquare className="h-4 w-4" />
+                  <span className="truncate">{task.title}</span>
+                </div>
+              </NewCardHeader>
               <GitErrorBanner />
               {autoPromptError && (
                 <div className="mx-4 mt-3">
@@ -323,9 +365,28 @@ export function ProjectConversation() {
   const [autoPromptError, setAutoPromptError] = useState<string | null>(null);
   const autoPromptAttemptedRef = useRef<string | null>(null);
   const autoPromptInFlightRef = useRef(false);
+  const [selectedTaskId, setSelectedTaskId] = useState<string | null>(null);
+  // Only conversations created here get the workflow prompt sent automatically
+  const [createdTaskId, setCreatedTaskId] = useState<string | null>(null);
+
+  const handleCreated = useCallback((taskId: string) => {
+    setSelectedTaskId(taskId);
+    setCreatedTaskId(taskId);
+  }, []);
+
+  const { tasks, task, attempt, isLoading, error, retryCreate } =
+    usePlanningSession(
+      projectId,
+      activated,
+      activationNonce,
+      selectedTaskId,
+      handleCreated
+    );
 
-  const { task, attempt, isLoading, error, retryCreate } =
-    usePlanningSession(projectId, activated, activationNonce);
+  const startNewConversation = () => {
+    setActivationNonce((n) => n + 1);
+    setActivated(true);
+  };
 
   const sendAutoPrompt = useCallback(
     async (options?: { force?: boolean }) => {
@@ -361,10 +422,19 @@ export function ProjectConversation() {
   useEffect(() => {
     if (!activated) return;
     if (!attempt?.id) return;
+    if (!task || task.id !== createdTaskId) return;
     if (isLoading || error) return;
 
     void sendAutoPrompt();
-  }, [activated, attempt?.id, isLoading, error, sendAutoPrompt]);
+  }, [
+    activated,
+    attempt?.id,
+    task,
+    createdTaskId,
+    isLoading,
+    error,
+    sendAutoPrompt,
+  ]);
 
   if (!projectId) {
     return (
@@ -393,20 +463,26 @@ export function ProjectConversation() {
                         Claude Code와 바로 대화를 시작해 기획을 정리하세요. 필요한 프롬프트는 직접 입력하면 됩니다.
                       </p>
                     </div>
-                    <Button
-                      size="lg"
-                      onClick={() => {
-                        setActivationNonce((n) => n + 1);
-                        setActivated(true);
-                      }}
-                    >
-                      기획 시작하기
-                    </Button>
+                    <div className="flex gap-2">
+                      <Button size="lg" onClick={startNewConversation}>
+                        기획 시작하기
+                      </Button>
+                      {tasks.length > 0 && (
+                        <Button
+                          size="lg"
+                          variant="outline"
+                          onClick={() => setActivated(true)}
+                        >
+                          이전 대화 이어하기
+                        </Button>
+                      )}
+                    </div>
                   </div>
                 </NewCard>
               </div>
             ) : (
               <PlanningChatPane
+                tasks={tasks}
                 task={task}
                 attempt={attempt}
                 isLoading={isLoading}
@@ -414,6 +490,8 @@ export function ProjectConversation() {
                 autoPromptError={autoPromptError}
                 onRetryAutoPrompt={() => sendAutoPrompt({ force: true })}
                 onRetry={retryCreate}
+                onSelect={setSelectedTaskId}
+                onNew={startNewConversation}
                 onReset={() => {
                   setActivated(false);
                   retryCreate();
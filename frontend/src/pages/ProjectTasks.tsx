@@ -373,9 +373,7 @@ export function ProjectTasks() {
     };
 
     tasks.forEach((task) => {
-      if (
-        isPlanningTask({ title: task.title, description: task.description })
-      ) {
+      if (isPlanningTask(task)) {
         return;
       }
 
@@ -780,6 +778,7 @@ export function ProjectTasks() {
                 variant: 'PLAN', // Use PLAN variant for plan mode
               },
               base_branch: baseBranch,
+              stacked_on_attempt_id: null,
             });
 
             // Plan 모드 시도 생성 후 바로 시도로 이동
@@ -10,10 +10,225 @@ export type DirectoryListResponse = { entries: Array<DirectoryEntry>, current_pa
 
 export type Project = { id: string, name: string, git_repo_path: string, setup_script: string | null, dev_script: string | null, cleanup_script: string | null, copy_files: string | null, remote_project_id: string | null, created_at: Date, updated_at: Date, };
 
-export type CreateProject = { name: string, git_repo_path: string, use_existing_repo: boolean, setup_script: string | null, dev_script: string | null, cleanup_script: string | null, copy_files: string | null, };
+export type CreateProject = { name: string, git_repo_path: string, use_existing_repo: boolean, setup_script: string | null, dev_script: string | null, cleanup_script: string | null, copy_files: string | null, 
+/**
+ * Template pack whose documents are created in the new project
+ */
+document_template_pack_id: string | null, };
 
 export type UpdateProject = { name: string | null, git_repo_path: string | null, setup_script: string | null, dev_script: string | null, cleanup_script: string | null, copy_files: string | null, };
 
+export type CommitSigning = "none" | "gpg" | "ssh";
+
+/**
+ * Identity and signing used for commits the agent makes in a project
+ */
+export type ProjectCommitSettings = { project_id: string, 
+/**
+ * Author and committer for agent commits; the ambient git identity when unset
+ */
+author_name: string | null, author_email: string | null, 
+/**
+ * Credit the user's own git identity with a `Co-authored-by` trailer
+ */
+co_author_user: boolean, signing: CommitSigning, 
+/**
+ * Signing key passed to git; the user's configured `user.signingkey` when unset
+ */
+signing_key: string | null, created_at: string, updated_at: string, };
+
+export type UpdateProjectCommitSettings = { author_name: string | null, author_email: string | null, co_author_user: boolean, signing: CommitSigning, signing_key: string | null, };
+
+/**
+ * MCP servers added to the coding agent runs of one project only
+ */
+export type ProjectMcpServers = { project_id: string, 
+/**
+ * Servers by name, e.g. `{"db": {"command": "npx", "args": [...]}}` or
+ * `{"docs": {"type": "http", "url": "..."}}`
+ */
+servers: { [key in string]?: JsonValue }, created_at: string, updated_at: string, };
+
+export type UpdateProjectMcpServers = { servers: { [key in string]?: JsonValue }, };
+
+export type DocumentRevisionSource = "edit" | "external" | "restore";
+
+/**
+ * Snapshot of a document after a save
+ */
+export type DocumentRevision = { id: string, document_id: string, 
+/**
+ * Numbered from 1 per document
+ */
+revision: bigint, title: string, category: string, content: string, source: DocumentRevisionSource, 
+/**
+ * Revision whose contents a restore brought back
+ */
+restored_from: bigint | null, created_at: string, };
+
+/**
+ * Revision without its content, for history listings
+ */
+export type DocumentRevisionSummary = { id: string, document_id: string, revision: bigint, title: string, category: string, source: DocumentRevisionSource, restored_from: bigint | null, 
+/**
+ * Content length in bytes
+ */
+size: bigint, created_at: string, };
+
+export type DocumentRevisionDiff = { from: bigint, to: bigint, 
+/**
+ * Unified diff of the content; empty when it did not change
+ */
+unified_diff: string, };
+
+/**
+ * Named group of document templates, e.g. to scaffold the docs of a new project
+ */
+export type DocumentTemplatePack = { id: string, name: string, description: string | null, created_at: string, updated_at: string, };
+
+/**
+ * Markdown template of a document. The content may use the `{{project_name}}`,
+ * `{{repo_name}}`, `{{repo_path}}` and `{{date}}` placeholders.
+ */
+export type DocumentTemplate = { id: string, pack_id: string, 
+/**
+ * Title of the documents created from it, i.e. their file name without `.md`
+ */
+title: string, 
+/**
+ * Category the documents are created in unless another one is asked for
+ */
+category: string, content: string, 
+/**
+ * Order in the pack, from 0
+ */
+position: bigint, created_at: string, updated_at: string, };
+
+export type DocumentTemplatePackWithTemplates = { templates: Array<DocumentTemplate>, id: string, name: string, description: string | null, created_at: string, updated_at: string, };
+
+export type CreateDocumentTemplatePack = { name: string, description: string | null, };
+
+export type UpdateDocumentTemplatePack = { name: string | null, description: string | null, };
+
+export type CreateDocumentTemplate = { title: string, category: string, content: string, };
+
+export type UpdateDocumentTemplate = { title: string | null, category: string | null, content: string | null, position: bigint | null, };
+
+export type InstantiateDocumentTemplate = { template_id: string, 
+/**
+ * Defaults to the template's title
+ */
+title: string | null, 
+/**
+ * Defaults to the template's category
+ */
+category: string | null, };
+
+export type DocumentSearchMode = "text" | "semantic";
+
+export type DocumentSearchResult = { document_id: string, title: string, category: string, 
+/**
+ * Heading of the matching section, for semantic matches
+ */
+heading: string | null, 
+/**
+ * Matching passage; in text mode the matched terms are wrapped in `**`
+ */
+snippet: string, 
+/**
+ * Higher is better. Scores are only comparable within one search.
+ */
+score: number, };
+
+export type ApprovalRecordStatus = "pending" | "approved" | "denied" | "timed_out";
+
+/**
+ * A tool approval request and its outcome, kept for restarts and auditing
+ */
+export type ApprovalRecord = { id: string, execution_process_id: string, tool_name: string, tool_input: JsonValue, tool_call_id: string, status: ApprovalRecordStatus, denial_reason: string | null, 
+/**
+ * Who answered the request; `None` for rule decisions and timeouts
+ */
+responded_by: string | null, 
+/**
+ * Approval rule that decided the request without asking
+ */
+approval_rule_id: string | null, created_at: string, timeout_at: string, timeout_action: ApprovalTimeoutAction, responded_at: string | null, };
+
+/**
+ * Approval timeout override for a project and/or executor profile
+ */
+export type ApprovalTimeoutSetting = { id: string, 
+/**
+ * Project the override applies to; every project when `None`
+ */
+project_id: string | null, 
+/**
+ * Executor the override applies to; every executor when `None`
+ */
+executor: BaseCodingAgent | null, 
+/**
+ * Executor profile variant the override applies to
+ */
+variant: string | null, 
+/**
+ * Keeps the broader setting when `None`
+ */
+timeout_seconds: bigint | null, 
+/**
+ * Keeps the broader setting when `None`
+ */
+timeout_action: ApprovalTimeoutAction | null, created_at: string, updated_at: string, };
+
+export type CreateApprovalTimeoutSetting = { project_id: string | null, executor: BaseCodingAgent | null, variant: string | null, timeout_seconds: bigint | null, timeout_action: ApprovalTimeoutAction | null, };
+
+export type UpdateApprovalTimeoutSetting = { executor: BaseCodingAgent | null, variant: string | null, timeout_seconds: bigint | null, timeout_action: ApprovalTimeoutAction | null, };
+
+export type ApprovalRuleAction = "allow" | "deny" | "ask";
+
+/**
+ * A rule of the approval policy applied to agent tool calls
+ */
+export type ApprovalRule = { id: string, 
+/**
+ * Project the rule is limited to; applies to every project when `None`
+ */
+project_id: string | null, 
+/**
+ * Attempt the rule is limited to, for approvals remembered for one attempt
+ */
+task_attempt_id: string | null, 
+/**
+ * Executor the rule is limited to; applies to every executor when `None`
+ */
+executor: BaseCodingAgent | null, 
+/**
+ * Executor profile variant the rule is limited to
+ */
+variant: string | null, 
+/**
+ * Regex that must match the whole tool name
+ */
+tool_pattern: string, 
+/**
+ * Regex searched for in the command, file path or URL of the tool input; matches
+ * any input when `None`
+ */
+input_pattern: string | null, 
+/**
+ * Tool input field `input_pattern` is matched against; the command, file path
+ * or URL of the call when `None`
+ */
+input_field: string | null, action: ApprovalRuleAction, 
+/**
+ * Shown in the conversation (and to the agent on deny) when the rule applies
+ */
+description: string | null, position: bigint, created_at: string, updated_at: string, };
+
+export type CreateApprovalRule = { project_id: string | null, task_attempt_id: string | null, executor: BaseCodingAgent | null, variant: string | null, tool_pattern: string, input_pattern: string | null, input_field: string | null, action: ApprovalRuleAction, description: string | null, };
+
+export type UpdateApprovalRule = { executor: BaseCodingAgent | null, variant: string | null, tool_pattern: string, input_pattern: string | null, input_field: string | null, action: ApprovalRuleAction, description: string | null, position: bigint | null, };
+
 export type SearchResult = { path: string, is_file: boolean, match_type: SearchMatchType, };
 
 export type SearchMatchType = "FileName" | "DirectoryName" | "FullPath";
@@ -22,6 +237,13 @@ export type CreateRemoteProjectRequest = { organization_id: string, name: string
 
 export type LinkToExistingRequest = { remote_project_id: string, };
 
+export type ProjectMcpServersResponse = { settings: ProjectMcpServers | null, 
+/**
+ * Configured coding agents that cannot take MCP servers per execution, and so
+ * run without the project's servers
+ */
+unsupported_executors: Array<BaseCodingAgent>, };
+
 export type ExecutorAction = { typ: ExecutorActionType, next_action: ExecutorAction | null, };
 
 export type McpConfig = { servers: { [key in string]?: JsonValue }, servers_path: Array<string>, template: JsonValue, preconfigured: JsonValue, is_toml_config: boolean, };
@@ -48,16 +270,106 @@ export type TagSearchParams = { search: string | null, };
 
 export type TaskStatus = "todo" | "plan" | "inprogress" | "inreview" | "done" | "cancelled";
 
-export type Task = { id: string, project_id: string, title: string, description: string | null, plan: string | null, status: TaskStatus, parent_task_attempt: string | null, shared_task_id: string | null, created_at: string, updated_at: string, };
+/**
+ * What a task is for. The kind decides how its attempts are set up.
+ */
+export type TaskKind = "implementation" | "planning_conversation" | "review" | "chore";
+
+export type Task = { id: string, project_id: string, title: string, description: string | null, plan: string | null, status: TaskStatus, kind: TaskKind, 
+/**
+ * Executor new attempts default to when the task has none yet
+ */
+suggested_executor: BaseCodingAgent | null, parent_task_attempt: string | null, shared_task_id: string | null, created_at: string, updated_at: string, };
+
+export type TaskWithAttemptStatus = { has_in_progress_attempt: boolean, has_merged_attempt: boolean, last_attempt_failed: boolean, executor: string, 
+/**
+ * Progress through the approved plan's checklist, if the task has one
+ */
+plan_progress: PlanProgress | null, id: string, project_id: string, title: string, description: string | null, plan: string | null, status: TaskStatus, kind: TaskKind, 
+/**
+ * Executor new attempts default to when the task has none yet
+ */
+suggested_executor: BaseCodingAgent | null, parent_task_attempt: string | null, shared_task_id: string | null, created_at: string, updated_at: string, };
 
-export type TaskWithAttemptStatus = { has_in_progress_attempt: boolean, has_merged_attempt: boolean, last_attempt_failed: boolean, executor: string, id: string, project_id: string, title: string, description: string | null, plan: string | null, status: TaskStatus, parent_task_attempt: string | null, shared_task_id: string | null, created_at: string, updated_at: string, };
+/**
+ * How far the implementation of a task's approved plan has come
+ */
+export type PlanProgress = { completed_steps: bigint, total_steps: bigint, 
+/**
+ * Step being worked on, from 1; the last step once all are completed
+ */
+current_step: bigint, 
+/**
+ * Completed share of the steps, 0 to 100
+ */
+percent: bigint, };
 
 export type TaskRelationships = { parent_task: Task | null, current_attempt: TaskAttempt, children: Array<Task>, };
 
-export type CreateTask = { project_id: string, title: string, description: string | null, status: TaskStatus | null, parent_task_attempt: string | null, image_ids: Array<string> | null, shared_task_id: string | null, };
+export type CreateTask = { project_id: string, title: string, description: string | null, status: TaskStatus | null, 
+/**
+ * Defaults to an implementation task
+ */
+kind: TaskKind | null, suggested_executor: BaseCodingAgent | null, parent_task_attempt: string | null, image_ids: Array<string> | null, shared_task_id: string | null, };
 
 export type UpdateTask = { title: string | null, description: string | null, status: TaskStatus | null, parent_task_attempt: string | null, image_ids: Array<string> | null, };
 
+export type TaskDependency = { task_id: string, depends_on_task_id: string, created_at: string, };
+
+/**
+ * Project document a task is based on
+ */
+export type TaskDocument = { task_id: string, document_id: string, created_at: string, };
+
+export type UpdateTaskDocuments = { 
+/**
+ * Replaces the task's linked documents, in the order given to the agent
+ */
+document_ids: Array<string>, };
+
+/**
+ * Run of a coding agent splitting a project document into kanban tasks
+ */
+export type TaskBreakdown = { id: string, project_id: string, 
+/**
+ * Document the tasks are derived from
+ */
+document_id: string, 
+/**
+ * Attempt the agent runs in
+ */
+task_attempt_id: string, 
+/**
+ * When the previewed tasks were created; a breakdown is applied once
+ */
+applied_at: string | null, created_at: string, };
+
+export type TaskPlanRevisionStatus = "proposed" | "changes_requested" | "approved";
+
+/**
+ * A plan presented by a plan mode run of a task
+ */
+export type TaskPlanRevision = { id: string, task_id: string, 
+/**
+ * Numbered from 1 per task
+ */
+revision: bigint, plan: string, execution_process_id: string | null, status: TaskPlanRevisionStatus, 
+/**
+ * Changes the reviewer asked for
+ */
+feedback: string | null, created_at: string, updated_at: string, };
+
+export type TaskPlanStepStatus = "pending" | "in_progress" | "completed";
+
+/**
+ * Checklist item of a task's approved plan
+ */
+export type TaskPlanStep = { id: string, task_id: string, 
+/**
+ * Order in the plan, from 0
+ */
+position: bigint, title: string, status: TaskPlanStepStatus, updated_at: string, };
+
 export type SharedTask = { id: string, remote_project_id: string, title: string, description: string | null, status: TaskStatus, assignee_user_id: string | null, assignee_first_name: string | null, assignee_last_name: string | null, assignee_username: string | null, version: bigint, last_event_seq: bigint | null, created_at: Date, updated_at: Date, };
 
 export type Image = { id: string, file_path: string, original_name: string, mime_type: string | null, size_bytes: bigint, hash: string, created_at: string, updated_at: string, };
@@ -158,6 +470,16 @@ export type RenameBranchResponse = { branch: string, };
 
 export type CommitCompareResult = { head_oid: string, target_oid: string, ahead_from_head: number, behind_from_head: number, is_linear: boolean, };
 
+export type AttemptStack = { 
+/**
+ * Attempts this attempt is stacked on, closest first
+ */
+ancestors: Array<TaskAttempt>, 
+/**
+ * Attempts stacked directly on this attempt's branch
+ */
+dependents: Array<TaskAttempt>, };
+
 export type OpenEditorRequest = { editor_type: string | null, file_path: string | null, };
 
 export type OpenEditorResponse = { url: string | null, };
@@ -170,11 +492,100 @@ export type ShareTaskResponse = { shared_task_id: string, };
 
 export type CreateAndStartTaskRequest = { task: CreateTask, executor_profile_id: ExecutorProfileId, base_branch: string, };
 
+export type CreateSubtask = { title: string, description: string | null, 
+/**
+ * Executor suggested for the subtask, used by default for its attempts
+ */
+executor: BaseCodingAgent | null, 
+/**
+ * Indices of earlier subtasks in the same request that must be done first
+ */
+depends_on: Array<number>, };
+
+export type CreateSubtasks = { 
+/**
+ * Attempt the subtasks are created from; defaults to the parent task's latest
+ * attempt
+ */
+parent_task_attempt: string | null, subtasks: Array<CreateSubtask>, };
+
+export type Subtask = { 
+/**
+ * Ids of the subtasks that must be done first
+ */
+depends_on: Array<string>, id: string, project_id: string, title: string, description: string | null, plan: string | null, status: TaskStatus, kind: TaskKind, 
+/**
+ * Executor new attempts default to when the task has none yet
+ */
+suggested_executor: BaseCodingAgent | null, parent_task_attempt: string | null, shared_task_id: string | null, created_at: string, updated_at: string, };
+
+export type RequestPlanChanges = { 
+/**
+ * What the revised plan should change
+ */
+feedback: string, };
+
+export type TaskPlanRevisionDiff = { from: bigint, to: bigint, 
+/**
+ * Empty when both revisions have the same plan
+ */
+unified_diff: string, };
+
+/**
+ * A task the breakdown suggests creating
+ */
+export type ProposedTask = { title: string, description: string | null, acceptance_criteria: Array<string>, 
+/**
+ * Indices of earlier tasks in the breakdown that must be done first
+ */
+depends_on: Array<number>, };
+
+export type StartTaskBreakdown = { 
+/**
+ * Defaults to the configured executor profile
+ */
+executor_profile_id: ExecutorProfileId | null, 
+/**
+ * Defaults to the branch checked out in the project repository
+ */
+base_branch: string | null, };
+
+export type TaskBreakdownStatus = "running" | "ready" | "failed" | "applied";
+
+export type TaskBreakdownPreview = { breakdown: TaskBreakdown, 
+/**
+ * Task the agent runs under, where follow-ups can refine the breakdown
+ */
+task_id: string, status: TaskBreakdownStatus, tasks: Array<ProposedTask>, error: string | null, };
+
+export type ApplyTaskBreakdown = { 
+/**
+ * The previewed tasks, possibly edited
+ */
+tasks: Array<ProposedTask>, };
+
 export type CreateGitHubPrRequest = { title: string, body: string | null, target_branch: string | null, };
 
 export type ImageResponse = { id: string, file_path: string, original_name: string, mime_type: string | null, size_bytes: bigint, hash: string, created_at: string, updated_at: string, };
 
-export type Config = { config_version: string, theme: ThemeMode, executor_profile: ExecutorProfileId, disclaimer_acknowledged: boolean, onboarding_acknowledged: boolean, notifications: NotificationConfig, editor: EditorConfig, github: GitHubConfig, analytics_enabled: boolean, workspace_dir: string | null, last_app_version: string | null, show_release_notes: boolean, language: UiLanguage, git_branch_prefix: string, showcases: ShowcaseState, };
+export type Config = { config_version: string, theme: ThemeMode, executor_profile: ExecutorProfileId, disclaimer_acknowledged: boolean, onboarding_acknowledged: boolean, notifications: NotificationConfig, editor: EditorConfig, github: GitHubConfig, analytics_enabled: boolean, workspace_dir: string | null, last_app_version: string | null, show_release_notes: boolean, language: UiLanguage, git_branch_prefix: string, showcases: ShowcaseState, 
+/**
+ * Send new PR review comments to the agent as soon as they are ingested
+ */
+auto_address_review_comments: boolean, 
+/**
+ * Send the output of failing PR checks to the agent when CI goes red
+ */
+auto_fix_failed_checks: boolean, 
+/**
+ * How long tool approvals wait for an answer, unless a project or profile overrides it.
+ * Must be positive: saving 0 is rejected, and a 0 in the file uses the default.
+ */
+approval_timeout_seconds: number, 
+/**
+ * What happens to tool approvals nobody answers in time
+ */
+approval_timeout_action: ApprovalTimeoutAction, approval_webhook: ApprovalWebhookConfig, document_search: DocumentSearchConfig, };
 
 export type NotificationConfig = { sound_enabled: boolean, push_enabled: boolean, sound_file: SoundFile, };
 
@@ -194,6 +605,31 @@ export type UiLanguage = "BROWSER" | "EN" | "KO";
 
 export type ShowcaseState = { seen_features: Array<string>, };
 
+/**
+ * Outgoing webhook for tool approval requests
+ */
+export type ApprovalWebhookConfig = { 
+/**
+ * Receives a JSON payload with signed approve/deny links for each approval request
+ */
+url: string | null, 
+/**
+ * Address the links should point to when the server is reached through a tunnel or
+ * proxy; defaults to the local address
+ */
+public_url: string | null, };
+
+/**
+ * Semantic search over project documents
+ */
+export type DocumentSearchConfig = { 
+/**
+ * Shell command that embeds texts with a local model. It gets a JSON array of
+ * strings on stdin and prints a JSON array of vectors, one per string. The
+ * built-in embedder, which only compares wording, is used when unset.
+ */
+embedding_command: string | null, };
+
 export type GitBranch = { name: string, is_current: boolean, is_remote: boolean, last_commit_date: Date, };
 
 export type Diff = { change: DiffChangeKind, oldPath: string | null, newPath: string | null, oldContent: string | null, newContent: string | null, 
@@ -236,11 +672,11 @@ export enum BaseAgentCapability { SESSION_FORK = "SESSION_FORK", SETUP_HELPER =
 
 export type ClaudeCode = { append_prompt: AppendPrompt, claude_code_router?: boolean | null, plan?: boolean | null, approvals?: boolean | null, model?: string | null, dangerously_skip_permissions?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
 
-export type Gemini = { append_prompt: AppendPrompt, model?: string | null, yolo?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
+export type Gemini = { append_prompt: AppendPrompt, model?: string | null, yolo?: boolean | null, plan?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
 
 export type Amp = { append_prompt: AppendPrompt, dangerously_allow_all?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
 
-export type Codex = { append_prompt: AppendPrompt, sandbox?: SandboxMode | null, ask_for_approval?: AskForApproval | null, oss?: boolean | null, model?: string | null, model_reasoning_effort?: ReasoningEffort | null, model_reasoning_summary?: ReasoningSummary | null, model_reasoning_summary_format?: ReasoningSummaryFormat | null, profile?: string | null, base_instructions?: string | null, include_plan_tool?: boolean | null, include_apply_patch_tool?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
+export type Codex = { append_prompt: AppendPrompt, plan?: boolean | null, sandbox?: SandboxMode | null, ask_for_approval?: AskForApproval | null, oss?: boolean | null, model?: string | null, model_reasoning_effort?: ReasoningEffort | null, model_reasoning_summary?: ReasoningSummary | null, model_reasoning_summary_format?: ReasoningSummaryFormat | null, profile?: string | null, base_instructions?: string | null, include_plan_tool?: boolean | null, include_apply_patch_tool?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
 
 export type SandboxMode = "auto" | "read-only" | "workspace-write" | "danger-full-access";
 
@@ -256,11 +692,11 @@ export type CursorAgent = { append_prompt: AppendPrompt, force?: boolean | null,
 
 export type Copilot = { append_prompt: AppendPrompt, model?: string | null, allow_all_tools?: boolean | null, allow_tool?: string | null, deny_tool?: string | null, add_dir?: Array<string> | null, disable_mcp_server?: Array<string> | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
 
-export type Opencode = { append_prompt: AppendPrompt, model?: string | null, agent?: string | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
+export type Opencode = { append_prompt: AppendPrompt, model?: string | null, agent?: string | null, plan?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
 
-export type QwenCode = { append_prompt: AppendPrompt, yolo?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
+export type QwenCode = { append_prompt: AppendPrompt, yolo?: boolean | null, plan?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
 
-export type Droid = { append_prompt: AppendPrompt, autonomy: Autonomy, model?: string | null, reasoning_effort?: DroidReasoningEffort | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
+export type Droid = { append_prompt: AppendPrompt, autonomy: Autonomy, plan?: boolean | null, model?: string | null, reasoning_effort?: DroidReasoningEffort | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
 
 export type Autonomy = "normal" | "low" | "medium" | "high" | "skip-permissions-unsafe";
 
@@ -284,7 +720,11 @@ export type CreateTaskAttemptBody = { task_id: string,
 /**
  * Executor profile specification
  */
-executor_profile_id: ExecutorProfileId, base_branch: string, };
+executor_profile_id: ExecutorProfileId, base_branch: string, 
+/**
+ * Stack the new attempt on another attempt's branch instead of `base_branch`
+ */
+stacked_on_attempt_id: string | null, };
 
 export type RunAgentSetupRequest = { executor_profile_id: ExecutorProfileId, };
 
@@ -296,6 +736,48 @@ export type RebaseTaskAttemptRequest = { old_base_branch: string | null, new_bas
 
 export type GitOperationError = { "type": "merge_conflicts", message: string, op: ConflictOp, } | { "type": "rebase_in_progress" };
 
+export type ApplyChangesTarget = { "type": "base_branch" } | { "type": "attempt", attempt_id: string, };
+
+export type ApplyChangesRequest = { 
+/**
+ * Files (and optionally hunks) from the attempt's diff to apply
+ */
+files: Array<DiffSelection>, target: ApplyChangesTarget, commit_message: string | null, };
+
+export type ApplyChangesResponse = { 
+/**
+ * Commit created on the target branch
+ */
+commit_sha: string, };
+
+export type SendReviewCommentsRequest = { 
+/**
+ * Send only these comments; all pending comments when omitted
+ */
+comment_ids: Array<string> | null, };
+
+export type DismissReviewCommentsRequest = { comment_ids: Array<string>, };
+
+export type AttemptConversationEntry = { execution_process_id: string, entry: NormalizedEntry, };
+
+export type AttemptConversation = { entries: Array<AttemptConversationEntry>, 
+/**
+ * Number of entries across all coding agent runs of the attempt
+ */
+total: number, offset: number, 
+/**
+ * Whether a coding agent run of the attempt is still in progress
+ */
+running: boolean, };
+
+export type DiffFileSummary = { path: string, change: DiffChangeKind, additions: number, deletions: number, };
+
+export type FileDiff = { path: string, change: DiffChangeKind, 
+/**
+ * Unified diff of the file; `None` for binary files
+ */
+unified_diff: string | null, };
+
 export type CreatePrError = { "type": "github_cli_not_installed" } | { "type": "github_cli_not_logged_in" } | { "type": "git_cli_not_logged_in" } | { "type": "git_cli_not_installed" };
 
 export type CommitInfo = { sha: string, subject: string, };
@@ -316,7 +798,17 @@ conflicted_files: Array<string>, };
 
 export type ConflictOp = "rebase" | "merge" | "cherry_pick" | "revert";
 
-export type TaskAttempt = { id: string, task_id: string, container_ref: string | null, branch: string, target_branch: string, executor: string, worktree_deleted: boolean, setup_completed_at: string | null, created_at: string, updated_at: string, };
+export type DiffSelection = { 
+/**
+ * Path of the file as reported by the diff (new path for renames)
+ */
+path: string, 
+/**
+ * Zero-based hunk indices to keep; `None` takes the whole file
+ */
+hunks: Array<number> | null, };
+
+export type TaskAttempt = { id: string, task_id: string, container_ref: string | null, branch: string, target_branch: string, executor: string, worktree_deleted: boolean, setup_completed_at: string | null, stacked_on_attempt_id: string | null, created_at: string, updated_at: string, };
 
 export type ExecutionProcess = { id: string, task_attempt_id: string, run_reason: ExecutionProcessRunReason, executor_action: ExecutorAction, 
 /**
@@ -346,7 +838,33 @@ export type PrMerge = { id: string, task_attempt_id: string, created_at: string,
 
 export type MergeStatus = "open" | "merged" | "closed" | "unknown";
 
-export type PullRequestInfo = { number: bigint, url: string, status: MergeStatus, merged_at: string | null, merge_commit_sha: string | null, };
+export type PullRequestInfo = { number: bigint, url: string, status: MergeStatus, merged_at: string | null, merge_commit_sha: string | null, 
+/**
+ * Rolled-up status of the PR's checks; `None` when the PR has no checks
+ */
+ci_status: CiStatus | null, ci_checks: Array<CiCheck>, };
+
+export type CiStatus = "pending" | "success" | "failure";
+
+/**
+ * A single check run or commit status reported on a PR
+ */
+export type CiCheck = { name: string, status: CiStatus, 
+/**
+ * Link to the check's logs/details page
+ */
+details_url: string | null, };
+
+export type ReviewCommentStatus = "pending" | "sent" | "addressed" | "dismissed";
+
+/**
+ * A review comment left on a pull request opened from a task attempt
+ */
+export type PrReviewComment = { id: string, merge_id: string, task_attempt_id: string, github_comment_id: bigint, author: string, body: string, 
+/**
+ * File the comment is anchored to; `None` for comments on the whole PR
+ */
+path: string | null, line: bigint | null, diff_hunk: string | null, url: string | null, status: ReviewCommentStatus, commented_at: string, created_at: string, updated_at: string, };
 
 export type Draft = { id: string, task_attempt_id: string, draft_type: DraftType, retry_process_id: string | null, prompt: string, queued: boolean, sending: boolean, variant: string | null, image_ids: Array<string> | null, created_at: string, updated_at: string, version: bigint, };
 
@@ -384,7 +902,16 @@ value: JsonValue, };
 
 export type ToolResultValueType = { "type": "markdown" } | { "type": "json" };
 
-export type ToolStatus = { "status": "created" } | { "status": "success" } | { "status": "failed" } | { "status": "denied", reason: string | null, } | { "status": "pending_approval", approval_id: string, requested_at: string, timeout_at: string, } | { "status": "timed_out" };
+/**
+ * What happens to a tool approval request nobody answers before its deadline
+ */
+export type ApprovalTimeoutAction = "deny" | "approve" | "pause";
+
+export type ToolStatus = { "status": "created" } | { "status": "success" } | { "status": "failed" } | { "status": "denied", reason: string | null, } | { "status": "pending_approval", approval_id: string, requested_at: string, timeout_at: string, 
+/**
+ * Outcome applied when `timeout_at` passes without an answer
+ */
+on_timeout: ApprovalTimeoutAction, } | { "status": "timed_out" };
 
 export type PatchType = { "type": "NORMALIZED_ENTRY", "content": NormalizedEntry } | { "type": "STDOUT", "content": string } | { "type": "STDERR", "content": string } | { "type": "DIFF", "content": Diff };
 
@@ -392,6 +919,15 @@ export type ApprovalStatus = { "status": "pending" } | { "status": "approved" }
 
 export type CreateApprovalRequest = { tool_name: string, tool_input: JsonValue, tool_call_id: string, };
 
-export type ApprovalResponse = { execution_process_id: string, status: ApprovalStatus, };
+/**
+ * How widely an approval should be remembered as an allow rule
+ */
+export type ApprovalRememberScope = "attempt" | "project" | "global";
+
+export type ApprovalResponse = { execution_process_id: string, status: ApprovalStatus, 
+/**
+ * Remember an approval so matching tool calls are allowed without asking
+ */
+remember?: ApprovalRememberScope, };
 
 export type JsonValue = number | string | boolean | Array<JsonValue> | { [key in string]?: JsonValue } | null;
\ No newline at end of file